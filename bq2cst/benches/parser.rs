@@ -0,0 +1,187 @@
+// to run the following benchmarks
+// you have to run `cargo bench`
+//
+// inputs are deterministic, so results can be compared across runs
+// `cargo bench -- --save-baseline before` then `cargo bench -- --baseline before`
+
+use bq2cst::cst::Node;
+use bq2cst::lexer::Lexer;
+use bq2cst::parser::Parser;
+use bq2cst::token::Token;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+
+// ----- inputs -----
+fn fixtures() -> Vec<(String, String)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../prettier-plugin-bq/input");
+    let mut paths: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sql"))
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().to_string();
+            (name, fs::read_to_string(&path).unwrap())
+        })
+        .collect()
+}
+
+fn stress_inputs() -> Vec<(String, String)> {
+    vec![
+        ("script_10k".to_string(), script(10_000)),
+        ("nested_expr".to_string(), nested_expr(80)),
+        ("in_list".to_string(), in_list(10_000)),
+        ("comments".to_string(), commented_statements(1_000)),
+    ]
+}
+
+fn script(n: usize) -> String {
+    let mut code = String::new();
+    for i in 0..n {
+        let stmt = match i % 4 {
+            0 => format!("DECLARE x{i} INT64 DEFAULT {i};\n"),
+            1 => format!("SET x{} = x{} + 1;\n", i - 1, i - 1),
+            2 => format!("SELECT a, b + {i} AS c FROM t WHERE d = 'x{i}' ORDER BY a;\n"),
+            _ => format!("INSERT INTO t (a, b) VALUES ({i}, 'x{i}');\n"),
+        };
+        code.push_str(&stmt);
+    }
+    code
+}
+
+// both parentheses and right-hand operands get deeper
+fn nested_expr(depth: usize) -> String {
+    let mut expr = "x".to_string();
+    for i in 0..depth {
+        expr = format!("({expr} + {i}) * f({i}, CASE WHEN y THEN {i} END)");
+    }
+    format!("SELECT {expr};\n")
+}
+
+fn in_list(n: usize) -> String {
+    let items: Vec<String> = (0..n).map(|i| format!("'item{i}'")).collect();
+    format!("SELECT * FROM t WHERE x IN ({});\n", items.join(", "))
+}
+
+// every `FROM (SELECT ...)` makes the parser try (and maybe roll back)
+// a subquery interpretation
+fn grouped_from_items(n: usize) -> String {
+    let mut code = String::new();
+    for i in 0..n {
+        code.push_str(&format!(
+            "SELECT * FROM (SELECT {i} AS x) AS t{i} JOIN ((SELECT 1) JOIN (SELECT 2) ON TRUE) USING (x);\n"
+        ));
+    }
+    code
+}
+
+// every token is surrounded by comments and templates
+fn commented_statements(n: usize) -> String {
+    let mut code = String::new();
+    for i in 0..n {
+        code.push_str(&format!(
+            "\
+-- statement {i}
+SELECT /* first */ a, -- trailing
+  {{{{ column }}}} /* second */ AS b, # hash comment
+  {{# jinja comment #}} c + {i} -- another
+FROM {{{{ ref('t') }}}} /* table */
+WHERE /* cond */ x = 1 {{% if flag %}} AND y = 2 {{% endif %}}; -- end
+"
+        ));
+    }
+    code
+}
+
+// ----- helpers -----
+fn tokenize(code: &str) -> Vec<Token<'_>> {
+    Lexer::new(code).tokenize_code().unwrap()
+}
+
+fn parse(code: &str) -> Vec<Node<'_>> {
+    Parser::new(tokenize(code)).parse_code().unwrap()
+}
+
+fn all_inputs() -> Vec<(String, String)> {
+    let mut inputs = fixtures();
+    inputs.extend(stress_inputs());
+    inputs
+}
+
+// ----- benchmarks -----
+fn bench_lex(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lex");
+    for (name, code) in all_inputs() {
+        group.throughput(Throughput::Bytes(code.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(&name), &code, |b, code| {
+            b.iter(|| black_box(tokenize(code)))
+        });
+    }
+    group.finish();
+}
+
+// tokens are prepared in advance, so that only the parser is measured
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, code) in all_inputs() {
+        let tokens = tokenize(&code);
+        group.throughput(Throughput::Bytes(code.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(&name), &tokens, |b, tokens| {
+            b.iter_batched(
+                || tokens.clone(),
+                |tokens| black_box(Parser::new(tokens).parse_code().unwrap()),
+                criterion::BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialize");
+    for (name, code) in all_inputs() {
+        let stmts = parse(&code);
+        group.throughput(Throughput::Bytes(code.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(&name), &stmts, |b, stmts| {
+            b.iter(|| black_box(serde_json::to_string(stmts).unwrap()))
+        });
+    }
+    group.finish();
+}
+
+fn bench_backtrack(c: &mut Criterion) {
+    let mut group = c.benchmark_group("backtrack");
+    for n in [100, 1000] {
+        let code = grouped_from_items(n);
+        group.bench_with_input(BenchmarkId::from_parameter(n), &code, |b, code| {
+            b.iter(|| black_box(parse(code)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_comments(c: &mut Criterion) {
+    let mut group = c.benchmark_group("comments");
+    for n in [100, 1000] {
+        let code = commented_statements(n);
+        group.bench_with_input(BenchmarkId::from_parameter(n), &code, |b, code| {
+            b.iter(|| black_box(parse(code)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_lex,
+    bench_parse,
+    bench_serialize,
+    bench_backtrack,
+    bench_comments
+);
+criterion_main!(benches);
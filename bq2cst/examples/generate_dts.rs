@@ -0,0 +1,4 @@
+// cargo run --example generate_dts > src/bq2cst.d.ts
+fn main() {
+    print!("{}", bq2cst::schema::typescript_declarations());
+}
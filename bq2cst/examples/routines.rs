@@ -0,0 +1,32 @@
+// cargo run --example routines -- path/to/dir [--dot]
+use bq2cst::routines::Project;
+use std::path::Path;
+
+fn collect(dir: &Path, files: &mut Vec<(String, String)>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect(&path, files);
+        } else if path.extension().is_some_and(|e| e == "sql") {
+            let code = std::fs::read_to_string(&path).unwrap();
+            files.push((path.display().to_string(), code));
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let mut files = Vec::new();
+    collect(Path::new(&args[1]), &mut files);
+    files.sort();
+    let files: Vec<(&str, &str)> = files
+        .iter()
+        .map(|(path, code)| (path.as_str(), code.as_str()))
+        .collect();
+    let project = Project::new(&files);
+    if args.iter().any(|a| a == "--dot") {
+        print!("{}", project.to_dot());
+    } else {
+        println!("{}", serde_json::to_string_pretty(&project).unwrap());
+    }
+}
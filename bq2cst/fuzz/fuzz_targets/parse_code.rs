@@ -0,0 +1,12 @@
+#![no_main]
+
+use bq2cst::lexer::Lexer;
+use bq2cst::parser::Parser;
+use libfuzzer_sys::fuzz_target;
+
+// any input must result in either `Ok` or `Err`, never a panic
+fuzz_target!(|code: &str| {
+    if let Ok(tokens) = Lexer::new(code).tokenize_code() {
+        let _ = Parser::new(tokens).parse_code();
+    }
+});
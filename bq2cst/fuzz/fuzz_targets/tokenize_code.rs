@@ -0,0 +1,9 @@
+#![no_main]
+
+use bq2cst::lexer::Lexer;
+use libfuzzer_sys::fuzz_target;
+
+// any input must result in either `Ok` or `Err`, never a panic
+fuzz_target!(|code: &str| {
+    let _ = Lexer::new(code).tokenize_code();
+});
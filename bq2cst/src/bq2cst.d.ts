@@ -1,9 +1,38 @@
 // NOTE:
-// typescript_custom_section is available (https://wasm-bindgen.github.io/wasm-bindgen/reference/attributes/on-rust-exports/typescript_custom_section.html)
-// but i prefer to maintain this file as plain .d.ts file.
-
-export function parse(code: string): UnknownNode[];
-export function tokenize(code: string): Token[];
+// This file is generated from src/schema.rs.
+// Do not edit it directly, run `make dts` instead.
+
+export function parse(code: string, strict?: boolean): UnknownNode[];
+export function tokenize(code: string, strict?: boolean): Token[];
+export function parseDocument(code: string, compact?: boolean): Document;
+export function nodeTypes(): string[];
+export function exportTree(
+  code: string,
+  format: "dot" | "mermaid" | "sexp",
+): string[];
+export function dbtCalls(code: string): DbtCall[];
+export function parseRelaxed(code: string): UnknownNode[];
+export function parseSqlx(code: string): UnknownNode[];
+export function sqlxTables(code: string): SqlxTable[];
+export function resolveVariables(code: string): Resolution;
+export function controlFlowGraph(code: string): string;
+export function checkControlFlow(code: string): Diagnostic[];
+export function validateLabels(code: string): Diagnostic[];
+export function analyzeRoutines(files: Record<string, string>): Project;
+export function schemaCatalog(code: string): Catalog;
+export function diffTables(before: string, after: string): Migration;
+export function tableSchema(code: string): TableFieldSchema[];
+export function columnDefinitions(fields: TableFieldSchema[]): string;
+export function extractOptions(code: string): OptionsClause[];
+export function decodeLiterals(code: string): DecodedLiteral[];
+export function renderTemplate(
+  code: string,
+  substitutions?: Record<string, string>,
+): string;
+export function parseTemplate(
+  code: string,
+  substitutions?: Record<string, string>,
+): UnknownNode[];
 
 export type UnknownNode =
   | AccessOperator
@@ -25,15 +54,15 @@ export type UnknownNode =
   | AssertStatement
   | Asterisk
   | BasePipeOperator
-  | BinaryOperator
   | BeginStatement
   | BetweenOperator
+  | BinaryOperator
   | BooleanLiteral
   | BreakContinueStatement
+  | CallStatement
   | CallingFunction
   | CallingTableFunction
   | CallingUnnest
-  | CallStatement
   | CaseExpr
   | CaseExprArm
   | CaseStatement
@@ -56,9 +85,9 @@ export type UnknownNode =
   | DotOperator
   | DropRowAccessPolicyStatement
   | DropStatement
+  | EOF
   | ElseIfClause
   | EmptyStruct
-  | EOF
   | ExecuteStatement
   | ExportDataStatement
   | ExportModelStatement
@@ -75,15 +104,26 @@ export type UnknownNode =
   | GroupedIdentWithOptions
   | GroupedPattern
   | GroupedStatement
-  | GroupedTypeDeclarationOrConstraints
   | GroupedType
+  | GroupedTypeDeclarationOrConstraints
+  | IdentWithOptions
   | Identifier
   | IfStatement
-  | IdentWithOptions
   | InOperator
   | InsertStatement
   | IntervalLiteral
   | IsDistinctFromOperator
+  | JinjaAttribute
+  | JinjaCall
+  | JinjaFilter
+  | JinjaGroup
+  | JinjaKeywordArg
+  | JinjaLiteral
+  | JinjaName
+  | JinjaOperator
+  | JinjaPair
+  | JinjaStatement
+  | JinjaSubscript
   | JoinOperator
   | JoinPipeOperator
   | Keyword
@@ -113,19 +153,21 @@ export type UnknownNode =
   | PatternClause
   | PatternQuantifier
   | PipeStatement
+  | PivotConfig
   | PivotOperator
   | PivotPipeOperator
-  | PivotConfig
   | RaiseStatement
   | RangeLiteral
   | RenameColumnClause
   | RepeatStatement
   | RevokeStatement
-  | SelectStatement
   | SelectPipeOperator
+  | SelectStatement
   | SetOperator
   | SetStatement
   | SingleTokenStatement
+  | SqlxBlock
+  | SqlxInterpolation
   | StandAloneExpr
   | StringLiteral
   | StructLiteral
@@ -133,12 +175,14 @@ export type UnknownNode =
   | TableSampleClause
   | TableSamplePipeOperator
   | TableSampleRatio
+  | TemplateBlock
   | TemplateExpr
-  | TemplateExprEnd
   | TemplateExprContinue
+  | TemplateExprEnd
   | TemplateExprStart
-  | TransactionStatement
+  | TemplateFragment
   | TrainingDataCustomHolidayClause
+  | TransactionStatement
   | TruncateStatement
   | Type
   | TypeDeclaration
@@ -146,8 +190,8 @@ export type UnknownNode =
   | UndropStatement
   | UnionPipeOperator
   | UnpivotConfig
-  | UnpivotPipeOperator
   | UnpivotOperator
+  | UnpivotPipeOperator
   | UpdateStatement
   | WhenClause
   | WhileStatement
@@ -157,8 +201,8 @@ export type UnknownNode =
   | WindowSpecification
   | WithClause
   | WithOffsetClause
-  | WithPipeOperator
   | WithPartitionColumnsClause
+  | WithPipeOperator
   | WithQuery
   | XXXByExprs;
 
@@ -168,6 +212,239 @@ export type Token = {
   literal: string;
 };
 
+export type Document =
+  | { version: number; compact: false; stmts: UnknownNode[] }
+  | { version: number; compact: true; stmts: CompactNode[] };
+
+export type CompactNode = {
+  token: Token | null;
+  // index of nodeTypes()
+  tag: number;
+  children?: { [key: string]: CompactNode | CompactNode[] };
+};
+
+export type DbtCall = {
+  kind: "Ref" | "Source" | "Var" | "Config";
+  token: Token;
+  // null means the argument is not a string literal
+  args: (string | null)[];
+  kwargs: [string, string | null][];
+};
+
+export type SqlxTable = {
+  // ref | resolve
+  token: Token;
+  // null means it is not given as a string literal
+  database: string | null;
+  schema: string | null;
+  name: string | null;
+};
+
+export type Span = {
+  line: number;
+  column: number;
+  // the position just after the last character
+  end_line: number;
+  end_column: number;
+};
+
+export type Diagnostic = {
+  severity: "Error" | "Warning";
+  code: string;
+  message: string;
+  span: Span;
+};
+
+export type Variable = {
+  token: Token;
+  kind: "Declare" | "For" | "Parameter" | "OutParameter";
+};
+
+export type Reference = {
+  token: Token;
+  // index of variables
+  variable: number;
+  write: boolean;
+};
+
+export type Resolution = {
+  variables: Variable[];
+  references: Reference[];
+  diagnostics: Diagnostic[];
+};
+
+export type RoutineKind =
+  | "Function"
+  | "AggregateFunction"
+  | "TableFunction"
+  | "Procedure";
+
+export type Parameter = {
+  name: string;
+  mode: "In" | "Out" | "InOut";
+  type: string;
+};
+
+export type Routine = {
+  file: string;
+  name: string[];
+  kind: RoutineKind;
+  temp: boolean;
+  parameters: Parameter[];
+  returns: string | null;
+  language: string;
+  span: Span;
+};
+
+export type Call = {
+  file: string;
+  // index of routines, null means the top level of the file
+  caller: number | null;
+  name: string[];
+  kind: RoutineKind;
+  args: number;
+  // index of routines, null means it is not defined in the project
+  routine: number | null;
+  span: Span;
+};
+
+export type FileDiagnostic = Diagnostic & { file: string };
+
+export type Project = {
+  routines: Routine[];
+  // calls of built-in functions (e.g. `COUNT(*)`) are not included
+  calls: Call[];
+  diagnostics: FileDiagnostic[];
+};
+
+// `value` is the original code of the expression
+export type OptionEntry = {
+  name: string;
+  value: string;
+};
+
+export type Column = {
+  name: string;
+  // null means it is not declared
+  type: string | null;
+  not_null: boolean;
+  default: string | null;
+  options: OptionEntry[];
+};
+
+export type Constraint = {
+  name: string | null;
+  kind: "PrimaryKey" | "ForeignKey";
+  columns: string[];
+  referenced_table: string[] | null;
+  referenced_columns: string[];
+  enforced: boolean;
+};
+
+export type Table = {
+  name: string;
+  kind:
+    | "Table"
+    | "ExternalTable"
+    | "Snapshot"
+    | "View"
+    | "MaterializedView";
+  columns: Column[];
+  constraints: Constraint[];
+  partition_by: string | null;
+  cluster_by: string[];
+  options: OptionEntry[];
+};
+
+export type Dataset = {
+  // null means the default project
+  project: string | null;
+  name: string;
+  options: OptionEntry[];
+  tables: Table[];
+};
+
+export type Catalog = {
+  datasets: Dataset[];
+  diagnostics: Diagnostic[];
+};
+
+export type Migration = {
+  statements: string[];
+  // changes which ALTER TABLE cannot apply, the table has to be recreated
+  rebuild_reasons: string[];
+  // RENAME COLUMN which may be used instead of DROP COLUMN and ADD COLUMN (keeping the data)
+  possible_renames: string[];
+  // changes which cannot be migrated (e.g. the type of a column is unknown)
+  warnings: string[];
+};
+
+export type TableFieldSchema = {
+  name: string;
+  type: string;
+  mode?: "NULLABLE" | "REQUIRED" | "REPEATED";
+  fields?: TableFieldSchema[];
+  description?: string;
+  maxLength?: string;
+  precision?: string;
+  scale?: string;
+  collation?: string;
+  defaultValueExpression?: string;
+  rangeElementType?: { type: string };
+};
+
+export type OptionValue =
+  | { type: "String"; value: string }
+  | { type: "Int"; value: number }
+  | { type: "Float"; value: number }
+  | { type: "Bool"; value: boolean }
+  | { type: "Null" }
+  | { type: "Array"; value: OptionValue[] }
+  | { type: "Struct"; value: { name: string | null; value: OptionValue }[] }
+  | { type: "Literal"; value: Literal }
+  | { type: "Expression"; value: string };
+
+export type OptionsClause = {
+  target: string[];
+  column: string[];
+  span: Span;
+  options: Record<string, OptionValue>;
+};
+
+export type Literal =
+  | { type: "String"; value: string }
+  | { type: "Bytes"; value: number[] }
+  | { type: "Int"; value: number }
+  | { type: "Float"; value: number }
+  | { type: "Bool"; value: boolean }
+  | { type: "Null" }
+  | {
+      type:
+        | "Date"
+        | "Datetime"
+        | "Time"
+        | "Timestamp"
+        | "Json"
+        | "Numeric"
+        | "BigNumeric";
+      value: string;
+    }
+  | {
+      type: "Range";
+      value: { element_type: string; start: string | null; end: string | null };
+    }
+  | {
+      type: "Interval";
+      value: { months: number; days: number; nanos: number };
+    };
+
+export type DecodedLiteral = {
+  span: Span;
+  // null if the literal is invalid (e.g. `NUMERIC 'abc'`), see `error`
+  literal: Literal | null;
+  error: string | null;
+};
+
 interface BaseNode {
   token: Token | null;
   node_type: string;
@@ -183,9 +460,22 @@ export type NodeVecChild = { NodeVec: UnknownNode[] };
 // ----- sub types of BaseNode (abstract) -----
 export type CallingFunctionGeneral = Expr & {
   children: {
-    func: { Node: IdentifierGeneral & UnknownNode };
+    func: {
+      Node:
+        | (IdentifierGeneral & UnknownNode)
+        | GroupedExpr
+        | FunctionChain;
+    };
     distinct?: NodeChild;
-    args?: { NodeVec: ((Expr & UnknownNode) | SelectStatement)[] };
+    args?: {
+      NodeVec: (
+        | (Expr & UnknownNode)
+        | SelectStatement
+        | SetOperator
+        | CastArgument
+        | ExtractArgument
+      )[];
+    };
     ignore_nulls?: NodeVecChild;
     orderby?: NodeChild;
     limit?: NodeChild;
@@ -199,7 +489,9 @@ export type Expr = BaseNode & {
   token: Token;
   children: {
     as?: { Node: Keyword };
-    alias?: { Node: Identifier };
+    alias?: { Node: Expr & UnknownNode };
+    // only in UNPIVOT operator
+    row_value_alias?: NodeChild;
     comma?: NodeChild;
     order?: NodeChild;
     null_order?: NodeVecChild;
@@ -208,7 +500,7 @@ export type Expr = BaseNode & {
 
 export type FromItemExpr = Expr & {
   children: {
-    with_offset: NodeChild;
+    with_offset?: NodeChild;
     pivot?: { Node: PivotOperator };
     unpivot?: { Node: UnpivotOperator };
     match_recognize?: NodeChild;
@@ -246,6 +538,10 @@ export type PipeOperator = BaseNode & {
   };
 };
 
+export type JinjaExpr = BaseNode & {
+  token: Token;
+};
+
 // ----- sub types of BaseNode (concrete) -----
 export type AddColumnClause = BaseNode & {
   node_type: "AddColumnClause";
@@ -290,7 +586,7 @@ export type AlterColumnStatement = BaseNode & {
     if_exists?: NodeVecChild;
     ident: NodeChild;
     // SET
-    set: NodeChild;
+    set?: NodeChild;
     options?: NodeChild;
     data_type?: NodeVecChild;
     type?: NodeChild;
@@ -440,16 +736,16 @@ export type AssertStatement = XXXStatement & {
   node_type: "AssertStatement";
   children: {
     expr: NodeChild;
-    as: NodeChild;
-    description: NodeChild;
+    as?: NodeChild;
+    description?: NodeChild;
   };
 };
 
 export type Asterisk = Expr & {
   node_type: "Asterisk";
   children: {
-    except?: { Node: KeywordWithGroupedXXX & UnknownNode };
-    replace?: { Node: KeywordWithGroupedXXX & UnknownNode };
+    except?: { Node: KeywordWithGroupedXXX };
+    replace?: { Node: KeywordWithGroupedXXX };
     order: undefined;
     null_order: undefined;
   };
@@ -703,7 +999,7 @@ export type CreateSchemaStatement = XXXStatement & {
     what: NodeChild;
     if_not_exists?: NodeVecChild;
     ident: NodeChild;
-    default_collate: NodeChild;
+    default_collate?: NodeChild;
     with_connection?: NodeChild;
     options?: NodeChild;
   };
@@ -735,8 +1031,8 @@ export type CreateTableStatement = XXXStatement & {
     what: NodeChild;
     if_not_exists?: NodeVecChild;
     ident: NodeChild;
-    like_or_copy: NodeChild;
-    source_table: NodeChild;
+    like_or_copy?: NodeChild;
+    source_table?: NodeChild;
     column_schema_group?: NodeChild;
     default_collate?: NodeChild;
     clone?: NodeChild;
@@ -777,7 +1073,7 @@ export type DeclareStatement = XXXStatement & {
 export type DeleteStatement = XXXStatement & {
   node_type: "DeleteStatement";
   children: {
-    from: NodeChild;
+    from?: NodeChild;
     table_name: NodeChild;
     where: NodeChild;
   };
@@ -795,8 +1091,8 @@ export type DifferentialPrivacyClause = BaseNode & {
 export type DotOperator = IdentifierGeneral & {
   node_type: "DotOperator";
   children: {
-    left: { Node: IdentifierGeneral & UnknownNode };
-    right: { Node: IdentifierGeneral & UnknownNode };
+    left: { Node: (IdentifierGeneral & UnknownNode) | FunctionChain };
+    right: { Node: (IdentifierGeneral & UnknownNode) | Asterisk };
   };
 };
 
@@ -853,7 +1149,7 @@ export type ExecuteStatement = XXXStatement & {
   children: {
     immediate: NodeChild;
     sql_expr: NodeChild;
-    into: NodeChild;
+    into?: NodeChild;
     using?: NodeChild;
   };
 };
@@ -883,8 +1179,8 @@ export type ExtractArgument = BaseNode & {
   children: {
     extract_datepart: NodeChild;
     extract_from: NodeChild;
-    at_time_zone: NodeVecChild;
-    time_zone: NodeChild;
+    at_time_zone?: NodeVecChild;
+    time_zone?: NodeChild;
   };
 };
 
@@ -952,7 +1248,7 @@ export type GroupByExprs = BaseNode & {
 export type GroupedExpr = FromItemExpr & {
   node_type: "GroupedExpr";
   children: {
-    expr: { Node: Expr & UnknownNode };
+    expr: { Node: (Expr & UnknownNode) | KeywordSequence };
     rparen: NodeChild;
   };
 };
@@ -962,7 +1258,7 @@ export type GroupedExprs = BaseNode & {
   node_type: "GroupedExprs";
   children: {
     // NOTE: contains GroupedExprs in UnpivotConfig. it's a little inconsistent...
-    exprs?: { NodeVec: (Expr & UnknownNode)[] };
+    exprs?: { NodeVec: ((Expr & UnknownNode) | GroupedExprs)[] };
     rparen: NodeChild;
     // only in UNPIVOT operator
     as?: NodeChild;
@@ -998,15 +1294,15 @@ export type GroupedStatement = FromItemExpr &
       with?: { Node: WithClause };
       stmt: NodeChild;
       rparen: NodeChild;
-      orderby: NodeChild;
-      limit: NodeChild;
+      orderby?: NodeChild;
+      limit?: NodeChild;
     };
   };
 
 export type GroupedTypeDeclarationOrConstraints = BaseNode & {
   node_type: "GroupedTypeDeclarationOrConstraints";
   children: {
-    declarations: NodeVecChild;
+    declarations?: NodeVecChild;
     rparen: NodeChild;
   };
 };
@@ -1039,8 +1335,8 @@ export type IfStatement = XXXStatement & {
   children: {
     condition: NodeChild;
     then: NodeChild;
-    elseifs: NodeVecChild;
-    else: NodeChild;
+    elseifs?: NodeVecChild;
+    else?: NodeChild;
     end_if: NodeVecChild;
   };
 };
@@ -1087,15 +1383,101 @@ export type IsDistinctFromOperator = Expr & {
   };
 };
 
+export type JinjaAttribute = JinjaExpr & {
+  node_type: "JinjaAttribute";
+  children: {
+    left: { Node: JinjaExpr & UnknownNode };
+    right: { Node: JinjaName };
+  };
+};
+
+export type JinjaCall = JinjaExpr & {
+  node_type: "JinjaCall";
+  children: {
+    func: { Node: JinjaExpr & UnknownNode };
+    args: { NodeVec: (JinjaExpr & UnknownNode)[] };
+    rparen: { Node: Symbol_ };
+  };
+};
+
+export type JinjaFilter = JinjaExpr & {
+  node_type: "JinjaFilter";
+  children: {
+    left: { Node: JinjaExpr & UnknownNode };
+    filter: { Node: JinjaName | JinjaAttribute | JinjaCall };
+  };
+};
+
+export type JinjaGroup = JinjaExpr & {
+  node_type: "JinjaGroup";
+  children: {
+    exprs: { NodeVec: (JinjaExpr & UnknownNode)[] };
+    end: { Node: Symbol_ };
+  };
+};
+
+export type JinjaKeywordArg = JinjaExpr & {
+  node_type: "JinjaKeywordArg";
+  children: {
+    left: { Node: JinjaExpr & UnknownNode };
+    right: { Node: JinjaExpr & UnknownNode };
+  };
+};
+
+export type JinjaLiteral = JinjaExpr & {
+  node_type: "JinjaLiteral";
+};
+
+export type JinjaName = JinjaExpr & {
+  node_type: "JinjaName";
+};
+
+export type JinjaOperator = JinjaExpr & {
+  node_type: "JinjaOperator";
+  children: {
+    left?: { Node: JinjaExpr & UnknownNode };
+    // is not
+    not?: { Node: Keyword };
+    // not in
+    in?: { Node: Keyword };
+    right: { Node: JinjaExpr & UnknownNode };
+  };
+};
+
+export type JinjaPair = JinjaExpr & {
+  node_type: "JinjaPair";
+  children: {
+    left: { Node: JinjaExpr & UnknownNode };
+    right: { Node: JinjaExpr & UnknownNode };
+  };
+};
+
+export type JinjaStatement = BaseNode & {
+  token: Token;
+  node_type: "JinjaStatement";
+  children: {
+    exprs: { NodeVec: (JinjaExpr & UnknownNode)[] };
+  };
+};
+
+export type JinjaSubscript = JinjaExpr & {
+  node_type: "JinjaSubscript";
+  children: {
+    left: { Node: JinjaExpr & UnknownNode };
+    right: { Node: JinjaExpr & UnknownNode };
+    rbrack: { Node: Symbol_ };
+  };
+};
+
 export type JoinOperator = FromItemExpr & {
   node_type: "JoinOperator";
   children: {
-    join_type: NodeChild;
-    outer: NodeChild;
+    join_type?: NodeChild;
+    outer?: NodeChild;
     left: NodeChild;
     right: NodeChild;
-    on: NodeChild;
-    using: NodeChild;
+    on?: NodeChild;
+    using?: NodeChild;
     order: undefined;
     null_order: undefined;
     comma: undefined;
@@ -1119,7 +1501,7 @@ export type Keyword = BaseNode & {
 export type KeywordSequence = BaseNode & {
   node_type: "KeywordSequence";
   children: {
-    next_keyword: {
+    next_keyword?: {
       Node:
         | Keyword
         | KeywordSequence
@@ -1133,7 +1515,7 @@ export type KeywordSequence = BaseNode & {
 export type KeywordWithExpr = BaseNode & {
   node_type: "KeywordWithExpr";
   children: {
-    expr: { Node: Expr & UnknownNode };
+    expr: { Node: (Expr & UnknownNode) | Keyword | KeywordSequence };
   };
 };
 
@@ -1205,7 +1587,7 @@ export type MatchRecognizeConfig = BaseNode & {
     orderby?: NodeChild;
     measures?: NodeChild;
     skip_rule?: NodeChild;
-    pattern: NodeChild;
+    pattern?: NodeChild;
     define?: NodeChild;
     options?: NodeChild;
     rparen: NodeChild;
@@ -1343,7 +1725,7 @@ export type PivotPipeOperator = PipeOperator & {
   node_type: "PivotPipeOperator";
   children: {
     exprs: undefined;
-    config: { Node: PivotConfig & UnknownNode };
+    config: { Node: PivotConfig };
     as?: NodeChild;
     alias?: NodeChild;
   };
@@ -1353,7 +1735,7 @@ export type PivotOperator = BaseNode & {
   token: Token;
   node_type: "PivotOperator";
   children: {
-    config: { Node: PivotConfig & UnknownNode };
+    config: { Node: PivotConfig };
     as?: NodeChild;
     alias?: NodeChild;
   };
@@ -1467,6 +1849,25 @@ export type SingleTokenStatement = XXXStatement & {
   node_type: "SingleTokenStatement";
 };
 
+export type SqlxBlock = BaseNode & {
+  token: Token;
+  node_type: "SqlxBlock";
+  children: {
+    // pre_operations and post_operations
+    stmts?: { NodeVec: (XXXStatement & UnknownNode)[] };
+  };
+};
+
+export type SqlxInterpolation = IdentifierGeneral & {
+  node_type: "SqlxInterpolation";
+  children: {
+    // simple JavaScript expressions are parsed as Jinja
+    expr?: { Node: JinjaExpr & UnknownNode };
+    // interpolations glued to other characters (e.g. `${self()}_tmp`)
+    exprs?: { NodeVec: (JinjaExpr & UnknownNode)[] };
+  };
+};
+
 export type StandAloneExpr = BaseNode & {
   token: null;
   node_type: "StandAloneExpr";
@@ -1522,8 +1923,21 @@ export type TableSampleRatio = BaseNode & {
   };
 };
 
+export type TemplateBlock = BaseNode & {
+  token: Token;
+  node_type: "TemplateBlock";
+  children: {
+    jinja?: { Node: JinjaStatement };
+    body: NodeVecChild;
+    end: NodeChild;
+  };
+};
+
 export type TemplateExpr = IdentifierGeneral & {
   node_type: "TemplateExpr";
+  children: {
+    jinja?: { Node: (JinjaExpr & UnknownNode) | JinjaStatement };
+  };
 };
 
 export type TemplateExprEnd = BaseNode & {
@@ -1544,12 +1958,22 @@ export type TemplateExprStart = FromItemExpr & {
   token: Token;
   node_type: "TemplateExprStart";
   children: {
+    jinja?: { Node: JinjaStatement };
     exprs: NodeVecChild;
     continues: NodeVecChild;
     end: NodeChild;
   };
 };
 
+export type TemplateFragment = BaseNode & {
+  token: Token;
+  node_type: "TemplateFragment";
+  children: {
+    keywords?: NodeVecChild;
+    exprs?: NodeVecChild;
+  };
+};
+
 export type TransactionStatement = XXXStatement & {
   node_type: "TransactionStatement";
   children: {
@@ -1578,7 +2002,8 @@ export type Type = BaseNode & {
   token: Token;
   node_type: "Type";
   children: {
-    type?: NodeChild; // ANY TYPE
+    // ANY TYPE
+    type?: NodeChild;
     type_declaration?: NodeChild;
     parameter?: NodeChild;
     not_null?: NodeVecChild;
@@ -1596,10 +2021,10 @@ export type Type = BaseNode & {
 };
 
 export type TypeDeclaration = BaseNode & {
-  token: Token;
+  token: Token | null;
   node_type: "TypeDeclaration";
   children: {
-    in_out: NodeChild;
+    in_out?: NodeChild;
     type: NodeChild;
     comma?: NodeChild;
   };
@@ -1646,7 +2071,7 @@ export type UnpivotPipeOperator = PipeOperator & {
   node_type: "UnpivotPipeOperator";
   children: {
     exprs: undefined;
-    config: { Node: UnpivotConfig & UnknownNode };
+    config: { Node: UnpivotConfig };
     as?: NodeChild;
     alias?: NodeChild;
   };
@@ -1656,8 +2081,8 @@ export type UnpivotOperator = BaseNode & {
   token: Token;
   node_type: "UnpivotOperator";
   children: {
-    include_or_exclude_nulls: NodeVecChild;
-    config: { Node: UnpivotConfig & UnknownNode };
+    include_or_exclude_nulls?: NodeVecChild;
+    config: { Node: UnpivotConfig };
     as?: NodeChild;
     alias?: NodeChild;
   };
@@ -1669,7 +2094,7 @@ export type UpdateStatement = XXXStatement & {
     table_name?: NodeChild;
     set: NodeChild;
     from?: NodeChild;
-    where: NodeChild;
+    where?: NodeChild;
   };
 };
 
@@ -1678,8 +2103,8 @@ export type WhenClause = BaseNode & {
   children: {
     not?: NodeChild;
     matched: NodeChild;
-    by_target_or_source: NodeVecChild;
-    and: NodeChild;
+    by_target_or_source?: NodeVecChild;
+    and?: NodeChild;
     then: NodeChild;
   };
 };
@@ -1727,9 +2152,9 @@ export type WindowSpecification = BaseNode & {
   node_type: "WindowSpecification";
   children: {
     name?: NodeChild;
-    partitionby: NodeChild;
-    orderby: NodeChild;
-    frame: NodeChild;
+    partitionby?: NodeChild;
+    orderby?: NodeChild;
+    frame?: NodeChild;
     rparen: NodeChild;
   };
 };
@@ -1764,7 +2189,7 @@ export type WithPartitionColumnsClause = BaseNode & {
   node_type: "WithPartitionColumnsClause";
   children: {
     partition_columns: NodeVecChild;
-    column_schema_group: NodeChild;
+    column_schema_group?: NodeChild;
   };
 };
 
@@ -1774,7 +2199,7 @@ export type WithQuery = BaseNode & {
   children: {
     as: { Node: Keyword };
     stmt: { Node: GroupedStatement };
-    comma: NodeChild;
+    comma?: NodeChild;
   };
 };
 
@@ -0,0 +1,452 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::Node;
+use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::literal::string_value;
+use crate::token::quote_identifier;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum TypeKind {
+    Int64,
+    Float64,
+    Numeric,
+    BigNumeric,
+    Bool,
+    String,
+    Bytes,
+    Date,
+    Datetime,
+    Time,
+    Timestamp,
+    Geography,
+    Json,
+    Interval,
+    Array(Box<BqType>),
+    Struct(Vec<StructField>),
+    Range(Box<BqType>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StructField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: BqType,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BqType {
+    pub kind: TypeKind,
+    // STRING(max_length), BYTES(max_length), NUMERIC(precision, scale)
+    pub max_length: Option<u64>,
+    pub precision: Option<u64>,
+    pub scale: Option<u64>,
+    pub collation: Option<String>,
+    pub not_null: bool,
+    // the original code of the expression
+    pub default: Option<String>,
+    // OPTIONS(description = '...')
+    pub description: Option<String>,
+}
+
+// https://cloud.google.com/bigquery/docs/reference/rest/v2/tables#TableFieldSchema
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableFieldSchema {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<TableFieldSchema>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    // int64 values are encoded as strings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precision: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_value_expression: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range_element_type: Option<RangeElementType>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RangeElementType {
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+fn error(node: &Node, message: String) -> BQ2CSTError {
+    match node.token {
+        Some(token) => BQ2CSTError::from_token(&token, message),
+        None => BQ2CSTError::new(0, 0, message),
+    }
+}
+
+fn scalar_kind(name: &str) -> Option<TypeKind> {
+    let kind = match name.to_uppercase().as_str() {
+        "INT64" | "INT" | "SMALLINT" | "INTEGER" | "BIGINT" | "TINYINT" | "BYTEINT" => {
+            TypeKind::Int64
+        }
+        "FLOAT64" | "FLOAT" => TypeKind::Float64,
+        "NUMERIC" | "DECIMAL" => TypeKind::Numeric,
+        "BIGNUMERIC" | "BIGDECIMAL" => TypeKind::BigNumeric,
+        "BOOL" | "BOOLEAN" => TypeKind::Bool,
+        "STRING" => TypeKind::String,
+        "BYTES" => TypeKind::Bytes,
+        "DATE" => TypeKind::Date,
+        "DATETIME" => TypeKind::Datetime,
+        "TIME" => TypeKind::Time,
+        "TIMESTAMP" => TypeKind::Timestamp,
+        "GEOGRAPHY" => TypeKind::Geography,
+        "JSON" => TypeKind::Json,
+        "INTERVAL" => TypeKind::Interval,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+fn parse_u64(value: &Option<String>, name: &str) -> BQ2CSTResult<Option<u64>> {
+    match value {
+        Some(value) => match value.parse() {
+            Ok(n) => Ok(Some(n)),
+            Err(_) => Err(BQ2CSTError::new(
+                0,
+                0,
+                format!("Invalid {}: {}", name, value),
+            )),
+        },
+        None => Ok(None),
+    }
+}
+
+impl BqType {
+    pub fn new(kind: TypeKind) -> BqType {
+        BqType {
+            kind,
+            max_length: None,
+            precision: None,
+            scale: None,
+            collation: None,
+            not_null: false,
+            default: None,
+            description: None,
+        }
+    }
+    // `node` is `Type`
+    pub fn from_node(node: &Node) -> BQ2CSTResult<BqType> {
+        let name = node.token.map(|t| t.literal).unwrap_or_default();
+        let element = || -> BQ2CSTResult<BqType> {
+            match node
+                .get_node("type_declaration")
+                .and_then(|n| n.get_node("type"))
+            {
+                Some(type_) => BqType::from_node(type_),
+                None => Err(error(
+                    node,
+                    format!("The element type is not found: {}", name),
+                )),
+            }
+        };
+        let kind = match name.to_uppercase().as_str() {
+            "ARRAY" => TypeKind::Array(Box::new(element()?)),
+            "RANGE" => TypeKind::Range(Box::new(element()?)),
+            "STRUCT" => {
+                let mut fields = Vec::new();
+                if let Some(group) = node.get_node("type_declaration") {
+                    for declaration in group.get_node_vec("declarations") {
+                        let type_ = match declaration.get_node("type") {
+                            Some(type_) => BqType::from_node(type_)?,
+                            None => continue,
+                        };
+                        fields.push(StructField {
+                            name: declaration
+                                .token
+                                .map(|t| t.literal.trim_matches('`').to_string())
+                                .unwrap_or_default(),
+                            type_,
+                        });
+                    }
+                }
+                TypeKind::Struct(fields)
+            }
+            _ => match scalar_kind(name) {
+                Some(kind) => kind,
+                None => return Err(error(node, format!("Unknown type: {}", name))),
+            },
+        };
+        let mut res = BqType::new(kind);
+        let parameters = match node.get_node("parameter") {
+            Some(parameter) => parameter
+                .get_node_vec("exprs")
+                .iter()
+                .map(|e| e.token.and_then(|t| t.literal.parse::<u64>().ok()))
+                .collect::<Option<Vec<u64>>>()
+                .ok_or_else(|| error(node, "Invalid type parameters.".to_string()))?,
+            None => Vec::new(),
+        };
+        match (&res.kind, parameters.as_slice()) {
+            (_, []) => (),
+            (TypeKind::String | TypeKind::Bytes, [max_length]) => {
+                res.max_length = Some(*max_length)
+            }
+            (TypeKind::Numeric | TypeKind::BigNumeric, [precision]) => {
+                res.precision = Some(*precision)
+            }
+            (TypeKind::Numeric | TypeKind::BigNumeric, [precision, scale]) => {
+                res.precision = Some(*precision);
+                res.scale = Some(*scale);
+            }
+            _ => return Err(error(node, format!("Invalid type parameters: {}", name))),
+        }
+        res.collation = node
+            .get_node("collate")
+            .and_then(|n| n.get_node("expr"))
+            .and_then(string_value);
+        res.not_null = !node.get_node_vec("not_null").is_empty();
+        res.default = node
+            .get_node("default")
+            .and_then(|n| n.get_node("expr"))
+            .map(Node::source_text);
+        if let Some(group) = node.get_node("options").and_then(|n| n.get_node("group")) {
+            for expr in group.get_node_vec("exprs") {
+                let name = expr.get_node("left").and_then(|n| n.token);
+                if name.is_some_and(|t| t.literal.eq_ignore_ascii_case("description")) {
+                    res.description = expr.get_node("right").and_then(string_value);
+                }
+            }
+        }
+        Ok(res)
+    }
+    pub fn to_field(&self, name: &str) -> BQ2CSTResult<TableFieldSchema> {
+        let (type_, mode) = match &self.kind {
+            TypeKind::Array(element) => {
+                if matches!(element.kind, TypeKind::Array(_)) {
+                    return Err(BQ2CSTError::new(
+                        0,
+                        0,
+                        format!("Nested arrays are not supported: {}", name),
+                    ));
+                }
+                let mut field = element.to_field(name)?;
+                field.mode = Some("REPEATED".to_string());
+                field.description = self.description.clone();
+                field.default_value_expression = self.default.clone();
+                return Ok(field);
+            }
+            _ if self.not_null => (self.type_name(), Some("REQUIRED".to_string())),
+            _ => (self.type_name(), None),
+        };
+        let mut fields = Vec::new();
+        if let TypeKind::Struct(struct_fields) = &self.kind {
+            for f in struct_fields {
+                // e.g. `STRUCT<INT64>`, REST API requires names
+                if f.name.is_empty() {
+                    return Err(BQ2CSTError::new(
+                        0,
+                        0,
+                        format!("Unnamed struct fields are not supported: {}", name),
+                    ));
+                }
+                fields.push(f.type_.to_field(&f.name)?);
+            }
+        }
+        Ok(TableFieldSchema {
+            name: name.to_string(),
+            type_: type_.to_string(),
+            mode,
+            fields,
+            description: self.description.clone(),
+            max_length: self.max_length.map(|n| n.to_string()),
+            precision: self.precision.map(|n| n.to_string()),
+            scale: self.scale.map(|n| n.to_string()),
+            collation: self.collation.clone(),
+            default_value_expression: self.default.clone(),
+            range_element_type: match &self.kind {
+                TypeKind::Range(element) => Some(RangeElementType {
+                    type_: element.type_name().to_string(),
+                }),
+                _ => None,
+            },
+        })
+    }
+    pub fn from_field(field: &TableFieldSchema) -> BQ2CSTResult<BqType> {
+        if field.name.is_empty() {
+            return Err(BQ2CSTError::new(
+                0,
+                0,
+                "The name of the field is empty.".to_string(),
+            ));
+        }
+        let kind = match field.type_.to_uppercase().as_str() {
+            "RECORD" | "STRUCT" => {
+                let mut fields = Vec::new();
+                for f in &field.fields {
+                    fields.push(StructField {
+                        name: f.name.clone(),
+                        type_: BqType::from_field(f)?,
+                    });
+                }
+                TypeKind::Struct(fields)
+            }
+            "RANGE" => {
+                let element = match &field.range_element_type {
+                    Some(element) => scalar_kind(&element.type_),
+                    None => None,
+                };
+                match element {
+                    Some(kind) => TypeKind::Range(Box::new(BqType::new(kind))),
+                    None => {
+                        return Err(BQ2CSTError::new(
+                            0,
+                            0,
+                            format!("Invalid range element type: {}", field.name),
+                        ))
+                    }
+                }
+            }
+            name => match scalar_kind(name) {
+                Some(kind) => kind,
+                None => return Err(BQ2CSTError::new(0, 0, format!("Unknown type: {}", name))),
+            },
+        };
+        let mut res = BqType::new(kind);
+        res.max_length = parse_u64(&field.max_length, "maxLength")?;
+        res.precision = parse_u64(&field.precision, "precision")?;
+        res.scale = parse_u64(&field.scale, "scale")?;
+        res.collation = field.collation.clone();
+        res.description = field.description.clone();
+        res.default = field.default_value_expression.clone();
+        match field.mode.as_deref().map(|m| m.to_uppercase()).as_deref() {
+            Some("REQUIRED") => res.not_null = true,
+            Some("REPEATED") => {
+                let mut element = res;
+                // they belong to the array
+                element.description = None;
+                element.default = None;
+                let mut array = BqType::new(TypeKind::Array(Box::new(element.clone())));
+                array.description = field.description.clone();
+                array.default = field.default_value_expression.clone();
+                res = array;
+            }
+            _ => (),
+        }
+        Ok(res)
+    }
+    // the name used in REST API
+    fn type_name(&self) -> &'static str {
+        match &self.kind {
+            TypeKind::Int64 => "INTEGER",
+            TypeKind::Float64 => "FLOAT",
+            TypeKind::Numeric => "NUMERIC",
+            TypeKind::BigNumeric => "BIGNUMERIC",
+            TypeKind::Bool => "BOOLEAN",
+            TypeKind::String => "STRING",
+            TypeKind::Bytes => "BYTES",
+            TypeKind::Date => "DATE",
+            TypeKind::Datetime => "DATETIME",
+            TypeKind::Time => "TIME",
+            TypeKind::Timestamp => "TIMESTAMP",
+            TypeKind::Geography => "GEOGRAPHY",
+            TypeKind::Json => "JSON",
+            TypeKind::Interval => "INTERVAL",
+            TypeKind::Array(_) => "ARRAY",
+            TypeKind::Struct(_) => "RECORD",
+            TypeKind::Range(_) => "RANGE",
+        }
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!(
+        "'{}'",
+        s.replace('\\', "\\\\")
+            .replace('\'', "\\'")
+            .replace('\n', "\\n")
+    )
+}
+
+// GoogleSQL, e.g. `STRUCT<a STRING(10) NOT NULL, b ARRAY<INT64>>`
+impl fmt::Display for BqType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            TypeKind::Array(element) => write!(f, "ARRAY<{}>", element)?,
+            TypeKind::Range(element) => write!(f, "RANGE<{}>", element)?,
+            TypeKind::Struct(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|field| match field.name.as_str() {
+                        "" => field.type_.to_string(),
+                        name => format!("{} {}", quote_identifier(name), field.type_),
+                    })
+                    .collect::<Vec<_>>();
+                write!(f, "STRUCT<{}>", fields.join(", "))?
+            }
+            TypeKind::Int64 => write!(f, "INT64")?,
+            TypeKind::Float64 => write!(f, "FLOAT64")?,
+            TypeKind::Bool => write!(f, "BOOL")?,
+            _ => write!(f, "{}", self.type_name())?,
+        }
+        match (self.max_length, self.precision, self.scale) {
+            (Some(n), _, _) => write!(f, "({})", n)?,
+            (_, Some(p), Some(s)) => write!(f, "({}, {})", p, s)?,
+            (_, Some(p), None) => write!(f, "({})", p)?,
+            _ => (),
+        }
+        if let Some(collation) = &self.collation {
+            write!(f, " COLLATE {}", quote(collation))?;
+        }
+        if let Some(default) = &self.default {
+            write!(f, " DEFAULT {}", default)?;
+        }
+        if self.not_null {
+            write!(f, " NOT NULL")?;
+        }
+        if let Some(description) = &self.description {
+            write!(f, " OPTIONS(description = {})", quote(description))?;
+        }
+        Ok(())
+    }
+}
+
+// the schema of `CREATE TABLE`, table constraints are ignored
+pub fn table_schema(stmt: &Node) -> BQ2CSTResult<Vec<TableFieldSchema>> {
+    let group = match stmt.get_node("column_schema_group") {
+        Some(group) => group,
+        None => {
+            return Err(error(
+                stmt,
+                "The column definitions are not found.".to_string(),
+            ))
+        }
+    };
+    let mut res = Vec::new();
+    for declaration in group.get_node_vec("declarations") {
+        if let (Some(token), Some(type_)) = (declaration.token, declaration.get_node("type")) {
+            res.push(BqType::from_node(type_)?.to_field(token.literal.trim_matches('`'))?);
+        }
+    }
+    Ok(res)
+}
+
+// column definitions of `CREATE TABLE`, e.g. `a INT64, b STRING`
+pub fn column_definitions(fields: &[TableFieldSchema]) -> BQ2CSTResult<String> {
+    let mut res = Vec::new();
+    for field in fields {
+        res.push(format!(
+            "{} {}",
+            quote_identifier(&field.name),
+            BqType::from_field(field)?
+        ));
+    }
+    Ok(res.join(",\n"))
+}
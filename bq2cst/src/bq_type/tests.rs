@@ -0,0 +1,124 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+const DDL: &str = "\
+CREATE TABLE ds.t (
+  id INT64 NOT NULL OPTIONS(description = 'the \\'id\\''),
+  name STRING(10) COLLATE 'und:ci',
+  amount NUMERIC(10, 2) DEFAULT 0,
+  tags ARRAY<STRING> OPTIONS(description = r'\\d'),
+  period RANGE<DATE>,
+  s STRUCT<a BIGNUMERIC(40) NOT NULL, `b` ARRAY<STRUCT<c BOOL>>>,
+  `select` STRUCT<`from` DATE, `a-b` TIME>,
+  PRIMARY KEY (id) NOT ENFORCED
+);";
+
+fn parse(code: &str) -> Vec<Node<'_>> {
+    let tokens = Lexer::new(code).tokenize_code().unwrap();
+    Parser::new(tokens).parse_code().unwrap()
+}
+
+#[test]
+fn test_from_node() {
+    let stmts = parse(DDL);
+    let declarations = stmts[0]
+        .get_node("column_schema_group")
+        .unwrap()
+        .get_node_vec("declarations");
+    let types = declarations
+        .iter()
+        .filter_map(|d| d.get_node("type"))
+        .map(|t| BqType::from_node(t).unwrap())
+        .collect::<Vec<_>>();
+    let mut expected = BqType::new(TypeKind::Int64);
+    expected.not_null = true;
+    expected.description = Some("the 'id'".to_string());
+    assert_eq!(types[0], expected);
+    let mut expected = BqType::new(TypeKind::String);
+    expected.max_length = Some(10);
+    expected.collation = Some("und:ci".to_string());
+    assert_eq!(types[1], expected);
+    assert_eq!(
+        types.iter().map(|t| t.to_string()).collect::<Vec<_>>(),
+        vec![
+            "INT64 NOT NULL OPTIONS(description = 'the \\'id\\'')",
+            "STRING(10) COLLATE 'und:ci'",
+            "NUMERIC(10, 2) DEFAULT 0",
+            "ARRAY<STRING> OPTIONS(description = '\\\\d')",
+            "RANGE<DATE>",
+            "STRUCT<a BIGNUMERIC(40) NOT NULL, b ARRAY<STRUCT<c BOOL>>>",
+            "STRUCT<`from` DATE, `a-b` TIME>",
+        ]
+    );
+}
+
+#[test]
+fn test_table_schema() {
+    let stmts = parse(DDL);
+    let fields = table_schema(&stmts[0]).unwrap();
+    let json = serde_json::to_value(&fields).unwrap();
+    let expected = serde_json::json!([
+        {"name": "id", "type": "INTEGER", "mode": "REQUIRED", "description": "the 'id'"},
+        {"name": "name", "type": "STRING", "maxLength": "10", "collation": "und:ci"},
+        {"name": "amount", "type": "NUMERIC", "precision": "10", "scale": "2", "defaultValueExpression": "0"},
+        {"name": "tags", "type": "STRING", "mode": "REPEATED", "description": "\\d"},
+        {"name": "period", "type": "RANGE", "rangeElementType": {"type": "DATE"}},
+        {"name": "s", "type": "RECORD", "fields": [
+            {"name": "a", "type": "BIGNUMERIC", "mode": "REQUIRED", "precision": "40"},
+            {"name": "b", "type": "RECORD", "mode": "REPEATED", "fields": [
+                {"name": "c", "type": "BOOLEAN"}
+            ]}
+        ]},
+        {"name": "select", "type": "RECORD", "fields": [
+            {"name": "from", "type": "DATE"},
+            {"name": "a-b", "type": "TIME"}
+        ]}
+    ]);
+    assert_eq!(json, expected);
+
+    // round trip
+    let definitions = column_definitions(&fields).unwrap();
+    let code = format!("CREATE TABLE ds.t ({});", definitions);
+    let stmts = parse(&code);
+    assert_eq!(table_schema(&stmts[0]).unwrap(), fields);
+}
+
+#[test]
+fn test_from_field() {
+    let fields: Vec<TableFieldSchema> = serde_json::from_value(serde_json::json!([
+        {"name": "a", "type": "INT64", "mode": "NULLABLE"},
+        {"name": "b", "type": "FLOAT", "mode": "REPEATED"},
+        {"name": "c", "type": "STRUCT", "fields": [{"name": "d", "type": "TIMESTAMP"}]},
+    ]))
+    .unwrap();
+    assert_eq!(
+        column_definitions(&fields).unwrap(),
+        "a INT64,\nb ARRAY<FLOAT64>,\nc STRUCT<d TIMESTAMP>"
+    );
+    let invalid = TableFieldSchema {
+        name: "x".to_string(),
+        type_: "UNKNOWN".to_string(),
+        ..Default::default()
+    };
+    assert!(BqType::from_field(&invalid).is_err());
+    let unnamed: Vec<TableFieldSchema> = serde_json::from_value(serde_json::json!([
+        {"name": "s", "type": "RECORD", "fields": [{"name": "", "type": "INT64"}]},
+    ]))
+    .unwrap();
+    assert!(column_definitions(&unnamed).is_err());
+}
+
+#[test]
+fn test_unnamed_fields() {
+    let stmts = parse("CREATE TABLE ds.t (s STRUCT<INT64, `select` STRING>);");
+    let type_ = stmts[0]
+        .get_node("column_schema_group")
+        .unwrap()
+        .get_node_vec("declarations")[0]
+        .get_node("type")
+        .unwrap();
+    let type_ = BqType::from_node(type_).unwrap();
+    assert_eq!(type_.to_string(), "STRUCT<INT64, `select` STRING>");
+    assert!(table_schema(&stmts[0]).is_err());
+}
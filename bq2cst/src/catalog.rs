@@ -0,0 +1,676 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{Node, NodeType};
+use crate::diagnostic::Diagnostic;
+use crate::token::{Keyword, Token, TokenKind};
+use serde::Serialize;
+
+// `value` is the original code of the expression
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OptionEntry {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TableKind {
+    Table,
+    ExternalTable,
+    Snapshot,
+    View,
+    MaterializedView,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Column {
+    pub name: String,
+    // `None` means it is not declared (e.g. `CREATE VIEW ... AS SELECT`)
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub not_null: bool,
+    pub default: Option<String>,
+    pub options: Vec<OptionEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ConstraintKind {
+    PrimaryKey,
+    ForeignKey,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Constraint {
+    pub name: Option<String>,
+    pub kind: ConstraintKind,
+    pub columns: Vec<String>,
+    pub referenced_table: Option<Vec<String>>,
+    pub referenced_columns: Vec<String>,
+    pub enforced: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Table {
+    pub name: String,
+    pub kind: TableKind,
+    pub columns: Vec<Column>,
+    // column level constraints are included
+    pub constraints: Vec<Constraint>,
+    pub partition_by: Option<String>,
+    pub cluster_by: Vec<String>,
+    pub options: Vec<OptionEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Dataset {
+    // `None` means the default project
+    pub project: Option<String>,
+    pub name: String,
+    pub options: Vec<OptionEntry>,
+    pub tables: Vec<Table>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Catalog {
+    pub datasets: Vec<Dataset>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+// the leftmost token of `proj.ds.t`
+fn first_token<'a>(node: &Node<'a>) -> Option<Token<'a>> {
+    match node.get_node("left") {
+        Some(left) => first_token(left),
+        None => node.token,
+    }
+}
+
+// `[project, dataset]` or `[dataset]`
+fn split_dataset(path: &[String]) -> Option<(Option<&String>, &String)> {
+    match path {
+        [name] => Some((None, name)),
+        [project, name] => Some((Some(project), name)),
+        _ => None,
+    }
+}
+
+// a missing project is the default project, which may be any project
+fn same_project(a: Option<&String>, b: Option<&String>) -> bool {
+    a.is_none() || b.is_none() || a == b
+}
+
+// whether `a` and `b` (e.g. `["ds"]` and `["proj", "ds"]`) may be the same dataset
+pub fn same_dataset(a: &[String], b: &[String]) -> bool {
+    match (split_dataset(a), split_dataset(b)) {
+        (Some((a_project, a_name)), Some((b_project, b_name))) => {
+            a_name == b_name && same_project(a_project, b_project)
+        }
+        _ => a == b,
+    }
+}
+
+fn name_of(node: &Node) -> String {
+    node.token
+        .map(|t| t.literal.trim_matches('`').to_string())
+        .unwrap_or_default()
+}
+
+fn options_of(node: Option<&Node>) -> Vec<OptionEntry> {
+    let exprs = match node.and_then(|n| n.get_node("group")) {
+        Some(group) => group.get_node_vec("exprs"),
+        None => return Vec::new(),
+    };
+    exprs
+        .iter()
+        .filter_map(|expr| {
+            Some(OptionEntry {
+                name: name_of(expr.get_node("left")?).to_lowercase(),
+                value: expr.get_node("right")?.source_text(),
+            })
+        })
+        .collect()
+}
+
+// `SET OPTIONS`, NULL means the default value
+fn merge_options(options: &mut Vec<OptionEntry>, new_options: Vec<OptionEntry>) {
+    for entry in new_options {
+        options.retain(|o| o.name != entry.name);
+        if !entry.value.eq_ignore_ascii_case("NULL") {
+            options.push(entry);
+        }
+    }
+}
+
+fn is_enforced(node: Option<&Node>) -> bool {
+    node.and_then(|n| n.token)
+        .is_some_and(|t| t.is(Keyword::ENFORCED))
+}
+
+fn identifiers(node: Option<&Node>) -> Vec<String> {
+    match node {
+        Some(node) => node.get_node_vec("exprs").iter().map(name_of).collect(),
+        None => Vec::new(),
+    }
+}
+
+// `REFERENCES ds.t(col)`
+fn set_references(constraint: &mut Constraint, references: Option<&Node>) {
+    if let Some(expr) = references.and_then(|n| n.get_node("expr")) {
+        constraint.referenced_table = expr.get_node("func").and_then(|f| f.identifier_path());
+        constraint.referenced_columns = expr.get_node_vec("args").iter().map(name_of).collect();
+    }
+}
+
+fn column_of(declaration: &Node, constraints: &mut Vec<Constraint>) -> Column {
+    let name = name_of(declaration);
+    let type_ = declaration.get_node("type");
+    if let Some(type_) = type_ {
+        let key = match (type_.get_node("primarykey"), type_.get_node("references")) {
+            (Some(_), _) => Some(ConstraintKind::PrimaryKey),
+            (_, Some(_)) => Some(ConstraintKind::ForeignKey),
+            _ => None,
+        };
+        if let Some(kind) = key {
+            let mut constraint = Constraint {
+                name: None,
+                kind,
+                columns: vec![name.clone()],
+                referenced_table: None,
+                referenced_columns: Vec::new(),
+                enforced: is_enforced(type_.get_node("enforced")),
+            };
+            set_references(&mut constraint, type_.get_node("references"));
+            constraints.push(constraint);
+        }
+    }
+    Column {
+        name,
+        type_: type_.map(Node::type_text),
+        not_null: type_.is_some_and(|t| !t.get_node_vec("not_null").is_empty()),
+        default: type_
+            .and_then(|t| t.get_node("default"))
+            .and_then(|d| d.get_node("expr"))
+            .map(Node::source_text),
+        options: options_of(type_.and_then(|t| t.get_node("options"))),
+    }
+}
+
+fn constraint_of(node: &Node) -> Constraint {
+    let kind = match node.token {
+        Some(t) if t.is(Keyword::FOREIGN) => ConstraintKind::ForeignKey,
+        _ => ConstraintKind::PrimaryKey,
+    };
+    let mut constraint = Constraint {
+        name: node.get_node("ident").map(name_of),
+        kind,
+        columns: identifiers(node.get_node("columns")),
+        referenced_table: None,
+        referenced_columns: Vec::new(),
+        enforced: is_enforced(node.get_node("enforced")),
+    };
+    set_references(&mut constraint, node.get_node("references"));
+    constraint
+}
+
+// column names of `SELECT`, types are unknown
+fn select_columns(stmt: &Node) -> Vec<Column> {
+    match stmt.node_type {
+        NodeType::SetOperator => {
+            return stmt
+                .get_node("left")
+                .map(select_columns)
+                .unwrap_or_default()
+        }
+        NodeType::GroupedStatement => {
+            return stmt
+                .get_node("stmt")
+                .map(select_columns)
+                .unwrap_or_default()
+        }
+        NodeType::SelectStatement => (),
+        _ => return Vec::new(),
+    }
+    stmt.get_node_vec("exprs")
+        .iter()
+        .filter_map(|expr| {
+            let name = match expr.get_node("alias") {
+                Some(alias) => name_of(alias),
+                None => match expr.node_type {
+                    NodeType::Identifier => name_of(expr),
+                    NodeType::DotOperator => name_of(expr.get_node("right")?),
+                    _ => return None,
+                },
+            };
+            Some(Column {
+                name,
+                type_: None,
+                not_null: false,
+                default: None,
+                options: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+fn find_column<'c>(columns: &'c mut [Column], name: &str) -> Option<&'c mut Column> {
+    columns
+        .iter_mut()
+        .find(|c| c.name.eq_ignore_ascii_case(name))
+}
+
+impl Catalog {
+    pub fn new() -> Catalog {
+        Catalog::default()
+    }
+    // statements are applied in order
+    pub fn from_stmts(stmts: &[Node]) -> Catalog {
+        let mut catalog = Catalog::new();
+        for stmt in stmts {
+            catalog.apply(stmt);
+        }
+        catalog
+    }
+    pub fn dataset(&self, path: &[String]) -> Option<&Dataset> {
+        let (project, name) = split_dataset(path)?;
+        self.datasets
+            .iter()
+            .find(|d| &d.name == name && same_project(project, d.project.as_ref()))
+    }
+    pub fn table(&self, path: &[String]) -> Option<&Table> {
+        let (name, dataset) = path.split_last()?;
+        self.dataset(dataset)?
+            .tables
+            .iter()
+            .find(|t| &t.name == name)
+    }
+    fn dataset_index(&self, path: &[String]) -> Option<usize> {
+        let dataset = self.dataset(path)?;
+        self.datasets.iter().position(|d| std::ptr::eq(d, dataset))
+    }
+    fn table_mut(&mut self, path: &[String]) -> Option<&mut Table> {
+        let (name, dataset) = path.split_last()?;
+        let i = self.dataset_index(dataset)?;
+        self.datasets[i].tables.iter_mut().find(|t| &t.name == name)
+    }
+    fn error(&mut self, code: &'static str, node: &Node, message: String) {
+        if let Some(token) = first_token(node) {
+            self.diagnostics
+                .push(Diagnostic::error(code, &token, message));
+        }
+    }
+    pub fn apply(&mut self, stmt: &Node) {
+        match stmt.node_type {
+            NodeType::CreateSchemaStatement => self.create_schema(stmt),
+            NodeType::CreateTableStatement | NodeType::CreateViewStatement => {
+                self.create_table(stmt)
+            }
+            NodeType::AlterTableStatement => self.alter_table(stmt),
+            NodeType::DropStatement => self.drop(stmt),
+            _ => (),
+        }
+    }
+    fn create_schema(&mut self, stmt: &Node) {
+        let ident = match stmt.get_node("ident") {
+            Some(ident) => ident,
+            None => return,
+        };
+        let path = ident.identifier_path().unwrap_or_default();
+        let (project, name) = match path.as_slice() {
+            [name] => (None, name.clone()),
+            [project, name] => (Some(project.clone()), name.clone()),
+            _ => return,
+        };
+        if self.dataset(&path).is_some() {
+            if stmt.get_node_vec("if_not_exists").is_empty() {
+                self.error(
+                    "already-exists",
+                    ident,
+                    format!("The dataset already exists: {}", path.join(".")),
+                );
+            }
+            return;
+        }
+        self.datasets.push(Dataset {
+            project,
+            name,
+            options: options_of(stmt.get_node("options")),
+            tables: Vec::new(),
+        });
+    }
+    fn create_table(&mut self, stmt: &Node) {
+        let ident = match stmt.get_node("ident") {
+            Some(ident) => ident,
+            None => return,
+        };
+        // temporary tables are not a part of datasets
+        if stmt.get_node("temp").is_some() {
+            return;
+        }
+        let path = ident.identifier_path().unwrap_or_default();
+        let (name, dataset) = match path.split_last() {
+            Some((name, dataset)) if !dataset.is_empty() && dataset.len() <= 2 => {
+                (name.clone(), dataset.to_vec())
+            }
+            _ => {
+                self.error(
+                    "unqualified-name",
+                    ident,
+                    format!("The dataset is not specified: {}", path.join(".")),
+                );
+                return;
+            }
+        };
+        let kind = match stmt.node_type {
+            NodeType::CreateViewStatement if stmt.get_node("materialized").is_some() => {
+                TableKind::MaterializedView
+            }
+            NodeType::CreateViewStatement => TableKind::View,
+            _ if stmt.get_node("external").is_some() => TableKind::ExternalTable,
+            _ if stmt.get_node("snapshot").is_some() => TableKind::Snapshot,
+            _ => TableKind::Table,
+        };
+        let mut table = Table {
+            name,
+            kind,
+            columns: Vec::new(),
+            constraints: Vec::new(),
+            partition_by: stmt.get_node("partitionby").map(|n| {
+                n.get_node_vec("exprs")
+                    .iter()
+                    .map(Node::source_text)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }),
+            cluster_by: stmt
+                .get_node("clusterby")
+                .map(|n| {
+                    n.get_node_vec("exprs")
+                        .iter()
+                        .map(Node::source_text)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            options: options_of(stmt.get_node("options")),
+        };
+        // LIKE, COPY and CLONE
+        let source = stmt
+            .get_node("source_table")
+            .or_else(|| stmt.get_node("clone").and_then(|n| n.get_node("expr")))
+            .and_then(|n| n.identifier_path());
+        if let Some(source) = source {
+            match self.table(&source) {
+                Some(source) => {
+                    table.columns = source.columns.clone();
+                    table.constraints = source.constraints.clone();
+                }
+                None => self.error(
+                    "unknown-table",
+                    ident,
+                    format!("The table is not defined: {}", source.join(".")),
+                ),
+            }
+        }
+        if let Some(group) = stmt.get_node("column_schema_group") {
+            for declaration in group.get_node_vec("declarations") {
+                match declaration.node_type {
+                    NodeType::Constraint => table.constraints.push(constraint_of(declaration)),
+                    _ => table
+                        .columns
+                        .push(column_of(declaration, &mut table.constraints)),
+                }
+            }
+        } else if let Some(list) = stmt.get_node("column_name_list") {
+            // CREATE VIEW v (a OPTIONS(...), b)
+            for ident in list.get_node_vec("idents") {
+                table.columns.push(Column {
+                    name: name_of(ident),
+                    type_: None,
+                    not_null: false,
+                    default: None,
+                    options: options_of(ident.get_node("options")),
+                });
+            }
+        } else if let Some(query) = stmt.get_node("as").and_then(|n| n.get_node("stmt")) {
+            table.columns = select_columns(query);
+        }
+        let i = match self.dataset_index(&dataset) {
+            Some(i) => i,
+            // datasets may be created outside of the DDL
+            None => {
+                let (project, name) = match dataset.as_slice() {
+                    [name] => (None, name.clone()),
+                    [project, name] => (Some(project.clone()), name.clone()),
+                    _ => return,
+                };
+                self.datasets.push(Dataset {
+                    project,
+                    name,
+                    options: Vec::new(),
+                    tables: Vec::new(),
+                });
+                self.datasets.len() - 1
+            }
+        };
+        let tables = &mut self.datasets[i].tables;
+        match tables.iter().position(|t| t.name == table.name) {
+            Some(j) if !stmt.get_node_vec("or_replace").is_empty() => tables[j] = table,
+            Some(_) if !stmt.get_node_vec("if_not_exists").is_empty() => (),
+            Some(_) => self.error(
+                "already-exists",
+                ident,
+                format!("The table already exists: {}", path.join(".")),
+            ),
+            None => tables.push(table),
+        }
+    }
+    fn alter_table(&mut self, stmt: &Node) {
+        let ident = match stmt.get_node("ident") {
+            Some(ident) => ident,
+            None => return,
+        };
+        let path = ident.identifier_path().unwrap_or_default();
+        let mut errors = Vec::new();
+        let table = match self.table_mut(&path) {
+            Some(table) => table,
+            None => {
+                if stmt.get_node_vec("if_exists").is_empty() {
+                    self.error(
+                        "unknown-table",
+                        ident,
+                        format!("The table is not defined: {}", path.join(".")),
+                    );
+                }
+                return;
+            }
+        };
+        if stmt.get_node("set").is_some() {
+            merge_options(&mut table.options, options_of(stmt.get_node("options")));
+        }
+        for clause in stmt.get_node_vec("add_columns") {
+            let declaration = match clause.get_node("type_declaration") {
+                Some(declaration) => declaration,
+                None => continue,
+            };
+            if find_column(&mut table.columns, &name_of(declaration)).is_some() {
+                if clause.get_node_vec("if_not_exists").is_empty() {
+                    errors.push((
+                        "already-exists",
+                        declaration,
+                        format!("The column already exists: {}", name_of(declaration)),
+                    ));
+                }
+                continue;
+            }
+            let column = column_of(declaration, &mut table.constraints);
+            table.columns.push(column);
+        }
+        for clause in stmt.get_node_vec("add_constraints") {
+            if let Some(constraint) = clause.get_node("what") {
+                table.constraints.push(constraint_of(constraint));
+            }
+        }
+        for clause in stmt.get_node_vec("rename_columns") {
+            let (from, to) = match (
+                clause.get_node("ident"),
+                clause.get_node("to").and_then(|n| n.get_node("expr")),
+            ) {
+                (Some(from), Some(to)) => (from, to),
+                _ => continue,
+            };
+            match find_column(&mut table.columns, &name_of(from)) {
+                Some(column) => column.name = name_of(to),
+                None if clause.get_node("if_exists").is_some() => (),
+                None => errors.push((
+                    "unknown-column",
+                    from,
+                    format!("The column is not defined: {}", name_of(from)),
+                )),
+            }
+        }
+        for clause in stmt.get_node_vec("drop_columns") {
+            let what = clause
+                .get_node("what")
+                .and_then(|n| n.token)
+                .map(|t| t.kind);
+            match (what, clause.get_node("ident")) {
+                (Some(TokenKind::Keyword(Keyword::PRIMARY)), _) => table
+                    .constraints
+                    .retain(|c| c.kind != ConstraintKind::PrimaryKey),
+                (Some(TokenKind::Keyword(Keyword::CONSTRAINT)), Some(name)) => {
+                    let name = name_of(name);
+                    table.constraints.retain(|c| {
+                        c.name
+                            .as_ref()
+                            .is_none_or(|n| !n.eq_ignore_ascii_case(&name))
+                    })
+                }
+                (Some(TokenKind::Keyword(Keyword::COLUMN)), Some(column)) => {
+                    let name = name_of(column);
+                    match table
+                        .columns
+                        .iter()
+                        .position(|c| c.name.eq_ignore_ascii_case(&name))
+                    {
+                        Some(i) => {
+                            table.columns.remove(i);
+                        }
+                        None if !clause.get_node_vec("if_exists").is_empty() => (),
+                        None => errors.push((
+                            "unknown-column",
+                            column,
+                            format!("The column is not defined: {}", name),
+                        )),
+                    }
+                }
+                _ => (),
+            }
+        }
+        if let Some(alter) = stmt.get_node("alter_column_stmt") {
+            if let Some(column_ident) = alter.get_node("ident") {
+                match find_column(&mut table.columns, &name_of(column_ident)) {
+                    Some(column) => {
+                        if let Some(type_) = alter.get_node("type") {
+                            column.type_ = Some(type_.type_text()); // SET DATA TYPE
+                        }
+                        if alter.get_node("options").is_some() {
+                            merge_options(
+                                &mut column.options,
+                                options_of(alter.get_node("options")),
+                            );
+                        }
+                        if let Some(default) =
+                            alter.get_node("default").and_then(|n| n.get_node("expr"))
+                        {
+                            column.default = Some(default.source_text());
+                        }
+                        if !alter.get_node_vec("drop_not_null").is_empty() {
+                            column.not_null = false;
+                        }
+                        if !alter.get_node_vec("drop_default").is_empty() {
+                            column.default = None;
+                        }
+                    }
+                    None if !alter.get_node_vec("if_exists").is_empty() => (),
+                    None => errors.push((
+                        "unknown-column",
+                        column_ident,
+                        format!("The column is not defined: {}", name_of(column_ident)),
+                    )),
+                }
+            }
+        }
+        // RENAME TO
+        if let Some(to) = stmt.get_node("to").and_then(|n| n.get_node("expr")) {
+            if let Some(name) = to.identifier_path().and_then(|p| p.last().cloned()) {
+                table.name = name;
+            }
+        }
+        for (code, node, message) in errors {
+            self.error(code, node, message);
+        }
+    }
+    fn drop(&mut self, stmt: &Node) {
+        let ident = match stmt.get_node("ident") {
+            Some(ident) => ident,
+            None => return,
+        };
+        let path = ident.identifier_path().unwrap_or_default();
+        let what = match stmt.get_node("what").and_then(|n| n.token) {
+            Some(Token {
+                kind: TokenKind::Keyword(keyword),
+                ..
+            }) => keyword,
+            _ => return,
+        };
+        let exists = match what {
+            Keyword::SCHEMA => match self.dataset_index(&path) {
+                Some(i) => {
+                    let cascade = stmt
+                        .get_node("cascade_or_restrict")
+                        .and_then(|n| n.token)
+                        .is_some_and(|t| t.is(Keyword::CASCADE));
+                    if !cascade && !self.datasets[i].tables.is_empty() {
+                        self.error(
+                            "not-empty",
+                            ident,
+                            format!("The dataset is not empty: {}", path.join(".")),
+                        );
+                    } else {
+                        self.datasets.remove(i);
+                    }
+                    true
+                }
+                None => false,
+            },
+            Keyword::TABLE | Keyword::VIEW => {
+                let i = path
+                    .split_last()
+                    .and_then(|(_, dataset)| self.dataset_index(dataset));
+                let name = path.last();
+                match (i, name) {
+                    (Some(i), Some(name)) => {
+                        let tables = &mut self.datasets[i].tables;
+                        let len = tables.len();
+                        tables.retain(|t| &t.name != name);
+                        tables.len() != len
+                    }
+                    _ => false,
+                }
+            }
+            _ => return,
+        };
+        if !exists && stmt.get_node_vec("if_exists").is_empty() {
+            match what {
+                Keyword::SCHEMA => self.error(
+                    "unknown-dataset",
+                    ident,
+                    format!("The dataset is not defined: {}", path.join(".")),
+                ),
+                _ => self.error(
+                    "unknown-table",
+                    ident,
+                    format!("The table is not defined: {}", path.join(".")),
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn catalog(code: &str) -> Catalog {
+    let tokens = Lexer::new(code).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    Catalog::from_stmts(&stmts)
+}
+
+fn path(s: &str) -> Vec<String> {
+    s.split('.').map(|s| s.to_string()).collect()
+}
+
+fn option(name: &str, value: &str) -> OptionEntry {
+    OptionEntry {
+        name: name.to_string(),
+        value: value.to_string(),
+    }
+}
+
+#[test]
+fn test_create() {
+    let catalog = catalog(
+        "\
+CREATE SCHEMA proj.ds OPTIONS(location = 'US', labels = [('a', 'b')]);
+CREATE TABLE ds.t (
+  id INT64 PRIMARY KEY NOT ENFORCED,
+  s STRUCT<a STRING, b ARRAY<INT64>> OPTIONS(description = 'x'),
+  u INT64 NOT NULL,
+  v INT64 DEFAULT 1,
+  CONSTRAINT fk FOREIGN KEY (u) REFERENCES ds.u(id) NOT ENFORCED
+)
+PARTITION BY DATE(ts) CLUSTER BY id, u
+OPTIONS(description = 'table');
+CREATE VIEW proj.ds.v (a OPTIONS(description = 'a'), b) AS SELECT 1, 2;
+CREATE MATERIALIZED VIEW ds.mv AS SELECT 1 AS x;
+CREATE VIEW ds.w AS SELECT id, t.u AS x, 1 + 1 FROM ds.t;
+CREATE TABLE ds.c LIKE ds.t;
+CREATE TEMP TABLE tmp (x INT64);
+",
+    );
+    assert_eq!(catalog.diagnostics, vec![]);
+    let dataset = catalog.dataset(&path("ds")).unwrap();
+    assert_eq!(dataset.project, Some("proj".to_string()));
+    assert_eq!(
+        dataset.options,
+        vec![option("location", "'US'"), option("labels", "[('a', 'b')]")]
+    );
+    let t = catalog.table(&path("proj.ds.t")).unwrap();
+    assert_eq!(
+        t.columns[1],
+        Column {
+            name: "s".to_string(),
+            type_: Some("STRUCT<a STRING, b ARRAY<INT64>>".to_string()),
+            not_null: false,
+            default: None,
+            options: vec![option("description", "'x'")],
+        }
+    );
+    assert!(t.columns[2].not_null);
+    assert_eq!(t.columns[3].default, Some("1".to_string()));
+    assert_eq!(
+        t.constraints,
+        vec![
+            Constraint {
+                name: None,
+                kind: ConstraintKind::PrimaryKey,
+                columns: vec!["id".to_string()],
+                referenced_table: None,
+                referenced_columns: vec![],
+                enforced: false,
+            },
+            Constraint {
+                name: Some("fk".to_string()),
+                kind: ConstraintKind::ForeignKey,
+                columns: vec!["u".to_string()],
+                referenced_table: Some(path("ds.u")),
+                referenced_columns: vec!["id".to_string()],
+                enforced: false,
+            },
+        ]
+    );
+    assert_eq!(t.partition_by, Some("DATE(ts)".to_string()));
+    assert_eq!(t.cluster_by, vec!["id".to_string(), "u".to_string()]);
+    let v = catalog.table(&path("ds.v")).unwrap();
+    assert_eq!(v.kind, TableKind::View);
+    assert_eq!(v.columns[0].options, vec![option("description", "'a'")]);
+    let w = catalog.table(&path("ds.w")).unwrap();
+    let names = w
+        .columns
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["id", "x"]);
+    assert_eq!(catalog.table(&path("ds.c")).unwrap().columns, t.columns);
+    let mv = catalog.table(&path("ds.mv")).unwrap();
+    assert_eq!(mv.kind, TableKind::MaterializedView);
+    assert_eq!(dataset.tables.len(), 5);
+}
+
+#[test]
+fn test_alter_and_drop() {
+    let catalog = catalog(
+        "\
+CREATE TABLE ds.t (a INT64 NOT NULL, b STRING, c STRING) OPTIONS(description = 'x', labels = []);
+ALTER TABLE ds.t SET OPTIONS(description = 'y', labels = NULL);
+ALTER TABLE ds.t ADD COLUMN d DATE, ADD COLUMN IF NOT EXISTS a INT64;
+ALTER TABLE ds.t RENAME COLUMN b TO b2, RENAME COLUMN IF EXISTS x TO y;
+ALTER TABLE ds.t DROP COLUMN c, DROP COLUMN IF EXISTS z;
+ALTER TABLE ds.t ALTER COLUMN a DROP NOT NULL;
+ALTER TABLE ds.t ALTER COLUMN a SET DATA TYPE NUMERIC;
+ALTER TABLE ds.t ADD PRIMARY KEY (a) NOT ENFORCED;
+ALTER TABLE ds.t RENAME TO t2;
+CREATE TABLE ds.dropped (x INT64);
+DROP TABLE ds.dropped;
+CREATE SCHEMA other;
+CREATE TABLE other.t (x INT64);
+DROP SCHEMA other CASCADE;
+",
+    );
+    assert_eq!(catalog.diagnostics, vec![]);
+    assert!(catalog.table(&path("ds.t")).is_none());
+    let t = catalog.table(&path("ds.t2")).unwrap();
+    assert_eq!(t.options, vec![option("description", "'y'")]);
+    let columns = t
+        .columns
+        .iter()
+        .map(|c| (c.name.as_str(), c.type_.as_deref().unwrap(), c.not_null))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        columns,
+        vec![
+            ("a", "NUMERIC", false),
+            ("b2", "STRING", false),
+            ("d", "DATE", false)
+        ]
+    );
+    assert_eq!(t.constraints.len(), 1);
+    assert_eq!(catalog.dataset(&path("ds")).unwrap().tables.len(), 1);
+    assert!(catalog.dataset(&path("other")).is_none());
+}
+
+#[test]
+fn test_diagnostics() {
+    let catalog = catalog(
+        "\
+CREATE SCHEMA ds;
+CREATE SCHEMA ds;
+CREATE SCHEMA IF NOT EXISTS ds;
+CREATE TABLE ds.t (a INT64);
+CREATE TABLE ds.t (a INT64);
+CREATE OR REPLACE TABLE ds.t (a INT64);
+CREATE TABLE t (a INT64);
+ALTER TABLE ds.unknown ADD COLUMN a INT64;
+ALTER TABLE ds.t ADD COLUMN a INT64;
+ALTER TABLE ds.t DROP COLUMN b;
+DROP SCHEMA ds;
+DROP TABLE ds.x;
+DROP SCHEMA x;
+",
+    );
+    let diagnostics = catalog
+        .diagnostics
+        .iter()
+        .map(|d| (d.code, d.span.line, d.span.column))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        diagnostics,
+        vec![
+            ("already-exists", 2, 15),
+            ("already-exists", 5, 14),
+            ("unqualified-name", 7, 14),
+            ("unknown-table", 8, 13),
+            ("already-exists", 9, 29),
+            ("unknown-column", 10, 30),
+            ("not-empty", 11, 13),
+            ("unknown-table", 12, 12),
+            ("unknown-dataset", 13, 13),
+        ]
+    );
+}
@@ -0,0 +1,445 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{Node, NodeType};
+use crate::diagnostic::Diagnostic;
+use crate::labels::label_name;
+use crate::token::Keyword;
+use std::collections::VecDeque;
+
+// NOTE
+// Each statement is a vertex. Compound statements (IF, LOOP, ...) are vertices as well,
+// they represent the evaluation of their conditions.
+// Any statement in `BEGIN ... EXCEPTION` may raise an error, it is represented by an edge
+// from BEGIN to the handler.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    Next,
+    True,  // the condition is satisfied
+    False, // the condition is not satisfied
+    Loop,  // back to the beginning of the loop
+    Break,
+    Continue,
+    Return,
+    Exception,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub kind: EdgeKind,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Vertex<'n, 'a> {
+    Entry,
+    Exit,
+    Statement(&'n Node<'a>),
+    Handler(&'n Node<'a>), // EXCEPTION WHEN ERROR THEN
+    Until(&'n Node<'a>),   // UNTIL of REPEAT
+}
+
+pub struct Cfg<'n, 'a> {
+    pub vertices: Vec<Vertex<'n, 'a>>,
+    pub edges: Vec<Edge>,
+    // indices of `edges` by vertex
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+}
+
+pub const ENTRY: usize = 0;
+pub const EXIT: usize = 1;
+
+type Pending = (usize, EdgeKind);
+
+// loops and labeled blocks
+struct Target {
+    label: Option<String>,
+    continue_to: Option<usize>, // `None` means it is not a loop
+    breaks: Vec<Pending>,
+}
+
+struct Builder<'n, 'a> {
+    cfg: Cfg<'n, 'a>,
+    targets: Vec<Target>,
+    handlers: Vec<usize>,
+}
+
+fn label_of(node: &Node) -> Option<String> {
+    let token = node.get_node("leading_label")?.token?;
+    Some(label_name(&token))
+}
+
+impl<'n, 'a> Builder<'n, 'a> {
+    fn add(&mut self, vertex: Vertex<'n, 'a>, pending: Vec<Pending>) -> usize {
+        let id = self.cfg.vertices.len();
+        self.cfg.vertices.push(vertex);
+        self.connect(pending, id);
+        id
+    }
+    fn connect(&mut self, pending: Vec<Pending>, to: usize) {
+        for (from, kind) in pending {
+            self.cfg.edges.push(Edge { from, to, kind });
+        }
+    }
+    // returns edges which leave the statements
+    fn stmts(&mut self, stmts: &'n [Node<'a>], mut pending: Vec<Pending>) -> Vec<Pending> {
+        for stmt in stmts {
+            if stmt.node_type != NodeType::EOF {
+                pending = self.stmt(stmt, pending);
+            }
+        }
+        pending
+    }
+    fn keyword_stmts(&mut self, node: Option<&'n Node<'a>>, pending: Vec<Pending>) -> Vec<Pending> {
+        match node {
+            Some(node) => self.stmts(node.get_node_vec("stmts"), pending),
+            None => pending,
+        }
+    }
+    fn loop_body(
+        &mut self,
+        node: &'n Node<'a>,
+        stmts: &'n [Node<'a>],
+        pending: Vec<Pending>,
+        continue_to: usize,
+    ) -> (Vec<Pending>, Vec<Pending>) {
+        self.targets.push(Target {
+            label: label_of(node),
+            continue_to: Some(continue_to),
+            breaks: Vec::new(),
+        });
+        let exits = self.stmts(stmts, pending);
+        let breaks = self.targets.pop().map(|t| t.breaks).unwrap_or_default();
+        (exits, breaks)
+    }
+    fn stmt(&mut self, node: &'n Node<'a>, pending: Vec<Pending>) -> Vec<Pending> {
+        let id = self.add(Vertex::Statement(node), pending);
+        match node.node_type {
+            NodeType::IfStatement => {
+                let mut exits =
+                    self.keyword_stmts(node.get_node("then"), vec![(id, EdgeKind::True)]);
+                let mut otherwise = (id, EdgeKind::False);
+                for elseif in node.get_node_vec("elseifs") {
+                    let elseif_id = self.add(Vertex::Statement(elseif), vec![otherwise]);
+                    exits.extend(
+                        self.keyword_stmts(
+                            elseif.get_node("then"),
+                            vec![(elseif_id, EdgeKind::True)],
+                        ),
+                    );
+                    otherwise = (elseif_id, EdgeKind::False);
+                }
+                match node.get_node("else") {
+                    Some(else_) => exits.extend(self.keyword_stmts(Some(else_), vec![otherwise])),
+                    None => exits.push(otherwise),
+                }
+                exits
+            }
+            NodeType::CaseStatement => {
+                let mut exits = Vec::new();
+                let mut otherwise = (id, EdgeKind::Next);
+                let mut has_else = false;
+                for arm in node.get_node_vec("arms") {
+                    if arm.get_node("expr").is_none() {
+                        // ELSE
+                        has_else = true;
+                        exits.extend(self.stmts(arm.get_node_vec("stmts"), vec![otherwise]));
+                        break;
+                    }
+                    let arm_id = self.add(Vertex::Statement(arm), vec![otherwise]);
+                    exits.extend(
+                        self.stmts(arm.get_node_vec("stmts"), vec![(arm_id, EdgeKind::True)]),
+                    );
+                    otherwise = (arm_id, EdgeKind::False);
+                }
+                if !has_else {
+                    exits.push(otherwise);
+                }
+                exits
+            }
+            NodeType::LoopStatement => {
+                let (exits, breaks) = self.loop_body(
+                    node,
+                    node.get_node_vec("stmts"),
+                    vec![(id, EdgeKind::Next)],
+                    id,
+                );
+                self.back(exits, id);
+                breaks
+            }
+            NodeType::WhileStatement | NodeType::ForStatement => {
+                let body = node
+                    .get_node("do")
+                    .map(|n| n.get_node_vec("stmts"))
+                    .unwrap_or_default();
+                let (exits, mut breaks) =
+                    self.loop_body(node, body, vec![(id, EdgeKind::True)], id);
+                self.back(exits, id);
+                breaks.push((id, EdgeKind::False));
+                breaks
+            }
+            NodeType::RepeatStatement => {
+                // ITERATE jumps to UNTIL
+                let until_id = self.cfg.vertices.len();
+                let until = match node.get_node("until") {
+                    Some(until) => until,
+                    None => return vec![(id, EdgeKind::Next)],
+                };
+                self.cfg.vertices.push(Vertex::Until(until));
+                let (exits, mut breaks) = self.loop_body(
+                    node,
+                    node.get_node_vec("stmts"),
+                    vec![(id, EdgeKind::Next)],
+                    until_id,
+                );
+                self.connect(exits, until_id);
+                self.connect(vec![(until_id, EdgeKind::False)], id);
+                breaks.push((until_id, EdgeKind::True));
+                breaks
+            }
+            NodeType::BreakContinueStatement => {
+                let label = node
+                    .get_node("label")
+                    .and_then(|n| n.token)
+                    .map(|t| label_name(&t));
+                let is_continue = node
+                    .token
+                    .is_some_and(|t| t.in_(&[Keyword::CONTINUE, Keyword::ITERATE]));
+                // BREAK without a label exits the innermost loop (not a block)
+                let target = self.targets.iter_mut().rev().find(|t| match &label {
+                    Some(label) => t.label.as_ref() == Some(label),
+                    None => t.continue_to.is_some(),
+                });
+                match (target, is_continue) {
+                    (Some(target), false) => target.breaks.push((id, EdgeKind::Break)),
+                    (
+                        Some(Target {
+                            continue_to: Some(to),
+                            ..
+                        }),
+                        true,
+                    ) => {
+                        let to = *to;
+                        self.cfg.edges.push(Edge {
+                            from: id,
+                            to,
+                            kind: EdgeKind::Continue,
+                        });
+                    }
+                    // the label is not found, see `labels` module
+                    _ => (),
+                }
+                vec![]
+            }
+            NodeType::SingleTokenStatement if node.token.is_some_and(|t| t.is(Keyword::RETURN)) => {
+                self.connect(vec![(id, EdgeKind::Return)], EXIT);
+                vec![]
+            }
+            NodeType::RaiseStatement => {
+                let to = self.handlers.last().copied().unwrap_or(EXIT);
+                self.connect(vec![(id, EdgeKind::Exception)], to);
+                vec![]
+            }
+            NodeType::BeginStatement => {
+                let handler = node.get_node("then");
+                let handler_id = handler.map(|h| {
+                    let handler_id = self.cfg.vertices.len();
+                    self.cfg.vertices.push(Vertex::Handler(h));
+                    handler_id
+                });
+                self.targets.push(Target {
+                    label: label_of(node),
+                    continue_to: None,
+                    breaks: Vec::new(),
+                });
+                if let Some(handler_id) = handler_id {
+                    self.handlers.push(handler_id);
+                }
+                let mut exits = self.stmts(node.get_node_vec("stmts"), vec![(id, EdgeKind::Next)]);
+                if let Some(handler_id) = handler_id {
+                    self.handlers.pop();
+                    self.connect(vec![(id, EdgeKind::Exception)], handler_id);
+                    exits.extend(self.keyword_stmts(handler, vec![(handler_id, EdgeKind::Next)]));
+                }
+                exits.extend(self.targets.pop().map(|t| t.breaks).unwrap_or_default());
+                exits
+            }
+            _ => vec![(id, EdgeKind::Next)],
+        }
+    }
+    // the end of the loop body
+    fn back(&mut self, exits: Vec<Pending>, to: usize) {
+        let exits = exits
+            .into_iter()
+            .map(|(from, kind)| match kind {
+                EdgeKind::Next => (from, EdgeKind::Loop),
+                kind => (from, kind),
+            })
+            .collect();
+        self.connect(exits, to);
+    }
+    fn finish(self) -> Cfg<'n, 'a> {
+        let mut cfg = self.cfg;
+        cfg.outgoing = vec![Vec::new(); cfg.vertices.len()];
+        cfg.incoming = vec![Vec::new(); cfg.vertices.len()];
+        for (i, edge) in cfg.edges.iter().enumerate() {
+            cfg.outgoing[edge.from].push(i);
+            cfg.incoming[edge.to].push(i);
+        }
+        cfg
+    }
+}
+
+impl<'n, 'a> Cfg<'n, 'a> {
+    pub fn new(stmts: &'n [Node<'a>]) -> Cfg<'n, 'a> {
+        let mut builder = Builder {
+            cfg: Cfg {
+                vertices: vec![Vertex::Entry, Vertex::Exit],
+                edges: Vec::new(),
+                outgoing: Vec::new(),
+                incoming: Vec::new(),
+            },
+            targets: Vec::new(),
+            handlers: Vec::new(),
+        };
+        let exits = builder.stmts(stmts, vec![(ENTRY, EdgeKind::Next)]);
+        builder.connect(exits, EXIT);
+        builder.finish()
+    }
+    pub fn successors(&self, id: usize) -> impl Iterator<Item = &Edge> {
+        self.outgoing[id].iter().map(move |i| &self.edges[*i])
+    }
+    pub fn predecessors(&self, id: usize) -> impl Iterator<Item = &Edge> {
+        self.incoming[id].iter().map(move |i| &self.edges[*i])
+    }
+    fn reachable(&self, from: usize, forward: bool) -> Vec<bool> {
+        let mut visited = vec![false; self.vertices.len()];
+        self.visit(from, forward, &mut visited);
+        visited
+    }
+    // marks vertices which can be reached from `from` (skipping visited ones)
+    fn visit(&self, from: usize, forward: bool, visited: &mut [bool]) {
+        let mut queue = VecDeque::from([from]);
+        visited[from] = true;
+        while let Some(id) = queue.pop_front() {
+            let edges = match forward {
+                true => &self.outgoing[id],
+                false => &self.incoming[id],
+            };
+            for edge in edges.iter().map(|i| &self.edges[*i]) {
+                let next = if forward { edge.to } else { edge.from };
+                if !visited[next] {
+                    visited[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    // vertices which cannot be reached from the entry
+    pub fn unreachable(&self) -> Vec<usize> {
+        let visited = self.reachable(ENTRY, true);
+        (0..self.vertices.len()).filter(|i| !visited[*i]).collect()
+    }
+    // reachable loops which never reach the exit (neither normally nor by an error)
+    pub fn infinite_loops(&self) -> Vec<usize> {
+        let from_entry = self.reachable(ENTRY, true);
+        let to_exit = self.reachable(EXIT, false);
+        (0..self.vertices.len())
+            .filter(|i| from_entry[*i] && !to_exit[*i])
+            .filter(|i| match self.vertices[*i] {
+                Vertex::Statement(n) => matches!(
+                    n.node_type,
+                    NodeType::LoopStatement
+                        | NodeType::WhileStatement
+                        | NodeType::RepeatStatement
+                        | NodeType::ForStatement
+                ),
+                _ => false,
+            })
+            .collect()
+    }
+    // the first statement of each unreachable region and infinite loops
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut res = Vec::new();
+        // in source order, anything reached from a reported statement belongs to its region
+        // (an unreachable loop has a predecessor, its back edge)
+        let mut visited = self.reachable(ENTRY, true);
+        let mut unreachable: Vec<_> = self
+            .unreachable()
+            .into_iter()
+            .filter_map(|id| match self.vertices[id] {
+                Vertex::Statement(Node {
+                    token: Some(token), ..
+                }) => Some((id, token)),
+                _ => None,
+            })
+            .collect();
+        unreachable.sort_by_key(|(_, t)| (t.line, t.column));
+        for (id, token) in unreachable {
+            if visited[id] {
+                continue;
+            }
+            self.visit(id, true, &mut visited);
+            res.push(Diagnostic::warning(
+                "unreachable-code",
+                token,
+                "This statement is never executed.".to_string(),
+            ));
+        }
+        for id in self.infinite_loops() {
+            if let Vertex::Statement(Node {
+                token: Some(token), ..
+            }) = self.vertices[id]
+            {
+                res.push(Diagnostic::warning(
+                    "infinite-loop",
+                    token,
+                    "This loop never exits.".to_string(),
+                ));
+            }
+        }
+        res.sort_by_key(|d| (d.span.line, d.span.column));
+        res
+    }
+    fn label(&self, id: usize) -> String {
+        let (name, node) = match self.vertices[id] {
+            Vertex::Entry => return "ENTRY".to_string(),
+            Vertex::Exit => return "EXIT".to_string(),
+            Vertex::Statement(n) => (format!("{:?}", n.node_type), n),
+            Vertex::Handler(n) => ("EXCEPTION".to_string(), n),
+            Vertex::Until(n) => ("UNTIL".to_string(), n),
+        };
+        match node.token {
+            Some(t) => format!("{}\\n{}:{}", name, t.line, t.column),
+            None => name,
+        }
+    }
+    // Graphviz DOT. unreachable vertices are dashed
+    pub fn to_dot(&self) -> String {
+        let reachable = self.reachable(ENTRY, true);
+        let mut res = vec!["digraph {".to_string(), "  node [shape=box];".to_string()];
+        for (id, reachable) in reachable.into_iter().enumerate() {
+            let style = if reachable { "" } else { ", style=dashed" };
+            res.push(format!(
+                "  n{} [label=\"{}\"{}];",
+                id,
+                self.label(id),
+                style
+            ));
+        }
+        for edge in &self.edges {
+            match edge.kind {
+                EdgeKind::Next => res.push(format!("  n{} -> n{};", edge.from, edge.to)),
+                kind => res.push(format!(
+                    "  n{} -> n{} [label=\"{:?}\"];",
+                    edge.from, edge.to, kind
+                )),
+            }
+        }
+        res.push("}".to_string());
+        res.join("\n") + "\n"
+    }
+}
@@ -0,0 +1,152 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn parse(code: &str) -> Vec<Node<'_>> {
+    let tokens = Lexer::new(code).tokenize_code().unwrap();
+    Parser::new(tokens).parse_code().unwrap()
+}
+
+fn diagnostics(code: &str) -> Vec<(&'static str, usize, usize)> {
+    let stmts = parse(code);
+    Cfg::new(&stmts)
+        .diagnostics()
+        .iter()
+        .map(|d| (d.code, d.span.line, d.span.column))
+        .collect()
+}
+
+#[test]
+fn test_to_dot() {
+    let stmts = parse("IF TRUE THEN SELECT 1; ELSE RETURN; END IF;\nSELECT 2;");
+    let expected = "\
+digraph {
+  node [shape=box];
+  n0 [label=\"ENTRY\"];
+  n1 [label=\"EXIT\"];
+  n2 [label=\"IfStatement\\n1:1\"];
+  n3 [label=\"SelectStatement\\n1:14\"];
+  n4 [label=\"SingleTokenStatement\\n1:29\"];
+  n5 [label=\"SelectStatement\\n2:1\"];
+  n0 -> n2;
+  n2 -> n3 [label=\"True\"];
+  n2 -> n4 [label=\"False\"];
+  n4 -> n1 [label=\"Return\"];
+  n3 -> n5;
+  n5 -> n1;
+}
+";
+    assert_eq!(Cfg::new(&stmts).to_dot(), expected);
+}
+
+#[test]
+fn test_edges() {
+    let stmts = parse(
+        "\
+label: LOOP
+  IF TRUE THEN LEAVE label; END IF;
+  WHILE TRUE DO
+    ITERATE label;
+  END WHILE;
+END LOOP;
+REPEAT
+  BREAK;
+UNTIL TRUE END REPEAT;
+BEGIN
+  RAISE;
+EXCEPTION WHEN ERROR THEN
+  SELECT 1;
+END;
+",
+    );
+    let cfg = Cfg::new(&stmts);
+    let edges = cfg
+        .edges
+        .iter()
+        .map(|e| (e.from, e.to, e.kind))
+        .collect::<Vec<_>>();
+    use EdgeKind::*;
+    // LOOP(2) IF(3) LEAVE(4) WHILE(5) ITERATE(6) REPEAT(7) UNTIL(8) BREAK(9)
+    // BEGIN(10) EXCEPTION(11) RAISE(12) SELECT(13)
+    assert_eq!(
+        edges,
+        vec![
+            (0, 2, Next),
+            (2, 3, Next),
+            (3, 4, True),
+            (3, 5, False),
+            (5, 6, True),
+            (6, 2, Continue),
+            (5, 2, False),
+            (4, 7, Break),
+            (7, 9, Next),
+            (8, 7, False),
+            (9, 10, Break),
+            (8, 10, True),
+            (10, 12, Next),
+            (12, 11, Exception),
+            (10, 11, Exception),
+            (11, 13, Next),
+            (13, 1, Next),
+        ]
+    );
+    assert_eq!(cfg.unreachable(), vec![8]);
+}
+
+#[test]
+fn test_diagnostics() {
+    let code = "\
+LOOP
+  SELECT 1;
+END LOOP;
+SELECT 2;
+WHILE TRUE DO
+  BREAK;
+  SELECT 3;
+  SELECT 4;
+END WHILE;
+BEGIN
+  RETURN;
+  CASE WHEN TRUE THEN SELECT 5; END CASE;
+END;
+";
+    assert_eq!(
+        diagnostics(code),
+        vec![
+            ("infinite-loop", 1, 1),
+            ("unreachable-code", 4, 1),
+            ("unreachable-code", 7, 3),
+            ("unreachable-code", 12, 3),
+        ]
+    );
+    let code = "\
+outer: LOOP
+  LOOP
+    BREAK outer;
+  END LOOP;
+END LOOP;
+LOOP
+  IF TRUE THEN RAISE; END IF;
+END LOOP;
+";
+    assert_eq!(diagnostics(code), vec![]);
+}
+
+#[test]
+fn test_unreachable_loop() {
+    assert_eq!(
+        diagnostics("RETURN;\nWHILE TRUE DO SELECT 1; END WHILE;\nSELECT 2;"),
+        vec![("unreachable-code", 2, 1)]
+    );
+}
+
+#[test]
+fn test_quoted_labels() {
+    for code in [
+        "`lbl`: LOOP BREAK lbl; END LOOP;",
+        "lbl: LOOP BREAK `lbl`; END LOOP;",
+        "`Lbl`: LOOP LEAVE `lBL`; END LOOP;",
+    ] {
+        assert_eq!(diagnostics(code), vec![], "{}", code);
+    }
+}
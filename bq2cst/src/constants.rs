@@ -1,98 +1,272 @@
-pub const KEYWORDS: [&str; 96] = [
-    "ALL",
-    "AND",
-    "ANY",
-    "ARRAY",
-    "AS",
-    "ASC",
-    "ASSERT_ROWS_MODIFIED",
-    "AT",
-    "BETWEEN",
-    "BY",
-    "CASE",
-    "CAST",
-    "COLLATE",
-    "CONTAINS",
-    "CREATE",
-    "CROSS",
-    "CUBE",
-    "CURRENT",
-    "DEFAULT",
-    "DEFINE",
-    "DESC",
-    "DISTINCT",
-    "ELSE",
-    "END",
-    "ENUM",
-    "ESCAPE",
-    "EXCEPT",
-    "EXCLUDE",
-    "EXISTS",
-    "EXTRACT",
-    "FALSE",
-    "FETCH",
-    "FOLLOWING",
-    "FOR",
-    "FROM",
-    "FULL",
-    "GROUP",
-    "GROUPING",
-    "GROUPS",
-    "HASH",
-    "HAVING",
-    "IF",
-    "IGNORE",
-    "IN",
-    "INNER",
-    "INTERSECT",
-    "INTERVAL",
-    "INTO",
-    "IS",
-    "JOIN",
-    "LATERAL",
-    "LEFT",
-    "LIKE",
-    "LIMIT",
-    "LOOKUP",
-    "MERGE",
-    "NATURAL",
-    "NEW",
-    "NO",
-    "NOT",
-    "NULL",
-    "NULLS",
-    "OF",
-    "ON",
-    "OR",
-    "ORDER",
-    "OUTER",
-    "OVER",
-    "PARTITION",
-    "PRECEDING",
-    "PROTO",
-    "QUALIFY",
-    "RANGE",
-    "RECURSIVE",
-    "RESPECT",
-    "RIGHT",
-    "ROLLUP",
-    "ROWS",
-    "SELECT",
-    "SET",
-    "SOME",
-    "STRUCT",
-    "TABLESAMPLE",
-    "THEN",
-    "TO",
-    "TREAT",
-    "TRUE",
-    "UNBOUNDED",
-    "UNION",
-    "UNNEST",
-    "USING",
-    "WHEN",
-    "WHERE",
-    "WINDOW",
-    "WITH",
-    "WITHIN",
-];
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+// keywords are classified once by the lexer (see `TokenKind`)
+// so that the parser never has to compare strings
+macro_rules! keywords {
+    (reserved: [$($reserved:ident,)*] non_reserved: [$($non_reserved:ident,)*]) => {
+        #[allow(non_camel_case_types)]
+        #[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+        pub enum Keyword {
+            $($reserved,)*
+            $($non_reserved,)*
+        }
+
+        impl Keyword {
+            pub const VARIANTS: &[Keyword] = &[$(Keyword::$reserved,)* $(Keyword::$non_reserved,)*];
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Keyword::$reserved => stringify!($reserved),)*
+                    $(Keyword::$non_reserved => stringify!($non_reserved),)*
+                }
+            }
+            pub fn is_reserved(&self) -> bool {
+                matches!(self, $(Keyword::$reserved)|*)
+            }
+        }
+    };
+}
+
+keywords! {
+    reserved: [
+        ALL,
+        AND,
+        ANY,
+        ARRAY,
+        AS,
+        ASC,
+        ASSERT_ROWS_MODIFIED,
+        AT,
+        BETWEEN,
+        BY,
+        CASE,
+        CAST,
+        COLLATE,
+        CONTAINS,
+        CREATE,
+        CROSS,
+        CUBE,
+        CURRENT,
+        DEFAULT,
+        DEFINE,
+        DESC,
+        DISTINCT,
+        ELSE,
+        END,
+        ENUM,
+        ESCAPE,
+        EXCEPT,
+        EXCLUDE,
+        EXISTS,
+        EXTRACT,
+        FALSE,
+        FETCH,
+        FOLLOWING,
+        FOR,
+        FROM,
+        FULL,
+        GROUP,
+        GROUPING,
+        GROUPS,
+        HASH,
+        HAVING,
+        IF,
+        IGNORE,
+        IN,
+        INNER,
+        INTERSECT,
+        INTERVAL,
+        INTO,
+        IS,
+        JOIN,
+        LATERAL,
+        LEFT,
+        LIKE,
+        LIMIT,
+        LOOKUP,
+        MERGE,
+        NATURAL,
+        NEW,
+        NO,
+        NOT,
+        NULL,
+        NULLS,
+        OF,
+        ON,
+        OR,
+        ORDER,
+        OUTER,
+        OVER,
+        PARTITION,
+        PRECEDING,
+        PROTO,
+        QUALIFY,
+        RANGE,
+        RECURSIVE,
+        RESPECT,
+        RIGHT,
+        ROLLUP,
+        ROWS,
+        SELECT,
+        SET,
+        SOME,
+        STRUCT,
+        TABLESAMPLE,
+        THEN,
+        TO,
+        TREAT,
+        TRUE,
+        UNBOUNDED,
+        UNION,
+        UNNEST,
+        USING,
+        WHEN,
+        WHERE,
+        WINDOW,
+        WITH,
+        WITHIN,
+    ]
+    non_reserved: [
+        ADD,
+        AFTER,
+        AGGREGATE,
+        ALTER,
+        ASSERT,
+        ASSIGNMENT,
+        B,
+        BEGIN,
+        BIGDECIMAL,
+        BIGNUMERIC,
+        BI_CAPACITY,
+        BR,
+        BREAK,
+        CALL,
+        CAPACITY,
+        CASCADE,
+        CLONE,
+        CLUSTER,
+        COLUMN,
+        COMMIT,
+        CONNECTION,
+        CONSTRAINT,
+        CONTINUE,
+        COPY,
+        CORRESPONDING,
+        DATA,
+        DATE,
+        DATETIME,
+        DECIMAL,
+        DECLARE,
+        DELETE,
+        DETERMINISTIC,
+        DROP,
+        ELSEIF,
+        ENFORCED,
+        EXCEPTION,
+        EXECUTE,
+        EXPORT,
+        EXTEND,
+        EXTERNAL,
+        FIRST,
+        FOREIGN,
+        FORMAT,
+        FUNCTION,
+        GENERATED,
+        GRANT,
+        INCLUDE,
+        INOUT,
+        INPUT,
+        INSERT,
+        ITERATE,
+        JSON,
+        KEY,
+        LANGUAGE,
+        LAST,
+        LEAVE,
+        LOAD,
+        LOOP,
+        MATCH_RECOGNIZE,
+        MATERIALIZED,
+        MEASURES,
+        MODEL,
+        NUMERIC,
+        OFFSET,
+        OPTIONS,
+        ORGANIZATION,
+        OUT,
+        OUTPUT,
+        OVERWRITE,
+        PARTITIONS,
+        PATTERN,
+        PIVOT,
+        PRIMARY,
+        PROCEDURE,
+        PROJECT,
+        R,
+        RAISE,
+        RB,
+        REFERENCES,
+        REMOTE,
+        RENAME,
+        REPEAT,
+        REPLACE,
+        REPLICA,
+        RESERVATION,
+        RESTRICT,
+        RETURN,
+        RETURNS,
+        REVOKE,
+        ROLLBACK,
+        ROW,
+        SAFE_CAST,
+        SCHEMA,
+        SEARCH,
+        SNAPSHOT,
+        STORING,
+        STRICT,
+        TABLE,
+        TEMP,
+        TEMPORARY,
+        TIME,
+        TIMESTAMP,
+        TRAINING_DATA,
+        TRANSACTION,
+        TRANSFORM,
+        TRUNCATE,
+        TYPE,
+        UNDROP,
+        UNPIVOT,
+        UNTIL,
+        UPDATE,
+        VALUES,
+        VECTOR,
+        VIEW,
+        WHILE,
+    ]
+}
+
+// long enough for every keyword
+const MAX_KEYWORD_LEN: usize = 32;
+
+static KEYWORD_MAP: LazyLock<HashMap<&'static str, Keyword>> = LazyLock::new(|| {
+    Keyword::VARIANTS
+        .iter()
+        .map(|kw| (kw.as_str(), *kw))
+        .collect()
+});
+
+impl Keyword {
+    // case insensitive, does not allocate
+    pub fn from_literal(literal: &str) -> Option<Keyword> {
+        if MAX_KEYWORD_LEN < literal.len() || !literal.is_ascii() {
+            return None;
+        }
+        let mut buf = [0u8; MAX_KEYWORD_LEN];
+        let buf = &mut buf[..literal.len()];
+        buf.copy_from_slice(literal.as_bytes());
+        buf.make_ascii_uppercase();
+        // ascii only, so this never fails
+        let upper = std::str::from_utf8(buf).ok()?;
+        KEYWORD_MAP.get(upper).copied()
+    }
+}
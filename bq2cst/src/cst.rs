@@ -2,18 +2,35 @@
 mod tests;
 
 use crate::token::Token;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::ser::{SerializeMap, SerializeSeq, SerializeStruct};
+use serde::{Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
-#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
-pub enum ContentType {
-    Node(Node),
-    NodeVec(Vec<Node>),
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub enum ContentType<'a> {
+    Node(Node<'a>),
+    NodeVec(Vec<Node<'a>>),
 }
 
-#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
-pub enum NodeType {
+// the JSON produced by `Document`.
+// bump this whenever the shape changes, including the order of `NodeType` (see `NodeType::tag`)
+pub const FORMAT_VERSION: u32 = 4;
+
+macro_rules! node_types {
+    ($($variant:ident,)*) => {
+        #[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Serialize)]
+        pub enum NodeType {
+            $($variant,)*
+        }
+
+        impl NodeType {
+            pub const VARIANTS: &[NodeType] = &[$(NodeType::$variant,)*];
+        }
+    };
+}
+
+node_types! {
     AccessOperator,        // arr[OFFSET(1)] | json['path']
     AddColumnClause,       // ADD COLUMN x INT64 OPTIONS()
     AddConstraintClause,   // ADD PRIMARY KEY (a) | ADD REFERENCES `table`(col) NOT ENFORCED
@@ -100,6 +117,17 @@ pub enum NodeType {
     IntervalLiteral,
     InOperator,
     IsDistinctFromOperator,
+    JinjaAttribute,  // {{ a.b }}
+    JinjaCall,       // {{ ref('x') }}
+    JinjaFilter,     // {{ x | upper }}
+    JinjaGroup,      // {{ (x) }} | {{ [x] }} | {{ {'k': x} }}
+    JinjaKeywordArg, // {{ f(k=x) }}
+    JinjaLiteral,    // {{ 'x' }} | {{ 1 }}
+    JinjaName,       // {{ x }}
+    JinjaOperator,   // {{ a + b }} | {{ not a }}
+    JinjaPair,       // {{ {'k': x} }}
+    JinjaStatement,  // {% if x %}
+    JinjaSubscript,  // {{ a[0] }}
     JoinOperator,     // JOIN | ,
     JoinPipeOperator, // JOIN | INNER JOIN
     LimitClause,
@@ -134,6 +162,8 @@ pub enum NodeType {
     SetOperator,          // UNION | INTERSECT | EXCEPT
     SetStatement,         // SET x = 5
     SingleTokenStatement, // BREAK; | LEAVE; | ...
+    SqlxBlock,            // config { ... }
+    SqlxInterpolation,    // ${ref("table")}
     StandAloneExpr,
     StringLiteral,
     StructLiteral,
@@ -141,10 +171,12 @@ pub enum NodeType {
     TableSampleClause, // TABLESAMPLE SYSTEM (10 PERCENT)
     TableSamplePipeOperator,
     TableSampleRatio, // (10 PERCENT)
+    TemplateBlock,    // {% macro m() %} ... {% endmacro %}
     TemplateExpr,
     TemplateExprEnd,
     TemplateExprContinue,
     TemplateExprStart,
+    TemplateFragment, // WHERE x = 1 (in a macro)
     TrainingDataCustomHolidayClause, // (training_data AS (SELECT ...), custom_holiday AS (SELECT ...))
     TransactionStatement,            // BEGIN | COMMIT | ROLLBACK
     TruncateStatement,
@@ -172,22 +204,42 @@ pub enum NodeType {
     XXXByExprs,                 // ORDER BY expr
 }
 
-#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
-pub struct Node {
-    pub token: Option<Token>,
+impl NodeType {
+    // short representation used by the compact encoding
+    pub fn tag(&self) -> u16 {
+        *self as u16
+    }
+    pub fn from_tag(tag: u16) -> Option<NodeType> {
+        NodeType::VARIANTS.get(tag as usize).copied()
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct Node<'a> {
+    pub token: Option<Token<'a>>,
     pub node_type: NodeType,
-    pub children: HashMap<String, ContentType>,
+    #[serde(serialize_with = "serialize_sorted")]
+    pub children: HashMap<String, ContentType<'a>>,
+}
+
+// the order of keys should not depend on the hash
+fn serialize_sorted<S: Serializer>(
+    children: &HashMap<String, ContentType>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let sorted: BTreeMap<&String, &ContentType> = children.iter().collect();
+    sorted.serialize(serializer)
 }
 
-impl Node {
-    pub fn new(token: Token, node_type: NodeType) -> Node {
+impl<'a> Node<'a> {
+    pub fn new(token: Token<'a>, node_type: NodeType) -> Node<'a> {
         Node {
             token: Some(token),
             node_type,
             children: HashMap::new(),
         }
     }
-    pub fn empty(node_type: NodeType) -> Node {
+    pub fn empty(node_type: NodeType) -> Node<'a> {
         Node {
             token: None,
             node_type,
@@ -197,30 +249,29 @@ impl Node {
     fn format(&self, indent: usize, is_array: bool) -> String {
         let mut res = Vec::new();
         // self & node_type
-        let literal = match self.token.clone() {
+        let literal = match self.token {
             Some(t) => t.literal,
-            None => "None".to_string(),
+            None => "None",
         };
-        let self_;
-        if is_array {
-            self_ = format!("{}- self: {}", " ".repeat((indent - 1) * 2), literal);
+        let self_ = if is_array {
+            format!("{}- self: {}", " ".repeat((indent - 1) * 2), literal)
         } else {
-            self_ = format!("{}self: {}", " ".repeat(indent * 2), literal);
-        }
+            format!("{}self: {}", " ".repeat(indent * 2), literal)
+        };
         let type_ = format!("{:?}", self.node_type);
         res.push(format!("{} ({})", self_, type_));
         // children
-        let mut keys: Vec<&String> = self.children.keys().collect();
-        keys.sort();
-        for k in keys {
-            match self.children.get(k) {
-                Some(ContentType::Node(n)) => {
+        let mut children: Vec<(&String, &ContentType)> = self.children.iter().collect();
+        children.sort_by_key(|(k, _)| *k);
+        for (k, content) in children {
+            match content {
+                ContentType::Node(n) => {
                     res.push(format!("{}{}:", " ".repeat(indent * 2), k));
                     res.push(n.format(indent + 1, false));
                 }
-                Some(ContentType::NodeVec(ns)) => {
+                ContentType::NodeVec(ns) => {
                     let mut empty_array = " []";
-                    if ns.len() != 0 {
+                    if !ns.is_empty() {
                         empty_array = ""
                     };
                     res.push(format!("{}{}:{}", " ".repeat(indent * 2), k, empty_array));
@@ -228,23 +279,337 @@ impl Node {
                         res.push(n.format(indent + 1, true));
                     }
                 }
-                None => panic!(),
             }
         }
         res.join("\n")
     }
-    pub fn push_node(&mut self, key: &str, node: Node) {
+    pub fn get_node(&self, key: &str) -> Option<&Node<'a>> {
+        match self.children.get(key) {
+            Some(ContentType::Node(n)) => Some(n),
+            _ => None,
+        }
+    }
+    // an empty slice is returned if the key does not exist
+    pub fn get_node_vec(&self, key: &str) -> &[Node<'a>] {
+        match self.children.get(key) {
+            Some(ContentType::NodeVec(ns)) => ns,
+            _ => &[],
+        }
+    }
+    // `proj.ds.t` and `proj`.ds.`t` are both ["proj", "ds", "t"]
+    pub fn identifier_path(&self) -> Option<Vec<String>> {
+        match self.node_type {
+            NodeType::Identifier => Some(
+                self.token?
+                    .literal
+                    .trim_matches('`')
+                    .split('.')
+                    .map(|s| s.to_string())
+                    .collect(),
+            ),
+            NodeType::MultiTokenIdentifier => {
+                let mut literal = self.token?.literal.to_string();
+                for ident in self.get_node_vec("trailing_idents") {
+                    literal.push_str(ident.token?.literal);
+                }
+                Some(literal.split('.').map(|s| s.to_string()).collect())
+            }
+            NodeType::DotOperator => {
+                let mut path = self.get_node("left")?.identifier_path()?;
+                path.extend(self.get_node("right")?.identifier_path()?);
+                Some(path)
+            }
+            _ => None,
+        }
+    }
+    fn collect_tokens(&self, tokens: &mut Vec<Token<'a>>) {
+        if let Some(token) = self.token {
+            tokens.push(token);
+        }
+        for (key, content) in &self.children {
+            if key == "leading_comments" || key == "trailing_comments" {
+                continue;
+            }
+            match content {
+                ContentType::Node(n) => n.collect_tokens(tokens),
+                ContentType::NodeVec(ns) => {
+                    for n in ns {
+                        n.collect_tokens(tokens);
+                    }
+                }
+            }
+        }
+    }
+    // the original code without comments, whitespaces between tokens are collapsed
+    pub fn source_text(&self) -> String {
+        let mut tokens = Vec::new();
+        self.collect_tokens(&mut tokens);
+        // trailing `,` and `;` are not a part of the node
+        for key in ["comma", "semicolon"] {
+            if let Some(token) = self.get_node(key).and_then(|n| n.token) {
+                tokens.retain(|t| (t.line, t.column) != (token.line, token.column));
+            }
+        }
+        tokens.sort_by_key(|t| (t.line, t.column));
+        let mut res = String::new();
+        let mut end: Option<(usize, usize)> = None;
+        for token in tokens {
+            if end.is_some_and(|end| end != (token.line, token.column)) {
+                res.push(' ');
+            }
+            res.push_str(token.literal);
+            end = Some((token.line, token.column + token.literal.chars().count()));
+        }
+        res
+    }
+    // normalized text of `Type`, e.g. `ARRAY<STRUCT<a NUMERIC(10, 2)>>`
+    pub fn type_text(&self) -> String {
+        let mut res = self
+            .token
+            .map(|t| t.literal.to_uppercase())
+            .unwrap_or_default();
+        match self.node_type {
+            NodeType::TypeDeclaration => {
+                if let Some(type_) = self.get_node("type") {
+                    res = match res.is_empty() {
+                        true => type_.type_text(),
+                        false => format!("{} {}", self.token.unwrap().literal, type_.type_text()),
+                    }
+                }
+            }
+            NodeType::GroupedType => {
+                res = format!(
+                    "<{}>",
+                    self.get_node("type")
+                        .map(Node::type_text)
+                        .unwrap_or_default()
+                );
+            }
+            NodeType::GroupedTypeDeclarationOrConstraints => {
+                let declarations = self.get_node_vec("declarations");
+                res = format!(
+                    "<{}>",
+                    declarations
+                        .iter()
+                        .map(Node::type_text)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+            NodeType::Type => {
+                if let Some(type_) = self.get_node("type") {
+                    res = format!("{} {}", res, type_.type_text()); // ANY TYPE
+                }
+                if let Some(declaration) = self.get_node("type_declaration") {
+                    res.push_str(&declaration.type_text());
+                }
+                if let Some(parameter) = self.get_node("parameter") {
+                    let exprs = parameter.get_node_vec("exprs");
+                    res = format!(
+                        "{}({})",
+                        res,
+                        exprs
+                            .iter()
+                            .filter_map(|e| e.token.map(|t| t.literal))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                }
+            }
+            _ => (),
+        }
+        res
+    }
+    pub fn push_node(&mut self, key: &str, node: Node<'a>) {
         self.children
             .insert(key.to_string(), ContentType::Node(node));
     }
-    pub fn push_node_vec(&mut self, key: &str, nodes: Vec<Node>) {
+    pub fn push_node_vec(&mut self, key: &str, nodes: Vec<Node<'a>>) {
         self.children
             .insert(key.to_string(), ContentType::NodeVec(nodes));
     }
+    // (key, child) sorted by key. elements of NodeVec are labeled as `key[i]`
+    fn edges(&self) -> Vec<(String, &Node<'a>)> {
+        let mut children: Vec<(&String, &ContentType)> = self.children.iter().collect();
+        children.sort_by_key(|(k, _)| *k);
+        let mut res = Vec::new();
+        for (k, content) in children {
+            match content {
+                ContentType::Node(n) => res.push((k.clone(), n)),
+                ContentType::NodeVec(ns) => {
+                    for (i, n) in ns.iter().enumerate() {
+                        res.push((format!("{}[{}]", k, i), n));
+                    }
+                }
+            }
+        }
+        res
+    }
+    fn walk<'n>(
+        &'n self,
+        id: &mut usize,
+        f: &mut impl FnMut(usize, &'n Node<'a>, Option<(usize, &str)>),
+    ) {
+        self.walk_inner(id, None, f);
+    }
+    fn walk_inner<'n>(
+        &'n self,
+        id: &mut usize,
+        parent: Option<(usize, &str)>,
+        f: &mut impl FnMut(usize, &'n Node<'a>, Option<(usize, &str)>),
+    ) {
+        let self_id = *id;
+        *id += 1;
+        f(self_id, self, parent);
+        for (label, n) in self.edges() {
+            n.walk_inner(id, Some((self_id, &label)), f);
+        }
+    }
+    // Graphviz DOT. each vertex shows node_type and literal, each edge shows the key
+    pub fn to_dot(&self) -> String {
+        let mut res = vec!["digraph {".to_string(), "  node [shape=box];".to_string()];
+        self.walk(&mut 0, &mut |id, n, parent| {
+            let label = match n.token {
+                Some(t) => format!("{:?}\\n{}", n.node_type, escape_dot(t.literal)),
+                None => format!("{:?}", n.node_type),
+            };
+            res.push(format!("  n{} [label=\"{}\"];", id, label));
+            if let Some((parent_id, key)) = parent {
+                res.push(format!("  n{} -> n{} [label=\"{}\"];", parent_id, id, key));
+            }
+        });
+        res.push("}".to_string());
+        res.join("\n") + "\n"
+    }
+    // Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let mut res = vec!["flowchart TD".to_string()];
+        self.walk(&mut 0, &mut |id, n, parent| {
+            let label = match n.token {
+                Some(t) => format!("{:?}<br/>{}", n.node_type, escape_mermaid(t.literal)),
+                None => format!("{:?}", n.node_type),
+            };
+            res.push(format!("  n{}[\"{}\"]", id, label));
+            if let Some((parent_id, key)) = parent {
+                res.push(format!("  n{} -->|\"{}\"| n{}", parent_id, key, id));
+            }
+        });
+        res.join("\n") + "\n"
+    }
+    // (NodeType "literal" :key (...) :vec_key ((...) (...)))
+    pub fn to_sexp(&self) -> String {
+        let mut res = format!("({:?}", self.node_type);
+        if let Some(t) = self.token {
+            res.push_str(&format!(" {:?}", t.literal));
+        }
+        let mut children: Vec<(&String, &ContentType)> = self.children.iter().collect();
+        children.sort_by_key(|(k, _)| *k);
+        for (k, content) in children {
+            let value = match content {
+                ContentType::Node(n) => n.to_sexp(),
+                ContentType::NodeVec(ns) => {
+                    let elements: Vec<String> = ns.iter().map(|n| n.to_sexp()).collect();
+                    format!("({})", elements.join(" "))
+                }
+            };
+            res.push_str(&format!(" :{} {}", k, value));
+        }
+        res.push(')');
+        res
+    }
+}
+
+pub(crate) fn escape_dot(literal: &str) -> String {
+    literal
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_mermaid(literal: &str) -> String {
+    literal
+        .replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+        .replace('\n', "<br/>")
 }
 
-impl fmt::Display for Node {
+impl fmt::Display for Node<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}\n", self.format(0, false))
+        writeln!(f, "{}", self.format(0, false))
+    }
+}
+
+// the versioned, top-level representation of parsed code
+pub struct Document<'n, 'a> {
+    stmts: &'n [Node<'a>],
+    compact: bool,
+}
+
+impl<'n, 'a> Document<'n, 'a> {
+    pub fn new(stmts: &'n [Node<'a>]) -> Document<'n, 'a> {
+        Document {
+            stmts,
+            compact: false,
+        }
+    }
+    // omit empty children and use `NodeType::tag` instead of `node_type`
+    pub fn with_compact(mut self, compact: bool) -> Document<'n, 'a> {
+        self.compact = compact;
+        self
+    }
+}
+
+impl Serialize for Document<'_, '_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Document", 3)?;
+        state.serialize_field("version", &FORMAT_VERSION)?;
+        state.serialize_field("compact", &self.compact)?;
+        if self.compact {
+            let stmts: Vec<Compact> = self.stmts.iter().map(Compact::Node).collect();
+            state.serialize_field("stmts", &stmts)?;
+        } else {
+            state.serialize_field("stmts", self.stmts)?;
+        }
+        state.end()
+    }
+}
+
+// Node is encoded as an object and NodeVec as an array
+enum Compact<'n, 'a> {
+    Node(&'n Node<'a>),
+    NodeVec(&'n [Node<'a>]),
+}
+
+impl Serialize for Compact<'_, '_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Compact::Node(node) => {
+                let len = if node.children.is_empty() { 2 } else { 3 };
+                let mut map = serializer.serialize_map(Some(len))?;
+                map.serialize_entry("token", &node.token)?;
+                map.serialize_entry("tag", &node.node_type.tag())?;
+                if !node.children.is_empty() {
+                    let children: BTreeMap<&String, Compact> = node
+                        .children
+                        .iter()
+                        .map(|(k, content)| match content {
+                            ContentType::Node(n) => (k, Compact::Node(n)),
+                            ContentType::NodeVec(ns) => (k, Compact::NodeVec(ns)),
+                        })
+                        .collect();
+                    map.serialize_entry("children", &children)?;
+                }
+                map.end()
+            }
+            Compact::NodeVec(nodes) => {
+                let mut seq = serializer.serialize_seq(Some(nodes.len()))?;
+                for n in nodes.iter() {
+                    seq.serialize_element(&Compact::Node(n))?;
+                }
+                seq.end()
+            }
+        }
     }
 }
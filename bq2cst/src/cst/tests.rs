@@ -21,7 +21,7 @@ fn test_to_string() {
     let mut parent = Node::new(Token::from_str0("parent"), NodeType::Unknown);
     parent.push_node("son", son);
     parent.push_node("daughter", daughter);
-    let res = format!("{}", parent.to_string());
+    let res = format!("{}", parent);
 
     println!("{}", res);
     assert_eq!(
@@ -40,3 +40,135 @@ son:
         res
     );
 }
+
+#[test]
+fn test_serialize() {
+    let mut parent = Node::new(Token::from_str0("parent"), NodeType::Unknown);
+    for key in ["c", "a", "d", "b"] {
+        parent.push_node(key, Node::new(Token::from_str0(key), NodeType::Keyword));
+    }
+    parent.push_node_vec("e", vec![Node::empty(NodeType::EOF)]);
+    let stmts = vec![parent];
+
+    let res = serde_json::to_string(&Document::new(&stmts)).unwrap();
+    let keys: Vec<usize> = ["\"a\"", "\"b\"", "\"c\"", "\"d\"", "\"e\""]
+        .iter()
+        .map(|k| res.find(k).unwrap())
+        .collect();
+    assert!(keys.is_sorted());
+    assert!(res.starts_with(&format!(
+        "{{\"version\":{},\"compact\":false,",
+        FORMAT_VERSION
+    )));
+
+    let res = serde_json::to_string(&Document::new(&stmts).with_compact(true)).unwrap();
+    let keyword = NodeType::Keyword.tag();
+    let eof = NodeType::EOF.tag();
+    let unknown = NodeType::Unknown.tag();
+    let token = |s: &str| format!("{{\"line\":0,\"column\":0,\"literal\":\"{}\"}}", s);
+    let leaf = |s: &str| format!("{{\"token\":{},\"tag\":{}}}", token(s), keyword);
+    assert_eq!(
+        format!(
+            "{{\"version\":{},\"compact\":true,\"stmts\":[{{\"token\":{},\"tag\":{},\"children\":{{\"a\":{},\"b\":{},\"c\":{},\"d\":{},\"e\":[{{\"token\":null,\"tag\":{}}}]}}}}]}}",
+            FORMAT_VERSION,
+            token("parent"),
+            unknown,
+            leaf("a"),
+            leaf("b"),
+            leaf("c"),
+            leaf("d"),
+            eof
+        ),
+        res
+    );
+}
+
+#[test]
+fn test_tag() {
+    for (i, t) in NodeType::VARIANTS.iter().enumerate() {
+        assert_eq!(i as u16, t.tag());
+        assert_eq!(Some(*t), NodeType::from_tag(t.tag()));
+    }
+    // tags are part of the format, bump FORMAT_VERSION if this fails
+    assert_eq!(171, NodeType::VARIANTS.len());
+    assert_eq!(None, NodeType::from_tag(171));
+}
+
+#[test]
+fn test_export() {
+    let mut parent = Node::new(Token::from_str0("SELECT"), NodeType::SelectStatement);
+    parent.push_node_vec(
+        "exprs",
+        vec![
+            Node::new(Token::from_str0("1"), NodeType::NumericLiteral),
+            Node::new(Token::from_str0("'\"a\"'"), NodeType::StringLiteral),
+        ],
+    );
+    parent.push_node(
+        "semicolon",
+        Node::new(Token::from_str0(";"), NodeType::Symbol),
+    );
+
+    assert_eq!(
+        "\
+digraph {
+  node [shape=box];
+  n0 [label=\"SelectStatement\\nSELECT\"];
+  n1 [label=\"NumericLiteral\\n1\"];
+  n0 -> n1 [label=\"exprs[0]\"];
+  n2 [label=\"StringLiteral\\n'\\\"a\\\"'\"];
+  n0 -> n2 [label=\"exprs[1]\"];
+  n3 [label=\"Symbol\\n;\"];
+  n0 -> n3 [label=\"semicolon\"];
+}
+",
+        parent.to_dot()
+    );
+    assert_eq!(
+        "\
+flowchart TD
+  n0[\"SelectStatement<br/>SELECT\"]
+  n1[\"NumericLiteral<br/>1\"]
+  n0 -->|\"exprs[0]\"| n1
+  n2[\"StringLiteral<br/>'#quot;a#quot;'\"]
+  n0 -->|\"exprs[1]\"| n2
+  n3[\"Symbol<br/>;\"]
+  n0 -->|\"semicolon\"| n3
+",
+        parent.to_mermaid()
+    );
+    assert_eq!(
+        "(SelectStatement \"SELECT\" :exprs ((NumericLiteral \"1\") (StringLiteral \"'\\\"a\\\"'\")) :semicolon (Symbol \";\"))",
+        parent.to_sexp()
+    );
+}
+
+#[test]
+fn test_text() {
+    let code = "\
+SELECT `proj.ds`.t.col, f(x,  1) + -- comment
+  2, 3;
+CREATE TABLE t (x array<STRUCT<a numeric(10, 2), b INT64 NOT NULL>>);";
+    let tokens = crate::lexer::Lexer::new(code).tokenize_code().unwrap();
+    let stmts = crate::parser::Parser::new(tokens).parse_code().unwrap();
+    let exprs = stmts[0].get_node_vec("exprs");
+    assert_eq!(
+        exprs[0].identifier_path(),
+        Some(vec![
+            "proj".to_string(),
+            "ds".to_string(),
+            "t".to_string(),
+            "col".to_string()
+        ])
+    );
+    assert_eq!(exprs[1].identifier_path(), None);
+    assert_eq!(exprs[1].source_text(), "f(x, 1) + 2");
+    let declaration = &stmts[1]
+        .get_node("column_schema_group")
+        .unwrap()
+        .get_node_vec("declarations")[0];
+    assert_eq!(
+        declaration.get_node("type").unwrap().type_text(),
+        "ARRAY<STRUCT<a NUMERIC(10, 2), b INT64>>"
+    );
+}
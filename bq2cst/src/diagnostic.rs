@@ -0,0 +1,64 @@
+use crate::token::Token;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    // the position just after the last character
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Span {
+    pub fn from_token(token: &Token) -> Span {
+        let (mut end_line, mut end_column) = (token.line, token.column);
+        for ch in token.literal.chars() {
+            if ch == '\n' {
+                end_line += 1;
+                end_column = 1;
+            } else {
+                end_column += 1;
+            }
+        }
+        Span {
+            line: token.line,
+            column: token.column,
+            end_line,
+            end_column,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str, // e.g. `undeclared-variable`
+    pub message: String,
+    pub span: Span,
+}
+
+impl Diagnostic {
+    pub fn error(code: &'static str, token: &Token, message: String) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            code,
+            message,
+            span: Span::from_token(token),
+        }
+    }
+    pub fn warning(code: &'static str, token: &Token, message: String) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            code,
+            message,
+            span: Span::from_token(token),
+        }
+    }
+}
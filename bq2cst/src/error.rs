@@ -23,6 +23,9 @@ impl BQ2CSTError {
             message,
         }
     }
+    pub fn message(&self) -> &str {
+        &self.message
+    }
 }
 
 pub type BQ2CSTResult<T> = Result<T, BQ2CSTError>;
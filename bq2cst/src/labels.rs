@@ -0,0 +1,199 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node, NodeType};
+use crate::diagnostic::Diagnostic;
+use crate::token::{Keyword, Token};
+
+// BEGIN, LOOP, WHILE, REPEAT and FOR (including unlabeled ones)
+struct Scope {
+    name: Option<String>,
+    is_loop: bool,
+}
+
+struct Validator {
+    scopes: Vec<Scope>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+// labels are case insensitive
+pub(crate) fn label_name(token: &Token) -> String {
+    token.literal.trim_matches('`').to_lowercase()
+}
+
+fn is_loop(node: &Node) -> bool {
+    matches!(
+        node.node_type,
+        NodeType::LoopStatement
+            | NodeType::WhileStatement
+            | NodeType::RepeatStatement
+            | NodeType::ForStatement
+    )
+}
+
+impl Validator {
+    fn node(&mut self, node: &Node) {
+        match node.node_type {
+            NodeType::BreakContinueStatement => {
+                self.break_continue(node);
+                return;
+            }
+            // labels are not visible from the body of the procedure
+            NodeType::CreateProcedureStatement => {
+                let scopes = std::mem::take(&mut self.scopes);
+                self.children(node);
+                self.scopes = scopes;
+                return;
+            }
+            _ => (),
+        }
+        let leading = node
+            .get_node("leading_label")
+            .and_then(|n| n.token.as_ref());
+        if let Some(token) = leading {
+            self.label(node, token);
+        }
+        let is_block = node.node_type == NodeType::BeginStatement;
+        if is_block || is_loop(node) {
+            self.scopes.push(Scope {
+                name: leading.map(label_name),
+                is_loop: is_loop(node),
+            });
+            self.children(node);
+            self.scopes.pop();
+        } else {
+            self.children(node);
+        }
+    }
+    fn children(&mut self, node: &Node) {
+        for content in node.children.values() {
+            match content {
+                ContentType::Node(n) => self.node(n),
+                ContentType::NodeVec(ns) => self.stmts(ns),
+            }
+        }
+    }
+    fn stmts(&mut self, nodes: &[Node]) {
+        // `LOOP ... END LOOP lbl;` is parsed as the loop and `lbl;`
+        for pair in nodes.windows(2) {
+            let is_unlabeled = (is_loop(&pair[0]) || pair[0].node_type == NodeType::BeginStatement)
+                && pair[0].get_node("leading_label").is_none()
+                && pair[0].get_node("semicolon").is_none();
+            let trailing = match pair[1].node_type {
+                NodeType::StandAloneExpr => pair[1]
+                    .get_node("expr")
+                    .filter(|n| n.node_type == NodeType::Identifier)
+                    .and_then(|n| n.token.as_ref()),
+                _ => None,
+            };
+            if let (true, Some(trailing)) = (is_unlabeled, trailing) {
+                self.diagnostics.push(Diagnostic::error(
+                    "mismatched-label",
+                    trailing,
+                    format!(
+                        "The trailing label requires the leading label: {}",
+                        trailing.literal
+                    ),
+                ));
+            }
+        }
+        for n in nodes {
+            self.node(n);
+        }
+    }
+    fn label(&mut self, node: &Node, token: &Token) {
+        let name = label_name(token);
+        if !(is_loop(node) || node.node_type == NodeType::BeginStatement) {
+            self.diagnostics.push(Diagnostic::error(
+                "misplaced-label",
+                token,
+                format!(
+                    "Only BEGIN, LOOP, WHILE, REPEAT and FOR can be labeled: {}",
+                    token.literal
+                ),
+            ));
+        }
+        if self.scopes.iter().any(|s| s.name.as_ref() == Some(&name)) {
+            self.diagnostics.push(Diagnostic::error(
+                "duplicate-label",
+                token,
+                format!(
+                    "The label is already used by an enclosing block: {}",
+                    token.literal
+                ),
+            ));
+        }
+        if let Some(trailing) = node
+            .get_node("trailing_label")
+            .and_then(|n| n.token.as_ref())
+        {
+            if label_name(trailing) != name {
+                self.diagnostics.push(Diagnostic::error(
+                    "mismatched-label",
+                    trailing,
+                    format!(
+                        "The trailing label does not match the leading label `{}`: {}",
+                        token.literal, trailing.literal
+                    ),
+                ));
+            }
+        }
+    }
+    fn break_continue(&mut self, node: &Node) {
+        let token = match &node.token {
+            Some(token) => token,
+            None => return,
+        };
+        let keyword = token.literal.to_uppercase();
+        let is_continue = token.in_(&[Keyword::CONTINUE, Keyword::ITERATE]);
+        match node.get_node("label").and_then(|n| n.token.as_ref()) {
+            Some(label) => {
+                let name = label_name(label);
+                match self
+                    .scopes
+                    .iter()
+                    .rev()
+                    .find(|s| s.name.as_ref() == Some(&name))
+                {
+                    None => self.diagnostics.push(Diagnostic::error(
+                        "unknown-label",
+                        label,
+                        format!(
+                            "The label is not defined in enclosing blocks: {}",
+                            label.literal
+                        ),
+                    )),
+                    Some(scope) if is_continue && !scope.is_loop => {
+                        self.diagnostics.push(Diagnostic::error(
+                            "misplaced-label",
+                            label,
+                            format!("{} cannot target a BEGIN block: {}", keyword, label.literal),
+                        ))
+                    }
+                    _ => (),
+                }
+            }
+            None => {
+                if !self.scopes.iter().any(|s| s.is_loop) {
+                    self.diagnostics.push(Diagnostic::error(
+                        "break-outside-loop",
+                        token,
+                        format!("{} must be inside of a loop.", keyword),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+// trailing labels, BREAK / CONTINUE targets and labeled statements
+pub fn validate_labels(stmts: &[Node]) -> Vec<Diagnostic> {
+    let mut validator = Validator {
+        scopes: Vec::new(),
+        diagnostics: Vec::new(),
+    };
+    validator.stmts(stmts);
+    let mut res = validator.diagnostics;
+    res.sort_by_key(|d| (d.span.line, d.span.column));
+    res
+}
@@ -0,0 +1,74 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn diagnostics(code: &str) -> Vec<(&'static str, usize, usize)> {
+    let tokens = Lexer::new(code).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    validate_labels(&stmts)
+        .iter()
+        .map(|d| (d.code, d.span.line, d.span.column))
+        .collect()
+}
+
+#[test]
+fn test_valid_labels() {
+    let code = "\
+outer: LOOP
+  inner: WHILE TRUE DO
+    IF TRUE THEN CONTINUE outer; END IF;
+    BREAK INNER;
+  END WHILE inner;
+  BREAK;
+END LOOP outer;
+block: BEGIN
+  LEAVE block;
+  FOR r IN (SELECT 1) DO
+    ITERATE;
+  END FOR;
+END block;
+";
+    assert_eq!(diagnostics(code), vec![]);
+}
+
+#[test]
+fn test_invalid_labels() {
+    let code = "\
+outer: LOOP
+  inner: REPEAT
+    BREAK other;
+  UNTIL TRUE END REPEAT outer;
+  outer: LOOP
+    BREAK;
+  END LOOP;
+END LOOP outer;
+block: BEGIN
+  ITERATE block;
+END block;
+BREAK;
+x: SELECT 1;
+LOOP
+  CREATE TEMP PROCEDURE p()
+  BEGIN
+    LEAVE;
+  END;
+  BREAK;
+END LOOP;
+WHILE TRUE DO
+  BREAK;
+END WHILE lbl;
+";
+    assert_eq!(
+        diagnostics(code),
+        vec![
+            ("unknown-label", 3, 11),
+            ("mismatched-label", 4, 25),
+            ("duplicate-label", 5, 3),
+            ("misplaced-label", 10, 11),
+            ("break-outside-loop", 12, 1),
+            ("misplaced-label", 13, 1),
+            ("break-outside-loop", 17, 5),
+            ("mismatched-label", 23, 11),
+        ]
+    );
+}
@@ -2,72 +2,91 @@
 mod tests;
 
 use crate::error::{BQ2CSTError, BQ2CSTResult};
-use crate::token::Token;
+use crate::literal;
+use crate::token::{Keyword, Symbol, Token};
 
-pub struct Lexer {
-    input: Vec<char>,
-    position: usize,
+pub struct Lexer<'a> {
+    input: &'a str,
+    position: usize, // byte offset in `input`
     line: usize,
     column: usize,
     type_declaration_depth: usize,
-    pub tokens: Vec<Token>,
+    sqlx: bool,
+    strict: bool,
+    pub tokens: Vec<Token<'a>>,
 }
 
-impl Lexer {
+// blocks of Dataform SQLX files
+const SQLX_BLOCKS: [&str; 4] = ["config", "js", "pre_operations", "post_operations"];
+
+impl<'a> Lexer<'a> {
     // ----- pub -----
-    pub fn new(input: String) -> Lexer {
-        let chars: Vec<char> = input.chars().collect();
+    pub fn new(input: &'a str) -> Lexer<'a> {
         Lexer {
-            input: chars,
+            input,
             position: 0,
             line: 1,
             column: 1,
             type_declaration_depth: 0,
+            sqlx: false,
+            strict: false,
             tokens: Vec::new(),
         }
     }
-    pub fn tokenize_code(mut self) -> BQ2CSTResult<Vec<Token>> {
+    // `config { ... }` and `${ ... }` are read as single tokens
+    pub fn with_sqlx(mut self, sqlx: bool) -> Lexer<'a> {
+        self.sqlx = sqlx;
+        self
+    }
+    // tokens start at the given position (e.g. the body of a SQLX block)
+    pub fn with_position(mut self, line: usize, column: usize) -> Lexer<'a> {
+        self.line = line;
+        self.column = column;
+        self
+    }
+    // escape sequences, newlines in strings and numeric literals are validated
+    pub fn with_strict(mut self, strict: bool) -> Lexer<'a> {
+        self.strict = strict;
+        self
+    }
+    pub fn tokenize_code(mut self) -> BQ2CSTResult<Vec<Token<'a>>> {
         let mut token = self.next_token()?;
-        while !token.is_none() {
+        while token.is_some() {
             token = self.next_token()?;
         }
         self.tokens.push(Token::eof());
         Ok(self.tokens)
     }
     // ----- core -----
-    fn construct_token(&mut self, line: usize, column: usize, literal: String) -> &Token {
+    fn construct_token(&mut self, line: usize, column: usize, literal: &'a str) -> &Token<'a> {
         let token = Token::new(line, column, literal);
         self.tokens.push(token);
-        &self.tokens.last().unwrap()
+        self.tokens.last().unwrap()
     }
     fn get_char(&self, offset: usize) -> Option<char> {
-        if self.position + offset < self.input.len() {
-            return Some(self.input[self.position + offset]);
-        } else {
-            return None; // EOF
-        }
+        // `None` means EOF
+        self.input[self.position..].chars().nth(offset)
     }
     fn next_char(&mut self) -> BQ2CSTResult<()> {
-        if self.position < self.input.len() {
-            if self.input[self.position] == '\n' {
-                self.column = 1;
-                self.line += 1;
-            } else {
-                self.column += 1;
+        match self.get_char(0) {
+            Some(ch) => {
+                if ch == '\n' {
+                    self.column = 1;
+                    self.line += 1;
+                } else {
+                    self.column += 1;
+                }
+                self.position += ch.len_utf8();
+                Ok(())
             }
-            self.position += 1;
-            Ok(())
-        } else if self.position == self.input.len() {
-            Err(BQ2CSTError::new(
+            None => Err(BQ2CSTError::new(
                 self.line,
                 self.column,
                 "Unexpected EOF.".to_string(),
-            ))
-        } else {
-            panic!("Something went wrong!")
+            )),
         }
     }
-    fn next_token(&mut self) -> BQ2CSTResult<Option<&Token>> {
+    fn next_token(&mut self) -> BQ2CSTResult<Option<&Token<'a>>> {
         self.skip_whitespace()?;
         let ch = match self.get_char(0) {
             Some(ch) => ch,
@@ -84,8 +103,8 @@ impl Lexer {
                     self.construct_token(line, column, literal)
                 }
                 _ => {
-                    self.next_char()?;
-                    self.construct_token(line, column, ch.to_string())
+                    let literal = self.read_symbol(1)?;
+                    self.construct_token(line, column, literal)
                 }
             },
             '#' => {
@@ -112,8 +131,8 @@ impl Lexer {
                     let literal = self.read_comment()?;
                     self.construct_token(line, column, literal)
                 } else {
-                    self.next_char()?;
-                    self.construct_token(line, column, ch.to_string())
+                    let literal = self.read_symbol(1)?;
+                    self.construct_token(line, column, literal)
                 }
             }
             '/' => {
@@ -121,83 +140,68 @@ impl Lexer {
                     let literal = self.read_multiline_comment()?;
                     self.construct_token(line, column, literal)
                 } else {
-                    self.next_char()?;
-                    self.construct_token(line, column, ch.to_string())
+                    let literal = self.read_symbol(1)?;
+                    self.construct_token(line, column, literal)
                 }
             }
             '|' => {
-                if self.get_char(1) == Some('|') {
-                    self.next_char()?;
-                    self.next_char()?;
-                    self.construct_token(line, column, "||".to_string())
-                } else if self.get_char(1) == Some('>') {
-                    self.next_char()?;
-                    self.next_char()?;
-                    self.construct_token(line, column, "|>".to_string())
+                if let Some('|' | '>') = self.get_char(1) {
+                    // || | |>
+                    let literal = self.read_symbol(2)?;
+                    self.construct_token(line, column, literal)
                 } else {
-                    self.next_char()?;
-                    self.construct_token(line, column, ch.to_string())
+                    let literal = self.read_symbol(1)?;
+                    self.construct_token(line, column, literal)
                 }
             }
             '<' => {
-                if self.get_char(1) == Some('<') {
-                    self.next_char()?;
-                    self.next_char()?;
-                    self.construct_token(line, column, "<<".to_string())
-                } else if self.get_char(1) == Some('=') {
-                    self.next_char()?;
-                    self.next_char()?;
-                    self.construct_token(line, column, "<=".to_string())
-                } else if self.get_char(1) == Some('>') {
-                    self.next_char()?;
-                    self.next_char()?;
-                    self.construct_token(line, column, "<>".to_string())
+                if let Some('<' | '=' | '>') = self.get_char(1) {
+                    // << | <= | <>
+                    let literal = self.read_symbol(2)?;
+                    self.construct_token(line, column, literal)
                 } else {
-                    if self.tokens.last().unwrap().literal.to_uppercase() == "ARRAY"
-                        || self.tokens.last().unwrap().literal.to_uppercase() == "STRUCT"
+                    // `<` may be the first token
+                    if let Some(true) = self
+                        .tokens
+                        .last()
+                        .map(|t| t.in_(&[Keyword::ARRAY, Keyword::STRUCT]))
                     {
                         self.type_declaration_depth += 1;
                     }
-                    self.next_char()?;
-                    self.construct_token(line, column, ch.to_string())
+                    let literal = self.read_symbol(1)?;
+                    self.construct_token(line, column, literal)
                 }
             }
             '>' => {
                 if 0 < self.type_declaration_depth {
                     self.type_declaration_depth -= 1;
-                    self.next_char()?;
-                    self.construct_token(line, column, ch.to_string())
-                } else if self.get_char(1) == Some('>') {
-                    self.next_char()?;
-                    self.next_char()?;
-                    self.construct_token(line, column, ">>".to_string())
-                } else if self.get_char(1) == Some('=') {
-                    self.next_char()?;
-                    self.next_char()?;
-                    self.construct_token(line, column, ">=".to_string())
+                    let literal = self.read_symbol(1)?;
+                    self.construct_token(line, column, literal)
+                } else if let Some('>' | '=') = self.get_char(1) {
+                    // >> | >=
+                    let literal = self.read_symbol(2)?;
+                    self.construct_token(line, column, literal)
                 } else {
-                    self.next_char()?;
-                    self.construct_token(line, column, ch.to_string())
+                    let literal = self.read_symbol(1)?;
+                    self.construct_token(line, column, literal)
                 }
             }
             '=' => {
                 if self.get_char(1) == Some('>') {
-                    self.next_char()?;
-                    self.next_char()?;
-                    self.construct_token(line, column, "=>".to_string())
+                    let literal = self.read_symbol(2)?;
+                    self.construct_token(line, column, literal)
                 } else {
-                    self.next_char()?;
-                    self.construct_token(line, column, ch.to_string())
+                    let literal = self.read_symbol(1)?;
+                    self.construct_token(line, column, literal)
                 }
             }
             '!' => {
                 if self.get_char(1) == Some('=') {
-                    self.next_char()?;
-                    self.next_char()?;
-                    self.construct_token(line, column, "!=".to_string())
+                    let literal = self.read_symbol(2)?;
+                    self.construct_token(line, column, literal)
                 } else {
-                    self.next_char()?;
-                    self.construct_token(line, column, ch.to_string())
+                    let literal = self.read_symbol(1)?;
+                    self.construct_token(line, column, literal)
                 }
             }
             // parameter
@@ -210,13 +214,24 @@ impl Lexer {
                 let next = self.get_char(1);
                 if next == Some('@') || is_digit(&next) || next == Some(',') {
                     // maybe it is pattern quantifier like {m,n}
-                    self.next_char()?;
-                    self.construct_token(line, column, ch.to_string())
+                    let literal = self.read_symbol(1)?;
+                    self.construct_token(line, column, literal)
                 } else {
+                    let first_position = self.position;
                     let template = self.read_template()?;
-                    self.construct_token(line, column, template)
+                    if Token::new(line, column, template).template_name() == Some("raw") {
+                        let literal = self.read_raw(first_position)?;
+                        self.construct_token(line, column, literal)
+                    } else {
+                        self.construct_token(line, column, template)
+                    }
                 }
             }
+            // dataform interpolation
+            '$' if self.sqlx && self.get_char(1) == Some('{') => {
+                let literal = self.read_sqlx_identifier(self.position)?;
+                self.construct_token(line, column, literal)
+            }
             // int64 or float64 literal
             '0'..='9' => {
                 let literal = self.read_number()?;
@@ -224,17 +239,45 @@ impl Lexer {
             }
             // other
             _ => {
-                if is_valid_1st_char_of_ident(&Some(ch)) {
-                    let literal = self.read_identifier()?;
+                if self.sqlx && self.is_sqlx_block_start() {
+                    let literal = self.read_sqlx_block()?;
+                    self.construct_token(line, column, literal)
+                } else if is_valid_1st_char_of_ident(&Some(ch)) {
+                    let first_position = self.position;
+                    let mut literal = self.read_identifier()?;
+                    if self.sqlx && self.is_sqlx_interpolation_start() {
+                        literal = self.read_sqlx_identifier(first_position)?;
+                    }
                     self.construct_token(line, column, literal)
                 } else {
-                    self.next_char()?;
-                    self.construct_token(line, column, ch.to_string())
+                    let literal = self.read_symbol(1)?;
+                    self.construct_token(line, column, literal)
                 }
             }
         };
         Ok(Some(token))
     }
+    // `config {` at the beginning of a line
+    fn is_sqlx_block_start(&self) -> bool {
+        let before = &self.input[..self.position];
+        if !before[before.rfind('\n').map_or(0, |i| i + 1)..]
+            .trim()
+            .is_empty()
+        {
+            return false;
+        }
+        let rest = &self.input[self.position..];
+        SQLX_BLOCKS.iter().any(|name| {
+            let Some(body) = rest.strip_prefix(name) else {
+                return false;
+            };
+            match body.trim_start().strip_prefix('{') {
+                // `{1,2}` is a pattern quantifier
+                Some(inner) => !inner.starts_with(|c: char| c.is_ascii_digit() || c == ','),
+                None => false,
+            }
+        })
+    }
     fn skip_whitespace(&mut self) -> BQ2CSTResult<()> {
         while is_whitespace(&self.get_char(0)) {
             self.next_char()?;
@@ -242,7 +285,7 @@ impl Lexer {
         Ok(())
     }
     // ----- read -----
-    fn read_back_quoted(&mut self) -> BQ2CSTResult<String> {
+    fn read_back_quoted(&mut self) -> BQ2CSTResult<&'a str> {
         let quote = self.get_char(0);
         let first_position = self.position;
         self.next_char()?;
@@ -250,24 +293,18 @@ impl Lexer {
             self.next_char()?;
         }
         self.next_char()?; // ' -> next_ch
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        let res = &self.input[first_position..self.position];
         Ok(res)
     }
-    fn read_comment(&mut self) -> BQ2CSTResult<String> {
+    fn read_comment(&mut self) -> BQ2CSTResult<&'a str> {
         let first_position = self.position;
         while !is_end_of_line(&self.get_char(0)) {
             self.next_char()?;
         }
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect::<String>()
-            .trim_end()
-            .to_string();
+        let res = self.input[first_position..self.position].trim_end();
         Ok(res)
     }
-    fn read_identifier(&mut self) -> BQ2CSTResult<String> {
+    fn read_identifier(&mut self) -> BQ2CSTResult<&'a str> {
         let first_position = self.position;
         let first_char = self.get_char(0);
         if !is_valid_1st_char_of_ident(&first_char) {
@@ -281,27 +318,24 @@ impl Lexer {
         while is_valid_char_of_ident(&self.get_char(0)) {
             self.next_char()?;
         }
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        let res = &self.input[first_position..self.position];
         Ok(res)
     }
-    fn read_multiline_comment(&mut self) -> BQ2CSTResult<String> {
+    fn read_multiline_comment(&mut self) -> BQ2CSTResult<&'a str> {
         let first_position = self.position;
         while !(self.get_char(0) == Some('*') && self.get_char(1) == Some('/')) {
             self.next_char()?;
         }
         self.next_char()?; // * -> /
         self.next_char()?; // / -> next_char
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        let res = &self.input[first_position..self.position];
         Ok(res)
     }
-    fn read_multiline_string(&mut self) -> BQ2CSTResult<String> {
+    fn read_multiline_string(&mut self) -> BQ2CSTResult<&'a str> {
         // NOTE '''abc''' is OK. ''''abc'''' should throw an error.
         let first_position = self.position;
         let ch = self.get_char(0);
+        let (raw, bytes) = self.string_prefix();
         let mut odd_backslashes = false;
         self.next_char()?; // 1st ' -> 2nd '
         self.next_char()?; // 2nd ' -> 3rd '
@@ -314,6 +348,10 @@ impl Lexer {
             {
                 break;
             }
+            if self.strict && !raw && self.get_char(0) == Some('\\') {
+                self.read_escape(bytes)?;
+                continue;
+            }
             if self.get_char(0) == Some('\\') {
                 odd_backslashes = !odd_backslashes;
             } else {
@@ -324,13 +362,31 @@ impl Lexer {
         self.next_char()?; // 1st ' -> 2nd '
         self.next_char()?; // 2nd ' -> 3rd '
         self.next_char()?; // 3rd ' ->  next_ch
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        let res = &self.input[first_position..self.position];
         Ok(res)
     }
-    fn read_number(&mut self) -> BQ2CSTResult<String> {
+    fn read_number(&mut self) -> BQ2CSTResult<&'a str> {
         let first_position = self.position;
+        let (line, column) = (self.line, self.column);
+        if self.get_char(0) == Some('0') && matches!(self.get_char(1), Some('x' | 'X')) {
+            let is_hex = |ch: Option<char>| ch.is_some_and(|ch| ch.is_ascii_hexdigit());
+            if is_hex(self.get_char(2)) {
+                self.next_char()?; // 0 -> x
+                self.next_char()?; // x -> 9
+                while is_hex(self.get_char(0)) {
+                    self.next_char()?;
+                }
+                if self.strict && is_valid_char_of_ident(&self.get_char(0)) {
+                    return Err(self.invalid_number());
+                }
+                return Ok(&self.input[first_position..self.position]);
+            }
+            if self.strict {
+                self.next_char()?; // 0 -> x
+                self.next_char()?; // x -> G
+                return Err(self.invalid_number());
+            }
+        }
         while is_digit(&self.get_char(0)) {
             self.next_char()?;
         } // 9 -> .
@@ -345,16 +401,78 @@ impl Lexer {
             if let Some('+') | Some('-') = self.get_char(0) {
                 self.next_char()?; // +, - -> 9
             }
+            if self.strict && !is_digit(&self.get_char(0)) {
+                return Err(self.invalid_number());
+            }
             while is_digit(&self.get_char(0)) {
                 self.next_char()?;
             }
         }
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        // `.1.2` may be a part of a path (e.g. `t.1.2`, `my-project-1.dataset.table`)
+        if self.strict
+            && self.input[first_position..].starts_with(|ch: char| ch.is_ascii_digit())
+            && !self.is_dashed_path(line, column)
+        {
+            let next = self.get_char(0);
+            if next == Some('.') || is_valid_char_of_ident(&next) {
+                return Err(self.invalid_number());
+            }
+        }
+        let res = &self.input[first_position..self.position];
         Ok(res)
     }
-    fn read_parameter(&mut self) -> BQ2CSTResult<String> {
+    // the previous tokens are `xxx-` without spaces (see `parse_identifier`)
+    fn is_dashed_path(&self, line: usize, column: usize) -> bool {
+        let (ident, minus) = match self.tokens.as_slice() {
+            [.., ident, minus] => (ident, minus),
+            _ => return false,
+        };
+        let is_adjacent = |t: &Token, line: usize, column: usize| {
+            t.line == line && t.column + t.literal.chars().count() == column
+        };
+        minus.is(Symbol::Minus)
+            && is_adjacent(minus, line, column)
+            && is_adjacent(ident, minus.line, minus.column)
+            && (ident.is_identifier() || ident.is_numeric() || ident.is_reserved_keyword())
+    }
+    fn invalid_number(&self) -> BQ2CSTError {
+        BQ2CSTError::new(
+            self.line,
+            self.column,
+            "Invalid numeric literal.".to_string(),
+        )
+    }
+    // `r'...'` and `b'...'`, the prefix is the previous token
+    fn string_prefix(&self) -> (bool, bool) {
+        let prefix = match self.tokens.last() {
+            Some(t)
+                if t.line == self.line && t.column + t.literal.chars().count() == self.column =>
+            {
+                t.literal.to_lowercase()
+            }
+            _ => return (false, false),
+        };
+        match prefix.as_str() {
+            "r" => (true, false),
+            "b" => (false, true),
+            "rb" | "br" => (true, true),
+            _ => (false, false),
+        }
+    }
+    // `\n`, `\101`, `\x41`, `\u0041`, `\U00000041` and so on
+    fn read_escape(&mut self, bytes: bool) -> BQ2CSTResult<()> {
+        let (line, column) = (self.line, self.column);
+        self.next_char()?; // \ ->
+        let mut chars = self.input[self.position..].chars();
+        literal::unescape_sequence(&mut chars, bytes, &mut Vec::new())
+            .map_err(|message| BQ2CSTError::new(line, column, message))?;
+        let end = self.input.len() - chars.as_str().len();
+        while self.position < end {
+            self.next_char()?;
+        }
+        Ok(())
+    }
+    fn read_parameter(&mut self) -> BQ2CSTResult<&'a str> {
         let first_position = self.position;
         while self.get_char(0) == Some('@') {
             self.next_char()?;
@@ -364,20 +482,31 @@ impl Lexer {
         } else {
             self.read_identifier()?;
         }
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        let res = &self.input[first_position..self.position];
         Ok(res)
     }
-    fn read_string(&mut self) -> BQ2CSTResult<String> {
+    fn read_string(&mut self) -> BQ2CSTResult<&'a str> {
         let quote = self.get_char(0);
         let first_position = self.position;
+        let (raw, bytes) = self.string_prefix();
         let mut odd_backslashes = false;
         self.next_char()?; // " ->
         loop {
             if !odd_backslashes && self.get_char(0) == quote {
                 break;
             }
+            if self.strict && self.get_char(0) == Some('\n') {
+                return Err(BQ2CSTError::new(
+                    self.line,
+                    self.column,
+                    "Newlines are not allowed in quoted strings, use triple quotes instead."
+                        .to_string(),
+                ));
+            }
+            if self.strict && !raw && self.get_char(0) == Some('\\') {
+                self.read_escape(bytes)?;
+                continue;
+            }
             if self.get_char(0) == Some('\\') {
                 odd_backslashes = !odd_backslashes;
             } else {
@@ -386,12 +515,18 @@ impl Lexer {
             self.next_char()?;
         }
         self.next_char()?; // " ->
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        let res = &self.input[first_position..self.position];
         Ok(res)
     }
-    fn read_template(&mut self) -> BQ2CSTResult<String> {
+    fn read_symbol(&mut self, length: usize) -> BQ2CSTResult<&'a str> {
+        let first_position = self.position;
+        for _ in 0..length {
+            self.next_char()?;
+        }
+        let res = &self.input[first_position..self.position];
+        Ok(res)
+    }
+    fn read_template(&mut self) -> BQ2CSTResult<&'a str> {
         let first_position = self.position;
         let outer_r = Some('}');
         self.next_char()?; // -> { | # | % | ...
@@ -424,16 +559,117 @@ impl Lexer {
             self.next_char()?;
         }
         self.next_char()?; // outer_r ->
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        let res = &self.input[first_position..self.position];
+        Ok(res)
+    }
+    // the rest of `{% raw %}...{% endraw %}`
+    fn read_raw(&mut self, first_position: usize) -> BQ2CSTResult<&'a str> {
+        loop {
+            if self.get_char(0) == Some('{') && self.get_char(1) == Some('%') {
+                let (line, column) = (self.line, self.column);
+                let template = self.read_template()?;
+                if Token::new(line, column, template).template_name() == Some("endraw") {
+                    break;
+                }
+            } else {
+                self.next_char()?;
+            }
+        }
+        let res = &self.input[first_position..self.position];
+        Ok(res)
+    }
+    // `config { ... }` or `${ ... }`. braces are matched as in JavaScript.
+    fn is_sqlx_interpolation_start(&self) -> bool {
+        self.get_char(0) == Some('$') && self.get_char(1) == Some('{')
+    }
+    // interpolations glued to identifier characters are a single token (e.g. `${self()}_tmp`)
+    fn read_sqlx_identifier(&mut self, first_position: usize) -> BQ2CSTResult<&'a str> {
+        loop {
+            if self.is_sqlx_interpolation_start() {
+                self.read_sqlx_block()?;
+            } else if is_valid_char_of_ident(&self.get_char(0)) {
+                self.next_char()?;
+            } else {
+                break;
+            }
+        }
+        Ok(&self.input[first_position..self.position])
+    }
+    fn read_sqlx_block(&mut self) -> BQ2CSTResult<&'a str> {
+        let first_position = self.position;
+        let (line, column) = (self.line, self.column);
+        while self.get_char(0) != Some('{') {
+            self.next_char()?;
+        }
+        // `{` or '`' (template literal)
+        let mut stack: Vec<char> = Vec::new();
+        loop {
+            let ch = match self.get_char(0) {
+                Some(ch) => ch,
+                None => {
+                    return Err(BQ2CSTError::new(
+                        line,
+                        column,
+                        "SQLX block is not closed.".to_string(),
+                    ))
+                }
+            };
+            let next = self.get_char(1);
+            if stack.last() == Some(&'`') {
+                match (ch, next) {
+                    ('\\', _) => self.next_char()?,
+                    ('`', _) => {
+                        stack.pop();
+                    }
+                    ('$', Some('{')) => {
+                        self.next_char()?;
+                        stack.push('{');
+                    }
+                    _ => (),
+                }
+                self.next_char()?;
+                continue;
+            }
+            match (ch, next) {
+                ('\'' | '"', _) => {
+                    self.next_char()?;
+                    while self.get_char(0) != Some(ch) {
+                        if self.get_char(0) == Some('\\') {
+                            self.next_char()?;
+                        }
+                        self.next_char()?;
+                    }
+                }
+                ('/', Some('/')) => {
+                    while !is_end_of_line(&self.get_char(0)) {
+                        self.next_char()?;
+                    }
+                    continue;
+                }
+                ('/', Some('*')) => {
+                    self.read_multiline_comment()?;
+                    continue;
+                }
+                ('`' | '{', _) => stack.push(ch),
+                ('}', _) => {
+                    stack.pop();
+                    if stack.is_empty() {
+                        self.next_char()?; // } ->
+                        break;
+                    }
+                }
+                _ => (),
+            }
+            self.next_char()?;
+        }
+        let res = &self.input[first_position..self.position];
         Ok(res)
     }
 }
 
 fn is_digit(ch: &Option<char>) -> bool {
     match ch {
-        Some(ch) => ch.is_digit(10),
+        Some(ch) => ch.is_ascii_digit(),
         None => false,
     }
 }
@@ -452,9 +688,30 @@ fn is_valid_1st_char_of_ident(ch: &Option<char>) -> bool {
     }
 }
 
+// `${ ... }` in the literal (e.g. `${self()}_tmp`, `'${ref("a")}'`), unclosed ones are ignored
+pub(crate) fn sqlx_interpolations<'a>(token: &Token<'a>) -> Vec<Token<'a>> {
+    let mut lexer = Lexer::new(token.literal)
+        .with_sqlx(true)
+        .with_position(token.line, token.column);
+    while lexer.get_char(0).is_some() {
+        let (line, column) = (lexer.line, lexer.column);
+        if lexer.is_sqlx_interpolation_start() {
+            match lexer.read_sqlx_block() {
+                Ok(literal) => {
+                    lexer.construct_token(line, column, literal);
+                }
+                Err(_) => break,
+            }
+        } else if lexer.next_char().is_err() {
+            break;
+        }
+    }
+    lexer.tokens
+}
+
 fn is_valid_char_of_ident(ch: &Option<char>) -> bool {
     match ch {
-        Some(ch) => ch.is_alphabetic() || ch.is_digit(10) || ch == &'_',
+        Some(ch) => ch.is_alphabetic() || ch.is_ascii_digit() || ch == &'_',
         None => false,
     }
 }
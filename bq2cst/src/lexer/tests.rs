@@ -1,19 +1,19 @@
 use super::*;
+use crate::token::{TemplateType, TokenKind};
 
 trait TestCase {
     fn test(&self);
 }
 
-struct SuccessTestCase {
-    code: String,
-    expected_tokens: Vec<Token>,
-    result_tokens: Vec<Token>,
+struct SuccessTestCase<'a> {
+    code: &'a str,
+    expected_tokens: Vec<Token<'a>>,
+    result_tokens: Vec<Token<'a>>,
 }
 
-impl SuccessTestCase {
-    fn new(code: &str, expected_tokens_without_eof: Vec<Token>) -> SuccessTestCase {
-        let code = code.to_string();
-        let l = Lexer::new(code.clone());
+impl<'a> SuccessTestCase<'a> {
+    fn new(code: &'a str, expected_tokens_without_eof: Vec<Token<'a>>) -> SuccessTestCase<'a> {
+        let l = Lexer::new(code);
         let tokens = l.tokenize_code();
         let result_tokens = tokens.expect("Failed to tokenize code.");
         let mut expected_tokens = expected_tokens_without_eof;
@@ -26,7 +26,7 @@ impl SuccessTestCase {
     }
 }
 
-impl TestCase for SuccessTestCase {
+impl TestCase for SuccessTestCase<'_> {
     fn test(&self) {
         println!(
             "========== testing ==========\n{:?}\n=============================",
@@ -47,8 +47,7 @@ struct ErrorTestCase {
 
 impl ErrorTestCase {
     fn new(code: &str, expected_error_line: usize, expected_error_column: usize) -> ErrorTestCase {
-        let code = code.to_string();
-        let l = Lexer::new(code.clone());
+        let l = Lexer::new(code);
         let error = match l.tokenize_code() {
             Ok(tokens) => panic!(
                 "Unexpectedly successed to tokenize code.
@@ -59,7 +58,7 @@ tokens: {:?}",
             Err(error) => error,
         };
         ErrorTestCase {
-            code,
+            code: code.to_string(),
             expected_error_position: [expected_error_line, expected_error_column],
             actual_error_position: [error.line, error.column],
         }
@@ -225,7 +224,7 @@ SELECT r'''\\'''",
         // numeric literal
         Box::new(SuccessTestCase::new(
             "\
-SELECT 1, 01, 1.1, .1, 1.1e+1, 1.1E-1, .1e10",
+SELECT 1, 01, 1.1, .1, 1.1e+1, 1.1E-1, .1e10, 0x1F, 0Xa",
             vec![
                 Token::from_str(1, 1, "SELECT"),
                 Token::from_str(1, 8, "1"),
@@ -241,6 +240,10 @@ SELECT 1, 01, 1.1, .1, 1.1e+1, 1.1E-1, .1e10",
                 Token::from_str(1, 32, "1.1E-1"),
                 Token::from_str(1, 38, ","),
                 Token::from_str(1, 40, ".1e10"),
+                Token::from_str(1, 45, ","),
+                Token::from_str(1, 47, "0x1F"),
+                Token::from_str(1, 51, ","),
+                Token::from_str(1, 53, "0Xa"),
             ],
         )),
         // timestamp, date literal
@@ -356,6 +359,15 @@ SELECT {{variable}};",
                 Token::from_str(1, 52, "1"),
             ],
         )),
+        Box::new(SuccessTestCase::new(
+            "\
+{% raw %}SELECT '{{'{% endraw %}
+{%- raw -%}x{%- endraw -%}",
+            vec![
+                Token::from_str(1, 1, "{% raw %}SELECT '{{'{% endraw %}"),
+                Token::from_str(2, 1, "{%- raw -%}x{%- endraw -%}"),
+            ],
+        )),
         // operator
         Box::new(SuccessTestCase::new(
             "\
@@ -449,10 +461,109 @@ MATCH_RECOGNIZE (
                 Token::from_str(4, 1, ")"),
             ],
         )),
+        // multibyte characters
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT 'あいう' AS 列, `テーブル`.x -- コメント
+FROM t",
+            vec![
+                Token::from_str(1, 1, "SELECT"),
+                Token::from_str(1, 8, "'あいう'"),
+                Token::from_str(1, 14, "AS"),
+                Token::from_str(1, 17, "列"),
+                Token::from_str(1, 18, ","),
+                Token::from_str(1, 20, "`テーブル`"),
+                Token::from_str(1, 26, "."),
+                Token::from_str(1, 27, "x"),
+                Token::from_str(1, 29, "-- コメント"),
+                Token::from_str(2, 1, "FROM"),
+                Token::from_str(2, 6, "t"),
+            ],
+        )),
         // empty
         Box::new(SuccessTestCase::new("", vec![])),
+        // unusual beginning
+        Box::new(SuccessTestCase::new(
+            "<>",
+            vec![Token::from_str(1, 1, "<>")],
+        )),
+        Box::new(SuccessTestCase::new(
+            "< 1",
+            vec![Token::from_str(1, 1, "<"), Token::from_str(1, 3, "1")],
+        )),
+        Box::new(ErrorTestCase::new("'abc", 1, 5)),
     ];
     for t in test_cases {
         t.test();
     }
 }
+
+#[test]
+fn test_strict() {
+    let valid = [
+        r#"SELECT '\a\b\f\n\r\t\v\\\?\"\'\`', "\101\x41\X41é\U0001F600";"#,
+        r"SELECT r'\d\q', rb'\u', b'\xff', '''\n
+''', 0x1F, 1.5e-3, .5, t.1.2;",
+        "SELECT * FROM my-project-1.dataset.table JOIN my-1st-project.ds.t USING (x);",
+    ];
+    for code in valid {
+        if let Err(error) = Lexer::new(code).with_strict(true).tokenize_code() {
+            panic!("{:?}: {:?}", code, error);
+        }
+    }
+    let invalid = [
+        (r"SELECT '\q';", 1, 9),
+        (r"SELECT 'ab\x4';", 1, 11),
+        (r"SELECT '\u12';", 1, 9),
+        (r"SELECT '\400';", 1, 9),
+        (r"SELECT '\uD800';", 1, 9),
+        (r"SELECT b'\u0041';", 1, 10),
+        (r#"SELECT """\q""";"#, 1, 11),
+        ("SELECT 'a\nb';", 1, 10),
+        ("SELECT r'a\nb';", 1, 11),
+        ("SELECT 1e;", 1, 10),
+        ("SELECT 1e+ 1;", 1, 11),
+        ("SELECT 1.2.3;", 1, 11),
+        ("SELECT 1x;", 1, 9),
+        ("SELECT 0xG;", 1, 10),
+    ];
+    for (code, line, column) in invalid {
+        // the default mode accepts them
+        assert!(Lexer::new(code).tokenize_code().is_ok(), "{:?}", code);
+        match Lexer::new(code).with_strict(true).tokenize_code() {
+            Ok(tokens) => panic!("{:?}: {:?}", code, tokens),
+            Err(error) => assert_eq!((error.line, error.column), (line, column), "{:?}", code),
+        }
+    }
+}
+
+#[test]
+fn test_multiline_set() {
+    let tokens = Lexer::new("{% set x = [\n  1,\n  2\n] %}\nSELECT 1;")
+        .tokenize_code()
+        .unwrap();
+    assert_eq!(tokens[0].get_template_type(), Some(TemplateType::Comment));
+    assert_eq!(tokens[1], Token::from_str(5, 1, "SELECT"));
+    assert_eq!(tokens[3].kind, TokenKind::Symbol(Symbol::Semicolon));
+}
+
+#[test]
+fn test_sqlx_identifier() {
+    let tokens = Lexer::new("SELECT ${x}_a, b_${y}.c, '${z}' FROM t")
+        .with_sqlx(true)
+        .tokenize_code()
+        .unwrap();
+    let literals: Vec<(&str, TokenKind)> = tokens.iter().map(|t| (t.literal, t.kind)).collect();
+    assert_eq!(literals[1], ("${x}_a", TokenKind::SqlxInterpolation));
+    assert_eq!(literals[3], ("b_${y}", TokenKind::SqlxInterpolation));
+    assert_eq!(literals[4], (".", TokenKind::Symbol(Symbol::Dot)));
+    assert_eq!(literals[7], ("'${z}'", TokenKind::StringLiteral));
+    let parts = sqlx_interpolations(&tokens[3]);
+    assert_eq!(
+        parts
+            .iter()
+            .map(|t| (t.literal, t.column))
+            .collect::<Vec<_>>(),
+        vec![("${y}", 18)]
+    );
+}
@@ -1,58 +1,263 @@
 // https://github.com/rustwasm/wasm-bindgen/issues/2882
 #![allow(non_upper_case_globals)]
 
+pub mod bq_type;
+pub mod catalog;
+pub mod cfg;
 mod constants;
-mod cst;
-mod error;
-mod lexer;
-mod parser;
-mod token;
+pub mod cst;
+pub mod diagnostic;
+pub mod error;
+pub mod labels;
+pub mod lexer;
+pub mod literal;
+pub mod migration;
+pub mod options;
+pub mod parser;
+pub mod render;
+pub mod routines;
+pub mod schema;
+pub mod script;
+pub mod sqlx;
+pub mod template;
+pub mod token;
 mod utils;
 
 use serde::Serialize;
 use serde_wasm_bindgen::Serializer;
+use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen(skip_typescript)]
-pub fn parse(code: String) -> Result<JsValue, JsValue> {
+pub fn parse(code: String, strict: Option<bool>) -> Result<JsValue, JsValue> {
     utils::set_panic_hook();
-    let l = lexer::Lexer::new(code);
-    let s = Serializer::json_compatible();
-    let mut p = parser::Parser::new(match l.tokenize_code() {
-        Ok(tokens) => tokens,
-        Err(bq2cst_error) => {
-            return Err(bq2cst_error
-                .serialize(&s)
-                .expect("Problem converting error struct to json."))
-        }
-    });
-    let stmts = match p.parse_code() {
-        Ok(stmts) => stmts,
-        Err(bq2cst_error) => {
-            return Err(bq2cst_error
-                .serialize(&s)
-                .expect("Problem converting error struct to json."))
-        }
+    let l = lexer::Lexer::new(&code).with_strict(strict.unwrap_or(false));
+    to_js(&parse_tokens(parser::Parser::new(tokenize_code(l)?))?)
+}
+
+#[wasm_bindgen(js_name = parseDocument, skip_typescript)]
+pub fn parse_document(code: String, compact: Option<bool>) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let stmts = parse_stmts(&code)?;
+    to_js(&cst::Document::new(&stmts).with_compact(compact.unwrap_or(false)))
+}
+
+// bodies of `{% macro %}` and `{% call %}` may be fragments of SQL
+#[wasm_bindgen(js_name = parseRelaxed, skip_typescript)]
+pub fn parse_relaxed(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let tokens = tokenize_code(lexer::Lexer::new(&code))?;
+    to_js(&parse_tokens(
+        parser::Parser::new(tokens).with_relaxed_macros(true),
+    )?)
+}
+
+// one graph (or S-expression) per statement
+#[wasm_bindgen(js_name = exportTree, skip_typescript)]
+pub fn export_tree(code: String, format: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let stmts = parse_stmts(&code)?;
+    let export = match format.as_str() {
+        "dot" => cst::Node::to_dot,
+        "mermaid" => cst::Node::to_mermaid,
+        "sexp" => cst::Node::to_sexp,
+        _ => return Err(JsValue::from(format!("Unknown format: {}", format))),
+    };
+    to_js(&stmts.iter().map(export).collect::<Vec<String>>())
+}
+
+// ref(), source(), var() and config() in templates
+#[wasm_bindgen(js_name = dbtCalls, skip_typescript)]
+pub fn dbt_calls(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    match template::dbt_calls(&code) {
+        Ok(calls) => to_js(&calls),
+        Err(bq2cst_error) => Err(to_js(&bq2cst_error)?),
+    }
+}
+
+// Dataform SQLX (`config { ... }`, `${ref("table")}` and so on)
+#[wasm_bindgen(js_name = parseSqlx, skip_typescript)]
+pub fn parse_sqlx(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    match sqlx::parse(&code) {
+        Ok(stmts) => to_js(&stmts),
+        Err(bq2cst_error) => Err(to_js(&bq2cst_error)?),
+    }
+}
+
+#[wasm_bindgen(js_name = sqlxTables, skip_typescript)]
+pub fn sqlx_tables(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    match sqlx::referenced_tables(&code) {
+        Ok(tables) => to_js(&tables),
+        Err(bq2cst_error) => Err(to_js(&bq2cst_error)?),
+    }
+}
+
+// `{{ x }}` is replaced with `substitutions["x"]` or a dummy identifier
+fn render_template(code: &str, substitutions: JsValue) -> Result<render::Rendered<'_>, JsValue> {
+    let substitutions: HashMap<String, String> = if substitutions.is_undefined() {
+        HashMap::new()
+    } else {
+        serde_wasm_bindgen::from_value(substitutions)?
     };
-    Ok(stmts
-        .serialize(&s)
-        .expect("Problem converting stmts to json."))
+    match render::Rendered::with_substitutions(code, &substitutions) {
+        Ok(rendered) => Ok(rendered),
+        Err(bq2cst_error) => Err(to_js(&bq2cst_error)?),
+    }
+}
+
+#[wasm_bindgen(js_name = renderTemplate, skip_typescript)]
+pub fn render_template_code(code: String, substitutions: JsValue) -> Result<String, JsValue> {
+    utils::set_panic_hook();
+    Ok(render_template(&code, substitutions)?.code)
+}
+
+// positions of tokens and errors point to the original code
+#[wasm_bindgen(js_name = parseTemplate, skip_typescript)]
+pub fn parse_template(code: String, substitutions: JsValue) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let rendered = render_template(&code, substitutions)?;
+    match rendered.parse() {
+        Ok(stmts) => to_js(&stmts),
+        Err(bq2cst_error) => Err(to_js(&bq2cst_error)?),
+    }
+}
+
+// undeclared, duplicate and unused variables of scripts
+#[wasm_bindgen(js_name = resolveVariables, skip_typescript)]
+pub fn resolve_variables(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let stmts = parse_stmts(&code)?;
+    to_js(&script::resolve_variables(&stmts))
+}
+
+// Graphviz DOT
+#[wasm_bindgen(js_name = controlFlowGraph, skip_typescript)]
+pub fn control_flow_graph(code: String) -> Result<String, JsValue> {
+    utils::set_panic_hook();
+    let stmts = parse_stmts(&code)?;
+    Ok(cfg::Cfg::new(&stmts).to_dot())
+}
+
+// unreachable code and infinite loops
+#[wasm_bindgen(js_name = checkControlFlow, skip_typescript)]
+pub fn check_control_flow(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let stmts = parse_stmts(&code)?;
+    to_js(&cfg::Cfg::new(&stmts).diagnostics())
+}
+
+#[wasm_bindgen(js_name = validateLabels, skip_typescript)]
+pub fn validate_labels(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let stmts = parse_stmts(&code)?;
+    to_js(&labels::validate_labels(&stmts))
+}
+
+// `files` maps paths to their contents
+#[wasm_bindgen(js_name = analyzeRoutines, skip_typescript)]
+pub fn analyze_routines(files: JsValue) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let files: HashMap<String, String> = serde_wasm_bindgen::from_value(files)?;
+    let mut files: Vec<(&str, &str)> = files
+        .iter()
+        .map(|(path, code)| (path.as_str(), code.as_str()))
+        .collect();
+    files.sort();
+    to_js(&routines::Project::new(&files))
+}
+
+// datasets and tables after applying DDL statements
+#[wasm_bindgen(js_name = schemaCatalog, skip_typescript)]
+pub fn schema_catalog(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let stmts = parse_stmts(&code)?;
+    to_js(&catalog::Catalog::from_stmts(&stmts))
+}
+
+// `before` and `after` contain a `CREATE TABLE` statement
+#[wasm_bindgen(js_name = diffTables, skip_typescript)]
+pub fn diff_tables(before: String, after: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let (before, after) = (parse_stmts(&before)?, parse_stmts(&after)?);
+    match migration::diff(&before[0], &after[0]) {
+        Ok(migration) => to_js(&migration),
+        Err(bq2cst_error) => Err(to_js(&bq2cst_error)?),
+    }
+}
+
+// BigQuery REST API schema of the first `CREATE TABLE` statement
+#[wasm_bindgen(js_name = tableSchema, skip_typescript)]
+pub fn table_schema(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let stmts = parse_stmts(&code)?;
+    match bq_type::table_schema(&stmts[0]) {
+        Ok(fields) => to_js(&fields),
+        Err(bq2cst_error) => Err(to_js(&bq2cst_error)?),
+    }
+}
+
+#[wasm_bindgen(js_name = columnDefinitions, skip_typescript)]
+pub fn column_definitions(fields: JsValue) -> Result<String, JsValue> {
+    utils::set_panic_hook();
+    let fields: Vec<bq_type::TableFieldSchema> = serde_wasm_bindgen::from_value(fields)?;
+    match bq_type::column_definitions(&fields) {
+        Ok(definitions) => Ok(definitions),
+        Err(bq2cst_error) => Err(to_js(&bq2cst_error)?),
+    }
+}
+
+// every `OPTIONS(...)` clause with decoded values
+#[wasm_bindgen(js_name = extractOptions, skip_typescript)]
+pub fn extract_options(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let stmts = parse_stmts(&code)?;
+    to_js(&options::collect(&stmts))
+}
+
+// every literal with its decoded value
+#[wasm_bindgen(js_name = decodeLiterals, skip_typescript)]
+pub fn decode_literals(code: String) -> Result<JsValue, JsValue> {
+    utils::set_panic_hook();
+    let stmts = parse_stmts(&code)?;
+    to_js(&literal::collect(&stmts))
+}
+
+// `nodeTypes()[tag]` is the node_type of the compact encoding
+#[wasm_bindgen(js_name = nodeTypes, skip_typescript)]
+pub fn node_types() -> Result<JsValue, JsValue> {
+    to_js(&cst::NodeType::VARIANTS)
 }
 
 #[wasm_bindgen(skip_typescript)]
-pub fn tokenize(code: String) -> Result<JsValue, JsValue> {
+pub fn tokenize(code: String, strict: Option<bool>) -> Result<JsValue, JsValue> {
     utils::set_panic_hook();
-    let l = lexer::Lexer::new(code);
+    let l = lexer::Lexer::new(&code).with_strict(strict.unwrap_or(false));
+    to_js(&tokenize_code(l)?)
+}
+
+fn tokenize_code(l: lexer::Lexer<'_>) -> Result<Vec<token::Token<'_>>, JsValue> {
+    match l.tokenize_code() {
+        Ok(tokens) => Ok(tokens),
+        Err(bq2cst_error) => Err(to_js(&bq2cst_error)?),
+    }
+}
+
+fn parse_tokens(mut p: parser::Parser<'_>) -> Result<Vec<cst::Node<'_>>, JsValue> {
+    match p.parse_code() {
+        Ok(stmts) => Ok(stmts),
+        Err(bq2cst_error) => Err(to_js(&bq2cst_error)?),
+    }
+}
+
+fn parse_stmts(code: &str) -> Result<Vec<cst::Node<'_>>, JsValue> {
+    parse_tokens(parser::Parser::new(tokenize_code(lexer::Lexer::new(code))?))
+}
+
+// serialization errors are returned (not thrown as a panic) as well
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
     let s = Serializer::json_compatible();
-    let tokens = match l.tokenize_code() {
-        Ok(tokens) => tokens,
-        Err(bq2cst_error) => {
-            return Err(bq2cst_error
-                .serialize(&s)
-                .expect("Problem converting error struct to json."))
-        }
-    };
-    Ok(tokens
-        .serialize(&s)
-        .expect("Problem converting tokens to json."))
+    value.serialize(&s).map_err(JsValue::from)
 }
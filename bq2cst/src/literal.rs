@@ -0,0 +1,534 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node, NodeType};
+use crate::diagnostic::Span;
+use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::token::Token;
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", content = "value")]
+pub enum Literal {
+    String(String),
+    Bytes(Vec<u8>),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Null,
+    // the contents of typed literals are kept as they are written (e.g. `DATE '2020-01-01'`)
+    Date(String),
+    Datetime(String),
+    Time(String),
+    Timestamp(String),
+    Json(String),
+    Numeric(String),
+    BigNumeric(String),
+    Range(Range),
+    Interval(Interval),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Range {
+    pub element_type: String,
+    // `None` means UNBOUNDED
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Interval {
+    pub months: i64,
+    pub days: i64,
+    pub nanos: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecodedLiteral {
+    pub span: Span,
+    // `None` if the literal is invalid (e.g. `NUMERIC 'abc'`), see `error`
+    pub literal: Option<Literal>,
+    pub error: Option<String>,
+}
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+// the parts of `INTERVAL '...' YEAR TO SECOND` in order
+const INTERVAL_PARTS: [&str; 6] = ["YEAR", "MONTH", "DAY", "HOUR", "MINUTE", "SECOND"];
+
+// the children of `Expr` which are not a part of the literal (e.g. `'a' AS x,`)
+const NON_LITERAL_KEYS: [&str; 8] = [
+    "as",
+    "alias",
+    "row_value_alias",
+    "comma",
+    "order",
+    "null_order",
+    "leading_comments",
+    "trailing_comments",
+];
+
+fn error(node: &Node, message: String) -> BQ2CSTError {
+    match node.token {
+        Some(token) => BQ2CSTError::from_token(&token, message),
+        None => BQ2CSTError::new(0, 0, message),
+    }
+}
+
+// `r'...'` and `b'...'` are parsed as `UnaryOperator`
+fn prefix(node: &Node) -> Option<(bool, bool)> {
+    if node.node_type != NodeType::UnaryOperator {
+        return None;
+    }
+    let prefix = node.token?.literal.to_lowercase();
+    let right = node.get_node("right")?;
+    if right.node_type != NodeType::StringLiteral {
+        return None;
+    }
+    match prefix.as_str() {
+        "r" => Some((true, false)),
+        "b" => Some((false, true)),
+        "rb" | "br" => Some((true, true)),
+        _ => None,
+    }
+}
+
+fn type_name(node: &Node) -> Option<String> {
+    if node.node_type != NodeType::UnaryOperator {
+        return None;
+    }
+    let name = node.token?.literal.to_uppercase();
+    let right = node.get_node("right")?;
+    let is_string = right.node_type == NodeType::StringLiteral || prefix(right).is_some();
+    match name.as_str() {
+        "DATE" | "DATETIME" | "TIME" | "TIMESTAMP" | "JSON" | "NUMERIC" | "BIGNUMERIC"
+            if is_string =>
+        {
+            Some(name)
+        }
+        _ => None,
+    }
+}
+
+fn is_negative_number(node: &Node) -> bool {
+    node.node_type == NodeType::UnaryOperator
+        && node.token.is_some_and(|t| t.literal == "-")
+        && node
+            .get_node("right")
+            .is_some_and(|n| n.node_type == NodeType::NumericLiteral)
+}
+
+pub fn is_literal(node: &Node) -> bool {
+    match node.node_type {
+        NodeType::StringLiteral
+        | NodeType::NumericLiteral
+        | NodeType::BooleanLiteral
+        | NodeType::NullLiteral
+        | NodeType::IntervalLiteral
+        | NodeType::RangeLiteral => true,
+        _ => prefix(node).is_some() || type_name(node).is_some() || is_negative_number(node),
+    }
+}
+
+fn unquote(literal: &str) -> Option<&str> {
+    let quote = ["'''", "\"\"\"", "'", "\""]
+        .into_iter()
+        .find(|q| literal.starts_with(q) && literal.len() >= q.len() * 2)?;
+    Some(&literal[quote.len()..literal.len() - quote.len()])
+}
+
+// exactly `n` digits
+fn digits(chars: &mut std::str::Chars, n: usize, radix: u32) -> Option<u32> {
+    let mut res = 0u32;
+    for _ in 0..n {
+        res = res.checked_mul(radix)? + chars.next()?.to_digit(radix)?;
+    }
+    Some(res)
+}
+
+// decodes an escape sequence following `\` (e.g. `n`, `101`, `x41`, `u0041`) into `res`.
+// the lexer also uses this to validate string literals in strict mode
+pub(crate) fn unescape_sequence(
+    chars: &mut std::str::Chars,
+    bytes: bool,
+    res: &mut Vec<u8>,
+) -> Result<(), String> {
+    let escaped = match chars.next() {
+        Some(escaped) => escaped,
+        None => return Err("The literal ends with a backslash.".to_string()),
+    };
+    let illegal = || format!("Illegal escape sequence: \\{}", escaped);
+    match escaped {
+        'a' => res.push(0x07),
+        'b' => res.push(0x08),
+        'f' => res.push(0x0c),
+        'n' => res.push(b'\n'),
+        'r' => res.push(b'\r'),
+        't' => res.push(b'\t'),
+        'v' => res.push(0x0b),
+        '\\' | '?' | '"' | '\'' | '`' => res.push(escaped as u8),
+        '0'..='7' => {
+            let rest = digits(chars, 2, 8).ok_or_else(illegal)?;
+            let value = escaped.to_digit(8).unwrap() * 64 + rest;
+            res.push(u8::try_from(value).map_err(|_| illegal())?);
+        }
+        'x' | 'X' => res.push(digits(chars, 2, 16).ok_or_else(illegal)? as u8),
+        'u' | 'U' if bytes => {
+            return Err("Unicode escapes are not allowed in bytes literals.".to_string())
+        }
+        'u' | 'U' => {
+            let n = if escaped == 'u' { 4 } else { 8 };
+            let ch = digits(chars, n, 16)
+                .and_then(char::from_u32)
+                .ok_or_else(illegal)?;
+            res.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+        _ => return Err(illegal()),
+    }
+    Ok(())
+}
+
+fn unescape(body: &str, bytes: bool) -> Result<Vec<u8>, String> {
+    let mut res = Vec::new();
+    let mut chars = body.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            unescape_sequence(&mut chars, bytes, &mut res)?;
+        } else {
+            res.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+    }
+    Ok(res)
+}
+
+// the decoded value of a string or bytes literal
+fn quoted(node: &Node) -> BQ2CSTResult<Literal> {
+    let (string, raw, bytes) = match prefix(node) {
+        Some((raw, bytes)) => (node.get_node("right").unwrap(), raw, bytes),
+        None => (node, false, false),
+    };
+    let literal = string.token.map(|t| t.literal).unwrap_or_default();
+    let body = unquote(literal).ok_or_else(|| error(string, "Invalid quotes.".to_string()))?;
+    let value = if raw {
+        body.as_bytes().to_vec()
+    } else {
+        unescape(body, bytes).map_err(|message| error(string, message))?
+    };
+    if bytes {
+        return Ok(Literal::Bytes(value));
+    }
+    match String::from_utf8(value) {
+        Ok(s) => Ok(Literal::String(s)),
+        Err(_) => Err(error(string, "The string is not valid UTF-8.".to_string())),
+    }
+}
+
+// the decoded value if `node` is a string literal (with or without `r` prefix)
+pub fn string_value(node: &Node) -> Option<String> {
+    if node.node_type != NodeType::StringLiteral && prefix(node).is_none() {
+        return None;
+    }
+    match quoted(node) {
+        Ok(Literal::String(s)) => Some(s),
+        _ => None,
+    }
+}
+
+// the sign is parsed together so that `-9223372036854775808` is INT64
+fn number(node: &Node, negative: bool) -> BQ2CSTResult<Literal> {
+    let literal = node.token.map(|t| t.literal).unwrap_or_default();
+    let sign = if negative { "-" } else { "" };
+    if let Some(hex) = literal
+        .strip_prefix("0x")
+        .or_else(|| literal.strip_prefix("0X"))
+    {
+        return match i64::from_str_radix(&format!("{}{}", sign, hex), 16) {
+            Ok(i) => Ok(Literal::Int(i)),
+            Err(_) => Err(error(node, format!("Invalid hex literal: {}", literal))),
+        };
+    }
+    let signed = format!("{}{}", sign, literal);
+    if let Ok(i) = signed.parse() {
+        return Ok(Literal::Int(i));
+    }
+    match signed.parse() {
+        Ok(f) => Ok(Literal::Float(f)),
+        Err(_) => Err(error(node, format!("Invalid numeric literal: {}", literal))),
+    }
+}
+
+// e.g. `-1.5`, `.5e+10` (`NaN` and `inf` are not allowed unlike FLOAT64)
+fn is_decimal(value: &str) -> bool {
+    let value = value.strip_prefix(['+', '-']).unwrap_or(value);
+    let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (value, None),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let is_digits = |s: &str| s.chars().all(|ch| ch.is_ascii_digit());
+    let exponent_is_valid = exponent.is_none_or(|e| {
+        let e = e.strip_prefix(['+', '-']).unwrap_or(e);
+        !e.is_empty() && is_digits(e)
+    });
+    !(int.is_empty() && frac.is_empty()) && is_digits(int) && is_digits(frac) && exponent_is_valid
+}
+
+fn typed(node: &Node, type_name: &str) -> BQ2CSTResult<Literal> {
+    let right = node.get_node("right").unwrap();
+    let value = match quoted(right)? {
+        Literal::String(s) => s,
+        _ => return Err(error(right, "Expected a string literal.".to_string())),
+    };
+    let literal = match type_name {
+        "DATE" => Literal::Date(value),
+        "DATETIME" => Literal::Datetime(value),
+        "TIME" => Literal::Time(value),
+        "TIMESTAMP" => Literal::Timestamp(value),
+        "JSON" => Literal::Json(value),
+        _ => {
+            let trimmed = value.trim().to_string();
+            if !is_decimal(&trimmed) {
+                return Err(error(
+                    right,
+                    format!("Invalid {} literal: {}", type_name, value),
+                ));
+            }
+            match type_name {
+                "NUMERIC" => Literal::Numeric(trimmed),
+                _ => Literal::BigNumeric(trimmed),
+            }
+        }
+    };
+    Ok(literal)
+}
+
+// `RANGE<DATE> '[2020-01-01, UNBOUNDED)'`
+fn range(node: &Node) -> BQ2CSTResult<Literal> {
+    let element_type = node
+        .get_node("type")
+        .and_then(|t| t.get_node("type_declaration"))
+        .and_then(|t| t.get_node("type"))
+        .map(Node::type_text)
+        .unwrap_or_default();
+    let literal = node.token.map(|t| t.literal).unwrap_or_default();
+    let invalid = || error(node, format!("Invalid range literal: {}", literal));
+    let body = unquote(literal).ok_or_else(invalid)?;
+    let value = String::from_utf8(unescape(body, false).map_err(|m| error(node, m))?)
+        .map_err(|_| invalid())?;
+    let (start, end) = value
+        .trim()
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(')'))
+        .and_then(|v| v.split_once(','))
+        .ok_or_else(invalid)?;
+    let bound = |b: &str| {
+        let b = b.trim();
+        if b.eq_ignore_ascii_case("UNBOUNDED") || b.eq_ignore_ascii_case("NULL") {
+            None
+        } else {
+            Some(b.to_string())
+        }
+    };
+    Ok(Literal::Range(Range {
+        element_type,
+        start: bound(start),
+        end: bound(end),
+    }))
+}
+
+impl Interval {
+    fn add(&mut self, part: &str, value: i64) -> Option<()> {
+        let (field, unit) = match part {
+            "YEAR" => (&mut self.months, 12),
+            "QUARTER" => (&mut self.months, 3),
+            "MONTH" => (&mut self.months, 1),
+            "WEEK" => (&mut self.days, 7),
+            "DAY" => (&mut self.days, 1),
+            "HOUR" => (&mut self.nanos, 3600 * NANOS_PER_SECOND),
+            "MINUTE" => (&mut self.nanos, 60 * NANOS_PER_SECOND),
+            "SECOND" => (&mut self.nanos, NANOS_PER_SECOND),
+            "MILLISECOND" => (&mut self.nanos, 1_000_000),
+            "MICROSECOND" => (&mut self.nanos, 1_000),
+            _ => return None,
+        };
+        *field = field.checked_add(value.checked_mul(unit)?)?;
+        Some(())
+    }
+}
+
+// e.g. `'1-2 3'` for YEAR TO DAY
+fn interval_fields(text: &str, from: usize, to: usize) -> Option<Interval> {
+    let mut interval = Interval::default();
+    let mut rest = text.trim();
+    let mut sign = 1;
+    for (i, part) in INTERVAL_PARTS.iter().enumerate().take(to + 1).skip(from) {
+        let separator = match i {
+            1 => '-',
+            2 | 3 => ' ',
+            _ => ':',
+        };
+        if i > from {
+            rest = rest.strip_prefix(separator)?;
+        }
+        if i == from || separator == ' ' {
+            rest = rest.trim_start();
+            sign = 1;
+            if let Some(r) = rest.strip_prefix('-') {
+                (rest, sign) = (r, -1);
+            } else if let Some(r) = rest.strip_prefix('+') {
+                rest = r;
+            }
+        }
+        let len = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let value: i64 = rest[..len].parse().ok()?;
+        rest = &rest[len..];
+        interval.add(part, sign * value)?;
+        if *part == "SECOND" {
+            if let Some(r) = rest.strip_prefix('.') {
+                let len = r.find(|c: char| !c.is_ascii_digit()).unwrap_or(r.len());
+                if len == 0 || len > 9 {
+                    return None;
+                }
+                let fraction: i64 = format!("{:0<9}", &r[..len]).parse().ok()?;
+                interval.nanos = interval.nanos.checked_add(sign * fraction)?;
+                rest = &r[len..];
+            }
+        }
+    }
+    if rest.is_empty() {
+        Some(interval)
+    } else {
+        None
+    }
+}
+
+fn interval(node: &Node) -> BQ2CSTResult<Literal> {
+    let part = |key| {
+        node.get_node(key)
+            .and_then(|n| n.token)
+            .map(|t| t.literal.to_uppercase())
+    };
+    let invalid = || error(node, "Invalid interval literal.".to_string());
+    let (date_part, expr) = match (part("date_part"), node.get_node("expr")) {
+        (Some(date_part), Some(expr)) => (date_part, expr),
+        _ => return Err(invalid()),
+    };
+    // INTERVAL '1-2' YEAR TO MONTH
+    if let Some(to_date_part) = part("to_date_part") {
+        let text = string_value(expr).ok_or_else(invalid)?;
+        let from = INTERVAL_PARTS.iter().position(|p| *p == date_part);
+        let to = INTERVAL_PARTS.iter().position(|p| *p == to_date_part);
+        return match (from, to) {
+            (Some(from), Some(to)) if from < to => interval_fields(&text, from, to)
+                .map(Literal::Interval)
+                .ok_or_else(invalid),
+            _ => Err(invalid()),
+        };
+    }
+    // INTERVAL 5 DAY, INTERVAL -5 DAY, INTERVAL '5' DAY
+    let value = match Literal::from_node(expr) {
+        Ok(Literal::Int(i)) => Some(i),
+        Ok(Literal::String(s)) => s.trim().parse().ok(),
+        _ => None,
+    };
+    let mut interval = Interval::default();
+    match value {
+        Some(value) if interval.add(&date_part, value).is_some() => Ok(Literal::Interval(interval)),
+        _ => Err(invalid()),
+    }
+}
+
+impl Literal {
+    pub fn from_node(node: &Node) -> BQ2CSTResult<Literal> {
+        match node.node_type {
+            NodeType::StringLiteral => quoted(node),
+            NodeType::NumericLiteral => number(node, false),
+            NodeType::BooleanLiteral => Ok(Literal::Bool(
+                node.token
+                    .is_some_and(|t| t.literal.eq_ignore_ascii_case("TRUE")),
+            )),
+            NodeType::NullLiteral => Ok(Literal::Null),
+            NodeType::IntervalLiteral => interval(node),
+            NodeType::RangeLiteral => range(node),
+            NodeType::UnaryOperator if prefix(node).is_some() => quoted(node),
+            NodeType::UnaryOperator => {
+                if let Some(type_name) = type_name(node) {
+                    return typed(node, &type_name);
+                }
+                if !is_negative_number(node) {
+                    return Err(error(node, "Expected a literal.".to_string()));
+                }
+                number(node.get_node("right").unwrap(), true)
+            }
+            _ => Err(error(node, "Expected a literal.".to_string())),
+        }
+    }
+}
+
+fn collect_tokens<'a>(node: &Node<'a>, tokens: &mut Vec<Token<'a>>) {
+    if let Some(token) = node.token {
+        tokens.push(token);
+    }
+    for (key, content) in &node.children {
+        if NON_LITERAL_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        match content {
+            ContentType::Node(n) => collect_tokens(n, tokens),
+            ContentType::NodeVec(ns) => ns.iter().for_each(|n| collect_tokens(n, tokens)),
+        }
+    }
+}
+
+// from the first token to the last token of the literal (e.g. `INTERVAL 1 DAY`)
+fn span(node: &Node) -> Option<Span> {
+    let mut tokens = Vec::new();
+    collect_tokens(node, &mut tokens);
+    let first = tokens.iter().min_by_key(|t| (t.line, t.column))?;
+    let last = tokens.iter().max_by_key(|t| (t.line, t.column))?;
+    Some(Span {
+        line: first.line,
+        column: first.column,
+        ..Span::from_token(last)
+    })
+}
+
+fn collect_node(node: &Node, res: &mut Vec<DecodedLiteral>) {
+    if is_literal(node) {
+        if let Some(span) = span(node) {
+            let (literal, error) = match Literal::from_node(node) {
+                Ok(literal) => (Some(literal), None),
+                Err(error) => (None, Some(error.message().to_string())),
+            };
+            res.push(DecodedLiteral {
+                span,
+                literal,
+                error,
+            });
+        }
+        return;
+    }
+    for content in node.children.values() {
+        match content {
+            ContentType::Node(n) => collect_node(n, res),
+            ContentType::NodeVec(ns) => {
+                for n in ns {
+                    collect_node(n, res);
+                }
+            }
+        }
+    }
+}
+
+// every literal in `stmts`, invalid literals are also returned with an error
+pub fn collect(stmts: &[Node]) -> Vec<DecodedLiteral> {
+    let mut res = Vec::new();
+    for stmt in stmts {
+        collect_node(stmt, &mut res);
+    }
+    // children are not ordered
+    res.sort_by_key(|l| (l.span.line, l.span.column));
+    res
+}
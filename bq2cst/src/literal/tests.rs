@@ -0,0 +1,176 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn decode_all(code: &str) -> Vec<DecodedLiteral> {
+    let tokens = Lexer::new(code).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    collect(&stmts)
+}
+
+// the first error if any
+fn decode(code: &str) -> Result<Vec<Literal>, String> {
+    decode_all(code)
+        .into_iter()
+        .map(|l| l.literal.ok_or_else(|| l.error.unwrap()))
+        .collect()
+}
+
+#[test]
+fn test_strings() {
+    let literals = decode(
+        r#"SELECT
+  'a\'b', "\x41éé\U0001F600", '''x
+y''', r'\d+', R"""\n""", '\101\a\?\`',
+  b'\x00\xff', B"a", rb'\x', Br'\\', NULL, TRUE, false;"#,
+    )
+    .unwrap();
+    assert_eq!(
+        literals,
+        vec![
+            Literal::String("a'b".to_string()),
+            Literal::String("Aéé😀".to_string()),
+            Literal::String("x\ny".to_string()),
+            Literal::String("\\d+".to_string()),
+            Literal::String("\\n".to_string()),
+            Literal::String("A\x07?`".to_string()),
+            Literal::Bytes(vec![0, 255]),
+            Literal::Bytes(vec![b'a']),
+            Literal::Bytes(b"\\x".to_vec()),
+            Literal::Bytes(b"\\\\".to_vec()),
+            Literal::Null,
+            Literal::Bool(true),
+            Literal::Bool(false),
+        ]
+    );
+    for code in [
+        r"SELECT '\q';",
+        r"SELECT '\x4';",
+        r"SELECT '\400';",
+        r"SELECT b'\u0041';",
+        r"SELECT '\xff';",
+    ] {
+        assert!(decode(code).is_err(), "{}", code);
+    }
+}
+
+#[test]
+fn test_numbers_and_typed_literals() {
+    let literals = decode(
+        "\
+SELECT 1, -2, 1.5E3, .5, DATE '2020-01-01', timestamp \"2020-01-01 00:00:00+09\",
+  JSON '{\"a\": 1}', NUMERIC ' 1.20 ', BIGNUMERIC '-3',
+  -9223372036854775808, 9223372036854775808, 0x7FFFFFFFFFFFFFFF, -0X8000000000000000,
+  NUMERIC '.5e-3', BIGNUMERIC '+1E10',
+  RANGE<DATE> '[2020-01-01, UNBOUNDED)';",
+    )
+    .unwrap();
+    assert_eq!(
+        literals,
+        vec![
+            Literal::Int(1),
+            Literal::Int(-2),
+            Literal::Float(1500.0),
+            Literal::Float(0.5),
+            Literal::Date("2020-01-01".to_string()),
+            Literal::Timestamp("2020-01-01 00:00:00+09".to_string()),
+            Literal::Json("{\"a\": 1}".to_string()),
+            Literal::Numeric("1.20".to_string()),
+            Literal::BigNumeric("-3".to_string()),
+            Literal::Int(i64::MIN),
+            Literal::Float(9223372036854775808.0),
+            Literal::Int(i64::MAX),
+            Literal::Int(i64::MIN),
+            Literal::Numeric(".5e-3".to_string()),
+            Literal::BigNumeric("+1E10".to_string()),
+            Literal::Range(Range {
+                element_type: "DATE".to_string(),
+                start: Some("2020-01-01".to_string()),
+                end: None,
+            }),
+        ]
+    );
+    for code in [
+        "SELECT NUMERIC 'abc';",
+        "SELECT NUMERIC 'NaN';",
+        "SELECT BIGNUMERIC 'inf';",
+        "SELECT NUMERIC '1e';",
+        "SELECT NUMERIC '.';",
+        "SELECT 0x8000000000000000;",
+    ] {
+        assert!(decode(code).is_err(), "{}", code);
+    }
+}
+
+#[test]
+fn test_intervals() {
+    let interval = |months, days, nanos| {
+        Literal::Interval(Interval {
+            months,
+            days,
+            nanos,
+        })
+    };
+    let literals = decode(
+        "\
+SELECT INTERVAL 5 DAY, INTERVAL -2 QUARTER, INTERVAL '3' HOUR,
+  INTERVAL '1-2 3' YEAR TO DAY, INTERVAL '-1-2' YEAR TO MONTH,
+  INTERVAL '10 -1:30:15.5' DAY TO SECOND, INTERVAL '2:03' MINUTE TO SECOND;",
+    )
+    .unwrap();
+    let hour = 3600 * NANOS_PER_SECOND;
+    assert_eq!(
+        literals,
+        vec![
+            interval(0, 5, 0),
+            interval(-6, 0, 0),
+            interval(0, 0, 3 * hour),
+            interval(14, 3, 0),
+            interval(-14, 0, 0),
+            interval(0, 10, -(hour + 1815 * NANOS_PER_SECOND + 500_000_000)),
+            interval(0, 0, 123 * NANOS_PER_SECOND),
+        ]
+    );
+    for code in [
+        "SELECT INTERVAL '1-2' DAY TO YEAR;",
+        "SELECT INTERVAL '1 2' YEAR TO MONTH;",
+        "SELECT INTERVAL 'x' DAY;",
+    ] {
+        assert!(decode(code).is_err(), "{}", code);
+    }
+}
+
+#[test]
+fn test_spans_and_errors() {
+    let literals = decode_all(
+        "\
+SELECT -1 AS a, NUMERIC 'abc', INTERVAL '1-2'
+  YEAR TO MONTH, b'x', 2;",
+    );
+    let spans: Vec<[usize; 4]> = literals
+        .iter()
+        .map(|l| {
+            [
+                l.span.line,
+                l.span.column,
+                l.span.end_line,
+                l.span.end_column,
+            ]
+        })
+        .collect();
+    assert_eq!(
+        spans,
+        vec![
+            [1, 8, 1, 10],
+            [1, 17, 1, 30],
+            [1, 32, 2, 16],
+            [2, 18, 2, 22],
+            [2, 24, 2, 25],
+        ]
+    );
+    // an invalid literal does not prevent the others from being decoded
+    assert_eq!(literals[1].literal, None);
+    assert!(literals[1].error.is_some());
+    assert_eq!(literals[4].literal, Some(Literal::Int(2)));
+    assert_eq!(literals[4].error, None);
+}
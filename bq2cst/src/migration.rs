@@ -0,0 +1,411 @@
+#[cfg(test)]
+mod tests;
+
+use crate::catalog::{
+    same_dataset, Catalog, Column, Constraint, ConstraintKind, OptionEntry, Table,
+};
+use crate::cst::{Node, NodeType};
+use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::token::quote_identifier;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Migration {
+    pub statements: Vec<String>,
+    // changes which `ALTER TABLE` cannot apply, the table has to be recreated
+    pub rebuild_reasons: Vec<String>,
+    // `RENAME COLUMN` which may be used instead of `DROP COLUMN` and `ADD COLUMN` (keeping the data)
+    pub possible_renames: Vec<String>,
+    // changes which cannot be migrated (e.g. the type of a column is unknown)
+    pub warnings: Vec<String>,
+}
+
+impl Migration {
+    pub fn rebuild_required(&self) -> bool {
+        !self.rebuild_reasons.is_empty()
+    }
+}
+
+fn normalize_type(base: &str) -> &str {
+    match base {
+        "INT" | "SMALLINT" | "INTEGER" | "BIGINT" | "TINYINT" | "BYTEINT" => "INT64",
+        "DECIMAL" => "NUMERIC",
+        "BIGDECIMAL" => "BIGNUMERIC",
+        "BOOLEAN" => "BOOL",
+        _ => base,
+    }
+}
+
+// `NUMERIC(10, 2)` -> ("NUMERIC", [10, 2])
+fn split_type(type_: &str) -> Option<(&str, Vec<u32>)> {
+    match type_.split_once('(') {
+        Some((base, params)) => {
+            let params = params
+                .strip_suffix(')')?
+                .split(',')
+                .map(|p| p.trim().parse().ok())
+                .collect::<Option<Vec<u32>>>()?;
+            Some((normalize_type(base), params))
+        }
+        None => Some((normalize_type(type_), Vec::new())),
+    }
+}
+
+// whether `ALTER COLUMN SET DATA TYPE` can change the type
+fn is_widening(old: &str, new: &str) -> bool {
+    let ((old_base, old_params), (new_base, new_params)) = match (split_type(old), split_type(new))
+    {
+        (Some(old), Some(new)) => (old, new),
+        _ => return false,
+    };
+    match (old_base, new_base) {
+        ("INT64", "NUMERIC" | "BIGNUMERIC" | "FLOAT64") => new_params.is_empty(),
+        ("NUMERIC", "BIGNUMERIC" | "FLOAT64") => new_params.is_empty(),
+        (old_base, new_base) if old_base != new_base => false,
+        ("STRING" | "BYTES", _) => match (old_params.as_slice(), new_params.as_slice()) {
+            (_, []) => true,
+            ([old], [new]) => old <= new,
+            _ => false,
+        },
+        ("NUMERIC" | "BIGNUMERIC", _) => match (old_params.as_slice(), new_params.as_slice()) {
+            (_, []) => true,
+            ([], _) => false,
+            (old, new) => {
+                let (old_p, old_s) = (old[0], old.get(1).copied().unwrap_or(0));
+                let (new_p, new_s) = (new[0], new.get(1).copied().unwrap_or(0));
+                old_s <= new_s && old_p.saturating_sub(old_s) <= new_p.saturating_sub(new_s)
+            }
+        },
+        _ => false,
+    }
+}
+
+fn options_text(options: &[OptionEntry]) -> String {
+    let entries = options
+        .iter()
+        .map(|o| format!("{} = {}", o.name, o.value))
+        .collect::<Vec<_>>();
+    format!("OPTIONS({})", entries.join(", "))
+}
+
+// `SET OPTIONS`, removed options are set to NULL
+fn options_diff(old: &[OptionEntry], new: &[OptionEntry]) -> Vec<OptionEntry> {
+    let mut res = Vec::new();
+    for entry in new {
+        if !old.contains(entry) {
+            res.push(entry.clone());
+        }
+    }
+    for entry in old {
+        if !new.iter().any(|o| o.name == entry.name) {
+            res.push(OptionEntry {
+                name: entry.name.clone(),
+                value: "NULL".to_string(),
+            });
+        }
+    }
+    res
+}
+
+fn quote_identifiers(names: &[String], separator: &str) -> String {
+    names
+        .iter()
+        .map(|n| quote_identifier(n))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+// `None` if the type is unknown (e.g. `CREATE TABLE ... AS SELECT`)
+fn column_definition(column: &Column) -> Option<String> {
+    let mut res = format!(
+        "{} {}",
+        quote_identifier(&column.name),
+        column.type_.as_deref()?
+    );
+    if let Some(default) = &column.default {
+        res.push_str(&format!(" DEFAULT {}", default));
+    }
+    if !column.options.is_empty() {
+        res.push_str(&format!(" {}", options_text(&column.options)));
+    }
+    Some(res)
+}
+
+fn constraint_definition(constraint: &Constraint) -> String {
+    let mut res = String::new();
+    if let Some(name) = &constraint.name {
+        res.push_str(&format!("CONSTRAINT {} ", quote_identifier(name)));
+    }
+    match constraint.kind {
+        ConstraintKind::PrimaryKey => res.push_str("PRIMARY KEY"),
+        ConstraintKind::ForeignKey => res.push_str("FOREIGN KEY"),
+    }
+    res.push_str(&format!(
+        " ({})",
+        quote_identifiers(&constraint.columns, ", ")
+    ));
+    if let Some(table) = &constraint.referenced_table {
+        res.push_str(&format!(
+            " REFERENCES {}({})",
+            quote_identifiers(table, "."),
+            quote_identifiers(&constraint.referenced_columns, ", ")
+        ));
+    }
+    res.push_str(" NOT ENFORCED");
+    res
+}
+
+fn same_definition(a: &Column, b: &Column) -> bool {
+    a.type_ == b.type_
+        && a.not_null == b.not_null
+        && a.default == b.default
+        && a.options == b.options
+}
+
+fn find<'c>(columns: &'c [Column], name: &str) -> Option<&'c Column> {
+    columns.iter().find(|c| c.name.eq_ignore_ascii_case(name))
+}
+
+struct Builder<'t> {
+    table: &'t str,
+    migration: Migration,
+}
+
+impl Builder<'_> {
+    fn alter(&mut self, clauses: Vec<String>) {
+        if !clauses.is_empty() {
+            self.migration.statements.push(format!(
+                "ALTER TABLE {} {};",
+                self.table,
+                clauses.join(", ")
+            ));
+        }
+    }
+    fn rebuild(&mut self, reason: String) {
+        self.migration.rebuild_reasons.push(reason);
+    }
+    fn warn(&mut self, warning: String) {
+        self.migration.warnings.push(warning);
+    }
+    fn constraints(&mut self, old: &[Constraint], new: &[Constraint]) -> Vec<String> {
+        let mut drops = Vec::new();
+        let mut adds = Vec::new();
+        for constraint in old.iter().filter(|c| !new.contains(c)) {
+            match (constraint.kind, &constraint.name) {
+                (ConstraintKind::PrimaryKey, _) => drops.push("DROP PRIMARY KEY".to_string()),
+                (ConstraintKind::ForeignKey, Some(name)) => {
+                    drops.push(format!("DROP CONSTRAINT {}", quote_identifier(name)))
+                }
+                (ConstraintKind::ForeignKey, None) => self.rebuild(format!(
+                    "The unnamed foreign key on ({}) cannot be dropped.",
+                    constraint.columns.join(", ")
+                )),
+            }
+        }
+        for constraint in new.iter().filter(|c| !old.contains(c)) {
+            adds.push(format!("ADD {}", constraint_definition(constraint)));
+        }
+        self.alter(drops);
+        adds
+    }
+    fn alter_type(&mut self, column: &Column, old_type: &str, new_type: &str) {
+        // e.g. INT64 and INTEGER
+        if old_type == new_type
+            || split_type(old_type).is_some_and(|t| split_type(new_type) == Some(t))
+        {
+            return;
+        }
+        if is_widening(old_type, new_type) {
+            self.alter(vec![format!(
+                "ALTER COLUMN {} SET DATA TYPE {}",
+                quote_identifier(&column.name),
+                new_type
+            )]);
+        } else {
+            self.rebuild(format!(
+                "The type of `{}` cannot be changed from {} to {}.",
+                column.name, old_type, new_type
+            ));
+        }
+    }
+    fn alter_column(&mut self, old: &Column, new: &Column) {
+        let name = &quote_identifier(&new.name);
+        match (&old.type_, &new.type_) {
+            (Some(old_type), Some(new_type)) => self.alter_type(new, old_type, new_type),
+            (None, None) => (),
+            _ => self.warn(format!(
+                "The type of `{}` is unknown, it is not compared.",
+                new.name
+            )),
+        }
+        match (old.not_null, new.not_null) {
+            (true, false) => self.alter(vec![format!("ALTER COLUMN {} DROP NOT NULL", name)]),
+            (false, true) => self.rebuild(format!("`{}` cannot be changed to NOT NULL.", new.name)),
+            _ => (),
+        }
+        if old.default != new.default {
+            match &new.default {
+                Some(default) => self.alter(vec![format!(
+                    "ALTER COLUMN {} SET DEFAULT {}",
+                    name, default
+                )]),
+                None => self.alter(vec![format!("ALTER COLUMN {} DROP DEFAULT", name)]),
+            }
+        }
+        let options = options_diff(&old.options, &new.options);
+        if !options.is_empty() {
+            self.alter(vec![format!(
+                "ALTER COLUMN {} SET {}",
+                name,
+                options_text(&options)
+            )]);
+        }
+    }
+}
+
+// statements to migrate `old` to `new`, `table` is the name used in the statements
+pub fn diff_tables(old: &Table, new: &Table, table: &str) -> Migration {
+    let mut builder = Builder {
+        table,
+        migration: Migration::default(),
+    };
+    if old.kind != new.kind {
+        builder.rebuild(format!(
+            "The kind is changed from {:?} to {:?}.",
+            old.kind, new.kind
+        ));
+        return builder.migration;
+    }
+    if old.partition_by != new.partition_by {
+        builder.rebuild("The partitioning is changed.".to_string());
+    }
+    if old.cluster_by != new.cluster_by {
+        builder.rebuild("The clustering is changed.".to_string());
+    }
+    let add_constraints = builder.constraints(&old.constraints, &new.constraints);
+
+    let dropped: Vec<&Column> = old
+        .columns
+        .iter()
+        .filter(|c| find(&new.columns, &c.name).is_none())
+        .collect();
+    let added: Vec<&Column> = new
+        .columns
+        .iter()
+        .filter(|c| find(&old.columns, &c.name).is_none())
+        .collect();
+    // a column which is replaced by the same definition at the same position may be renamed,
+    // it is only reported because it cannot be distinguished from replacing the column
+    for old_column in &dropped {
+        let position = old.columns.iter().position(|c| c == *old_column);
+        let renamed = added.iter().find(|new_column| {
+            new.columns.iter().position(|c| c == **new_column) == position
+                && same_definition(old_column, new_column)
+        });
+        if let Some(new_column) = renamed {
+            builder.migration.possible_renames.push(format!(
+                "ALTER TABLE {} RENAME COLUMN {} TO {};",
+                table,
+                quote_identifier(&old_column.name),
+                quote_identifier(&new_column.name)
+            ));
+        }
+    }
+    builder.alter(
+        dropped
+            .iter()
+            .map(|c| format!("DROP COLUMN {}", quote_identifier(&c.name)))
+            .collect(),
+    );
+    let mut adds = Vec::new();
+    for column in added {
+        if column.not_null {
+            builder.rebuild(format!(
+                "NOT NULL column `{}` cannot be added.",
+                column.name
+            ));
+        } else {
+            match column_definition(column) {
+                Some(definition) => adds.push(format!("ADD COLUMN {}", definition)),
+                None => builder.warn(format!(
+                    "The type of `{}` is unknown, it is not added.",
+                    column.name
+                )),
+            }
+        }
+    }
+    builder.alter(adds);
+    for new_column in &new.columns {
+        if let Some(old_column) = find(&old.columns, &new_column.name) {
+            builder.alter_column(old_column, new_column);
+        }
+    }
+    builder.alter(add_constraints);
+    let options = options_diff(&old.options, &new.options);
+    if !options.is_empty() {
+        builder.alter(vec![format!("SET {}", options_text(&options))]);
+    }
+    builder.migration
+}
+
+fn table_of(stmt: &Node) -> BQ2CSTResult<Table> {
+    let error = |message: &str| match stmt.token {
+        Some(token) => BQ2CSTError::from_token(&token, message.to_string()),
+        None => BQ2CSTError::new(0, 0, message.to_string()),
+    };
+    if stmt.node_type != NodeType::CreateTableStatement {
+        return Err(error("Expected CREATE TABLE statement."));
+    }
+    let path = stmt
+        .get_node("ident")
+        .and_then(|n| n.identifier_path())
+        .ok_or_else(|| error("The table name is not found."))?;
+    let catalog = Catalog::from_stmts(std::slice::from_ref(stmt));
+    match catalog.table(&path) {
+        Some(table) => Ok(table.clone()),
+        None => Err(error("The table name should be qualified by the dataset.")),
+    }
+}
+
+fn path_of(stmt: &Node) -> Vec<String> {
+    stmt.get_node("ident")
+        .and_then(|n| n.identifier_path())
+        .unwrap_or_default()
+}
+
+// `old` and `new` are `CREATE TABLE` statements
+pub fn diff(old: &Node, new: &Node) -> BQ2CSTResult<Migration> {
+    let (old_table, new_table) = (table_of(old)?, table_of(new)?);
+    let name = new
+        .get_node("ident")
+        .map(|n| n.source_text())
+        .unwrap_or_default();
+    let mut migration = diff_tables(&old_table, &new_table, &name);
+    let (old_path, new_path) = (path_of(old), path_of(new));
+    if !same_dataset(
+        &old_path[..old_path.len() - 1],
+        &new_path[..new_path.len() - 1],
+    ) {
+        // `RENAME TO` cannot move the table to another dataset
+        migration.rebuild_reasons.insert(
+            0,
+            format!(
+                "The table is moved from {} to {}.",
+                old_path.join("."),
+                new_path.join(".")
+            ),
+        );
+    } else if old_table.name != new_table.name {
+        let old_name = old
+            .get_node("ident")
+            .map(|n| n.source_text())
+            .unwrap_or_default();
+        let rename = format!(
+            "ALTER TABLE {} RENAME TO {};",
+            old_name,
+            quote_identifier(&new_table.name)
+        );
+        migration.statements.insert(0, rename);
+    }
+    Ok(migration)
+}
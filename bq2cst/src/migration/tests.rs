@@ -0,0 +1,199 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn migrate(old: &str, new: &str) -> Migration {
+    let old = Parser::new(Lexer::new(old).tokenize_code().unwrap())
+        .parse_code()
+        .unwrap();
+    let new = Parser::new(Lexer::new(new).tokenize_code().unwrap())
+        .parse_code()
+        .unwrap();
+    diff(&old[0], &new[0]).unwrap()
+}
+
+#[test]
+fn test_alter_table() {
+    let migration = migrate(
+        "\
+CREATE TABLE ds.t (
+  id INT64,
+  a STRING NOT NULL,
+  b STRING(10),
+  c NUMERIC(10, 2) OPTIONS(description = 'c'),
+  old_name DATE,
+  d BOOL,
+  CONSTRAINT fk FOREIGN KEY (id) REFERENCES ds.u(id) NOT ENFORCED
+) OPTIONS(description = 'x', labels = [('a', 'b')]);",
+        "\
+CREATE TABLE ds.t (
+  id INTEGER PRIMARY KEY NOT ENFORCED,
+  a STRING DEFAULT 'a',
+  b STRING(20),
+  c NUMERIC(12, 3) OPTIONS(description = 'C', rounding_mode = 'ROUND_HALF_EVEN'),
+  new_name DATE,
+  e ARRAY<INT64> OPTIONS(description = 'e')
+) OPTIONS(description = 'y');",
+    );
+    assert_eq!(
+        migration.statements,
+        vec![
+            "ALTER TABLE ds.t DROP CONSTRAINT fk;",
+            "ALTER TABLE ds.t DROP COLUMN old_name, DROP COLUMN d;",
+            "ALTER TABLE ds.t ADD COLUMN new_name DATE, ADD COLUMN e ARRAY<INT64> OPTIONS(description = 'e');",
+            "ALTER TABLE ds.t ALTER COLUMN a DROP NOT NULL;",
+            "ALTER TABLE ds.t ALTER COLUMN a SET DEFAULT 'a';",
+            "ALTER TABLE ds.t ALTER COLUMN b SET DATA TYPE STRING(20);",
+            "ALTER TABLE ds.t ALTER COLUMN c SET DATA TYPE NUMERIC(12, 3);",
+            "ALTER TABLE ds.t ALTER COLUMN c SET OPTIONS(description = 'C', rounding_mode = 'ROUND_HALF_EVEN');",
+            "ALTER TABLE ds.t ADD PRIMARY KEY (id) NOT ENFORCED;",
+            "ALTER TABLE ds.t SET OPTIONS(description = 'y', labels = NULL);",
+        ]
+    );
+    assert!(!migration.rebuild_required());
+    assert_eq!(
+        migration.possible_renames,
+        vec!["ALTER TABLE ds.t RENAME COLUMN old_name TO new_name;"]
+    );
+}
+
+#[test]
+fn test_quoted_identifiers() {
+    let migration = migrate(
+        "CREATE TABLE ds.t (`select` INT64, `a-b` STRING, PRIMARY KEY (`select`) NOT ENFORCED);",
+        "\
+CREATE TABLE ds.`from` (
+  `select` NUMERIC,
+  `a-b` STRING DEFAULT 'x',
+  `group` DATE,
+  CONSTRAINT `order` FOREIGN KEY (`group`) REFERENCES `my-project`.ds.u(`by`) NOT ENFORCED
+);",
+    );
+    assert_eq!(
+        migration.statements,
+        vec![
+            "ALTER TABLE ds.t RENAME TO `from`;",
+            "ALTER TABLE ds.`from` DROP PRIMARY KEY;",
+            "ALTER TABLE ds.`from` ADD COLUMN `group` DATE;",
+            "ALTER TABLE ds.`from` ALTER COLUMN `select` SET DATA TYPE NUMERIC;",
+            "ALTER TABLE ds.`from` ALTER COLUMN `a-b` SET DEFAULT 'x';",
+            "ALTER TABLE ds.`from` ADD CONSTRAINT `order` FOREIGN KEY (`group`) REFERENCES `my-project`.ds.u(`by`) NOT ENFORCED;",
+        ]
+    );
+}
+
+#[test]
+fn test_move_table() {
+    for (old, new) in [
+        (
+            "CREATE TABLE ds.t (a INT64);",
+            "CREATE TABLE ds2.t (a INT64);",
+        ),
+        (
+            "CREATE TABLE p.ds.t (a INT64);",
+            "CREATE TABLE q.ds.u (a INT64);",
+        ),
+    ] {
+        let migration = migrate(old, new);
+        assert!(migration.statements.is_empty(), "{}", new);
+        assert!(migration.rebuild_required(), "{}", new);
+    }
+    // the project is omitted
+    for (old, new) in [
+        (
+            "CREATE TABLE ds.t (a INT64);",
+            "CREATE TABLE myproj.ds.t (a INT64);",
+        ),
+        (
+            "CREATE TABLE myproj.ds.t (a INT64);",
+            "CREATE TABLE ds.t (a INT64);",
+        ),
+    ] {
+        assert_eq!(migrate(old, new), Migration::default(), "{}", new);
+    }
+    assert_eq!(
+        migrate(
+            "CREATE TABLE ds.t (a INT64);",
+            "CREATE TABLE myproj.ds.u (a INT64);"
+        )
+        .statements,
+        vec!["ALTER TABLE ds.t RENAME TO u;"]
+    );
+    assert_eq!(
+        migrate(
+            "CREATE TABLE ds.t (a INT64);",
+            "CREATE TABLE ds2.t (a INT64);"
+        )
+        .rebuild_reasons,
+        vec!["The table is moved from ds.t to ds2.t."]
+    );
+}
+
+#[test]
+fn test_rebuild() {
+    let migration = migrate(
+        "\
+CREATE TABLE ds.t (
+  a NUMERIC,
+  b STRING,
+  c STRUCT<x INT64>,
+  d INT64,
+  FOREIGN KEY (a) REFERENCES ds.u(a) NOT ENFORCED
+) PARTITION BY DATE(ts);",
+        "\
+CREATE TABLE ds.t2 (
+  a INT64,
+  b STRING(10),
+  c STRUCT<x INT64, y INT64>,
+  d FLOAT64 NOT NULL,
+  e INT64 NOT NULL
+) CLUSTER BY a;",
+    );
+    assert_eq!(
+        migration.statements,
+        vec![
+            "ALTER TABLE ds.t RENAME TO t2;",
+            "ALTER TABLE ds.t2 ALTER COLUMN d SET DATA TYPE FLOAT64;",
+        ]
+    );
+    assert_eq!(
+        migration.rebuild_reasons,
+        vec![
+            "The partitioning is changed.",
+            "The clustering is changed.",
+            "The unnamed foreign key on (a) cannot be dropped.",
+            "NOT NULL column `e` cannot be added.",
+            "The type of `a` cannot be changed from NUMERIC to INT64.",
+            "The type of `b` cannot be changed from STRING to STRING(10).",
+            "The type of `c` cannot be changed from STRUCT<x INT64> to STRUCT<x INT64, y INT64>.",
+            "`d` cannot be changed to NOT NULL.",
+        ]
+    );
+}
+
+#[test]
+fn test_unknown_types() {
+    let migration = migrate(
+        "CREATE TABLE ds.t (a INT64, b INT64);",
+        "\
+CREATE TABLE ds.t AS
+SELECT 1 AS a, 'x' AS b, 2 AS c;",
+    );
+    assert!(migration.statements.is_empty());
+    assert_eq!(
+        migration.warnings,
+        vec![
+            "The type of `c` is unknown, it is not added.",
+            "The type of `a` is unknown, it is not compared.",
+            "The type of `b` is unknown, it is not compared.",
+        ]
+    );
+}
+
+#[test]
+fn test_error() {
+    let stmts = Parser::new(Lexer::new("SELECT 1;").tokenize_code().unwrap())
+        .parse_code()
+        .unwrap();
+    assert!(diff(&stmts[0], &stmts[0]).is_err());
+}
@@ -0,0 +1,278 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node, NodeType};
+use crate::diagnostic::Span;
+use crate::literal::{is_literal, Literal};
+use crate::schema;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", content = "value")]
+pub enum OptionValue {
+    String(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Null,
+    Array(Vec<OptionValue>),
+    Struct(Vec<OptionField>),
+    // other literals (e.g. `TIMESTAMP '2020-01-01'`)
+    Literal(Literal),
+    // anything which cannot be decoded statically (e.g. `CURRENT_TIMESTAMP()`)
+    Expression(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OptionField {
+    pub name: Option<String>,
+    pub value: OptionValue,
+}
+
+impl OptionValue {
+    pub fn from_node(node: &Node) -> OptionValue {
+        decode(node).unwrap_or_else(|| OptionValue::Expression(node.source_text()))
+    }
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            OptionValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            OptionValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            OptionValue::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+    // integers are also accepted
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            OptionValue::Int(i) => Some(*i as f64),
+            OptionValue::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+    pub fn is_expression(&self) -> bool {
+        match self {
+            OptionValue::Expression(_) => true,
+            OptionValue::Array(values) => values.iter().any(OptionValue::is_expression),
+            OptionValue::Struct(fields) => fields.iter().any(|f| f.value.is_expression()),
+            _ => false,
+        }
+    }
+}
+
+fn decode(node: &Node) -> Option<OptionValue> {
+    if is_literal(node) {
+        let value = match Literal::from_node(node).ok()? {
+            Literal::String(s) => OptionValue::String(s),
+            Literal::Int(i) => OptionValue::Int(i),
+            Literal::Float(f) => OptionValue::Float(f),
+            Literal::Bool(b) => OptionValue::Bool(b),
+            Literal::Null => OptionValue::Null,
+            literal => OptionValue::Literal(literal),
+        };
+        return Some(value);
+    }
+    let value = match node.node_type {
+        NodeType::ArrayLiteral => OptionValue::Array(
+            node.get_node_vec("exprs")
+                .iter()
+                .map(|n| OptionValue::from_node(n))
+                .collect(),
+        ),
+        NodeType::StructLiteral => OptionValue::Struct(
+            node.get_node_vec("exprs")
+                .iter()
+                .map(|n| OptionField {
+                    name: n
+                        .get_node("alias")
+                        .and_then(|a| a.token)
+                        .map(|t| t.literal.trim_matches('`').to_string()),
+                    value: OptionValue::from_node(n),
+                })
+                .collect(),
+        ),
+        _ => return None,
+    };
+    Some(value)
+}
+
+// the entries of an `OPTIONS(...)` clause, names are lowercased
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Options {
+    pub entries: Vec<(String, OptionValue)>,
+}
+
+impl Serialize for Options {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+        for (name, value) in &self.entries {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+impl Options {
+    // `node` is the `OPTIONS` keyword node
+    pub fn new(node: &Node) -> Options {
+        let exprs = match node.get_node("group") {
+            Some(group) => group.get_node_vec("exprs"),
+            None => &[],
+        };
+        let mut options = Options::default();
+        for expr in exprs {
+            let (name, value) = match (expr.get_node("left"), expr.get_node("right")) {
+                (Some(left), Some(right)) => (left, right),
+                _ => continue,
+            };
+            let name = match name.token {
+                Some(token) => token.literal.trim_matches('`').to_lowercase(),
+                None => continue,
+            };
+            // the last one wins
+            options.entries.retain(|(n, _)| *n != name);
+            options.entries.push((name, OptionValue::from_node(value)));
+        }
+        options
+    }
+    // the clause which belongs to `node` itself (e.g. a statement)
+    pub fn of(node: &Node) -> Option<Options> {
+        node.get_node("options").map(Options::new)
+    }
+    pub fn get(&self, name: &str) -> Option<&OptionValue> {
+        self.entries
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v)
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.get("description")?.as_str()
+    }
+    // `[("key", "value"), ...]`, `None` if it is not a constant
+    pub fn labels(&self) -> Option<Vec<(String, String)>> {
+        let values = match self.get("labels")? {
+            OptionValue::Array(values) => values,
+            _ => return None,
+        };
+        values
+            .iter()
+            .map(|v| match v {
+                OptionValue::Struct(fields) if fields.len() == 2 => Some((
+                    fields[0].value.as_str()?.to_string(),
+                    fields[1].value.as_str()?.to_string(),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+    pub fn partition_expiration_days(&self) -> Option<f64> {
+        self.get("partition_expiration_days")?.as_f64()
+    }
+    pub fn require_partition_filter(&self) -> Option<bool> {
+        self.get("require_partition_filter")?.as_bool()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OptionsClause {
+    // the name of the statement's target (e.g. `["ds", "t"]`)
+    pub target: Vec<String>,
+    // the path to the column or struct field, empty if the clause belongs to the target
+    pub column: Vec<String>,
+    pub span: Span,
+    pub options: Options,
+}
+
+struct Collector {
+    target: Vec<String>,
+    column: Vec<String>,
+    clauses: Vec<OptionsClause>,
+}
+
+impl Collector {
+    fn node(&mut self, node: &Node) {
+        let is_statement = schema::get_by_node_type(&node.node_type)
+            .is_some_and(|s| s.is_subtype_of("XXXStatement"));
+        if is_statement {
+            let target = node
+                .get_node("ident")
+                .and_then(|n| n.identifier_path())
+                .unwrap_or_default();
+            let target = std::mem::replace(&mut self.target, target);
+            let column = std::mem::take(&mut self.column);
+            self.children(node);
+            self.target = target;
+            self.column = column;
+            return;
+        }
+        let column = match node.node_type {
+            NodeType::KeywordWithGroupedXXX
+                if node
+                    .token
+                    .is_some_and(|t| t.literal.eq_ignore_ascii_case("OPTIONS")) =>
+            {
+                self.clauses.push(OptionsClause {
+                    target: self.target.clone(),
+                    column: self.column.clone(),
+                    span: Span::from_token(&node.token.unwrap()),
+                    options: Options::new(node),
+                });
+                return;
+            }
+            NodeType::TypeDeclaration => {
+                node.token.map(|t| t.literal.trim_matches('`').to_string())
+            }
+            NodeType::AlterColumnStatement => node
+                .get_node("ident")
+                .and_then(|n| n.token)
+                .map(|t| t.literal.trim_matches('`').to_string()),
+            _ => None,
+        };
+        match column {
+            Some(column) => {
+                self.column.push(column);
+                self.children(node);
+                self.column.pop();
+            }
+            None => self.children(node),
+        }
+    }
+    fn children(&mut self, node: &Node) {
+        for content in node.children.values() {
+            match content {
+                ContentType::Node(n) => self.node(n),
+                ContentType::NodeVec(ns) => {
+                    for n in ns {
+                        self.node(n);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// every `OPTIONS(...)` clause in `stmts`
+pub fn collect(stmts: &[Node]) -> Vec<OptionsClause> {
+    let mut collector = Collector {
+        target: Vec::new(),
+        column: Vec::new(),
+        clauses: Vec::new(),
+    };
+    for stmt in stmts {
+        collector.node(stmt);
+    }
+    // children are not ordered
+    let mut clauses = collector.clauses;
+    clauses.sort_by_key(|c| (c.span.line, c.span.column));
+    clauses
+}
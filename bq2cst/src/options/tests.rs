@@ -0,0 +1,109 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn parse(code: &str) -> Vec<Node<'_>> {
+    let tokens = Lexer::new(code).tokenize_code().unwrap();
+    Parser::new(tokens).parse_code().unwrap()
+}
+
+#[test]
+fn test_options() {
+    let stmts = parse(
+        "\
+CREATE TABLE ds.t (x INT64)
+PARTITION BY _PARTITIONDATE
+OPTIONS(
+  Description = '''a\\tb''',
+  labels = [('env', 'prod'), ('team', r'\\d')],
+  partition_expiration_days = 7,
+  require_partition_filter = TRUE,
+  max_staleness = NULL,
+  ratio = -1.5,
+  s = STRUCT('a' AS x, 1),
+  expiration_timestamp = TIMESTAMP_ADD(CURRENT_TIMESTAMP(), INTERVAL 1 DAY)
+);",
+    );
+    let options = Options::of(&stmts[0]).unwrap();
+    assert_eq!(options.description(), Some("a\tb"));
+    assert_eq!(
+        options.labels(),
+        Some(vec![
+            ("env".to_string(), "prod".to_string()),
+            ("team".to_string(), "\\d".to_string()),
+        ])
+    );
+    assert_eq!(options.partition_expiration_days(), Some(7.0));
+    assert_eq!(options.require_partition_filter(), Some(true));
+    assert_eq!(options.get("max_staleness"), Some(&OptionValue::Null));
+    assert_eq!(options.get("ratio"), Some(&OptionValue::Float(-1.5)));
+    assert_eq!(
+        options.get("s"),
+        Some(&OptionValue::Struct(vec![
+            OptionField {
+                name: Some("x".to_string()),
+                value: OptionValue::String("a".to_string()),
+            },
+            OptionField {
+                name: None,
+                value: OptionValue::Int(1),
+            },
+        ]))
+    );
+    let expiration = options.get("expiration_timestamp").unwrap();
+    assert!(expiration.is_expression());
+    assert_eq!(
+        expiration,
+        &OptionValue::Expression("TIMESTAMP_ADD(CURRENT_TIMESTAMP(), INTERVAL 1 DAY)".to_string())
+    );
+}
+
+#[test]
+fn test_collect() {
+    let stmts = parse(
+        "\
+CREATE TABLE ds.t (
+  x STRUCT<y INT64 OPTIONS(description = 'y')> OPTIONS(description = 'x')
+) OPTIONS(description = 't');
+ALTER TABLE ds.t ALTER COLUMN x SET OPTIONS(description = NULL);
+CREATE SCHEMA ds OPTIONS(labels = [('a', 'b')]);",
+    );
+    let summary: Vec<(String, String, String)> = collect(&stmts)
+        .iter()
+        .map(|c| {
+            (
+                c.target.join("."),
+                c.column.join("."),
+                serde_json::to_string(&c.options).unwrap(),
+            )
+        })
+        .collect();
+    let expected = [
+        (
+            "ds.t",
+            "x.y",
+            r#"{"description":{"type":"String","value":"y"}}"#,
+        ),
+        (
+            "ds.t",
+            "x",
+            r#"{"description":{"type":"String","value":"x"}}"#,
+        ),
+        (
+            "ds.t",
+            "",
+            r#"{"description":{"type":"String","value":"t"}}"#,
+        ),
+        ("ds.t", "x", r#"{"description":{"type":"Null"}}"#),
+        (
+            "ds",
+            "",
+            r#"{"labels":{"type":"Array","value":[{"type":"Struct","value":[{"name":null,"value":{"type":"String","value":"a"}},{"name":null,"value":{"type":"String","value":"b"}}]}]}}"#,
+        ),
+    ];
+    let expected: Vec<(String, String, String)> = expected
+        .iter()
+        .map(|(a, b, c)| (a.to_string(), b.to_string(), c.to_string()))
+        .collect();
+    assert_eq!(summary, expected);
+}
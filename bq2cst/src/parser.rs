@@ -5,23 +5,101 @@ use crate::cst::ContentType;
 use crate::cst::Node;
 use crate::cst::NodeType;
 use crate::error::{BQ2CSTError, BQ2CSTResult};
-use crate::token::{TemplateType, Token};
+use crate::lexer;
+use crate::sqlx;
+use crate::template;
+use crate::token::{Keyword, Symbol, TemplateType, Token, TokenKind};
 
-#[derive(Clone)]
-pub struct Parser {
+pub struct Parser<'a> {
     position: usize,
     leading_comment_indices: Vec<usize>,
     trailing_comment_indices: Vec<usize>,
-    tokens: Vec<Token>,
+    tokens: Vec<Token<'a>>,
+    // computed once in `new()` so that lookahead does not scan comments repeatedly
+    code_ranks: Vec<usize>,   // number of non-comment tokens before each token
+    code_indices: Vec<usize>, // indices of non-comment tokens
+    depth: usize,
+    max_depth: usize,
+    relaxed_macros: bool,
 }
 
-impl Parser {
-    pub fn new(tokens: Vec<Token>) -> Parser {
+// deep enough for handwritten SQL, shallow enough for the wasm stack
+pub const DEFAULT_MAX_DEPTH: usize = 100;
+
+// keywords which may start a fragment of a clause
+const FRAGMENT_KEYWORDS: &[Keyword] = &[
+    Keyword::ALL,
+    Keyword::AND,
+    Keyword::AS,
+    Keyword::ASC,
+    Keyword::BY,
+    Keyword::CROSS,
+    Keyword::DESC,
+    Keyword::DISTINCT,
+    Keyword::ELSE,
+    Keyword::END,
+    Keyword::EXCEPT,
+    Keyword::FROM,
+    Keyword::FULL,
+    Keyword::GROUP,
+    Keyword::HAVING,
+    Keyword::INNER,
+    Keyword::INTERSECT,
+    Keyword::JOIN,
+    Keyword::LEFT,
+    Keyword::LIMIT,
+    Keyword::NULLS,
+    Keyword::OFFSET,
+    Keyword::ON,
+    Keyword::OR,
+    Keyword::ORDER,
+    Keyword::OUTER,
+    Keyword::PARTITION,
+    Keyword::QUALIFY,
+    Keyword::RIGHT,
+    Keyword::THEN,
+    Keyword::UNION,
+    Keyword::USING,
+    Keyword::WHEN,
+    Keyword::WHERE,
+    Keyword::WINDOW,
+];
+
+fn has_unknown(node: &Node) -> bool {
+    node.node_type == NodeType::Unknown
+        || node.children.values().any(|c| match c {
+            ContentType::Node(n) => has_unknown(n),
+            ContentType::NodeVec(ns) => ns.iter().any(has_unknown),
+        })
+}
+
+// cursor state needed to backtrack (tokens themselves are never modified)
+struct ParserState {
+    position: usize,
+    leading_comment_indices: Vec<usize>,
+    trailing_comment_indices: Vec<usize>,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(tokens: Vec<Token<'a>>) -> Parser<'a> {
+        let mut code_ranks = Vec::with_capacity(tokens.len());
+        let mut code_indices = Vec::with_capacity(tokens.len());
+        for (idx, token) in tokens.iter().enumerate() {
+            code_ranks.push(code_indices.len());
+            if !token.is_comment() {
+                code_indices.push(idx);
+            }
+        }
         let mut p = Parser {
             position: 0,
             leading_comment_indices: Vec::new(),
             trailing_comment_indices: Vec::new(),
             tokens,
+            code_ranks,
+            code_indices,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            relaxed_macros: false,
         };
         while p.tokens[p.position].is_comment() {
             p.leading_comment_indices.push(p.position);
@@ -39,7 +117,16 @@ impl Parser {
         }
         p
     }
-    pub fn parse_code(&mut self) -> BQ2CSTResult<Vec<Node>> {
+    pub fn with_max_depth(mut self, max_depth: usize) -> Parser<'a> {
+        self.max_depth = max_depth;
+        self
+    }
+    // bodies of `{% macro %}` and `{% call %}` are parsed as fragments (e.g. `WHERE x = 1`)
+    pub fn with_relaxed_macros(mut self, relaxed_macros: bool) -> Parser<'a> {
+        self.relaxed_macros = relaxed_macros;
+        self
+    }
+    pub fn parse_code(&mut self) -> BQ2CSTResult<Vec<Node<'a>>> {
         let mut stmts: Vec<Node> = Vec::new();
         while !self.is_eof(0) {
             let stmt = self.parse_statement(true)?;
@@ -50,15 +137,15 @@ impl Parser {
         Ok(stmts)
     }
     // ----- core -----
-    fn construct_node(&self, node_type: NodeType) -> BQ2CSTResult<Node> {
+    fn construct_node(&self, node_type: NodeType) -> BQ2CSTResult<Node<'a>> {
         // NOTE
-        // It is possible to avoid cloning tokens (see #20)
-        // but it does not improve execution time.
+        // Tokens borrow their literal from the source code,
+        // so copying them into nodes does not allocate.
         let curr_token = self.get_token(0)?;
         let mut node = match node_type {
             NodeType::EOF | NodeType::StandAloneExpr => Node::empty(node_type),
             NodeType::Unknown => {
-                let mut node = Node::new(curr_token.clone(), node_type);
+                let mut node = Node::new(*curr_token, node_type);
                 if curr_token.is_identifier() {
                     node.node_type = NodeType::Identifier;
                 } else if curr_token.is_numeric() {
@@ -69,33 +156,45 @@ impl Parser {
                     node.node_type = NodeType::BooleanLiteral;
                 } else if curr_token.is_parameter() {
                     node.node_type = NodeType::Parameter;
-                } else if curr_token.literal.to_uppercase() == "NULL" {
+                } else if curr_token.is(Keyword::NULL) {
                     node.node_type = NodeType::NullLiteral;
-                } else if let "(" | "." = self.get_token(1)?.literal.as_str() {
+                } else if self.get_token(1)?.in_(&[Symbol::LParen, Symbol::Dot]) {
                     node.node_type = NodeType::Identifier;
                 }
                 node
             }
-            _ => Node::new(self.get_token(0)?.clone(), node_type),
+            _ => Node::new(*self.get_token(0)?, node_type),
         };
         // leading_comments
         let mut leading_comment_nodes = Vec::new();
         for idx in &self.leading_comment_indices {
-            leading_comment_nodes.push(Node::new(self.tokens[*idx].clone(), NodeType::Comment))
+            leading_comment_nodes.push(Node::new(self.tokens[*idx], NodeType::Comment))
         }
-        if 0 < leading_comment_nodes.len() {
+        if !leading_comment_nodes.is_empty() {
             node.push_node_vec("leading_comments", leading_comment_nodes);
         }
         // trailing comments
         let mut trailing_comment_nodes = Vec::new();
         for idx in &self.trailing_comment_indices {
-            trailing_comment_nodes.push(Node::new(self.tokens[*idx].clone(), NodeType::Comment))
+            trailing_comment_nodes.push(Node::new(self.tokens[*idx], NodeType::Comment))
         }
-        if 0 < trailing_comment_nodes.len() {
+        if !trailing_comment_nodes.is_empty() {
             node.push_node_vec("trailing_comments", trailing_comment_nodes);
         }
         Ok(node)
     }
+    fn save_state(&self) -> ParserState {
+        ParserState {
+            position: self.position,
+            leading_comment_indices: self.leading_comment_indices.clone(),
+            trailing_comment_indices: self.trailing_comment_indices.clone(),
+        }
+    }
+    fn restore_state(&mut self, state: ParserState) {
+        self.position = state.position;
+        self.leading_comment_indices = state.leading_comment_indices;
+        self.trailing_comment_indices = state.trailing_comment_indices;
+    }
     fn get_precedence(&self, offset: usize) -> BQ2CSTResult<usize> {
         // https://cloud.google.com/bigquery/docs/reference/standard-sql/operators
         // 001... - (identifier e.g. region-us)
@@ -113,20 +212,34 @@ impl Parser {
         // 111... AND
         // 112... OR
         // 200... => (ST_GEOGFROMGEOJSON)
-        let precedence = match self.get_token(offset)?.literal.to_uppercase().as_str() {
+        let precedence = match self.get_token(offset)?.kind {
             // return precedence of BINARY operator
-            "(" | "[" => 101,
-            "." => 102, // when used with chained function call (otherwise 101)
-            "*" | "/" | "||" => 103,
-            "+" | "-" => 104,
-            "<<" | ">>" => 105,
-            "&" => 106,
-            "^" => 107,
-            "|" => 108,
-            "=" | "<" | ">" | "<=" | ">=" | "!=" | "<>" | "LIKE" | "BETWEEN" | "IN" | "IS" => 109,
-            "NOT" => match self.get_token(offset + 1)?.literal.to_uppercase().as_str() {
-                "IN" | "LIKE" | "BETWEEN" => 109,
-                "ENFORCED" => usize::MAX,
+            TokenKind::Symbol(Symbol::LParen) | TokenKind::Symbol(Symbol::LBracket) => 101,
+            TokenKind::Symbol(Symbol::Dot) => 102, // when used with chained function call (otherwise 101)
+            TokenKind::Symbol(Symbol::Asterisk)
+            | TokenKind::Symbol(Symbol::Slash)
+            | TokenKind::Symbol(Symbol::Concat) => 103,
+            TokenKind::Symbol(Symbol::Plus) | TokenKind::Symbol(Symbol::Minus) => 104,
+            TokenKind::Symbol(Symbol::ShiftLeft) | TokenKind::Symbol(Symbol::ShiftRight) => 105,
+            TokenKind::Symbol(Symbol::Ampersand) => 106,
+            TokenKind::Symbol(Symbol::Caret) => 107,
+            TokenKind::Symbol(Symbol::Pipe) => 108,
+            TokenKind::Symbol(Symbol::Eq)
+            | TokenKind::Symbol(Symbol::Lt)
+            | TokenKind::Symbol(Symbol::Gt)
+            | TokenKind::Symbol(Symbol::LtEq)
+            | TokenKind::Symbol(Symbol::GtEq)
+            | TokenKind::Symbol(Symbol::NotEq)
+            | TokenKind::Symbol(Symbol::LtGt)
+            | TokenKind::Keyword(Keyword::LIKE)
+            | TokenKind::Keyword(Keyword::BETWEEN)
+            | TokenKind::Keyword(Keyword::IN)
+            | TokenKind::Keyword(Keyword::IS) => 109,
+            TokenKind::Keyword(Keyword::NOT) => match self.get_token(offset + 1)?.kind {
+                TokenKind::Keyword(Keyword::IN)
+                | TokenKind::Keyword(Keyword::LIKE)
+                | TokenKind::Keyword(Keyword::BETWEEN) => 109,
+                TokenKind::Keyword(Keyword::ENFORCED) => usize::MAX,
                 _ => {
                     return Err(BQ2CSTError::from_token(
                         self.get_token(offset + 1)?,
@@ -137,9 +250,9 @@ impl Parser {
                     ))
                 }
             },
-            "AND" => 111,
-            "OR" => 112,
-            "=>" => 200,
+            TokenKind::Keyword(Keyword::AND) => 111,
+            TokenKind::Keyword(Keyword::OR) => 112,
+            TokenKind::Symbol(Symbol::Arrow) => 200,
             _ => usize::MAX,
         };
         Ok(precedence)
@@ -148,30 +261,82 @@ impl Parser {
         if offset == 0 {
             return Ok(self.position);
         }
-        let mut cnt = 0;
-        let mut idx = self.position + 1;
-        loop {
-            if idx < self.tokens.len() {
-                if !self.tokens[idx].is_comment() {
-                    cnt += 1;
-                    if offset <= cnt {
-                        break;
-                    }
+        let rank = self.code_ranks[self.position] + offset;
+        match self.code_indices.get(rank) {
+            Some(idx) => Ok(*idx),
+            None => Err(BQ2CSTError::from_token(
+                &self.tokens[self.tokens.len() - 1],
+                "Followed by unexpected EOF".to_string(),
+            )),
+        }
+    }
+    // the contents of templates are optional (they may not be Jinja)
+    fn push_jinja(&self, node: &mut Node<'a>) -> BQ2CSTResult<()> {
+        if let Ok(jinja) = template::parse_tag(self.get_token(0)?) {
+            node.push_node("jinja", jinja);
+        }
+        Ok(())
+    }
+    // the contents of `pre_operations { ... }` and `post_operations { ... }` are SQL
+    fn parse_sqlx_block(&self) -> BQ2CSTResult<Node<'a>> {
+        let mut node = self.construct_node(NodeType::SqlxBlock)?;
+        if let Some(tokens) = sqlx::sql_block_tokens(self.get_token(0)?)? {
+            let mut stmts = Parser::new(tokens).parse_code()?;
+            stmts.retain(|s| s.node_type != NodeType::EOF);
+            node.push_node_vec("stmts", stmts);
+        }
+        Ok(node)
+    }
+    // the contents of interpolations are optional as well
+    fn construct_sqlx_interpolation(&self) -> BQ2CSTResult<Node<'a>> {
+        let mut node = self.construct_node(NodeType::SqlxInterpolation)?;
+        let token = self.get_token(0)?;
+        let parts = lexer::sqlx_interpolations(token);
+        match parts.as_slice() {
+            [part] if part.literal == token.literal => {
+                if let Ok(expr) = template::parse_interpolation(token) {
+                    node.push_node("expr", expr);
                 }
-                idx += 1;
-            } else {
-                return Err(BQ2CSTError::from_token(
-                    &self.tokens[self.tokens.len() - 1],
-                    "Followed by unexpected EOF".to_string(),
-                ));
             }
+            // e.g. `${self()}_tmp`
+            _ => node.push_node_vec(
+                "exprs",
+                parts
+                    .iter()
+                    .filter_map(|p| template::parse_interpolation(p).ok())
+                    .collect(),
+            ),
         }
-        Ok(idx)
+        Ok(node)
     }
-    fn get_token(&self, offset: usize) -> BQ2CSTResult<&Token> {
+    fn get_template_type(&self, offset: usize) -> BQ2CSTResult<Option<TemplateType>> {
+        let idx = self.get_offset_index(offset)?;
+        Ok(self.tokens[idx].get_template_type())
+    }
+    fn get_token(&self, offset: usize) -> BQ2CSTResult<&Token<'a>> {
         let idx = self.get_offset_index(offset)?;
         Ok(&self.tokens[idx])
     }
+    // recursive descent is guarded so that deeply nested SQL does not overflow the stack
+    fn nest<T>(&mut self, f: impl FnOnce(&mut Self) -> BQ2CSTResult<T>) -> BQ2CSTResult<T> {
+        let depth = self.depth;
+        self.deepen()?;
+        let res = f(self);
+        self.depth = depth; // links of chains counted in `f` are also released
+        res
+    }
+    // chains (e.g. `a AND b AND c`) are parsed in a loop but build a left-deep tree,
+    // which is serialized and dropped recursively. so each link is counted as nesting
+    fn deepen(&mut self) -> BQ2CSTResult<()> {
+        if self.max_depth <= self.depth {
+            return Err(BQ2CSTError::from_token(
+                self.get_token(0)?,
+                format!("Nesting is too deep (max depth: {})", self.max_depth),
+            ));
+        }
+        self.depth += 1;
+        Ok(())
+    }
     fn is_eof(&self, offset: usize) -> bool {
         let idx = match self.get_offset_index(offset) {
             Ok(i) => i,
@@ -206,7 +371,7 @@ impl Parser {
         }
         Ok(())
     }
-    fn parse_between_operator(&mut self, left: Node) -> BQ2CSTResult<Node> {
+    fn parse_between_operator(&mut self, left: Node<'a>) -> BQ2CSTResult<Node<'a>> {
         let precedence = self.get_precedence(0)?;
         let mut between = self.construct_node(NodeType::BetweenOperator)?;
         between.push_node("left", left);
@@ -226,14 +391,17 @@ impl Parser {
         );
         Ok(between)
     }
-    fn parse_binary_operator(&mut self, left: Node) -> BQ2CSTResult<Node> {
+    fn parse_binary_operator(&mut self, left: Node<'a>) -> BQ2CSTResult<Node<'a>> {
         let precedence = self.get_precedence(0)?;
         let mut node = self.construct_node(NodeType::BinaryOperator)?;
-        if self.get_token(0)?.is("IS") && self.get_token(1)?.is("NOT") {
+        if self.get_token(0)?.is(Keyword::IS) && self.get_token(1)?.is(Keyword::NOT) {
             self.next_token()?; // IS -> NOT
             node.push_node("not", self.construct_node(NodeType::Keyword)?);
         }
-        if self.get_token(1)?.in_(&vec!["ALL", "ANY", "SOME"]) {
+        if self
+            .get_token(1)?
+            .in_(&[Keyword::ALL, Keyword::ANY, Keyword::SOME])
+        {
             self.next_token()?; // -> ALL | ANY | SOME
             node.push_node("quantifier", self.construct_node(NodeType::Keyword)?);
         }
@@ -245,11 +413,11 @@ impl Parser {
         );
         Ok(node)
     }
-    fn parse_constraint(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_constraint(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut res;
-        if self.get_token(0)?.is("CONSTRAINT") {
+        if self.get_token(0)?.is(Keyword::CONSTRAINT) {
             let constraint = self.construct_node(NodeType::Keyword)?;
-            if self.get_token(1)?.is("IF") {
+            if self.get_token(1)?.is(Keyword::IF) {
                 self.next_token()?; // -> IF
                 let _if_not_exists = self.parse_n_keywords(3)?;
                 self.next_token()?; // -> ident
@@ -272,11 +440,11 @@ impl Parser {
         }
         self.next_token()?; // -> KEY
         res.push_node("key", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("(") {
+        if self.get_token(1)?.is(Symbol::LParen) {
             self.next_token()?; // -> (
             res.push_node("columns", self.parse_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is("REFERENCES") {
+        if self.get_token(1)?.is(Keyword::REFERENCES) {
             self.next_token()?; // -> REFERENCES
             let mut references = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // -> table_ident
@@ -286,13 +454,13 @@ impl Parser {
             );
             res.push_node("references", references);
         }
-        if self.get_token(1)?.in_(&vec!["NOT", "ENFORCED"]) {
+        if self.get_token(1)?.in_(&[Keyword::NOT, Keyword::ENFORCED]) {
             self.next_token()?; // -> NOT | ENFORCED
             res.push_node("enforced", self.parse_enforced()?);
         }
-        return Ok(res);
+        Ok(res)
     }
-    fn parse_cte(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_cte(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut query = self.construct_node(NodeType::WithQuery)?;
         self.next_token()?; // ident -> AS
         query.push_node("as", self.construct_node(NodeType::Keyword)?);
@@ -300,13 +468,13 @@ impl Parser {
 
         let mut grouped = self.construct_node(NodeType::GroupedStatement)?;
         self.next_token()?; // ( -> SELECT | FROM
-        if self.get_token(0)?.is("FROM") {
+        if self.get_token(0)?.is(Keyword::FROM) {
             grouped.push_node("stmt", self.parse_from_statement()?);
         } else {
             grouped.push_node("stmt", self.parse_select_statement(false, true)?);
         }
         self.next_token()?; // stmt -> )
-        if !self.get_token(0)?.is(")") {
+        if !self.get_token(0)?.is(Symbol::RParen) {
             return Err(BQ2CSTError::from_token(
                 self.get_token(0)?,
                 "expected )".to_string(),
@@ -315,15 +483,15 @@ impl Parser {
         grouped.push_node("rparen", self.construct_node(NodeType::Symbol)?);
 
         query.push_node("stmt", grouped);
-        if self.get_token(1)?.literal.as_str() == "," {
+        if self.get_token(1)?.is(Symbol::Comma) {
             self.next_token()?; // ) -> ,
             query.push_node("comma", self.construct_node(NodeType::Symbol)?);
         };
         Ok(query)
     }
-    fn parse_enforced(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_enforced(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut enforced;
-        if self.get_token(0)?.is("NOT") {
+        if self.get_token(0)?.is(Keyword::NOT) {
             enforced = self.construct_node(NodeType::KeywordSequence)?;
             self.next_token()?; // -> ENFORCED
             enforced.push_node("next_keyword", self.construct_node(NodeType::Keyword)?);
@@ -339,7 +507,17 @@ impl Parser {
         as_table: bool,
         after_dot: bool,
         order: bool,
-    ) -> BQ2CSTResult<Node> {
+    ) -> BQ2CSTResult<Node<'a>> {
+        self.nest(|p| p.parse_expr_inner(precedence, alias, as_table, after_dot, order))
+    }
+    fn parse_expr_inner(
+        &mut self,
+        precedence: usize,
+        alias: bool,
+        as_table: bool,
+        after_dot: bool,
+        order: bool,
+    ) -> BQ2CSTResult<Node<'a>> {
         let mut left = if after_dot {
             self.construct_node(NodeType::Identifier)?
         } else {
@@ -347,36 +525,36 @@ impl Parser {
         };
         if as_table {
             left = self.parse_identifier()?;
-        } else if let Some(type_) = self.get_token(0)?.get_template_type() {
+        } else if self.get_token(0)?.kind == TokenKind::SqlxInterpolation {
+            left = self.construct_sqlx_interpolation()?;
+        } else if let Some(type_) = self.get_template_type(0)? {
             match type_ {
                 TemplateType::Expr => {
                     left.node_type = NodeType::TemplateExpr;
+                    self.push_jinja(&mut left)?;
                 }
                 TemplateType::ExprStart => {
                     left.node_type = NodeType::TemplateExprStart;
-                    if self.get_token(1)?.get_template_type() != Some(TemplateType::ExprEnd)
-                        && self.get_token(1)?.get_template_type()
-                            != Some(TemplateType::ExprContinue)
+                    self.push_jinja(&mut left)?;
+                    if self.get_template_type(1)? != Some(TemplateType::ExprEnd)
+                        && self.get_template_type(1)? != Some(TemplateType::ExprContinue)
                     {
                         self.next_token()?; // -> expr
-                        left.push_node_vec("exprs", self.parse_exprs(&vec![], alias, order)?);
+                        left.push_node_vec("exprs", self.parse_exprs(&[], alias, order)?);
                     } else {
                         // {% block %} is sometimes empty
                         left.push_node_vec("exprs", Vec::new());
                     }
 
                     let mut continues = Vec::new();
-                    while self.get_token(1)?.get_template_type() == Some(TemplateType::ExprContinue)
-                    {
+                    while self.get_template_type(1)? == Some(TemplateType::ExprContinue) {
                         self.next_token()?; // -> {% else %}
                         let mut continue_ = self.construct_node(NodeType::TemplateExprContinue)?;
                         self.next_token()?; // -> exprs
-                        if self.get_token(0)?.get_template_type() != Some(TemplateType::ExprEnd)
-                            && self.get_token(0)?.get_template_type()
-                                != Some(TemplateType::ExprContinue)
+                        if self.get_template_type(0)? != Some(TemplateType::ExprEnd)
+                            && self.get_template_type(0)? != Some(TemplateType::ExprContinue)
                         {
-                            continue_
-                                .push_node_vec("exprs", self.parse_exprs(&vec![], alias, order)?);
+                            continue_.push_node_vec("exprs", self.parse_exprs(&[], alias, order)?);
                         } else {
                             // {% block %} is sometimes empty
                             continue_.push_node_vec("exprs", Vec::new());
@@ -390,507 +568,550 @@ impl Parser {
                 _ => {}
             }
         } else if !after_dot {
-            // prefix or literal
-            match self.get_token(0)?.literal.to_uppercase().as_str() {
-                "*" => {
-                    left.node_type = NodeType::Asterisk;
-                    match self.get_token(1)?.literal.to_uppercase().as_str() {
-                        "REPLACE" => {
-                            self.next_token()?; // * -> REPLACE
-                            let mut replace =
-                                self.construct_node(NodeType::KeywordWithGroupedXXX)?;
-                            self.next_token()?; // REPLACE -> (
-                            replace.push_node("group", self.parse_grouped_exprs(true)?);
-                            left.push_node("replace", replace);
-                        }
-                        "EXCEPT" => {
-                            self.next_token()?; // * -> except
-                            let mut except =
-                                self.construct_node(NodeType::KeywordWithGroupedXXX)?;
-                            self.next_token()?; // except -> (
-                            except.push_node("group", self.parse_grouped_exprs(false)?);
-                            left.push_node("except", except);
-                        }
-                        _ => (),
+            left = self.parse_prefix_expr(left)?;
+        }
+        // infix
+        while self.get_precedence(1)? < precedence {
+            self.deepen()?;
+            left = self.parse_infix_expr(left, as_table)?;
+        }
+        // alias
+        if alias {
+            if self.get_token(1)?.is(Keyword::AS) {
+                self.next_token()?; // expr -> AS
+                left.push_node("as", self.construct_node(NodeType::Keyword)?);
+                self.next_token()?; // AS -> alias
+
+                // NOTE: use parse_expr instead of parse_identifier in the case of WITH(a AS 'a', a)
+                left.push_node(
+                    "alias",
+                    self.parse_expr(usize::MAX, false, false, false, false)?,
+                );
+            } else if self.get_token(1)?.is_identifier() {
+                self.next_token()?; // expr -> alias
+                left.push_node("alias", self.construct_node(NodeType::Identifier)?);
+            }
+        }
+        if order {
+            if self.get_token(1)?.in_(&[Keyword::ASC, Keyword::DESC]) {
+                self.next_token()?; // expr -> ASC, DESC
+                let order = self.construct_node(NodeType::Keyword)?;
+                left.push_node("order", order);
+            }
+            if self.get_token(1)?.in_(&[Keyword::NULLS])
+                && self.get_token(2)?.in_(&[Keyword::FIRST, Keyword::LAST])
+            {
+                let mut nulls_first = Vec::new();
+                self.next_token()?; // ASC -> NULLS
+                nulls_first.push(self.construct_node(NodeType::Keyword)?);
+                self.next_token()?; // NULLS -> FIRST, LAST
+                nulls_first.push(self.construct_node(NodeType::Keyword)?);
+                left.push_node_vec("null_order", nulls_first);
+            }
+        }
+        Ok(left)
+    }
+    // NOTE: if trailing comma is allowed, you have to specify `until` carefully.
+    // NOTE
+    // prefix and infix expressions are parsed in their own functions
+    // to keep the stack frame of `parse_expr_inner` (which is called recursively) small
+    // precedence literals such as `002` are intentional
+    #[allow(clippy::zero_prefixed_literal, clippy::collapsible_match)]
+    fn parse_prefix_expr(&mut self, mut left: Node<'a>) -> BQ2CSTResult<Node<'a>> {
+        match self.get_token(0)?.kind {
+            TokenKind::Symbol(Symbol::Asterisk) => {
+                left.node_type = NodeType::Asterisk;
+                match self.get_token(1)?.kind {
+                    TokenKind::Keyword(Keyword::REPLACE) => {
+                        self.next_token()?; // * -> REPLACE
+                        let mut replace = self.construct_node(NodeType::KeywordWithGroupedXXX)?;
+                        self.next_token()?; // REPLACE -> (
+                        replace.push_node("group", self.parse_grouped_exprs(true)?);
+                        left.push_node("replace", replace);
+                    }
+                    TokenKind::Keyword(Keyword::EXCEPT) => {
+                        self.next_token()?; // * -> except
+                        let mut except = self.construct_node(NodeType::KeywordWithGroupedXXX)?;
+                        self.next_token()?; // except -> (
+                        except.push_node("group", self.parse_grouped_exprs(false)?);
+                        left.push_node("except", except);
                     }
+                    _ => (),
                 }
-                // STRUCT
-                "(" => {
-                    self.next_token()?; // ( -> expr
-                    let mut exprs;
-                    if self.get_token(0)?.in_(&vec!["WITH", "SELECT"]) {
-                        left.node_type = NodeType::GroupedStatement;
-                        left.push_node("stmt", self.parse_select_statement(false, true)?);
-                        self.next_token()?; // expr -> )
-                        left.push_node("rparen", self.construct_node(NodeType::Symbol)?);
-                    } else if self.get_token(0)?.is(")") {
-                        left.node_type = NodeType::EmptyStruct;
-                        left.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+            }
+            // STRUCT
+            TokenKind::Symbol(Symbol::LParen) => {
+                self.next_token()?; // ( -> expr
+                let mut exprs;
+                if self.get_token(0)?.in_(&[Keyword::WITH, Keyword::SELECT]) {
+                    left.node_type = NodeType::GroupedStatement;
+                    left.push_node("stmt", self.parse_select_statement(false, true)?);
+                    self.next_token()?; // expr -> )
+                    left.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+                } else if self.get_token(0)?.is(Symbol::RParen) {
+                    left.node_type = NodeType::EmptyStruct;
+                    left.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+                } else {
+                    exprs = self.parse_exprs(&[], true, true)?; // parse alias in the case of struct
+                    if exprs.len() == 1 {
+                        left.node_type = NodeType::GroupedExpr;
+                        left.push_node("expr", exprs.pop().unwrap());
                     } else {
-                        exprs = self.parse_exprs(&vec![], true, true)?; // parse alias in the case of struct
-                        if exprs.len() == 1 {
-                            left.node_type = NodeType::GroupedExpr;
-                            left.push_node("expr", exprs.pop().unwrap());
-                        } else {
-                            left.node_type = NodeType::StructLiteral;
-                            left.push_node_vec("exprs", exprs);
-                        }
-                        self.next_token()?; // expr -> )
-                        left.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+                        left.node_type = NodeType::StructLiteral;
+                        left.push_node_vec("exprs", exprs);
                     }
+                    self.next_token()?; // expr -> )
+                    left.push_node("rparen", self.construct_node(NodeType::Symbol)?);
                 }
-                "STRUCT" => {
-                    let type_ = self.parse_type(false, false)?;
-                    self.next_token()?; // STRUCT -> (, > -> (
-                    let mut struct_literal = self.construct_node(NodeType::StructLiteral)?;
-                    let mut exprs = vec![];
-                    while !self.get_token(1)?.is(")") {
-                        self.next_token()?; // -> expr
-                        let mut expr = self.parse_expr(usize::MAX, true, false, false, true)?;
-                        if self.get_token(1)?.is(",") {
-                            self.next_token()?; // -> ,
-                            expr.push_node("comma", self.construct_node(NodeType::Symbol)?);
-                        }
-                        exprs.push(expr)
+            }
+            TokenKind::Keyword(Keyword::STRUCT) => {
+                let type_ = self.parse_type(false, false)?;
+                self.next_token()?; // STRUCT -> (, > -> (
+                let mut struct_literal = self.construct_node(NodeType::StructLiteral)?;
+                let mut exprs = vec![];
+                while !self.get_token(1)?.is(Symbol::RParen) {
+                    self.next_token()?; // -> expr
+                    let mut expr = self.parse_expr(usize::MAX, true, false, false, true)?;
+                    if self.get_token(1)?.is(Symbol::Comma) {
+                        self.next_token()?; // -> ,
+                        expr.push_node("comma", self.construct_node(NodeType::Symbol)?);
                     }
-                    self.next_token()?; // -> )
-                    struct_literal.push_node("rparen", self.construct_node(NodeType::Symbol)?);
-                    struct_literal.push_node_vec("exprs", exprs);
-                    struct_literal.push_node("type", type_);
-                    left = struct_literal;
+                    exprs.push(expr)
                 }
-                "RANGE" => {
-                    let type_ = self.parse_type(false, false)?;
-                    self.next_token()?; // > -> '[lower, upper)'
-                    let mut range_literal = self.construct_node(NodeType::RangeLiteral)?;
-                    range_literal.push_node("type", type_);
-                    left = range_literal;
+                self.next_token()?; // -> )
+                struct_literal.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+                struct_literal.push_node_vec("exprs", exprs);
+                struct_literal.push_node("type", type_);
+                left = struct_literal;
+            }
+            TokenKind::Keyword(Keyword::RANGE) => {
+                let type_ = self.parse_type(false, false)?;
+                self.next_token()?; // > -> '[lower, upper)'
+                let mut range_literal = self.construct_node(NodeType::RangeLiteral)?;
+                range_literal.push_node("type", type_);
+                left = range_literal;
+            }
+            // ARRAY
+            TokenKind::Symbol(Symbol::LBracket) => {
+                left.node_type = NodeType::ArrayLiteral;
+                self.next_token()?; // [ -> exprs
+                if self.get_token(0)?.is(Symbol::RBracket) {
+                    left.push_node_vec("exprs", vec![]);
+                } else {
+                    left.push_node_vec("exprs", self.parse_exprs(&[], false, true)?);
+                    self.next_token()?; // exprs -> ]
                 }
-                // ARRAY
-                "[" => {
-                    left.node_type = NodeType::ArrayLiteral;
-                    self.next_token()?; // [ -> exprs
-                    if self.get_token(0)?.is("]") {
-                        left.push_node_vec("exprs", vec![]);
+                left.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+            }
+            TokenKind::Keyword(Keyword::ARRAY) => {
+                // when used as literal
+                if !self.get_token(1)?.is(Symbol::LParen) {
+                    let type_ = self.parse_type(false, false)?;
+                    self.next_token()?; // > -> [
+                    let mut arr = self.construct_node(NodeType::ArrayLiteral)?;
+                    self.next_token()?; // [ -> exprs | ]
+                    if self.get_token(0)?.is(Symbol::RBracket) {
+                        arr.push_node_vec("exprs", vec![]);
                     } else {
-                        left.push_node_vec("exprs", self.parse_exprs(&vec![], false, true)?);
+                        arr.push_node_vec("exprs", self.parse_exprs(&[], false, true)?);
                         self.next_token()?; // exprs -> ]
                     }
-                    left.push_node("rparen", self.construct_node(NodeType::Symbol)?);
-                }
-                "ARRAY" => {
-                    // when used as literal
-                    if !self.get_token(1)?.is("(") {
-                        let type_ = self.parse_type(false, false)?;
-                        self.next_token()?; // > -> [
-                        let mut arr = self.construct_node(NodeType::ArrayLiteral)?;
-                        self.next_token()?; // [ -> exprs | ]
-                        if self.get_token(0)?.is("]") {
-                            arr.push_node_vec("exprs", vec![]);
-                        } else {
-                            arr.push_node_vec("exprs", self.parse_exprs(&vec![], false, true)?);
-                            self.next_token()?; // exprs -> ]
-                        }
-                        arr.push_node("rparen", self.construct_node(NodeType::Symbol)?);
-                        arr.push_node("type", type_);
-                        left = arr;
-                    }
+                    arr.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+                    arr.push_node("type", type_);
+                    left = arr;
                 }
-                "-" | "+" | "~" => {
+            }
+            TokenKind::Symbol(Symbol::Minus)
+            | TokenKind::Symbol(Symbol::Plus)
+            | TokenKind::Symbol(Symbol::Tilde) => {
+                left.node_type = NodeType::UnaryOperator;
+                self.next_token()?; // - -> expr
+                let right = self.parse_expr(102, false, false, false, true)?;
+                left.push_node("right", right);
+            }
+            TokenKind::Keyword(Keyword::DATE)
+            | TokenKind::Keyword(Keyword::TIME)
+            | TokenKind::Keyword(Keyword::DATETIME)
+            | TokenKind::Keyword(Keyword::TIMESTAMP)
+            | TokenKind::Keyword(Keyword::NUMERIC)
+            | TokenKind::Keyword(Keyword::BIGNUMERIC)
+            | TokenKind::Keyword(Keyword::DECIMAL)
+            | TokenKind::Keyword(Keyword::BIGDECIMAL)
+            | TokenKind::Keyword(Keyword::JSON) => {
+                if self.get_token(1)?.is_string()
+                    || self
+                        .get_token(1)?
+                        .in_(&[Keyword::B, Keyword::R, Keyword::BR, Keyword::RB])
+                        && self.get_token(2)?.is_string()
+                {
                     left.node_type = NodeType::UnaryOperator;
-                    self.next_token()?; // - -> expr
-                    let right = self.parse_expr(102, false, false, false, true)?;
+                    self.next_token()?; // -> expr
+                    let right = self.parse_expr(002, false, false, false, true)?;
                     left.push_node("right", right);
                 }
-                "DATE" | "TIME" | "DATETIME" | "TIMESTAMP" | "NUMERIC" | "BIGNUMERIC"
-                | "DECIMAL" | "BIGDECIMAL" | "JSON" => {
-                    if self.get_token(1)?.is_string()
-                        || self.get_token(1)?.in_(&vec!["b", "r", "br", "rb"])
-                            && self.get_token(2)?.is_string()
-                    {
-                        left.node_type = NodeType::UnaryOperator;
-                        self.next_token()?; // -> expr
-                        let right = self.parse_expr(002, false, false, false, true)?;
-                        left.push_node("right", right);
-                    }
-                }
-                "INTERVAL" => {
-                    left.node_type = NodeType::IntervalLiteral;
-                    self.next_token()?; // INTERVAL -> expr
-                    let right = self.parse_expr(usize::MAX, false, false, false, true)?;
-                    self.next_token()?; // expr -> HOUR
-                    left.push_node("date_part", self.construct_node(NodeType::Keyword)?);
-                    if self.get_token(1)?.is("TO") {
-                        self.next_token()?; // -> TO
-                        left.push_node("to", self.construct_node(NodeType::Keyword)?);
-                        self.next_token()?; // -> date_part
-                        left.push_node("to_date_part", self.construct_node(NodeType::Keyword)?);
-                    }
-                    left.push_node("expr", right);
+            }
+            TokenKind::Keyword(Keyword::INTERVAL) => {
+                left.node_type = NodeType::IntervalLiteral;
+                self.next_token()?; // INTERVAL -> expr
+                let right = self.parse_expr(usize::MAX, false, false, false, true)?;
+                self.next_token()?; // expr -> HOUR
+                left.push_node("date_part", self.construct_node(NodeType::Keyword)?);
+                if self.get_token(1)?.is(Keyword::TO) {
+                    self.next_token()?; // -> TO
+                    left.push_node("to", self.construct_node(NodeType::Keyword)?);
+                    self.next_token()?; // -> date_part
+                    left.push_node("to_date_part", self.construct_node(NodeType::Keyword)?);
                 }
-                "TABLE" | "MODEL" => {
-                    left.node_type = NodeType::UnaryOperator;
-                    self.next_token()?; // TABLE -> ident
-                    let right = self.parse_expr(002, false, true, false, true)?;
+                left.push_node("expr", right);
+            }
+            TokenKind::Keyword(Keyword::TABLE) | TokenKind::Keyword(Keyword::MODEL) => {
+                left.node_type = NodeType::UnaryOperator;
+                self.next_token()?; // TABLE -> ident
+                let right = self.parse_expr(002, false, true, false, true)?;
+                left.push_node("right", right);
+            }
+            TokenKind::Keyword(Keyword::B)
+            | TokenKind::Keyword(Keyword::R)
+            | TokenKind::Keyword(Keyword::BR)
+            | TokenKind::Keyword(Keyword::RB) => {
+                if self.get_token(1)?.is_string() {
+                    self.next_token()?; // R -> 'string'
+                    let right = self.parse_expr(001, false, false, false, true)?;
                     left.push_node("right", right);
+                    left.node_type = NodeType::UnaryOperator;
                 }
-                "B" | "R" | "BR" | "RB" => {
-                    if self.get_token(1)?.is_string() {
-                        self.next_token()?; // R -> 'string'
-                        let right = self.parse_expr(001, false, false, false, true)?;
-                        left.push_node("right", right);
-                        left.node_type = NodeType::UnaryOperator;
-                    }
+            }
+            TokenKind::Keyword(Keyword::WITH) => {
+                if !self.get_token(1)?.is(Symbol::LParen) {
+                    left = self.parse_select_statement(false, true)?;
                 }
-                "WITH" => {
-                    if !self.get_token(1)?.is("(") {
-                        left = self.parse_select_statement(false, true)?;
-                    }
+            }
+            TokenKind::Keyword(Keyword::SELECT) => {
+                // in the case of `ARRAY(SELECT 1)`
+                left = self.parse_select_statement(false, true)?;
+            }
+            TokenKind::Keyword(Keyword::NOT) => {
+                self.next_token()?; // NOT -> boolean
+                let right = self.parse_expr(110, false, false, false, true)?;
+                left.push_node("right", right);
+                left.node_type = NodeType::UnaryOperator;
+            }
+            TokenKind::Keyword(Keyword::CASE) => {
+                left.node_type = NodeType::CaseExpr;
+                self.next_token()?; // CASE -> expr, CASE -> when
+                if !self.get_token(0)?.is(Keyword::WHEN) {
+                    left.push_node(
+                        "expr",
+                        self.parse_expr(usize::MAX, false, false, false, true)?,
+                    );
+                    self.next_token()?; // expr -> WHEN
                 }
-                "SELECT" => {
-                    // in the case of `ARRAY(SELECT 1)`
-                    left = self.parse_select_statement(false, true)?;
+                let mut arms = Vec::new();
+                while self.get_token(0)?.is(Keyword::WHEN) {
+                    let mut arm = self.construct_node(NodeType::CaseExprArm)?;
+                    self.next_token()?; // WHEN -> expr
+                    arm.push_node(
+                        "expr",
+                        self.parse_expr(usize::MAX, false, false, false, true)?,
+                    );
+                    self.next_token()?; // expr ->THEN
+                    arm.push_node("then", self.construct_node(NodeType::Keyword)?);
+                    self.next_token()?; // THEN -> result_expr
+                    arm.push_node(
+                        "result",
+                        self.parse_expr(usize::MAX, false, false, false, true)?,
+                    );
+                    self.next_token()?; // -> ELSE | WHEN | END
+                    arms.push(arm);
                 }
-                "NOT" => {
-                    self.next_token()?; // NOT -> boolean
-                    let right = self.parse_expr(110, false, false, false, true)?;
-                    left.push_node("right", right);
-                    left.node_type = NodeType::UnaryOperator;
+                if self.get_token(0)?.is(Keyword::ELSE) {
+                    let mut else_ = self.construct_node(NodeType::CaseExprArm)?;
+                    self.next_token()?; // ELSE -> result_expr
+                    else_.push_node(
+                        "result",
+                        self.parse_expr(usize::MAX, false, false, false, true)?,
+                    );
+                    arms.push(else_);
+                    self.next_token()?; // result_expr -> end
                 }
-                "CASE" => {
-                    left.node_type = NodeType::CaseExpr;
-                    self.next_token()?; // CASE -> expr, CASE -> when
-                    if !self.get_token(0)?.is("WHEN") {
-                        left.push_node(
-                            "expr",
-                            self.parse_expr(usize::MAX, false, false, false, true)?,
-                        );
-                        self.next_token()?; // expr -> WHEN
-                    }
-                    let mut arms = Vec::new();
-                    while self.get_token(0)?.is("WHEN") {
-                        let mut arm = self.construct_node(NodeType::CaseExprArm)?;
-                        self.next_token()?; // WHEN -> expr
-                        arm.push_node(
-                            "expr",
-                            self.parse_expr(usize::MAX, false, false, false, true)?,
-                        );
-                        self.next_token()?; // expr ->THEN
-                        arm.push_node("then", self.construct_node(NodeType::Keyword)?);
-                        self.next_token()?; // THEN -> result_expr
-                        arm.push_node(
-                            "result",
-                            self.parse_expr(usize::MAX, false, false, false, true)?,
-                        );
-                        self.next_token()?; // -> ELSE | WHEN | END
-                        arms.push(arm);
-                    }
-                    if self.get_token(0)?.is("ELSE") {
-                        let mut else_ = self.construct_node(NodeType::CaseExprArm)?;
-                        self.next_token()?; // ELSE -> result_expr
-                        else_.push_node(
-                            "result",
-                            self.parse_expr(usize::MAX, false, false, false, true)?,
-                        );
-                        arms.push(else_);
-                        self.next_token()?; // result_expr -> end
-                    }
-                    left.push_node_vec("arms", arms);
-                    left.push_node("end", self.construct_node(NodeType::Keyword)?);
+                left.push_node_vec("arms", arms);
+                left.push_node("end", self.construct_node(NodeType::Keyword)?);
+            }
+            _ => (),
+        };
+        Ok(left)
+    }
+    fn parse_infix_expr(&mut self, mut left: Node<'a>, as_table: bool) -> BQ2CSTResult<Node<'a>> {
+        match self.get_token(1)?.kind {
+            TokenKind::Symbol(Symbol::LParen) => {
+                let func = self.get_token(0)?.kind;
+                self.next_token()?; // ident -> (
+                let mut node = self.construct_node(NodeType::CallingFunction)?;
+                if self.get_token(1)?.is(Keyword::DISTINCT) {
+                    self.next_token()?; // ( -> DISTINCT
+                    node.push_node("distinct", self.construct_node(NodeType::Keyword)?);
                 }
-                _ => (),
-            };
-        }
-        // infix
-        while self.get_precedence(1)? < precedence {
-            match self.get_token(1)?.literal.to_uppercase().as_str() {
-                "(" => {
-                    let func = self.get_token(0)?.literal.to_uppercase();
-                    self.next_token()?; // ident -> (
-                    let mut node = self.construct_node(NodeType::CallingFunction)?;
-                    if self.get_token(1)?.is("distinct") {
-                        self.next_token()?; // ( -> DISTINCT
-                        node.push_node("distinct", self.construct_node(NodeType::Keyword)?);
-                    }
-                    self.next_token()?; // ( -> args
-                    node.push_node("func", left);
-                    if !self.get_token(0)?.is(")") {
-                        match func.as_str() {
-                            "CAST" | "SAFE_CAST" => {
-                                let cast_from =
-                                    self.parse_expr(usize::MAX, false, false, false, true)?;
-                                self.next_token()?; // expr -> AS
-                                let mut as_ = self.construct_node(NodeType::CastArgument)?;
-                                as_.push_node("cast_from", cast_from);
-                                self.next_token()?; // -> type
-                                as_.push_node("cast_to", self.parse_type(false, false)?);
-                                if self.get_token(1)?.is("FORMAT") {
-                                    self.next_token()?; // -> FORMAT
-                                    let mut format =
-                                        self.construct_node(NodeType::KeywordWithExpr)?;
-                                    self.next_token()?; // -> string
-                                    format.push_node(
-                                        "expr",
-                                        self.parse_expr(usize::MAX, false, false, false, true)?,
-                                    );
-                                    as_.push_node("format", format);
-                                }
-                                node.push_node_vec("args", vec![as_]);
-                            }
-                            "EXTRACT" => {
-                                let datepart =
-                                    self.parse_expr(usize::MAX, false, false, false, true)?;
-                                self.next_token()?; // expr -> FROM
-                                let mut from = self.construct_node(NodeType::ExtractArgument)?;
-                                self.next_token()?; // FROM -> timestamp_expr
-                                from.push_node("extract_datepart", datepart);
-                                from.push_node(
-                                    "extract_from",
+                self.next_token()?; // ( -> args
+                node.push_node("func", left);
+                if !self.get_token(0)?.is(Symbol::RParen) {
+                    match func {
+                        TokenKind::Keyword(Keyword::CAST)
+                        | TokenKind::Keyword(Keyword::SAFE_CAST) => {
+                            let cast_from =
+                                self.parse_expr(usize::MAX, false, false, false, true)?;
+                            self.next_token()?; // expr -> AS
+                            let mut as_ = self.construct_node(NodeType::CastArgument)?;
+                            as_.push_node("cast_from", cast_from);
+                            self.next_token()?; // -> type
+                            as_.push_node("cast_to", self.parse_type(false, false)?);
+                            if self.get_token(1)?.is(Keyword::FORMAT) {
+                                self.next_token()?; // -> FORMAT
+                                let mut format = self.construct_node(NodeType::KeywordWithExpr)?;
+                                self.next_token()?; // -> string
+                                format.push_node(
+                                    "expr",
                                     self.parse_expr(usize::MAX, false, false, false, true)?,
                                 );
-                                if self.get_token(1)?.is("AT") {
-                                    let mut at_time_zone = Vec::new();
-                                    self.next_token()?; // timestamp_expr -> AT
-                                    at_time_zone.push(self.construct_node(NodeType::Keyword)?);
-                                    self.next_token()?; // AT -> TIME
-                                    at_time_zone.push(self.construct_node(NodeType::Keyword)?);
-                                    self.next_token()?; // TIME -> ZONE
-                                    at_time_zone.push(self.construct_node(NodeType::Keyword)?);
-                                    from.push_node_vec("at_time_zone", at_time_zone);
-                                    self.next_token()?; // ZONE -> 'UTC'
-                                    from.push_node(
-                                        "time_zone",
-                                        self.parse_expr(usize::MAX, false, false, false, true)?,
-                                    );
-                                }
-                                node.push_node_vec("args", vec![from]);
-                            }
-                            _ => {
-                                node.push_node_vec(
-                                    "args",
-                                    self.parse_exprs(&vec![], func == "WITH", true)?,
-                                );
+                                as_.push_node("format", format);
                             }
+                            node.push_node_vec("args", vec![as_]);
                         }
-                        if self.get_token(1)?.in_(&vec!["RESPECT", "IGNORE"]) {
-                            self.next_token()?; // expr -> RESPECT, IGNORE
-                            let ignore_or_respect = self.construct_node(NodeType::Keyword)?;
-                            self.next_token()?; // RESPECT, IGNORE -> NULLS
-                            node.push_node_vec(
-                                "ignore_nulls",
-                                vec![ignore_or_respect, self.construct_node(NodeType::Keyword)?],
-                            );
-                        }
-                        if self.get_token(1)?.is("ORDER") {
-                            self.next_token()?; // expr -> ORDER
-                            let mut orderby = self.construct_node(NodeType::XXXByExprs)?;
-                            self.next_token()?; // ORDER -> BY
-                            orderby.push_node("by", self.construct_node(NodeType::Keyword)?);
-                            self.next_token()?; // BY -> expr
-                            orderby.push_node_vec("exprs", self.parse_exprs(&vec![], false, true)?);
-                            node.push_node("orderby", orderby);
-                        }
-                        if self.get_token(1)?.is("LIMIT") {
-                            self.next_token()?; // -> LIMIT
-                            let mut limit = self.construct_node(NodeType::KeywordWithExpr)?;
-                            self.next_token()?;
-                            limit.push_node(
-                                "expr",
+                        TokenKind::Keyword(Keyword::EXTRACT) => {
+                            let datepart =
+                                self.parse_expr(usize::MAX, false, false, false, true)?;
+                            self.next_token()?; // expr -> FROM
+                            let mut from = self.construct_node(NodeType::ExtractArgument)?;
+                            self.next_token()?; // FROM -> timestamp_expr
+                            from.push_node("extract_datepart", datepart);
+                            from.push_node(
+                                "extract_from",
                                 self.parse_expr(usize::MAX, false, false, false, true)?,
                             );
-                            node.push_node("limit", limit);
+                            if self.get_token(1)?.is(Keyword::AT) {
+                                let mut at_time_zone = Vec::new();
+                                self.next_token()?; // timestamp_expr -> AT
+                                at_time_zone.push(self.construct_node(NodeType::Keyword)?);
+                                self.next_token()?; // AT -> TIME
+                                at_time_zone.push(self.construct_node(NodeType::Keyword)?);
+                                self.next_token()?; // TIME -> ZONE
+                                at_time_zone.push(self.construct_node(NodeType::Keyword)?);
+                                from.push_node_vec("at_time_zone", at_time_zone);
+                                self.next_token()?; // ZONE -> 'UTC'
+                                from.push_node(
+                                    "time_zone",
+                                    self.parse_expr(usize::MAX, false, false, false, true)?,
+                                );
+                            }
+                            node.push_node_vec("args", vec![from]);
                         }
-                        if self.get_token(1)?.is("HAVING") {
-                            // TODO
-                            // check if parse order is collect
-                            // this block shold be placed before RESPECT/IGNORE?
-                            self.next_token()?; // expr -> HAVING
-                            let mut having = self.construct_node(NodeType::KeywordSequence)?;
-                            self.next_token()?; // -> MAX | MIN
-                            let mut max = self.construct_node(NodeType::KeywordWithExpr)?;
-                            self.next_token()?; // -> expr
-                            max.push_node(
-                                "expr",
-                                self.parse_expr(usize::MAX, false, false, false, true)?,
+                        _ => {
+                            node.push_node_vec(
+                                "args",
+                                self.parse_exprs(
+                                    &[],
+                                    func == TokenKind::Keyword(Keyword::WITH),
+                                    true,
+                                )?,
                             );
-                            having.push_node("next_keyword", max);
-                            node.push_node("having", having);
                         }
-                        self.next_token()?; // expr -> )
                     }
-                    node.push_node("rparen", self.construct_node(NodeType::Symbol)?);
-                    if self.get_token(1)?.is("over") {
-                        self.next_token()?; // ) -> OVER
-                        let mut over = self.construct_node(NodeType::OverClause)?;
-                        self.next_token()?; // OVER -> (, OVER -> named_expr
-                        over.push_node("window", self.parse_window_expr()?);
-                        node.push_node("over", over);
+                    if self.get_token(1)?.in_(&[Keyword::RESPECT, Keyword::IGNORE]) {
+                        self.next_token()?; // expr -> RESPECT, IGNORE
+                        let ignore_or_respect = self.construct_node(NodeType::Keyword)?;
+                        self.next_token()?; // RESPECT, IGNORE -> NULLS
+                        node.push_node_vec(
+                            "ignore_nulls",
+                            vec![ignore_or_respect, self.construct_node(NodeType::Keyword)?],
+                        );
                     }
-                    left = node;
-                }
-                "[" => {
-                    self.next_token()?; // expr -> [
-                    let mut node = self.construct_node(NodeType::AccessOperator)?;
-                    node.push_node("left", left);
-                    self.next_token()?; // [ -> expr
-                    node.push_node(
-                        "right",
-                        self.parse_expr(usize::MAX, false, false, false, true)?,
-                    );
-                    self.next_token()?; // expr -> ]
-                    node.push_node("rparen", self.construct_node(NodeType::Symbol)?);
-                    left = node;
-                }
-                "." => {
-                    self.next_token()?; // -> .
-                    let mut first_node = &left;
-                    while first_node.node_type == NodeType::DotOperator {
-                        match first_node.children.get("left") {
-                            Some(ContentType::Node(n)) => first_node = n,
-                            _ => break,
-                        };
+                    if self.get_token(1)?.is(Keyword::ORDER) {
+                        self.next_token()?; // expr -> ORDER
+                        let mut orderby = self.construct_node(NodeType::XXXByExprs)?;
+                        self.next_token()?; // ORDER -> BY
+                        orderby.push_node("by", self.construct_node(NodeType::Keyword)?);
+                        self.next_token()?; // BY -> expr
+                        orderby.push_node_vec("exprs", self.parse_exprs(&[], false, true)?);
+                        node.push_node("orderby", orderby);
                     }
-                    let is_chained_function = (
-                        // check left side of operator
-                        first_node.node_type != NodeType::Identifier
-                    ) && (
-                        // check right side of operator
-                        self.get_token(1)?.is("(") || self.get_token(2)?.is("(")
-                    );
-                    let precedence = if is_chained_function {
-                        self.get_precedence(0)?
-                    } else {
-                        101
-                    };
-                    let mut dot = if is_chained_function {
-                        self.construct_node(NodeType::FunctionChain)?
-                    } else {
-                        self.construct_node(NodeType::DotOperator)?
-                    };
-                    self.next_token()?; // -> identifier
-                    dot.push_node("left", left);
-                    if self.get_token(0)?.literal.as_str() == "*" {
-                        dot.push_node(
-                            "right",
+                    if self.get_token(1)?.is(Keyword::LIMIT) {
+                        self.next_token()?; // -> LIMIT
+                        let mut limit = self.construct_node(NodeType::KeywordWithExpr)?;
+                        self.next_token()?;
+                        limit.push_node(
+                            "expr",
                             self.parse_expr(usize::MAX, false, false, false, true)?,
                         );
-                    } else {
-                        // after dot true means parse as identifier
-                        dot.push_node(
-                            "right",
-                            self.parse_expr(
-                                precedence,
-                                false,
-                                as_table,
-                                !is_chained_function,
-                                true,
-                            )?,
-                        );
+                        node.push_node("limit", limit);
                     }
-                    left = dot;
-                }
-                "*" | "/" | "||" | "+" | "-" | "<<" | ">>" | "&" | "^" | "|" | "=" | "<" | ">"
-                | "<=" | ">=" | "<>" | "!=" | "LIKE" | "AND" | "OR" | "=>" => {
-                    self.next_token()?; // expr -> binary_operator
-                    left = self.parse_binary_operator(left)?;
-                }
-                "IS" => {
-                    self.next_token()?; // expr -> IS
-                    if self.get_token(1)?.is("DISTINCT")
-                        || (self.get_token(1)?.is("NOT") && self.get_token(2)?.is("DISTINCT"))
-                    {
-                        left = self.parse_is_distinct_from_operator(left)?
-                    } else {
-                        left = self.parse_binary_operator(left)?
+                    if self.get_token(1)?.is(Keyword::HAVING) {
+                        // TODO
+                        // check if parse order is collect
+                        // this block shold be placed before RESPECT/IGNORE?
+                        self.next_token()?; // expr -> HAVING
+                        let mut having = self.construct_node(NodeType::KeywordSequence)?;
+                        self.next_token()?; // -> MAX | MIN
+                        let mut max = self.construct_node(NodeType::KeywordWithExpr)?;
+                        self.next_token()?; // -> expr
+                        max.push_node(
+                            "expr",
+                            self.parse_expr(usize::MAX, false, false, false, true)?,
+                        );
+                        having.push_node("next_keyword", max);
+                        node.push_node("having", having);
                     }
+                    self.next_token()?; // expr -> )
                 }
-                "BETWEEN" => {
-                    self.next_token()?; // expr -> BETWEEN
-                    left = self.parse_between_operator(left)?;
+                node.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+                if self.get_token(1)?.is(Keyword::OVER) {
+                    self.next_token()?; // ) -> OVER
+                    let mut over = self.construct_node(NodeType::OverClause)?;
+                    self.next_token()?; // OVER -> (, OVER -> named_expr
+                    over.push_node("window", self.parse_window_expr()?);
+                    node.push_node("over", over);
                 }
-                "IN" => {
-                    self.next_token()?; // expr -> IN
-                    left = self.parse_in_operator(left)?;
+                left = node;
+            }
+            TokenKind::Symbol(Symbol::LBracket) => {
+                self.next_token()?; // expr -> [
+                let mut node = self.construct_node(NodeType::AccessOperator)?;
+                node.push_node("left", left);
+                self.next_token()?; // [ -> expr
+                node.push_node(
+                    "right",
+                    self.parse_expr(usize::MAX, false, false, false, true)?,
+                );
+                self.next_token()?; // expr -> ]
+                node.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+                left = node;
+            }
+            TokenKind::Symbol(Symbol::Dot) => {
+                self.next_token()?; // -> .
+                let mut first_node = &left;
+                while first_node.node_type == NodeType::DotOperator {
+                    match first_node.children.get("left") {
+                        Some(ContentType::Node(n)) => first_node = n,
+                        _ => break,
+                    };
                 }
-                "NOT" => {
-                    self.next_token()?; // expr -> NOT
-                    let not = self.construct_node(NodeType::Keyword)?;
-                    self.next_token()?; // NOT -> IN, LIKE, BETWEEN
-                    if self.get_token(0)?.is("IN") {
-                        left = self.parse_in_operator(left)?;
-                        left.push_node("not", not);
-                    } else if self.get_token(0)?.is("LIKE") {
-                        left = self.parse_binary_operator(left)?;
-                        left.push_node("not", not);
-                    } else if self.get_token(0)?.is("BETWEEN") {
-                        left = self.parse_between_operator(left)?;
-                        left.push_node("not", not);
-                    } else {
-                        return Err(BQ2CSTError::from_token(
-                            self.get_token(1)?,
-                            format!(
-                                "Expected `LIKE`, `BETWEEN` or `IN` but got: {:?}",
-                                self.get_token(1)?
-                            ),
-                        ));
-                    }
+                let is_chained_function = (
+                    // check left side of operator
+                    first_node.node_type != NodeType::Identifier
+                ) && (
+                    // check right side of operator
+                    self.get_token(1)?.is(Symbol::LParen) || self.get_token(2)?.is(Symbol::LParen)
+                );
+                let precedence = if is_chained_function {
+                    self.get_precedence(0)?
+                } else {
+                    101
+                };
+                let mut dot = if is_chained_function {
+                    self.construct_node(NodeType::FunctionChain)?
+                } else {
+                    self.construct_node(NodeType::DotOperator)?
+                };
+                self.next_token()?; // -> identifier
+                dot.push_node("left", left);
+                if self.get_token(0)?.is(Symbol::Asterisk) {
+                    dot.push_node(
+                        "right",
+                        self.parse_expr(usize::MAX, false, false, false, true)?,
+                    );
+                } else {
+                    // after dot true means parse as identifier
+                    dot.push_node(
+                        "right",
+                        self.parse_expr(precedence, false, as_table, !is_chained_function, true)?,
+                    );
                 }
-                _ => {
-                    return Err(BQ2CSTError::from_token(
-                        self.get_token(0)?,
-                        "Something went wrong.".to_string(),
-                    ))
+                left = dot;
+            }
+            TokenKind::Symbol(Symbol::Asterisk)
+            | TokenKind::Symbol(Symbol::Slash)
+            | TokenKind::Symbol(Symbol::Concat)
+            | TokenKind::Symbol(Symbol::Plus)
+            | TokenKind::Symbol(Symbol::Minus)
+            | TokenKind::Symbol(Symbol::ShiftLeft)
+            | TokenKind::Symbol(Symbol::ShiftRight)
+            | TokenKind::Symbol(Symbol::Ampersand)
+            | TokenKind::Symbol(Symbol::Caret)
+            | TokenKind::Symbol(Symbol::Pipe)
+            | TokenKind::Symbol(Symbol::Eq)
+            | TokenKind::Symbol(Symbol::Lt)
+            | TokenKind::Symbol(Symbol::Gt)
+            | TokenKind::Symbol(Symbol::LtEq)
+            | TokenKind::Symbol(Symbol::GtEq)
+            | TokenKind::Symbol(Symbol::LtGt)
+            | TokenKind::Symbol(Symbol::NotEq)
+            | TokenKind::Keyword(Keyword::LIKE)
+            | TokenKind::Keyword(Keyword::AND)
+            | TokenKind::Keyword(Keyword::OR)
+            | TokenKind::Symbol(Symbol::Arrow) => {
+                self.next_token()?; // expr -> binary_operator
+                left = self.parse_binary_operator(left)?;
+            }
+            TokenKind::Keyword(Keyword::IS) => {
+                self.next_token()?; // expr -> IS
+                if self.get_token(1)?.is(Keyword::DISTINCT)
+                    || (self.get_token(1)?.is(Keyword::NOT)
+                        && self.get_token(2)?.is(Keyword::DISTINCT))
+                {
+                    left = self.parse_is_distinct_from_operator(left)?
+                } else {
+                    left = self.parse_binary_operator(left)?
                 }
             }
-        }
-        // alias
-        if alias {
-            if self.get_token(1)?.is("AS") {
-                self.next_token()?; // expr -> AS
-                left.push_node("as", self.construct_node(NodeType::Keyword)?);
-                self.next_token()?; // AS -> alias
-
-                // NOTE: use parse_expr instead of parse_identifier in the case of WITH(a AS 'a', a)
-                left.push_node(
-                    "alias",
-                    self.parse_expr(usize::MAX, false, false, false, false)?,
-                );
-            } else if self.get_token(1)?.is_identifier() {
-                self.next_token()?; // expr -> alias
-                left.push_node("alias", self.construct_node(NodeType::Identifier)?);
+            TokenKind::Keyword(Keyword::BETWEEN) => {
+                self.next_token()?; // expr -> BETWEEN
+                left = self.parse_between_operator(left)?;
             }
-        }
-        if order {
-            if self.get_token(1)?.in_(&vec!["ASC", "DESC"]) {
-                self.next_token()?; // expr -> ASC, DESC
-                let order = self.construct_node(NodeType::Keyword)?;
-                left.push_node("order", order);
+            TokenKind::Keyword(Keyword::IN) => {
+                self.next_token()?; // expr -> IN
+                left = self.parse_in_operator(left)?;
             }
-            if self.get_token(1)?.in_(&vec!["NULLS"])
-                && self.get_token(2)?.in_(&vec!["FIRST", "LAST"])
-            {
-                let mut nulls_first = Vec::new();
-                self.next_token()?; // ASC -> NULLS
-                nulls_first.push(self.construct_node(NodeType::Keyword)?);
-                self.next_token()?; // NULLS -> FIRST, LAST
-                nulls_first.push(self.construct_node(NodeType::Keyword)?);
-                left.push_node_vec("null_order", nulls_first);
+            TokenKind::Keyword(Keyword::NOT) => {
+                self.next_token()?; // expr -> NOT
+                let not = self.construct_node(NodeType::Keyword)?;
+                self.next_token()?; // NOT -> IN, LIKE, BETWEEN
+                if self.get_token(0)?.is(Keyword::IN) {
+                    left = self.parse_in_operator(left)?;
+                    left.push_node("not", not);
+                } else if self.get_token(0)?.is(Keyword::LIKE) {
+                    left = self.parse_binary_operator(left)?;
+                    left.push_node("not", not);
+                } else if self.get_token(0)?.is(Keyword::BETWEEN) {
+                    left = self.parse_between_operator(left)?;
+                    left.push_node("not", not);
+                } else {
+                    return Err(BQ2CSTError::from_token(
+                        self.get_token(1)?,
+                        format!(
+                            "Expected `LIKE`, `BETWEEN` or `IN` but got: {:?}",
+                            self.get_token(1)?
+                        ),
+                    ));
+                }
+            }
+            _ => {
+                return Err(BQ2CSTError::from_token(
+                    self.get_token(0)?,
+                    "Something went wrong.".to_string(),
+                ))
             }
         }
         Ok(left)
     }
-    // NOTE: if trailing comma is allowed, you have to specify `until` carefully.
     fn parse_exprs(
         &mut self,
-        until: &Vec<&str>,
+        until: &[TokenKind],
         alias: bool,
         order: bool,
-    ) -> BQ2CSTResult<Vec<Node>> {
+    ) -> BQ2CSTResult<Vec<Node<'a>>> {
         let mut exprs: Vec<Node> = Vec::new();
         // first expr
         let mut expr = self.parse_expr(usize::MAX, alias, false, false, order)?;
         let should_continue = self.should_continue(&expr)?;
-        if self.get_token(1)?.is(",") {
+        if self.get_token(1)?.is(Symbol::Comma) {
             self.next_token()?; // expr -> ,
             expr.push_node("comma", self.construct_node(NodeType::Symbol)?);
         } else if !should_continue {
@@ -901,13 +1122,13 @@ impl Parser {
         // second expr and later
         while !self.get_token(1)?.in_(until)
             && !self.is_eof(1)
-            && self.get_token(1)?.get_template_type() != Some(TemplateType::ExprContinue)
-            && self.get_token(1)?.get_template_type() != Some(TemplateType::ExprEnd)
+            && self.get_template_type(1)? != Some(TemplateType::ExprContinue)
+            && self.get_template_type(1)? != Some(TemplateType::ExprEnd)
         {
             self.next_token()?;
             let mut expr = self.parse_expr(usize::MAX, alias, false, false, true)?;
             let should_continue = self.should_continue(&expr)?;
-            if self.get_token(1)?.is(",") {
+            if self.get_token(1)?.is(Symbol::Comma) {
                 self.next_token()?; // expr -> ,
                 expr.push_node("comma", self.construct_node(NodeType::Symbol)?);
                 exprs.push(expr);
@@ -919,12 +1140,12 @@ impl Parser {
         Ok(exprs)
     }
     fn should_continue(&self, last_expr: &Node) -> BQ2CSTResult<bool> {
-        let mut should_continue = self.get_token(1)?.is(",");
+        let mut should_continue = self.get_token(1)?.is(Symbol::Comma);
         let mut temp = last_expr;
         while !should_continue && temp.node_type == NodeType::TemplateExprStart {
             if let Some(ContentType::NodeVec(v)) = temp.children.get("exprs") {
                 if let Some(last) = v.last() {
-                    if last.children.get("comma").is_some() {
+                    if last.children.contains_key("comma") {
                         should_continue = true;
                     } else {
                         temp = last;
@@ -937,11 +1158,14 @@ impl Parser {
         }
         Ok(should_continue)
     }
-    fn parse_grouped_exprs(&mut self, alias: bool) -> BQ2CSTResult<Node> {
+    fn parse_grouped_exprs(&mut self, alias: bool) -> BQ2CSTResult<Node<'a>> {
         let mut group = self.construct_node(NodeType::GroupedExprs)?;
-        if !self.get_token(1)?.is(")") {
+        if !self.get_token(1)?.is(Symbol::RParen) {
             self.next_token()?; // ( -> exprs
-            group.push_node_vec("exprs", self.parse_exprs(&vec![")"], alias, true)?);
+            group.push_node_vec(
+                "exprs",
+                self.parse_exprs(&[TokenKind::Symbol(Symbol::RParen)], alias, true)?,
+            );
         }
         self.next_token()?; // exprs -> )
         group.push_node("rparen", self.construct_node(NodeType::Symbol)?);
@@ -951,14 +1175,22 @@ impl Parser {
         &mut self,
         schema: bool,
         aggregate: bool,
-    ) -> BQ2CSTResult<Node> {
+    ) -> BQ2CSTResult<Node<'a>> {
         let mut group = self.construct_node(NodeType::GroupedTypeDeclarationOrConstraints)?;
         self.next_token()?; // ( -> INOUT | ident | type | PRIMARY | CONSTRAING | FOREIGN
         let mut type_declarations = Vec::new();
-        let marker_tokens = vec![",", ">", ")", "TYPE", "<"];
-        while !self.get_token(0)?.in_(&vec![">", ")"]) {
+        let marker_tokens = [
+            TokenKind::Symbol(Symbol::Comma),
+            TokenKind::Symbol(Symbol::Gt),
+            TokenKind::Symbol(Symbol::RParen),
+            TokenKind::Keyword(Keyword::TYPE),
+            TokenKind::Symbol(Symbol::Lt),
+        ];
+        while !self.get_token(0)?.in_(&[Symbol::Gt, Symbol::RParen]) {
             let mut type_declaration;
-            if self.get_token(0)?.in_(&vec!["IN", "OUT", "INOUT"])
+            if self
+                .get_token(0)?
+                .in_(&[Keyword::IN, Keyword::OUT, Keyword::INOUT])
                 && !self.get_token(2)?.in_(&marker_tokens)
             {
                 // `self.get_token(1).is_identifier()` does not work here
@@ -974,9 +1206,11 @@ impl Parser {
                 type_declaration.push_node("in_out", in_out);
                 self.next_token()?; // -> type
                 type_declaration.push_node("type", self.parse_type(schema, aggregate)?);
-            } else if (self.get_token(0)?.is("PRIMARY") && self.get_token(1)?.is("KEY"))
-                || (self.get_token(0)?.is("FOREIGN") && self.get_token(1)?.is("KEY"))
-                || (self.get_token(0)?.is("CONSTRAINT") && !self.get_token(2)?.in_(&marker_tokens))
+            } else if (self.get_token(0)?.is(Keyword::PRIMARY)
+                && self.get_token(1)?.is(Keyword::KEY))
+                || (self.get_token(0)?.is(Keyword::FOREIGN) && self.get_token(1)?.is(Keyword::KEY))
+                || (self.get_token(0)?.is(Keyword::CONSTRAINT)
+                    && !self.get_token(2)?.in_(&marker_tokens))
             {
                 type_declaration = self.parse_constraint()?;
             } else if !self.get_token(1)?.in_(&marker_tokens) {
@@ -988,39 +1222,50 @@ impl Parser {
                 type_declaration.push_node("type", self.parse_type(schema, aggregate)?);
             }
             self.next_token()?; //  -> , | > | )
-            if self.get_token(0)?.is(",") {
+            if self.get_token(0)?.is(Symbol::Comma) {
                 type_declaration.push_node("comma", self.construct_node(NodeType::Symbol)?);
                 self.next_token()?; // , -> type
             }
             type_declarations.push(type_declaration);
         }
-        if 0 < type_declarations.len() {
+        if !type_declarations.is_empty() {
             group.push_node_vec("declarations", type_declarations);
         }
         group.push_node("rparen", self.construct_node(NodeType::Symbol)?);
         Ok(group)
     }
-    fn parse_identifier(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_identifier(&mut self) -> BQ2CSTResult<Node<'a>> {
         // NOTE
         // This method is used to parse only identifier.
         // If you want to parse table function, you have to use parse_expr().
-        fn parse_single_or_multi_token_identifier(parser: &mut Parser) -> BQ2CSTResult<Node> {
+        fn parse_single_or_multi_token_identifier<'a>(
+            parser: &mut Parser<'a>,
+        ) -> BQ2CSTResult<Node<'a>> {
+            if parser.get_token(0)?.kind == TokenKind::SqlxInterpolation {
+                return parser.construct_sqlx_interpolation();
+            }
             let mut root = parser.construct_node(NodeType::Identifier)?;
             let mut trailing_idents = vec![];
             loop {
                 let curr_token = parser.get_token(0)?;
-                if curr_token.literal.chars().next().unwrap() == '`' {
+                if curr_token.literal.starts_with('`') {
                     break;
                 }
                 let next_token = match parser.get_token(1) {
                     Ok(n) => n,
                     Err(_) => break, // in the case of EOF
                 };
-                if next_token.in_(&vec![",", ".", "(", ")", ";"]) {
+                if next_token.in_(&[
+                    Symbol::Comma,
+                    Symbol::Dot,
+                    Symbol::LParen,
+                    Symbol::RParen,
+                    Symbol::Semicolon,
+                ]) {
                     break;
                 }
-                if !(curr_token.literal.chars().last().unwrap() == '.')
-                    && !(curr_token.line == next_token.line
+                if !(curr_token.literal.ends_with('.')
+                    || curr_token.line == next_token.line
                         && curr_token.column + curr_token.literal.chars().count()
                             == next_token.column)
                 {
@@ -1029,7 +1274,7 @@ impl Parser {
                 parser.next_token()?;
                 trailing_idents.push(parser.construct_node(NodeType::Identifier)?);
             }
-            if trailing_idents.len() > 0 {
+            if !trailing_idents.is_empty() {
                 root.node_type = NodeType::MultiTokenIdentifier;
                 root.push_node_vec("trailing_idents", trailing_idents);
             }
@@ -1037,7 +1282,8 @@ impl Parser {
         }
 
         let mut left = parse_single_or_multi_token_identifier(self)?;
-        while self.get_token(1)?.is(".") {
+        while self.get_token(1)?.is(Symbol::Dot) {
+            self.deepen()?;
             self.next_token()?; // ident -> .
             let mut operator = self.construct_node(NodeType::DotOperator)?;
             operator.push_node("left", left);
@@ -1047,10 +1293,10 @@ impl Parser {
         }
         Ok(left)
     }
-    fn parse_in_operator(&mut self, left: Node) -> BQ2CSTResult<Node> {
+    fn parse_in_operator(&mut self, left: Node<'a>) -> BQ2CSTResult<Node<'a>> {
         let mut node = self.construct_node(NodeType::InOperator)?;
         node.push_node("left", left);
-        if self.get_token(1)?.is("UNNEST") {
+        if self.get_token(1)?.is(Keyword::UNNEST) {
             self.next_token()?; // IN -> UNNEST
             let mut unnest = self.parse_expr(
                 102, // NOTE 102 is a little greater than `(` (calling function)
@@ -1060,7 +1306,7 @@ impl Parser {
             node.push_node("right", unnest);
         } else {
             self.next_token()?; // IN -> (
-            if self.get_token(1)?.in_(&vec!["SELECT", "WITH"]) {
+            if self.get_token(1)?.in_(&[Keyword::SELECT, Keyword::WITH]) {
                 let mut lparen = self.construct_node(NodeType::GroupedStatement)?;
                 self.next_token()?; // -> SELECT | WITH
                 lparen.push_node("stmt", self.parse_select_statement(false, true)?);
@@ -1073,11 +1319,11 @@ impl Parser {
         }
         Ok(node)
     }
-    fn parse_is_distinct_from_operator(&mut self, left: Node) -> BQ2CSTResult<Node> {
+    fn parse_is_distinct_from_operator(&mut self, left: Node<'a>) -> BQ2CSTResult<Node<'a>> {
         let precedence = self.get_precedence(0)?;
         let mut node = self.construct_node(NodeType::IsDistinctFromOperator)?;
         node.push_node("left", left);
-        if self.get_token(1)?.is("NOT") {
+        if self.get_token(1)?.is(Keyword::NOT) {
             self.next_token()?; // IS -> NOT
             node.push_node("not", self.construct_node(NodeType::Keyword)?);
         }
@@ -1092,13 +1338,13 @@ impl Parser {
         );
         Ok(node)
     }
-    fn parse_keyword_with_grouped_exprs(&mut self, alias: bool) -> BQ2CSTResult<Node> {
+    fn parse_keyword_with_grouped_exprs(&mut self, alias: bool) -> BQ2CSTResult<Node<'a>> {
         let mut keyword = self.construct_node(NodeType::KeywordWithGroupedXXX)?;
         self.next_token()?; // keyword -> (
         keyword.push_node("group", self.parse_grouped_exprs(alias)?);
         Ok(keyword)
     }
-    fn parse_keyword_with_statements(&mut self, until: &Vec<&str>) -> BQ2CSTResult<Node> {
+    fn parse_keyword_with_statements(&mut self, until: &[Keyword]) -> BQ2CSTResult<Node<'a>> {
         let mut node = self.construct_node(NodeType::KeywordWithStatements)?;
         let mut stmts = Vec::new();
         while !self.get_token(1)?.in_(until) {
@@ -1108,7 +1354,7 @@ impl Parser {
         node.push_node_vec("stmts", stmts);
         Ok(node)
     }
-    fn parse_n_keywords(&mut self, n: usize) -> BQ2CSTResult<Vec<Node>> {
+    fn parse_n_keywords(&mut self, n: usize) -> BQ2CSTResult<Vec<Node<'a>>> {
         let mut nodes = Vec::new();
         nodes.push(self.construct_node(NodeType::Keyword)?);
         for _ in 1..n {
@@ -1117,17 +1363,18 @@ impl Parser {
         }
         Ok(nodes)
     }
-    fn parse_set_operator(&mut self, left: Node) -> BQ2CSTResult<Node> {
+    fn parse_set_operator(&mut self, left: Node<'a>) -> BQ2CSTResult<Node<'a>> {
+        self.deepen()?;
         let mut operator: Node;
 
         // NOTE:
         // when you modify here, also modify parse_union_pipe_operator()
         if self
             .get_token(0)?
-            .in_(&vec!["INNER", "FULL", "LEFT", "OUTER"])
+            .in_(&[Keyword::INNER, Keyword::FULL, Keyword::LEFT, Keyword::OUTER])
         {
             let mut method;
-            if self.get_token(1)?.is("OUTER") {
+            if self.get_token(1)?.is(Keyword::OUTER) {
                 method = self.construct_node(NodeType::KeywordSequence)?;
                 self.next_token()?; // -> OUTER
                 let outer = self.construct_node(NodeType::Keyword)?;
@@ -1145,10 +1392,13 @@ impl Parser {
         self.next_token()?; // ALL | DISTINCT
         operator.push_node("distinct_or_all", self.construct_node(NodeType::Keyword)?);
 
-        if self.get_token(1)?.is("BY") {
+        if self.get_token(1)?.is(Keyword::BY) {
             self.next_token()?; // -> BY
             operator.push_node("by", self.parse_by_name_clause()?);
-        } else if self.get_token(1)?.in_(&vec!["STRICT", "CORRESPONDING"]) {
+        } else if self
+            .get_token(1)?
+            .in_(&[Keyword::STRICT, Keyword::CORRESPONDING])
+        {
             self.next_token()?; // -> STRICT | CORRESPONDING
             operator.push_node("corresponding", self.parse_corresponding_clause()?);
         }
@@ -1157,46 +1407,64 @@ impl Parser {
         operator.push_node("right", self.parse_select_statement(false, false)?);
         Ok(operator)
     }
-    fn parse_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
-        let node = match self.get_token(0)?.literal.to_uppercase().as_str() {
+    fn parse_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
+        self.nest(|p| p.parse_statement_inner(semicolon))
+    }
+    fn parse_statement_inner(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
+        let node = match self.get_token(0)?.kind {
             // SELECT
-            "WITH" | "SELECT" | "(" => {
+            TokenKind::Keyword(Keyword::WITH)
+            | TokenKind::Keyword(Keyword::SELECT)
+            | TokenKind::Symbol(Symbol::LParen) => {
                 // actually, it may be pipe syntax
                 self.parse_select_statement(semicolon, true)?
             }
-            "FROM" => self.parse_from_statement()?, // pipe syntax
+            TokenKind::Keyword(Keyword::FROM) => self.parse_from_statement()?, // pipe syntax
             // DML
-            "INSERT" => self.parse_insert_statement(semicolon)?,
-            "DELETE" => self.parse_delete_statement(semicolon)?,
-            "TRUNCATE" => self.parse_truncate_statement(semicolon)?,
-            "UPDATE" => self.parse_update_statement(semicolon)?,
-            "MERGE" => self.parse_merge_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::INSERT) => self.parse_insert_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::DELETE) => self.parse_delete_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::TRUNCATE) => self.parse_truncate_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::UPDATE) => self.parse_update_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::MERGE) => self.parse_merge_statement(semicolon)?,
             // DDL
-            "CREATE" => {
+            TokenKind::Keyword(Keyword::CREATE) => {
                 let mut offset = 1;
                 loop {
-                    match self.get_token(offset)?.literal.to_uppercase().as_str() {
-                        "SCHEMA" => return self.parse_create_schema_statement(semicolon),
-                        "TABLE" => {
-                            if self.get_token(offset + 1)?.literal.to_uppercase().as_str()
-                                == "FUNCTION"
-                            {
+                    match self.get_token(offset)?.kind {
+                        TokenKind::Keyword(Keyword::SCHEMA) => {
+                            return self.parse_create_schema_statement(semicolon)
+                        }
+                        TokenKind::Keyword(Keyword::TABLE) => {
+                            if self.get_token(offset + 1)?.is(Keyword::FUNCTION) {
                                 return self.parse_create_function_statement(semicolon);
                             } else {
                                 return self.parse_create_table_statement(semicolon);
                             }
                         }
-                        "VIEW" => return self.parse_create_view_statement(semicolon),
-                        "FUNCTION" => return self.parse_create_function_statement(semicolon),
-                        "PROCEDURE" => return self.parse_create_procedure_statement(semicolon),
-                        "ROW" => return self.parse_create_row_access_policy_statement(semicolon),
-                        "CAPACITY" | "RESERVATION" | "ASSIGNMENT" => {
+                        TokenKind::Keyword(Keyword::VIEW) => {
+                            return self.parse_create_view_statement(semicolon)
+                        }
+                        TokenKind::Keyword(Keyword::FUNCTION) => {
+                            return self.parse_create_function_statement(semicolon)
+                        }
+                        TokenKind::Keyword(Keyword::PROCEDURE) => {
+                            return self.parse_create_procedure_statement(semicolon)
+                        }
+                        TokenKind::Keyword(Keyword::ROW) => {
+                            return self.parse_create_row_access_policy_statement(semicolon)
+                        }
+                        TokenKind::Keyword(Keyword::CAPACITY)
+                        | TokenKind::Keyword(Keyword::RESERVATION)
+                        | TokenKind::Keyword(Keyword::ASSIGNMENT) => {
                             return self.parse_create_reservation_statement(semicolon)
                         }
-                        "SEARCH" | "VECTOR" => {
+                        TokenKind::Keyword(Keyword::SEARCH)
+                        | TokenKind::Keyword(Keyword::VECTOR) => {
                             return self.parse_create_search_index_statement(semicolon)
                         }
-                        "MODEL" => return self.parse_create_model_statement(semicolon),
+                        TokenKind::Keyword(Keyword::MODEL) => {
+                            return self.parse_create_model_statement(semicolon)
+                        }
                         _ => {
                             offset += 1;
                             if 5 < offset {
@@ -1210,24 +1478,41 @@ impl Parser {
                     format!("Expected `SCHEMA`, `TABLE`, `VIEW`, `FUNCTION`, `PROCEDURE`, 'CAPACITY', 'RESERVATION' or 'ASSIGNMENT' but not found around here: {:?}", self.get_token(0)?)
                 ));
             }
-            "ALTER" => {
+            TokenKind::Keyword(Keyword::ALTER) => {
                 let mut offset = 1;
                 loop {
-                    match self.get_token(offset)?.literal.to_uppercase().as_str() {
-                        "SCHEMA" => return self.parse_alter_schema_statement(semicolon),
-                        "TABLE" => return self.parse_alter_table_statement(semicolon),
-                        "COLUMN" => return self.parse_alter_column_statement(semicolon),
-                        "VIEW" => return self.parse_alter_view_statement(semicolon),
-                        "VECTOR" => return self.parse_alter_vector_index_statement(semicolon),
-                        "ORGANIZATION" => {
+                    match self.get_token(offset)?.kind {
+                        TokenKind::Keyword(Keyword::SCHEMA) => {
+                            return self.parse_alter_schema_statement(semicolon)
+                        }
+                        TokenKind::Keyword(Keyword::TABLE) => {
+                            return self.parse_alter_table_statement(semicolon)
+                        }
+                        TokenKind::Keyword(Keyword::COLUMN) => {
+                            return self.parse_alter_column_statement(semicolon)
+                        }
+                        TokenKind::Keyword(Keyword::VIEW) => {
+                            return self.parse_alter_view_statement(semicolon)
+                        }
+                        TokenKind::Keyword(Keyword::VECTOR) => {
+                            return self.parse_alter_vector_index_statement(semicolon)
+                        }
+                        TokenKind::Keyword(Keyword::ORGANIZATION) => {
                             return self.parse_alter_organization_statement(semicolon)
                         }
-                        "PROJECT" => return self.parse_alter_project_statement(semicolon),
-                        "BI_CAPACITY" => return self.parse_alter_bicapacity_statement(semicolon),
-                        "CAPACITY" | "RESERVATION" => {
+                        TokenKind::Keyword(Keyword::PROJECT) => {
+                            return self.parse_alter_project_statement(semicolon)
+                        }
+                        TokenKind::Keyword(Keyword::BI_CAPACITY) => {
+                            return self.parse_alter_bicapacity_statement(semicolon)
+                        }
+                        TokenKind::Keyword(Keyword::CAPACITY)
+                        | TokenKind::Keyword(Keyword::RESERVATION) => {
                             return self.parse_alter_reservation_statement(semicolon)
                         }
-                        "MODEL" => return self.parse_alter_model_statement(semicolon),
+                        TokenKind::Keyword(Keyword::MODEL) => {
+                            return self.parse_alter_model_statement(semicolon)
+                        }
                         _ => {
                             offset += 1;
                             if 5 < offset {
@@ -1244,53 +1529,71 @@ impl Parser {
                     ),
                 ));
             }
-            "DROP" => {
-                if self.get_token(1)?.in_(&vec!["ALL", "ROW"]) {
+            TokenKind::Keyword(Keyword::DROP) => {
+                if self.get_token(1)?.in_(&[Keyword::ALL, Keyword::ROW]) {
                     self.parse_drop_row_access_policy_statement(semicolon)?
                 } else {
                     self.parse_drop_statement_general(semicolon)?
                 }
             }
-            "UNDROP" => self.parse_undrop_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::UNDROP) => self.parse_undrop_statement(semicolon)?,
             // DCL
-            "GRANT" => self.parse_grant_statement(semicolon)?,
-            "REVOKE" => self.parse_revoke_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::GRANT) => self.parse_grant_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::REVOKE) => self.parse_revoke_statement(semicolon)?,
             // script
-            "DECLARE" => self.parse_declare_statement(semicolon)?,
-            "SET" => self.parse_set_statement(semicolon)?,
-            "EXECUTE" => self.parse_execute_statement(semicolon)?,
-            "IF" => self.parse_if_statement(semicolon)?,
-            "BEGIN" => {
-                if self.get_token(1)?.in_(&vec!["TRANSACTION", ";"]) || self.is_eof(1) {
-                    return Ok(self.parse_transaction_statement(semicolon)?);
+            TokenKind::Keyword(Keyword::DECLARE) => self.parse_declare_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::SET) => self.parse_set_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::EXECUTE) => self.parse_execute_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::IF) => self.parse_if_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::BEGIN) => {
+                if self.get_token(1)?.in_(&[
+                    TokenKind::Keyword(Keyword::TRANSACTION),
+                    TokenKind::Symbol(Symbol::Semicolon),
+                ]) || self.is_eof(1)
+                {
+                    return self.parse_transaction_statement(semicolon);
                 }
                 self.parse_begin_statement(semicolon)?
             }
-            "CASE" => self.parse_case_statement(semicolon)?,
-            "LOAD" => self.parse_load_statement(semicolon)?,
-            "LOOP" => self.parse_loop_statement(semicolon)?,
-            "REPEAT" => self.parse_repeat_statement(semicolon)?,
-            "WHILE" => self.parse_while_statement(semicolon)?,
-            "BREAK" | "LEAVE" | "CONTINUE" | "ITERATE" => {
+            TokenKind::Keyword(Keyword::CASE) => self.parse_case_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::LOAD) => self.parse_load_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::LOOP) => self.parse_loop_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::REPEAT) => self.parse_repeat_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::WHILE) => self.parse_while_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::BREAK)
+            | TokenKind::Keyword(Keyword::LEAVE)
+            | TokenKind::Keyword(Keyword::CONTINUE)
+            | TokenKind::Keyword(Keyword::ITERATE) => {
                 self.parse_break_continue_statement(semicolon)?
             }
-            "FOR" => self.parse_for_statement(semicolon)?,
-            "COMMIT" | "ROLLBACK" => self.parse_transaction_statement(semicolon)?,
-            "RAISE" => self.parse_raise_statement(semicolon)?,
-            "RETURN" => self.parse_single_token_statement(semicolon)?,
-            "CALL" => self.parse_call_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::FOR) => self.parse_for_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::COMMIT) | TokenKind::Keyword(Keyword::ROLLBACK) => {
+                self.parse_transaction_statement(semicolon)?
+            }
+            TokenKind::Keyword(Keyword::RAISE) => self.parse_raise_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::RETURN) => self.parse_single_token_statement(semicolon)?,
+            TokenKind::Keyword(Keyword::CALL) => self.parse_call_statement(semicolon)?,
             // DEBUG
-            "ASSERT" => self.parse_assert_satement(semicolon)?,
+            TokenKind::Keyword(Keyword::ASSERT) => self.parse_assert_satement(semicolon)?,
             // other
-            "EXPORT" => {
-                if self.get_token(1)?.is("DATA") {
+            TokenKind::Keyword(Keyword::EXPORT) => {
+                if self.get_token(1)?.is(Keyword::DATA) {
                     self.parse_export_data_statement(semicolon)?
                 } else {
                     self.parse_export_model_statement(semicolon)?
                 }
             }
+            // template
+            TokenKind::Template(TemplateType::ExprStart)
+                if self.relaxed_macros
+                    && matches!(self.get_token(0)?.template_name(), Some("macro" | "call")) =>
+            {
+                self.parse_template_block()?
+            }
+            // Dataform SQLX
+            TokenKind::SqlxBlock => self.parse_sqlx_block()?,
             _ => {
-                if self.get_token(1)?.is(":") {
+                if self.get_token(1)?.is(Symbol::Colon) {
                     self.parse_labeled_statement(semicolon)?
                 } else {
                     self.parse_standalone_expr()?
@@ -1299,34 +1602,37 @@ impl Parser {
         };
         Ok(node)
     }
-    fn parse_table(&mut self, root: bool) -> BQ2CSTResult<Node> {
+    fn parse_table(&mut self, root: bool) -> BQ2CSTResult<Node<'a>> {
+        self.nest(|p| p.parse_table_inner(root))
+    }
+    fn parse_table_inner(&mut self, root: bool) -> BQ2CSTResult<Node<'a>> {
         let mut left: Node;
-        match self.get_token(0)?.literal.to_uppercase().as_str() {
-            "(" => {
+        match self.get_token(0)?.kind {
+            TokenKind::Symbol(Symbol::LParen) => {
                 let mut group;
                 let mut statement_flg = false;
                 let mut offset = 0;
                 loop {
                     offset += 1;
-                    if self.get_token(offset)?.in_(&vec!["WITH", "SELECT", "FROM"]) {
+                    if self
+                        .get_token(offset)?
+                        .in_(&[Keyword::WITH, Keyword::SELECT, Keyword::FROM])
+                    {
                         statement_flg = true;
                         break;
-                    } else if !self.get_token(offset)?.is("(") {
+                    } else if !self.get_token(offset)?.is(Symbol::LParen) {
                         break;
                     }
                 }
                 if statement_flg {
-                    let org = self.clone();
+                    let org = self.save_state();
                     group = match self.parse_select_statement(false, false) {
                         Ok(stmt) => stmt,
 
                         // maybe that is a table quoted by ()! not a select statement!
                         Err(_) => {
                             // restore original state
-                            self.position = org.position;
-                            self.leading_comment_indices = org.leading_comment_indices;
-                            self.trailing_comment_indices = org.trailing_comment_indices;
-                            self.tokens = org.tokens;
+                            self.restore_state(org);
 
                             // retry
                             let mut group = self.construct_node(NodeType::GroupedExpr)?;
@@ -1346,7 +1652,7 @@ impl Parser {
                 }
                 left = group;
             }
-            "UNNEST" => {
+            TokenKind::Keyword(Keyword::UNNEST) => {
                 left = self.parse_expr(usize::MAX, false, false, false, true)?;
                 left.node_type = NodeType::CallingUnnest;
             }
@@ -1362,13 +1668,17 @@ impl Parser {
         // NOTE PIVOT, UNPIVOT and MATCH_RECOGNIZE are not reserved keywords
         if !(self
             .get_token(1)?
-            .in_(&vec!["PIVOT", "UNPIVOT", "MATCH_RECOGNIZE"])
-            && self.get_token(2)?.in_(&vec!["(", "INCLUDE", "EXCLUDE"]))
+            .in_(&[Keyword::PIVOT, Keyword::UNPIVOT, Keyword::MATCH_RECOGNIZE])
+            && self.get_token(2)?.in_(&[
+                TokenKind::Symbol(Symbol::LParen),
+                TokenKind::Keyword(Keyword::INCLUDE),
+                TokenKind::Keyword(Keyword::EXCLUDE),
+            ]))
         {
             left = self.push_trailing_alias(left)?;
         }
         // FOR SYSTEM_TIME AS OF
-        if self.get_token(1)?.literal.to_uppercase() == "FOR" {
+        if self.get_token(1)?.is(Keyword::FOR) {
             self.next_token()?; // TABLE -> FOR
             let mut for_ = self.construct_node(NodeType::ForSystemTimeAsOfClause)?;
             self.next_token()?; // FOR -> SYSTEM_TIME
@@ -1387,12 +1697,12 @@ impl Parser {
             left.push_node("for_system_time_as_of", for_);
         }
         // WITH, OFFSET
-        if self.get_token(1)?.literal.to_uppercase() == "WITH" {
+        if self.get_token(1)?.is(Keyword::WITH) {
             self.next_token()?; // UNNEST() -> WITH
             let mut with = self.construct_node(NodeType::WithOffsetClause)?;
             self.next_token()?; // WITH -> OFFSET
             with.push_node("offset", self.construct_node(NodeType::Keyword)?);
-            if self.get_token(1)?.is("AS") {
+            if self.get_token(1)?.is(Keyword::AS) {
                 self.next_token()?; // OFFSET -> AS
                 with.push_node("as", self.construct_node(NodeType::Keyword)?);
                 self.next_token()?; // AS -> alias
@@ -1404,17 +1714,20 @@ impl Parser {
             left.push_node("with_offset", with);
         }
         // PIVOT, UNPIVOT
-        if self.get_token(1)?.is("PIVOT") {
+        if self.get_token(1)?.is(Keyword::PIVOT) {
             self.next_token()?; // -> PIVOT
             let mut pivot = self.construct_node(NodeType::PivotOperator)?;
             self.next_token()?; // -> (
             pivot.push_node("config", self.parse_pivot_config_clause()?);
             pivot = self.push_trailing_alias(pivot)?;
             left.push_node("pivot", pivot);
-        } else if self.get_token(1)?.is("UNPIVOT") {
+        } else if self.get_token(1)?.is(Keyword::UNPIVOT) {
             self.next_token()?; // -> UNPIVOT
             let mut unpivot = self.construct_node(NodeType::UnpivotOperator)?;
-            if self.get_token(1)?.in_(&vec!["INCLUDE", "EXCLUDE"]) {
+            if self
+                .get_token(1)?
+                .in_(&[Keyword::INCLUDE, Keyword::EXCLUDE])
+            {
                 self.next_token()?; // -> INCLUDE | EXCLUDE
                 unpivot.push_node_vec("include_or_exclude_nulls", self.parse_n_keywords(2)?);
             }
@@ -1422,12 +1735,12 @@ impl Parser {
             unpivot.push_node("config", self.parse_unpivot_config_clause()?);
             unpivot = self.push_trailing_alias(unpivot)?;
             left.push_node("unpivot", unpivot);
-        } else if self.get_token(1)?.is("MATCH_RECOGNIZE") {
+        } else if self.get_token(1)?.is(Keyword::MATCH_RECOGNIZE) {
             self.next_token()?; // -> MATCH_RECOGNIZE
             left.push_node("match_recognize", self.parse_match_recognize_clause()?);
         }
         // TABLESAMPLE
-        if self.get_token(1)?.is("tablesample") {
+        if self.get_token(1)?.is(Keyword::TABLESAMPLE) {
             // TODO check when it becomes GA
             self.next_token()?; // -> TABLESAMPLE
             let mut tablesample = self.construct_node(NodeType::TableSampleClause)?;
@@ -1438,18 +1751,27 @@ impl Parser {
             left.push_node("tablesample", tablesample);
         }
         // JOIN
-        while self.get_token(1)?.in_(&vec![
-            "left", "right", "cross", "inner", "full", "join", ",",
+        while self.get_token(1)?.in_(&[
+            TokenKind::Keyword(Keyword::LEFT),
+            TokenKind::Keyword(Keyword::RIGHT),
+            TokenKind::Keyword(Keyword::CROSS),
+            TokenKind::Keyword(Keyword::INNER),
+            TokenKind::Keyword(Keyword::FULL),
+            TokenKind::Keyword(Keyword::JOIN),
+            TokenKind::Symbol(Symbol::Comma),
         ]) && root
         {
+            self.deepen()?;
             self.next_token()?; // table -> LEFT, RIGHT, INNER, CROSS, FULL, JOIN, ","
-            let mut join = if self.get_token(0)?.in_(&vec!["join", ","]) {
-                let join = self.construct_node(NodeType::JoinOperator)?;
-                join
+            let mut join = if self.get_token(0)?.in_(&[
+                TokenKind::Keyword(Keyword::JOIN),
+                TokenKind::Symbol(Symbol::Comma),
+            ]) {
+                self.construct_node(NodeType::JoinOperator)?
             } else {
                 let type_ = self.construct_node(NodeType::Keyword)?;
                 self.next_token()?; // join_type -> OUTER, JOIN
-                if self.get_token(0)?.is("OUTER") {
+                if self.get_token(0)?.is(Keyword::OUTER) {
                     let outer = self.construct_node(NodeType::Keyword)?;
                     self.next_token()?; // OUTER -> JOIN
                     let mut join = self.construct_node(NodeType::JoinOperator)?;
@@ -1464,7 +1786,7 @@ impl Parser {
             };
             self.next_token()?; // -> table
             let right = self.parse_table(false)?;
-            if self.get_token(1)?.is("on") {
+            if self.get_token(1)?.is(Keyword::ON) {
                 self.next_token()?; // `table` -> ON
                 let mut on = self.construct_node(NodeType::KeywordWithExpr)?;
                 self.next_token()?; // ON -> expr
@@ -1473,7 +1795,7 @@ impl Parser {
                     self.parse_expr(usize::MAX, false, false, false, true)?,
                 );
                 join.push_node("on", on);
-            } else if self.get_token(1)?.is("using") {
+            } else if self.get_token(1)?.is(Keyword::USING) {
                 self.next_token()?; // -> USING
                 join.push_node(
                     "using",
@@ -1486,11 +1808,14 @@ impl Parser {
         }
         Ok(left)
     }
-    fn parse_type(&mut self, schema: bool, aggregate: bool) -> BQ2CSTResult<Node> {
-        let mut res = match self.get_token(0)?.literal.to_uppercase().as_str() {
-            "ARRAY" | "RANGE" => {
+    fn parse_type(&mut self, schema: bool, aggregate: bool) -> BQ2CSTResult<Node<'a>> {
+        self.nest(|p| p.parse_type_inner(schema, aggregate))
+    }
+    fn parse_type_inner(&mut self, schema: bool, aggregate: bool) -> BQ2CSTResult<Node<'a>> {
+        let mut res = match self.get_token(0)?.kind {
+            TokenKind::Keyword(Keyword::ARRAY) | TokenKind::Keyword(Keyword::RANGE) => {
                 let mut res = self.construct_node(NodeType::Type)?;
-                if self.get_token(1)?.literal.as_str() == "<" {
+                if self.get_token(1)?.is(Symbol::Lt) {
                     self.next_token()?; // -> <
                     let mut type_ = self.construct_node(NodeType::GroupedType)?;
                     self.next_token()?; // < -> type
@@ -1501,17 +1826,22 @@ impl Parser {
                 }
                 res
             }
-            "STRUCT" | "TABLE" => {
+            TokenKind::Keyword(Keyword::STRUCT) | TokenKind::Keyword(Keyword::TABLE) => {
                 let mut res = self.construct_node(NodeType::Type)?;
-                if self.get_token(1)?.literal.as_str() == "<" {
+                if self.get_token(1)?.is(Symbol::Lt) {
                     self.next_token()?; // STRUCT -> <
                     let mut type_ =
                         self.construct_node(NodeType::GroupedTypeDeclarationOrConstraints)?;
                     self.next_token()?; // < -> type or ident
                     let mut type_declarations = Vec::new();
-                    while !self.get_token(0)?.is(">") {
+                    while !self.get_token(0)?.is(Symbol::Gt) {
                         let mut type_declaration;
-                        if !self.get_token(1)?.in_(&vec![",", ">", "TYPE", "<"]) {
+                        if !self.get_token(1)?.in_(&[
+                            TokenKind::Symbol(Symbol::Comma),
+                            TokenKind::Symbol(Symbol::Gt),
+                            TokenKind::Keyword(Keyword::TYPE),
+                            TokenKind::Symbol(Symbol::Lt),
+                        ]) {
                             // `is_identifier` is not availabe here,
                             // because `int64` is valid identifier
                             type_declaration = self.construct_node(NodeType::TypeDeclaration)?;
@@ -1521,7 +1851,7 @@ impl Parser {
                         }
                         type_declaration.push_node("type", self.parse_type(schema, false)?);
                         self.next_token()?; // type -> , or next_declaration
-                        if self.get_token(0)?.is(",") {
+                        if self.get_token(0)?.is(Symbol::Comma) {
                             type_declaration
                                 .push_node("comma", self.construct_node(NodeType::Symbol)?);
                             self.next_token()?; // , -> next_declaration
@@ -1534,7 +1864,7 @@ impl Parser {
                 }
                 res
             }
-            "ANY" => {
+            TokenKind::Keyword(Keyword::ANY) => {
                 let mut res = self.construct_node(NodeType::Type)?;
                 self.next_token()?; // ANY -> TYPE
                 res.push_node("type", self.construct_node(NodeType::Keyword)?);
@@ -1542,14 +1872,14 @@ impl Parser {
             }
             _ => {
                 let mut res = self.construct_node(NodeType::Type)?;
-                if self.get_token(1)?.is("(") {
+                if self.get_token(1)?.is(Symbol::LParen) {
                     self.next_token()?; // -> (
                     res.push_node("parameter", self.parse_grouped_exprs(false)?);
                 }
                 res
             }
         };
-        if self.get_token(1)?.is("COLLATE") {
+        if self.get_token(1)?.is(Keyword::COLLATE) {
             // run even if schema == false
             self.next_token()?; // -> COLLATE
             let mut collate = self.construct_node(NodeType::KeywordWithExpr)?;
@@ -1561,39 +1891,39 @@ impl Parser {
             );
             res.push_node("collate", collate);
         }
-        if self.get_token(1)?.is("CONSTRAINT") {
+        if self.get_token(1)?.is(Keyword::CONSTRAINT) {
             self.next_token()?; // -> constraint
             let mut constraint = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // -> ident
             constraint.push_node("expr", self.parse_identifier()?);
             res.push_node("constraint", constraint);
         }
-        if self.get_token(1)?.is("PRIMARY") {
+        if self.get_token(1)?.is(Keyword::PRIMARY) {
             self.next_token()?; // -> PRIMARY
             let mut primary = self.construct_node(NodeType::KeywordSequence)?;
             self.next_token()?; // -> KEY
             let key = self.construct_node(NodeType::Keyword)?;
             primary.push_node("next_keyword", key);
             res.push_node("primarykey", primary);
-            if self.get_token(1)?.in_(&vec!["NOT", "ENFORCED"]) {
+            if self.get_token(1)?.in_(&[Keyword::NOT, Keyword::ENFORCED]) {
                 self.next_token()?; // -> NOT | ENFORCED
                 res.push_node("enforced", self.parse_enforced()?);
             }
         }
-        if self.get_token(1)?.is("REFERENCES") {
+        if self.get_token(1)?.is(Keyword::REFERENCES) {
             self.next_token()?; // -> REFERENCES
             let mut references = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // -> ident
             let col = self.parse_expr(usize::MAX, false, true, false, true)?;
             references.push_node("expr", col);
             res.push_node("references", references);
-            if self.get_token(1)?.in_(&vec!["NOT", "ENFORCED"]) {
+            if self.get_token(1)?.in_(&[Keyword::NOT, Keyword::ENFORCED]) {
                 self.next_token()?; // -> NOT | ENFORCED
                 res.push_node("enforced", self.parse_enforced()?);
             }
         }
         if schema {
-            if self.get_token(1)?.is("DEFAULT") {
+            if self.get_token(1)?.is(Keyword::DEFAULT) {
                 self.next_token()?; // -> DEFAULT
                 let mut default = self.construct_node(NodeType::KeywordWithExpr)?;
                 self.next_token()?; // -> expr
@@ -1602,7 +1932,7 @@ impl Parser {
                     self.parse_expr(usize::MAX, false, false, false, true)?,
                 );
                 res.push_node("default", default);
-            } else if self.get_token(1)?.is("GENERATED") {
+            } else if self.get_token(1)?.is(Keyword::GENERATED) {
                 self.next_token()?; // -> GENERATED
                 let mut generated = self.construct_node(NodeType::KeywordSequence)?;
                 self.next_token()?; // -> ALWAYS
@@ -1625,67 +1955,68 @@ impl Parser {
                 stored.push_node("next_keyword", options);
                 res.push_node("stored_options", stored);
             }
-            if self.get_token(1)?.is("NOT") {
+            if self.get_token(1)?.is(Keyword::NOT) {
                 self.next_token()?; // -> NOT
                 let not_ = self.construct_node(NodeType::Keyword)?;
                 self.next_token()?; // -> null
                 let null = self.construct_node(NodeType::Keyword)?;
                 res.push_node_vec("not_null", vec![not_, null]);
             }
-            if self.get_token(1)?.is("OPTIONS") {
+            if self.get_token(1)?.is(Keyword::OPTIONS) {
                 self.next_token()?; // -> OPTIONS
                 let options = self.parse_keyword_with_grouped_exprs(false)?;
                 res.push_node("options", options);
             }
         }
-        if aggregate {
-            if self.get_token(1)?.is("NOT") {
-                self.next_token()?; // -> NOT
-                let mut not_ = self.construct_node(NodeType::KeywordSequence)?;
-                self.next_token()?; // -> AGGREGATE
-                let null = self.construct_node(NodeType::Keyword)?;
-                not_.push_node("next_keyword", null);
-                res.push_node("aggregate", not_);
-            }
+        if aggregate && self.get_token(1)?.is(Keyword::NOT) {
+            self.next_token()?; // -> NOT
+            let mut not_ = self.construct_node(NodeType::KeywordSequence)?;
+            self.next_token()?; // -> AGGREGATE
+            let null = self.construct_node(NodeType::Keyword)?;
+            not_.push_node("next_keyword", null);
+            res.push_node("aggregate", not_);
         }
         Ok(res)
     }
-    fn parse_window_expr(&mut self) -> BQ2CSTResult<Node> {
-        if self.get_token(0)?.is("(") {
+    fn parse_window_expr(&mut self) -> BQ2CSTResult<Node<'a>> {
+        if self.get_token(0)?.is(Symbol::LParen) {
             let mut window = self.construct_node(NodeType::WindowSpecification)?;
             if self.get_token(1)?.is_identifier() {
                 self.next_token()?; // ( -> identifier
                 window.push_node("name", self.construct_node(NodeType::Identifier)?);
             }
-            if self.get_token(1)?.is("PARTITION") {
+            if self.get_token(1)?.is(Keyword::PARTITION) {
                 self.next_token()?; // ( -> PARTITION
                 let mut partition = self.construct_node(NodeType::XXXByExprs)?;
                 self.next_token()?; // PARTITION -> BY
                 partition.push_node("by", self.construct_node(NodeType::Keyword)?);
                 self.next_token()?; // BY -> exprs
-                partition.push_node_vec("exprs", self.parse_exprs(&vec![], false, true)?);
+                partition.push_node_vec("exprs", self.parse_exprs(&[], false, true)?);
                 window.push_node("partitionby", partition);
             }
-            if self.get_token(1)?.is("ORDER") {
+            if self.get_token(1)?.is(Keyword::ORDER) {
                 self.next_token()?; // ( -> ORDER
                 let mut order = self.construct_node(NodeType::XXXByExprs)?;
                 self.next_token()?; // ORDER -> BY
                 order.push_node("by", self.construct_node(NodeType::Keyword)?);
                 self.next_token()?; // BY -> exprs
-                order.push_node_vec("exprs", self.parse_exprs(&vec![], false, true)?);
+                order.push_node_vec("exprs", self.parse_exprs(&[], false, true)?);
                 window.push_node("orderby", order);
             }
-            if self.get_token(1)?.in_(&vec!["RANGE", "ROWS"]) {
+            if self.get_token(1)?.in_(&[Keyword::RANGE, Keyword::ROWS]) {
                 self.next_token()?; // ( -> ROWS, expr -> ROWS
                 let mut frame = self.construct_node(NodeType::WindowFrameClause)?;
-                if self.get_token(1)?.is("BETWEEN") {
+                if self.get_token(1)?.is(Keyword::BETWEEN) {
                     // frame_between
                     self.next_token()?; // ROWS -> BETWEEN
                     frame.push_node("between", self.construct_node(NodeType::Keyword)?);
                     // start
                     self.next_token()?; // BETWEEN -> UNBOUNDED, CURRENT
                     let mut frame_start = Vec::new();
-                    if self.get_token(0)?.in_(&vec!["UNBOUNDED", "CURRENT"]) {
+                    if self
+                        .get_token(0)?
+                        .in_(&[Keyword::UNBOUNDED, Keyword::CURRENT])
+                    {
                         frame_start.push(self.construct_node(NodeType::Keyword)?);
                     } else {
                         frame_start.push(self.parse_expr(usize::MAX, false, false, false, true)?);
@@ -1698,7 +2029,10 @@ impl Parser {
                     // end
                     self.next_token()?; // AND -> UNBOUNDED, CURRENT
                     let mut frame_end = Vec::new();
-                    if self.get_token(0)?.in_(&vec!["UNBOUNDED", "CURRENT"]) {
+                    if self
+                        .get_token(0)?
+                        .in_(&[Keyword::UNBOUNDED, Keyword::CURRENT])
+                    {
                         frame_end.push(self.construct_node(NodeType::Keyword)?);
                     } else {
                         frame_end.push(self.parse_expr(usize::MAX, false, false, false, true)?);
@@ -1708,10 +2042,13 @@ impl Parser {
                     frame.push_node_vec("end", frame_end);
                 } else {
                     // frame_start
-                    if !self.get_token(1)?.is(")") {
+                    if !self.get_token(1)?.is(Symbol::RParen) {
                         self.next_token()?; // ROWS -> UNBOUNDED, CURRENT
                         let mut frame_start = Vec::new();
-                        if self.get_token(0)?.in_(&vec!["UNBOUNDED", "CURRENT"]) {
+                        if self
+                            .get_token(0)?
+                            .in_(&[Keyword::UNBOUNDED, Keyword::CURRENT])
+                        {
                             frame_start.push(self.construct_node(NodeType::Keyword)?);
                         } else {
                             frame_start.push(self.parse_expr(
@@ -1736,12 +2073,12 @@ impl Parser {
             Ok(self.construct_node(NodeType::Identifier)?)
         }
     }
-    fn parse_with_connection_clause(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_with_connection_clause(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut with = self.construct_node(NodeType::KeywordSequence)?;
         self.next_token()?; // -> CONNECTION
         let mut connection = self.construct_node(NodeType::KeywordWithExpr)?;
         self.next_token()?; // -> ident | DEFAULT
-        if self.get_token(0)?.is("DEFAULT") {
+        if self.get_token(0)?.is(Keyword::DEFAULT) {
             connection.push_node("expr", self.construct_node(NodeType::Keyword)?);
         } else {
             connection.push_node("expr", self.parse_identifier()?);
@@ -1749,25 +2086,25 @@ impl Parser {
         with.push_node("next_keyword", connection);
         Ok(with)
     }
-    fn parse_with_clause(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_with_clause(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut with = self.construct_node(NodeType::WithClause)?;
-        if self.get_token(1)?.is("RECURSIVE") {
+        if self.get_token(1)?.is(Keyword::RECURSIVE) {
             self.next_token()?; // -> RECURSIVE
             with.push_node("recursive", self.construct_node(NodeType::Keyword)?);
         }
         let mut queries = Vec::new();
-        while self.get_token(1)?.literal.to_uppercase() != "SELECT"
-            && self.get_token(1)?.literal != "("
-            && self.get_token(1)?.literal != "|>"
-            && self.get_token(1)?.literal.to_uppercase() != "FROM"
+        while !self.get_token(1)?.is(Keyword::SELECT)
+            && !self.get_token(1)?.is(Symbol::LParen)
+            && !self.get_token(1)?.is(Symbol::PipeOperator)
+            && !self.get_token(1)?.is(Keyword::FROM)
         {
             self.next_token()?; // WITH -> ident, ) -> ident
             queries.push(self.parse_cte()?);
         }
         with.push_node_vec("queries", queries);
-        return Ok(with);
+        Ok(with)
     }
-    fn parse_window_clause(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_window_clause(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut window = self.construct_node(NodeType::WindowClause)?;
         let mut window_exprs = Vec::new();
         while self.get_token(1)?.is_identifier() {
@@ -1777,44 +2114,44 @@ impl Parser {
             window_expr.push_node("as", self.construct_node(NodeType::Keyword)?);
             self.next_token()?; // AS -> (, AS -> named_window
             window_expr.push_node("window", self.parse_window_expr()?);
-            if self.get_token(1)?.is(",") {
+            if self.get_token(1)?.is(Symbol::Comma) {
                 self.next_token()?; // -> ,
                 window_expr.push_node("comma", self.construct_node(NodeType::Symbol)?);
             }
             window_exprs.push(window_expr);
         }
         window.push_node_vec("window_exprs", window_exprs);
-        return Ok(window);
+        Ok(window)
     }
-    fn parse_xxxby_exprs(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_xxxby_exprs(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut xxxby = self.construct_node(NodeType::XXXByExprs)?;
         self.next_token()?; // xxx -> BY
         xxxby.push_node("by", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // BY -> expr
-        xxxby.push_node_vec("exprs", self.parse_exprs(&vec![], false, true)?);
+        xxxby.push_node_vec("exprs", self.parse_exprs(&[], false, true)?);
         Ok(xxxby)
     }
-    fn parse_groupby_exprs(&mut self, alias: bool) -> BQ2CSTResult<Node> {
+    fn parse_groupby_exprs(&mut self, alias: bool) -> BQ2CSTResult<Node<'a>> {
         let mut groupby = self.construct_node(NodeType::GroupByExprs)?;
         self.next_token()?; // GROUP -> BY
         groupby.push_node("by", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> ROLLUP | CUBE | GROUPING | ALL | exprs
-        if self.get_token(0)?.in_(&vec!["ROLLUP", "CUBE"]) {
+        if self.get_token(0)?.in_(&[Keyword::ROLLUP, Keyword::CUBE]) {
             groupby.push_node_vec("how", self.parse_n_keywords(1)?);
             self.next_token()?; // BY -> expr
-            groupby.push_node_vec("exprs", self.parse_exprs(&vec![], alias, true)?);
-        } else if self.get_token(0)?.in_(&vec!["GROUPING"]) {
+            groupby.push_node_vec("exprs", self.parse_exprs(&[], alias, true)?);
+        } else if self.get_token(0)?.in_(&[Keyword::GROUPING]) {
             groupby.push_node_vec("how", self.parse_n_keywords(2)?);
             self.next_token()?; // BY -> expr
-            groupby.push_node_vec("exprs", self.parse_exprs(&vec![], alias, true)?);
-        } else if self.get_token(0)?.in_(&vec!["ALL"]) {
+            groupby.push_node_vec("exprs", self.parse_exprs(&[], alias, true)?);
+        } else if self.get_token(0)?.in_(&[Keyword::ALL]) {
             groupby.push_node_vec("how", self.parse_n_keywords(1)?);
         } else {
-            groupby.push_node_vec("exprs", self.parse_exprs(&vec![], alias, true)?);
+            groupby.push_node_vec("exprs", self.parse_exprs(&[], alias, true)?);
         }
         Ok(groupby)
     }
-    fn parse_table_sample_ratio(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_table_sample_ratio(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut group = self.construct_node(NodeType::TableSampleRatio)?;
         self.next_token()?; // -> expr
         group.push_node(
@@ -1827,10 +2164,10 @@ impl Parser {
         group.push_node("rparen", self.construct_node(NodeType::Symbol)?);
         Ok(group)
     }
-    fn parse_pivot_config_clause(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_pivot_config_clause(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut config = self.construct_node(NodeType::PivotConfig)?;
         self.next_token()?; // -> expr
-        config.push_node_vec("exprs", self.parse_exprs(&vec![], true, true)?);
+        config.push_node_vec("exprs", self.parse_exprs(&[], true, true)?);
         self.next_token()?; // -> FOR
         let mut for_ = self.construct_node(NodeType::KeywordWithExpr)?;
         self.next_token()?; // -> expr
@@ -1842,10 +2179,10 @@ impl Parser {
         config.push_node("rparen", self.construct_node(NodeType::Symbol)?);
         Ok(config)
     }
-    fn parse_unpivot_config_clause(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_unpivot_config_clause(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut config = self.construct_node(NodeType::UnpivotConfig)?;
         self.next_token()?; // -> expr
-        if self.get_token(0)?.is("(") {
+        if self.get_token(0)?.is(Symbol::LParen) {
             // in the case of multi column unpivot
             config.push_node("expr", self.parse_grouped_exprs(false)?);
         } else {
@@ -1864,16 +2201,16 @@ impl Parser {
         self.next_token()?; // -> (
         let mut group = self.construct_node(NodeType::GroupedExprs)?;
         let mut exprs = Vec::new();
-        while !self.get_token(1)?.is(")") {
+        while !self.get_token(1)?.is(Symbol::RParen) {
             self.next_token()?; // -> expr
             let mut expr;
-            if self.get_token(0)?.is("(") {
+            if self.get_token(0)?.is(Symbol::LParen) {
                 // in the case of multi column unpivot
                 expr = self.parse_grouped_exprs(false)?;
             } else {
                 expr = self.parse_expr(usize::MAX, false, false, false, true)?;
             }
-            if self.get_token(1)?.is("AS") {
+            if self.get_token(1)?.is(Keyword::AS) {
                 self.next_token()?; // -> AS
                 expr.push_node("as", self.construct_node(NodeType::Keyword)?);
             }
@@ -1884,7 +2221,7 @@ impl Parser {
                     self.parse_expr(usize::MAX, false, false, false, true)?,
                 );
             }
-            if self.get_token(1)?.is(",") {
+            if self.get_token(1)?.is(Symbol::Comma) {
                 self.next_token()?; // -> ,
                 expr.push_node("comma", self.construct_node(NodeType::Symbol)?);
             } else {
@@ -1902,36 +2239,36 @@ impl Parser {
         config.push_node("rparen", self.construct_node(NodeType::Symbol)?);
         Ok(config)
     }
-    fn parse_match_recognize_clause(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_match_recognize_clause(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut match_recognize = self.construct_node(NodeType::MatchRecognizeClause)?;
         self.next_token()?; // -> (
         match_recognize.push_node("config", self.parse_match_recognize_config()?);
         match_recognize = self.push_trailing_alias(match_recognize)?;
         Ok(match_recognize)
     }
-    fn parse_match_recognize_config(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_match_recognize_config(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut config = self.construct_node(NodeType::MatchRecognizeConfig)?;
-        if self.get_token(1)?.is("PARTITION") {
+        if self.get_token(1)?.is(Keyword::PARTITION) {
             self.next_token()?; // -> PARTITION
             config.push_node("partitionby", self.parse_xxxby_exprs()?);
         }
-        if self.get_token(1)?.is("ORDER") {
+        if self.get_token(1)?.is(Keyword::ORDER) {
             self.next_token()?; // -> ORDER
             config.push_node("orderby", self.parse_xxxby_exprs()?);
         }
-        if self.get_token(1)?.is("MEASURES") {
+        if self.get_token(1)?.is(Keyword::MEASURES) {
             self.next_token()?; // -> MEASURES
             let mut measures = self.construct_node(NodeType::KeywordWithExprs)?;
             self.next_token()?; // -> expr
 
             // NOTE: currently trailing "," is not allowed
-            measures.push_node_vec("exprs", self.parse_exprs(&vec![], true, false)?);
+            measures.push_node_vec("exprs", self.parse_exprs(&[], true, false)?);
             config.push_node("measures", measures);
         }
         // NOTE: AFTER is not reserved keyword but it is not confusing. because ...
         // * measures does not allow trailing comma (so after is not consumed as expr)
         // * measures has alias (so after is not consumed as alias)
-        if self.get_token(1)?.is("AFTER") {
+        if self.get_token(1)?.is(Keyword::AFTER) {
             self.next_token()?; // -> AFTER
             let mut after = self.construct_node(NodeType::KeywordSequence)?;
             self.next_token()?; // -> MATCH
@@ -1951,21 +2288,21 @@ impl Parser {
             after.push_node("next_keyword", match_);
             config.push_node("skip_rule", after);
         }
-        if self.get_token(1)?.is("PATTERN") {
+        if self.get_token(1)?.is(Keyword::PATTERN) {
             self.next_token()?; // -> PATTERN
             let pattern = self.parse_pattern_clause()?;
             config.push_node("pattern", pattern);
         }
-        if self.get_token(1)?.is("DEFINE") {
+        if self.get_token(1)?.is(Keyword::DEFINE) {
             self.next_token()?; // -> DEFINE
             let mut define = self.construct_node(NodeType::KeywordWithExprs)?;
             self.next_token()?; // -> expr
 
             // NOTE: currently trailing "," is not allowed
-            define.push_node_vec("exprs", self.parse_exprs(&vec![], true, false)?);
+            define.push_node_vec("exprs", self.parse_exprs(&[], true, false)?);
             config.push_node("define", define);
         };
-        if self.get_token(1)?.is("OPTIONS") {
+        if self.get_token(1)?.is(Keyword::OPTIONS) {
             self.next_token()?; // -> OPTIONS
             config.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
         };
@@ -1973,11 +2310,11 @@ impl Parser {
         config.push_node("rparen", self.construct_node(NodeType::Symbol)?);
         Ok(config)
     }
-    fn parse_by_name_clause(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_by_name_clause(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut by = self.construct_node(NodeType::KeywordSequence)?;
         self.next_token()?; // -> NAME
         let mut name: Node;
-        if self.get_token(1)?.is("ON") {
+        if self.get_token(1)?.is(Keyword::ON) {
             name = self.construct_node(NodeType::KeywordSequence)?;
             self.next_token()?; // -> ON
             let mut on = self.construct_node(NodeType::KeywordWithGroupedXXX)?;
@@ -1992,22 +2329,22 @@ impl Parser {
         by.push_node("next_keyword", name);
         Ok(by)
     }
-    fn parse_pattern_clause(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_pattern_clause(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut pattern = self.construct_node(NodeType::PatternClause)?;
         self.next_token()?; // -> (
         pattern.push_node("pattern", self.parse_pattern()?);
         Ok(pattern)
     }
-    fn parse_grouped_pattern(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_grouped_pattern(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut group = self.construct_node(NodeType::GroupedPattern)?;
         let mut patterns = Vec::new();
-        while !self.get_token(1)?.is(")") {
+        while !self.get_token(1)?.is(Symbol::RParen) {
             self.next_token()?; // -> symbol | `|` | (
-            if self.get_token(0)?.is("|") {
+            if self.get_token(0)?.is(Symbol::Pipe) {
                 let mut or = self.construct_node(NodeType::OrPattern)?;
                 or.push_node_vec("left", patterns);
                 let mut right_patterns = Vec::new();
-                while !self.get_token(1)?.in_(&vec!["|", ")"]) {
+                while !self.get_token(1)?.in_(&[Symbol::Pipe, Symbol::RParen]) {
                     self.next_token()?; // -> right
                     right_patterns.push(self.parse_pattern()?);
                 }
@@ -2022,10 +2359,10 @@ impl Parser {
         group.push_node("rparen", self.construct_node(NodeType::Symbol)?);
         Ok(group)
     }
-    fn parse_pattern(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_pattern(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut pattern;
         let curr_token = self.get_token(0)?;
-        if curr_token.is("(") {
+        if curr_token.is(Symbol::LParen) {
             pattern = self.parse_grouped_pattern()?;
         } else {
             pattern = self.construct_node(NodeType::Pattern)?; // ident | ^ | $
@@ -2033,12 +2370,14 @@ impl Parser {
 
         let mut suffixes = Vec::new();
         loop {
-            match self.get_token(1)?.literal.as_str() {
-                "?" | "+" | "*" => {
+            match self.get_token(1)?.kind {
+                TokenKind::Symbol(Symbol::Question)
+                | TokenKind::Symbol(Symbol::Plus)
+                | TokenKind::Symbol(Symbol::Asterisk) => {
                     self.next_token()?;
                     suffixes.push(self.construct_node(NodeType::Symbol)?);
                 }
-                "{" => {
+                TokenKind::Symbol(Symbol::LBrace) => {
                     self.next_token()?; // -> {
                     suffixes.push(self.parse_quantifier()?);
                 }
@@ -2050,13 +2389,13 @@ impl Parser {
         pattern.push_node_vec("suffixes", suffixes);
         Ok(pattern)
     }
-    fn parse_quantifier(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_quantifier(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut quantifier = self.construct_node(NodeType::PatternQuantifier)?;
-        if !self.get_token(1)?.is(",") {
+        if !self.get_token(1)?.is(Symbol::Comma) {
             self.next_token()?; // -> m
             quantifier.push_node("min", self.construct_node(NodeType::NumericLiteral)?);
         }
-        if self.get_token(1)?.is(",") {
+        if self.get_token(1)?.is(Symbol::Comma) {
             self.next_token()?; // -> ,
             quantifier.push_node("comma", self.construct_node(NodeType::Symbol)?);
             self.next_token()?; // -> n
@@ -2066,16 +2405,16 @@ impl Parser {
         quantifier.push_node("rbrace", self.construct_node(NodeType::Symbol)?);
         Ok(quantifier)
     }
-    fn parse_corresponding_clause(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_corresponding_clause(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut strict_exists = false;
         let mut strict = Node::empty(NodeType::Unknown);
-        if self.get_token(0)?.is("STRICT") {
+        if self.get_token(0)?.is(Keyword::STRICT) {
             strict_exists = true;
             strict = self.construct_node(NodeType::KeywordSequence)?;
             self.next_token()?; // ->  CORRESPONDING
         }
         let mut corresponding = self.construct_node(NodeType::Keyword)?;
-        if self.get_token(1)?.is("BY") {
+        if self.get_token(1)?.is(Keyword::BY) {
             corresponding.node_type = NodeType::KeywordSequence;
             self.next_token()?; // ->  BY
             let mut by = self.construct_node(NodeType::KeywordWithGroupedXXX)?;
@@ -2089,8 +2428,8 @@ impl Parser {
         }
         Ok(corresponding)
     }
-    fn push_trailing_alias(&mut self, mut node: Node) -> BQ2CSTResult<Node> {
-        if self.get_token(1)?.is("AS") {
+    fn push_trailing_alias(&mut self, mut node: Node<'a>) -> BQ2CSTResult<Node<'a>> {
+        if self.get_token(1)?.is(Keyword::AS) {
             self.next_token()?; // -> AS
             node.push_node("as", self.construct_node(NodeType::Keyword)?);
             self.next_token()?; // AS -> ident
@@ -2102,43 +2441,50 @@ impl Parser {
         Ok(node)
     }
     // ----- SELECT statement -----
-    fn parse_select_statement(&mut self, semicolon: bool, root: bool) -> BQ2CSTResult<Node> {
-        if self.get_token(0)?.literal.to_uppercase() == "(" {
+    fn parse_select_statement(&mut self, semicolon: bool, root: bool) -> BQ2CSTResult<Node<'a>> {
+        self.nest(|p| p.parse_select_statement_inner(semicolon, root))
+    }
+    fn parse_select_statement_inner(
+        &mut self,
+        semicolon: bool,
+        root: bool,
+    ) -> BQ2CSTResult<Node<'a>> {
+        if self.get_token(0)?.is(Symbol::LParen) {
             let mut node = self.construct_node(NodeType::GroupedStatement)?;
             self.next_token()?; // ( -> SELECT
-            if self.get_token(0)?.is("FROM") {
+            if self.get_token(0)?.is(Keyword::FROM) {
                 node.push_node("stmt", self.parse_from_statement()?);
             } else {
                 node.push_node("stmt", self.parse_select_statement(false, true)?);
             }
             self.next_token()?; // stmt -> )
-            if !self.get_token(0)?.is(")") {
+            if !self.get_token(0)?.is(Symbol::RParen) {
                 return Err(BQ2CSTError::from_token(
                     self.get_token(0)?,
                     "expected )".to_string(),
                 ));
             }
             node.push_node("rparen", self.construct_node(NodeType::Symbol)?);
-            while self.get_token(1)?.in_(&vec![
-                "UNION",
-                "INTERSECT",
-                "EXCEPT",
-                "INNER",
-                "FULL",
-                "LEFT",
-                "OUTER",
+            while self.get_token(1)?.in_(&[
+                Keyword::UNION,
+                Keyword::INTERSECT,
+                Keyword::EXCEPT,
+                Keyword::INNER,
+                Keyword::FULL,
+                Keyword::LEFT,
+                Keyword::OUTER,
             ]) && root
             {
                 self.next_token()?;
                 node = self.parse_set_operator(node)?;
             }
             // ORDER BY
-            if self.get_token(1)?.is("ORDER") && root {
+            if self.get_token(1)?.is(Keyword::ORDER) && root {
                 self.next_token()?; // -> ORDER
                 node.push_node("orderby", self.parse_xxxby_exprs()?);
             }
             // LIMIT
-            if self.get_token(1)?.is("LIMIT") && root {
+            if self.get_token(1)?.is(Keyword::LIMIT) && root {
                 self.next_token()?; // -> LIMIT
                 let mut limit = self.construct_node(NodeType::LimitClause)?;
                 self.next_token()?; // -> expr
@@ -2146,7 +2492,7 @@ impl Parser {
                     "expr",
                     self.parse_expr(usize::MAX, false, false, false, true)?,
                 );
-                if self.get_token(1)?.literal.to_uppercase() == "OFFSET" {
+                if self.get_token(1)?.is(Keyword::OFFSET) {
                     self.next_token()?; // expr -> OFFSET
                     let mut offset = self.construct_node(NodeType::KeywordWithExpr)?;
                     self.next_token()?; // OFFSET -> expr
@@ -2158,19 +2504,19 @@ impl Parser {
                 }
                 node.push_node("limit", limit);
             }
-            if self.get_token(1)?.is(";") && semicolon && root {
+            if self.get_token(1)?.is(Symbol::Semicolon) && semicolon && root {
                 self.next_token()?; // expr -> ;
                 node.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
-            } else if self.get_token(1)?.is("|>") && root {
+            } else if self.get_token(1)?.is(Symbol::PipeOperator) && root {
                 self.next_token()?; // -> |>
                 return self.parse_pipe_statement(node);
             }
             return Ok(node);
         }
-        if self.get_token(0)?.literal.to_uppercase() == "WITH" {
+        if self.get_token(0)?.is(Keyword::WITH) {
             let with = self.parse_with_clause()?;
             self.next_token()?; // -> SELECT | '(' | FROM
-            let mut node = if self.get_token(0)?.is("FROM") {
+            let mut node = if self.get_token(0)?.is(Keyword::FROM) {
                 self.parse_from_statement()?
             } else {
                 self.parse_select_statement(semicolon, true)?
@@ -2182,7 +2528,7 @@ impl Parser {
         let mut node = self.construct_node(NodeType::SelectStatement)?;
 
         // WITH DIFFERENTIAL_PRIVACY
-        if self.get_token(1)?.is("WITH") && !self.get_token(2)?.is("(") {
+        if self.get_token(1)?.is(Keyword::WITH) && !self.get_token(2)?.is(Symbol::LParen) {
             self.next_token()?; // -> WITH
             let mut with = self.construct_node(NodeType::DifferentialPrivacyClause)?;
             self.next_token()?; // -> differential_privacy
@@ -2190,7 +2536,7 @@ impl Parser {
                 "differential_privacy",
                 self.construct_node(NodeType::Keyword)?,
             );
-            if self.get_token(1)?.is("OPTIONS") {
+            if self.get_token(1)?.is(Keyword::OPTIONS) {
                 self.next_token()?; // -> OPTIONS
                 with.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
             };
@@ -2198,13 +2544,13 @@ impl Parser {
         }
 
         // DISTINCT
-        if self.get_token(1)?.in_(&vec!["ALL", "DISTINCT"]) {
+        if self.get_token(1)?.in_(&[Keyword::ALL, Keyword::DISTINCT]) {
             self.next_token()?; // select -> all, distinct
             node.push_node("distinct_or_all", self.construct_node(NodeType::Keyword)?);
         }
 
         // AS STRUCT, VALUE
-        if self.get_token(1)?.literal.to_uppercase() == "AS" {
+        if self.get_token(1)?.is(Keyword::AS) {
             self.next_token()?; // SELECT -> AS
             let as_ = self.construct_node(NodeType::Keyword)?;
             self.next_token()?; // AS -> STRUCT, VALUE
@@ -2220,31 +2566,31 @@ impl Parser {
         node.push_node_vec(
             "exprs",
             self.parse_exprs(
-                &vec![
-                    "FROM",
-                    "WHERE",
-                    "GROUP",
-                    "HAVING",
-                    "QUALIFY",
-                    "WINDOW",
-                    "ORDER",
-                    "LIMIT",
-                    "UNION",
-                    "INTERSECT",
-                    "EXCEPT",
-                    "INNER",
-                    "FULL",
-                    "LEFT",
-                    "OUTER",
-                    ";",
-                    ")",
+                &[
+                    TokenKind::Keyword(Keyword::FROM),
+                    TokenKind::Keyword(Keyword::WHERE),
+                    TokenKind::Keyword(Keyword::GROUP),
+                    TokenKind::Keyword(Keyword::HAVING),
+                    TokenKind::Keyword(Keyword::QUALIFY),
+                    TokenKind::Keyword(Keyword::WINDOW),
+                    TokenKind::Keyword(Keyword::ORDER),
+                    TokenKind::Keyword(Keyword::LIMIT),
+                    TokenKind::Keyword(Keyword::UNION),
+                    TokenKind::Keyword(Keyword::INTERSECT),
+                    TokenKind::Keyword(Keyword::EXCEPT),
+                    TokenKind::Keyword(Keyword::INNER),
+                    TokenKind::Keyword(Keyword::FULL),
+                    TokenKind::Keyword(Keyword::LEFT),
+                    TokenKind::Keyword(Keyword::OUTER),
+                    TokenKind::Symbol(Symbol::Semicolon),
+                    TokenKind::Symbol(Symbol::RParen),
                 ],
                 true,
                 true,
             )?,
         );
         // FROM
-        if self.get_token(1)?.is("FROM") {
+        if self.get_token(1)?.is(Keyword::FROM) {
             self.next_token()?; // expr -> FROM
             let mut from = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // FROM -> table
@@ -2252,7 +2598,7 @@ impl Parser {
             node.push_node("from", from);
         }
         // WHERE
-        if self.get_token(1)?.is("WHERE") {
+        if self.get_token(1)?.is(Keyword::WHERE) {
             self.next_token()?; // expr -> WHERE
             let mut where_ = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // WHERE -> expr
@@ -2263,12 +2609,12 @@ impl Parser {
             node.push_node("where", where_);
         }
         // GROUP BY
-        if self.get_token(1)?.is("GROUP") {
+        if self.get_token(1)?.is(Keyword::GROUP) {
             self.next_token()?; // expr -> GROUP
             node.push_node("groupby", self.parse_groupby_exprs(false)?);
         }
         // HAVING
-        if self.get_token(1)?.is("HAVING") {
+        if self.get_token(1)?.is(Keyword::HAVING) {
             self.next_token()?; // expr -> HAVING
             let mut having = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // HAVING -> expr
@@ -2279,7 +2625,7 @@ impl Parser {
             node.push_node("having", having);
         }
         // QUALIFY
-        if self.get_token(1)?.is("QUALIFY") {
+        if self.get_token(1)?.is(Keyword::QUALIFY) {
             self.next_token()?; // -> QUALIFY
             let mut qualify = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // -> expr
@@ -2290,17 +2636,17 @@ impl Parser {
             node.push_node("qualify", qualify);
         }
         // WINDOW
-        if self.get_token(1)?.is("WINDOW") {
+        if self.get_token(1)?.is(Keyword::WINDOW) {
             self.next_token()?; // -> WINDOW
             node.push_node("window", self.parse_window_clause()?);
         }
         // ORDER BY
-        if self.get_token(1)?.is("ORDER") {
+        if self.get_token(1)?.is(Keyword::ORDER) {
             self.next_token()?; // expr -> ORDER
             node.push_node("orderby", self.parse_xxxby_exprs()?);
         }
         // LIMIT
-        if self.get_token(1)?.is("LIMIT") {
+        if self.get_token(1)?.is(Keyword::LIMIT) {
             self.next_token()?; // expr -> LIMIT
             let mut limit = self.construct_node(NodeType::LimitClause)?;
             self.next_token()?; // LIMIT -> expr
@@ -2308,7 +2654,7 @@ impl Parser {
                 "expr",
                 self.parse_expr(usize::MAX, false, false, false, true)?,
             );
-            if self.get_token(1)?.literal.to_uppercase() == "OFFSET" {
+            if self.get_token(1)?.is(Keyword::OFFSET) {
                 self.next_token()?; // expr -> OFFSET
                 let mut offset = self.construct_node(NodeType::KeywordWithExpr)?;
                 self.next_token()?; // OFFSET -> expr
@@ -2321,111 +2667,133 @@ impl Parser {
             node.push_node("limit", limit);
         }
         // UNION
-        while self.get_token(1)?.in_(&vec![
-            "UNION",
-            "INTERSECT",
-            "EXCEPT",
-            "INNER",
-            "FULL",
-            "LEFT",
-            "OUTER",
+        while self.get_token(1)?.in_(&[
+            Keyword::UNION,
+            Keyword::INTERSECT,
+            Keyword::EXCEPT,
+            Keyword::INNER,
+            Keyword::FULL,
+            Keyword::LEFT,
+            Keyword::OUTER,
         ]) && root
         {
             self.next_token()?;
             node = self.parse_set_operator(node)?;
         }
         // ;
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // expr -> ;
             node.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
-        } else if self.get_token(1)?.is("|>") && root {
+        } else if self.get_token(1)?.is(Symbol::PipeOperator) && root {
             self.next_token()?; // -> |>
             return self.parse_pipe_statement(node);
         }
         Ok(node)
     }
-    fn parse_from_statement(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_from_statement(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut from = self.construct_node(NodeType::FromStatement)?;
         self.next_token()?; // -> ident
         from.push_node("expr", self.parse_table(true)?);
 
-        if self.get_token(1)?.is("|>") {
+        if self.get_token(1)?.is(Symbol::PipeOperator) {
             self.next_token()?; // -> |>
             return self.parse_pipe_statement(from);
         }
 
-        if self.get_token(1)?.is(";") {
+        if self.get_token(1)?.is(Symbol::Semicolon) {
             self.next_token()?; // -> ;
             from.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
         };
         Ok(from)
     }
-    fn parse_pipe_statement(&mut self, left: Node) -> BQ2CSTResult<Node> {
-        let mut pipe = self.construct_node(NodeType::PipeStatement)?;
-        pipe.push_node("left", left);
-        self.next_token()?; // -> SELECT | LIMIT | ...
+    fn parse_pipe_statement(&mut self, left: Node<'a>) -> BQ2CSTResult<Node<'a>> {
+        // loop instead of recursion, because pipe operators may be chained many times
+        let mut pipe = left;
+        loop {
+            self.deepen()?;
+            let mut node = self.construct_node(NodeType::PipeStatement)?;
+            node.push_node("left", pipe);
+            self.next_token()?; // -> SELECT | LIMIT | ...
 
-        let operator = match self.get_token(0)?.literal.to_uppercase().as_str() {
-            "SET" | "DROP" | "RENAME" | "AS" | "WHERE" | "CALL" => {
-                self.parse_base_pipe_operator(false)?
-            }
-            "EXTEND" => self.parse_extend_pipe_operator()?,
-            "ORDER" => self.parse_base_pipe_operator(true)?,
-            "SELECT" => self.parse_select_pipe_operator()?,
-            "LIMIT" => self.parse_limit_pipe_operator()?,
-            "AGGREGATE" => self.parse_aggregate_pipe_operator()?,
-            "UNION" | "INTERSECT" | "EXCEPT" => self.parse_union_pipe_operator()?,
-            // "," is not abalable for cross join
-            "JOIN" => self.parse_join_pipe_operator()?,
-            "INNER" | "FULL" | "LEFT" | "RIGHT" | "CROSS" | "OUTER" => {
-                if self.get_token(1)?.is("JOIN") || self.get_token(2)?.is("JOIN") {
-                    self.parse_join_pipe_operator()?
-                } else {
-                    self.parse_union_pipe_operator()?
+            let operator = match self.get_token(0)?.kind {
+                TokenKind::Keyword(Keyword::SET)
+                | TokenKind::Keyword(Keyword::DROP)
+                | TokenKind::Keyword(Keyword::RENAME)
+                | TokenKind::Keyword(Keyword::AS)
+                | TokenKind::Keyword(Keyword::WHERE)
+                | TokenKind::Keyword(Keyword::CALL) => self.parse_base_pipe_operator(false)?,
+                TokenKind::Keyword(Keyword::EXTEND) => self.parse_extend_pipe_operator()?,
+                TokenKind::Keyword(Keyword::ORDER) => self.parse_base_pipe_operator(true)?,
+                TokenKind::Keyword(Keyword::SELECT) => self.parse_select_pipe_operator()?,
+                TokenKind::Keyword(Keyword::LIMIT) => self.parse_limit_pipe_operator()?,
+                TokenKind::Keyword(Keyword::AGGREGATE) => self.parse_aggregate_pipe_operator()?,
+                TokenKind::Keyword(Keyword::UNION)
+                | TokenKind::Keyword(Keyword::INTERSECT)
+                | TokenKind::Keyword(Keyword::EXCEPT) => self.parse_union_pipe_operator()?,
+                // "," is not abalable for cross join
+                TokenKind::Keyword(Keyword::JOIN) => self.parse_join_pipe_operator()?,
+                TokenKind::Keyword(Keyword::INNER)
+                | TokenKind::Keyword(Keyword::FULL)
+                | TokenKind::Keyword(Keyword::LEFT)
+                | TokenKind::Keyword(Keyword::RIGHT)
+                | TokenKind::Keyword(Keyword::CROSS)
+                | TokenKind::Keyword(Keyword::OUTER) => {
+                    if self.get_token(1)?.is(Keyword::JOIN) || self.get_token(2)?.is(Keyword::JOIN)
+                    {
+                        self.parse_join_pipe_operator()?
+                    } else {
+                        self.parse_union_pipe_operator()?
+                    }
                 }
-            }
-            "TABLESAMPLE" => self.parse_tablesample_pipe_operator()?,
-            "PIVOT" => self.parse_pivot_pipe_operator()?,
-            "UNPIVOT" => self.parse_unpivot_pipe_operator()?,
-            "WITH" => self.parse_with_pipe_operator()?,
-            "MATCH_RECOGNIZE" => self.parse_match_recognize_pipe_operator()?,
-            "DISTINCT" => self.construct_node(NodeType::Keyword)?,
-            _ => {
-                return Err(BQ2CSTError::from_token(
-                    self.get_token(0)?,
-                    format!("Expected pipe operator but got: {:?}", self.get_token(0)?),
-                ))
-            }
-        };
-        pipe.push_node("right", operator);
+                TokenKind::Keyword(Keyword::TABLESAMPLE) => {
+                    self.parse_tablesample_pipe_operator()?
+                }
+                TokenKind::Keyword(Keyword::PIVOT) => self.parse_pivot_pipe_operator()?,
+                TokenKind::Keyword(Keyword::UNPIVOT) => self.parse_unpivot_pipe_operator()?,
+                TokenKind::Keyword(Keyword::WITH) => self.parse_with_pipe_operator()?,
+                TokenKind::Keyword(Keyword::MATCH_RECOGNIZE) => {
+                    self.parse_match_recognize_pipe_operator()?
+                }
+                TokenKind::Keyword(Keyword::DISTINCT) => self.construct_node(NodeType::Keyword)?,
+                _ => {
+                    return Err(BQ2CSTError::from_token(
+                        self.get_token(0)?,
+                        format!("Expected pipe operator but got: {:?}", self.get_token(0)?),
+                    ))
+                }
+            };
+            node.push_node("right", operator);
+            pipe = node;
 
-        if self.get_token(1)?.is("|>") {
-            self.next_token()?; // -> |>
-            return self.parse_pipe_statement(pipe);
+            if self.get_token(1)?.is(Symbol::PipeOperator) {
+                self.next_token()?; // -> |>
+            } else {
+                break;
+            }
         }
 
-        if self.get_token(1)?.is(";") {
+        if self.get_token(1)?.is(Symbol::Semicolon) {
             self.next_token()?; // -> ;
             pipe.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
         };
         Ok(pipe)
     }
-    fn parse_select_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_select_pipe_operator(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut operator = self.construct_node(NodeType::SelectPipeOperator)?;
 
         // WITH DIFFERENTIAL_PRIVACY seems not supported
         let mut keywords: Vec<Node> = vec![];
-        if self.get_token(1)?.in_(&vec!["ALL", "DISTINCT"]) {
+        if self.get_token(1)?.in_(&[Keyword::ALL, Keyword::DISTINCT]) {
             self.next_token()?; // -> ALL | DISTINCT
             keywords.push(self.construct_node(NodeType::Keyword)?);
         }
-        if self.get_token(1)?.is("AS") {
+        if self.get_token(1)?.is(Keyword::AS) {
             self.next_token()?; // -> AS
             keywords.push(self.construct_node(NodeType::Keyword)?);
             self.next_token()?; // -> STRUCT | VALUE
             keywords.push(self.construct_node(NodeType::Keyword)?);
         }
-        if 0 < keywords.len() {
+        if !keywords.is_empty() {
             let mut temp = keywords.pop().unwrap();
             while let Some(mut kw) = keywords.pop() {
                 kw.node_type = NodeType::KeywordSequence;
@@ -2435,9 +2803,17 @@ impl Parser {
             operator.push_node("keywords", temp)
         }
         self.next_token()?; // -> expr
-        let exprs = self.parse_exprs(&vec![";", "WINDOW", "|>"], true, true)?;
+        let exprs = self.parse_exprs(
+            &[
+                TokenKind::Symbol(Symbol::Semicolon),
+                TokenKind::Keyword(Keyword::WINDOW),
+                TokenKind::Symbol(Symbol::PipeOperator),
+            ],
+            true,
+            true,
+        )?;
         operator.push_node_vec("exprs", exprs);
-        if self.get_token(1)?.is("WINDOW") {
+        if self.get_token(1)?.is(Keyword::WINDOW) {
             self.next_token()?; // -> WINDOW
             let window = self.parse_window_clause()?;
             operator.push_node("window", window);
@@ -2445,24 +2821,40 @@ impl Parser {
 
         Ok(operator)
     }
-    fn parse_extend_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_extend_pipe_operator(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut operator = self.construct_node(NodeType::ExtendPipeOperator)?;
         self.next_token()?; // -> expr
-        let exprs = self.parse_exprs(&vec!["|>", ";", "WINDOW"], true, false)?;
+        let exprs = self.parse_exprs(
+            &[
+                TokenKind::Symbol(Symbol::PipeOperator),
+                TokenKind::Symbol(Symbol::Semicolon),
+                TokenKind::Keyword(Keyword::WINDOW),
+            ],
+            true,
+            false,
+        )?;
         operator.push_node_vec("exprs", exprs);
-        if self.get_token(1)?.is("WINDOW") {
+        if self.get_token(1)?.is(Keyword::WINDOW) {
             self.next_token()?; // -> WINDOW
             let window = self.parse_window_clause()?;
             operator.push_node("window", window);
         }
         Ok(operator)
     }
-    fn parse_limit_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_limit_pipe_operator(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut operator = self.construct_node(NodeType::LimitPipeOperator)?;
         self.next_token()?; // -> expr
-        let exprs = self.parse_exprs(&vec!["|>", ";", "OFFSET"], false, true)?; // if alias is true, offset is handled as alias
+        let exprs = self.parse_exprs(
+            &[
+                TokenKind::Symbol(Symbol::PipeOperator),
+                TokenKind::Symbol(Symbol::Semicolon),
+                TokenKind::Keyword(Keyword::OFFSET),
+            ],
+            false,
+            true,
+        )?; // if alias is true, offset is handled as alias
         operator.push_node_vec("exprs", exprs);
-        if self.get_token(1)?.is("OFFSET") {
+        if self.get_token(1)?.is(Keyword::OFFSET) {
             self.next_token()?; // -> OFFSET
             let mut offset = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // -> expr
@@ -2474,15 +2866,23 @@ impl Parser {
         }
         Ok(operator)
     }
-    fn parse_aggregate_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_aggregate_pipe_operator(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut operator = self.construct_node(NodeType::AggregatePipeOperator)?;
         self.next_token()?; // -> expr
-        let exprs = self.parse_exprs(&vec!["|>", ";", "GROUP"], true, true)?;
+        let exprs = self.parse_exprs(
+            &[
+                TokenKind::Symbol(Symbol::PipeOperator),
+                TokenKind::Symbol(Symbol::Semicolon),
+                TokenKind::Keyword(Keyword::GROUP),
+            ],
+            true,
+            true,
+        )?;
         operator.push_node_vec("exprs", exprs);
-        if self.get_token(1)?.is("GROUP") {
+        if self.get_token(1)?.is(Keyword::GROUP) {
             self.next_token()?; // expr -> GROUP
             let mut group = self.construct_node(NodeType::KeywordSequence)?;
-            if self.get_token(1)?.is("AND") {
+            if self.get_token(1)?.is(Keyword::AND) {
                 self.next_token()?; // AND
                 let mut and = self.construct_node(NodeType::KeywordSequence)?;
                 self.next_token()?; // -> ORDER
@@ -2490,7 +2890,17 @@ impl Parser {
                 self.next_token()?; // -> BY
                 let mut by = self.construct_node(NodeType::KeywordWithExprs)?;
                 self.next_token()?; // -> exprs
-                by.push_node_vec("exprs", self.parse_exprs(&vec!["|>", ";"], true, true)?);
+                by.push_node_vec(
+                    "exprs",
+                    self.parse_exprs(
+                        &[
+                            TokenKind::Symbol(Symbol::PipeOperator),
+                            TokenKind::Symbol(Symbol::Semicolon),
+                        ],
+                        true,
+                        true,
+                    )?,
+                );
                 order.push_node("next_keyword", by);
                 and.push_node("next_keyword", order);
                 group.push_node("next_keyword", and);
@@ -2498,7 +2908,17 @@ impl Parser {
                 self.next_token()?; // -> BY
                 let mut by = self.construct_node(NodeType::KeywordWithExprs)?;
                 self.next_token()?; // -> exprs
-                by.push_node_vec("exprs", self.parse_exprs(&vec!["|>", ";"], true, true)?);
+                by.push_node_vec(
+                    "exprs",
+                    self.parse_exprs(
+                        &[
+                            TokenKind::Symbol(Symbol::PipeOperator),
+                            TokenKind::Symbol(Symbol::Semicolon),
+                        ],
+                        true,
+                        true,
+                    )?,
+                );
                 group.push_node("next_keyword", by);
             }
             operator.push_node("group_and_order_by", group);
@@ -2506,17 +2926,17 @@ impl Parser {
         Ok(operator)
     }
     // INTERSECT and EXCEPT are also supported
-    fn parse_union_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_union_pipe_operator(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut operator: Node;
 
         // NOTE:
         // when you modify here, also modify parse_set_operator()
         if self
             .get_token(0)?
-            .in_(&vec!["INNER", "FULL", "LEFT", "OUTER"])
+            .in_(&[Keyword::INNER, Keyword::FULL, Keyword::LEFT, Keyword::OUTER])
         {
             let mut method;
-            if self.get_token(1)?.is("OUTER") {
+            if self.get_token(1)?.is(Keyword::OUTER) {
                 method = self.construct_node(NodeType::KeywordSequence)?;
                 self.next_token()?; // -> OUTER
                 let outer = self.construct_node(NodeType::Keyword)?;
@@ -2534,27 +2954,41 @@ impl Parser {
 
         self.next_token()?; // -> ALL | DISTINCT
         operator.push_node("keywords", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("BY") {
+        if self.get_token(1)?.is(Keyword::BY) {
             self.next_token()?; // -> BY
             operator.push_node("by", self.parse_by_name_clause()?);
-        } else if self.get_token(1)?.in_(&vec!["STRICT", "CORRESPONDING"]) {
+        } else if self
+            .get_token(1)?
+            .in_(&[Keyword::STRICT, Keyword::CORRESPONDING])
+        {
             self.next_token()?; // -> STRICT | CORRESPONDING
             operator.push_node("corresponding", self.parse_corresponding_clause()?);
         }
         self.next_token()?; // -> expr
-        let exprs = self.parse_exprs(&vec!["|>", ";"], true, true)?;
+        let exprs = self.parse_exprs(
+            &[
+                TokenKind::Symbol(Symbol::PipeOperator),
+                TokenKind::Symbol(Symbol::Semicolon),
+            ],
+            true,
+            true,
+        )?;
         operator.push_node_vec("exprs", exprs);
         Ok(operator)
     }
-    fn parse_join_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_join_pipe_operator(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut operator: Node;
 
-        if self
-            .get_token(0)?
-            .in_(&vec!["INNER", "FULL", "LEFT", "RIGHT", "CROSS", "OUTER"])
-        {
+        if self.get_token(0)?.in_(&[
+            Keyword::INNER,
+            Keyword::FULL,
+            Keyword::LEFT,
+            Keyword::RIGHT,
+            Keyword::CROSS,
+            Keyword::OUTER,
+        ]) {
             let mut method;
-            if self.get_token(1)?.is("OUTER") {
+            if self.get_token(1)?.is(Keyword::OUTER) {
                 method = self.construct_node(NodeType::KeywordSequence)?;
                 self.next_token()?; // -> OUTER
                 let outer = self.construct_node(NodeType::Keyword)?;
@@ -2572,7 +3006,7 @@ impl Parser {
 
         self.next_token()?; // -> table
         operator.push_node_vec("exprs", vec![self.parse_table(false)?]);
-        if self.get_token(1)?.is("on") {
+        if self.get_token(1)?.is(Keyword::ON) {
             self.next_token()?; // `table` -> ON
             let mut on = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // ON -> expr
@@ -2581,7 +3015,7 @@ impl Parser {
                 self.parse_expr(usize::MAX, false, false, false, true)?,
             );
             operator.push_node("on", on);
-        } else if self.get_token(1)?.is("using") {
+        } else if self.get_token(1)?.is(Keyword::USING) {
             self.next_token()?; // -> USING
             operator.push_node(
                 "using",
@@ -2590,7 +3024,7 @@ impl Parser {
         }
         Ok(operator)
     }
-    fn parse_tablesample_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_tablesample_pipe_operator(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut operator = self.construct_node(NodeType::TableSamplePipeOperator)?;
         self.next_token()?; // -> SYSTEM
         operator.push_node("keywords", self.construct_node(NodeType::Keyword)?);
@@ -2599,16 +3033,19 @@ impl Parser {
         operator.push_node("group", self.parse_table_sample_ratio()?);
         Ok(operator)
     }
-    fn parse_pivot_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_pivot_pipe_operator(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut operator = self.construct_node(NodeType::PivotPipeOperator)?;
         self.next_token()?; // -> (
         operator.push_node("config", self.parse_pivot_config_clause()?);
         operator = self.push_trailing_alias(operator)?;
         Ok(operator)
     }
-    fn parse_unpivot_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_unpivot_pipe_operator(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut operator = self.construct_node(NodeType::UnpivotPipeOperator)?;
-        if self.get_token(1)?.in_(&vec!["INCLUDE", "EXCLUDE"]) {
+        if self
+            .get_token(1)?
+            .in_(&[Keyword::INCLUDE, Keyword::EXCLUDE])
+        {
             self.next_token()?; // -> INCLUDE | EXCLUDE
             let mut include_or_exclude = self.construct_node(NodeType::KeywordSequence)?;
             self.next_token()?; // -> NULLS
@@ -2620,17 +3057,17 @@ impl Parser {
         operator = self.push_trailing_alias(operator)?;
         Ok(operator)
     }
-    fn parse_with_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_with_pipe_operator(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut operator = self.parse_with_clause()?;
         operator.node_type = NodeType::WithPipeOperator;
         Ok(operator)
     }
-    fn parse_match_recognize_pipe_operator(&mut self) -> BQ2CSTResult<Node> {
+    fn parse_match_recognize_pipe_operator(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut operator = self.parse_match_recognize_clause()?;
         operator.node_type = NodeType::MatchRecognizePipeOperator;
         Ok(operator)
     }
-    fn parse_base_pipe_operator(&mut self, keywords: bool) -> BQ2CSTResult<Node> {
+    fn parse_base_pipe_operator(&mut self, keywords: bool) -> BQ2CSTResult<Node<'a>> {
         let mut operator = self.construct_node(NodeType::BasePipeOperator)?;
         // NOTE: for now, single keyword is only allowed
         if keywords {
@@ -2638,34 +3075,45 @@ impl Parser {
             operator.push_node("keywords", self.construct_node(NodeType::Keyword)?);
         }
         self.next_token()?; // -> expr
-        let exprs = self.parse_exprs(&vec!["|>", ";"], true, true)?;
+        let exprs = self.parse_exprs(
+            &[
+                TokenKind::Symbol(Symbol::PipeOperator),
+                TokenKind::Symbol(Symbol::Semicolon),
+            ],
+            true,
+            true,
+        )?;
         operator.push_node_vec("exprs", exprs);
         Ok(operator)
     }
     // ----- DML -----
-    fn parse_insert_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_insert_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut insert = self.construct_node(NodeType::InsertStatement)?;
-        if self.get_token(1)?.is("INTO") {
+        if self.get_token(1)?.is(Keyword::INTO) {
             self.next_token()?; // INSERT -> INTO
             insert.push_node("into", self.construct_node(NodeType::Keyword)?);
         }
-        if !self.get_token(1)?.in_(&vec!["(", "VALUES", "ROW"]) {
+        if !self.get_token(1)?.in_(&[
+            TokenKind::Symbol(Symbol::LParen),
+            TokenKind::Keyword(Keyword::VALUES),
+            TokenKind::Keyword(Keyword::ROW),
+        ]) {
             // identifier does not appear when called by parse_merge_statement()
             self.next_token()?; // INSERT -> identifier
             insert.push_node("target_name", self.parse_identifier()?);
         }
-        if self.get_token(1)?.is("(") {
+        if self.get_token(1)?.is(Symbol::LParen) {
             self.next_token()?; // identifier -> (
             insert.push_node("columns", self.parse_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is("VALUES") {
+        if self.get_token(1)?.is(Keyword::VALUES) {
             self.next_token()?; // ) -> values
             let mut values = self.construct_node(NodeType::KeywordWithExprs)?;
             let mut lparens = Vec::new();
-            while self.get_token(1)?.is("(") {
+            while self.get_token(1)?.is(Symbol::LParen) {
                 self.next_token()?; // VALUES -> (, ',' -> (
                 let mut lparen = self.parse_grouped_exprs(false)?;
-                if self.get_token(1)?.is(",") {
+                if self.get_token(1)?.is(Symbol::Comma) {
                     self.next_token()?; // ) -> ,
                     lparen.push_node("comma", self.construct_node(NodeType::Symbol)?);
                 }
@@ -2673,30 +3121,30 @@ impl Parser {
             }
             values.push_node_vec("exprs", lparens);
             insert.push_node("input", values);
-        } else if self.get_token(1)?.is("ROW") {
+        } else if self.get_token(1)?.is(Keyword::ROW) {
             self.next_token()?; // -> ROW
             insert.push_node("input", self.construct_node(NodeType::Keyword)?);
         } else {
             self.next_token()?; // ) -> SELECT
             insert.push_node("input", self.parse_select_statement(false, true)?);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             insert.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(insert)
     }
-    fn parse_delete_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_delete_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut delete = self.construct_node(NodeType::DeleteStatement)?;
-        if self.get_token(1)?.is("FROM") {
+        if self.get_token(1)?.is(Keyword::FROM) {
             self.next_token()?; // DELETE -> FROM
             delete.push_node("from", self.construct_node(NodeType::Keyword)?);
         }
         self.next_token()?; // -> table_name
         let mut table_name = self.parse_identifier()?;
-        if !self.get_token(1)?.is("WHERE") {
+        if !self.get_token(1)?.is(Keyword::WHERE) {
             self.next_token()?; // -> AS, ident
-            if self.get_token(0)?.is("AS") {
+            if self.get_token(0)?.is(Keyword::AS) {
                 table_name.push_node("as", self.construct_node(NodeType::Keyword)?);
                 self.next_token()?; // AS -> ident
             }
@@ -2711,35 +3159,35 @@ impl Parser {
             self.parse_expr(usize::MAX, false, false, false, true)?,
         );
         delete.push_node("where", where_);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             delete.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(delete)
     }
-    fn parse_truncate_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_truncate_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut truncate = self.construct_node(NodeType::TruncateStatement)?;
         self.next_token()?; // TRUNCATE -> TABLE
         truncate.push_node("table", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // TABLE -> ident
         truncate.push_node("table_name", self.parse_identifier()?);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             truncate.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(truncate)
     }
-    fn parse_update_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_update_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut update = self.construct_node(NodeType::UpdateStatement)?;
-        if !self.get_token(1)?.is("SET") {
+        if !self.get_token(1)?.is(Keyword::SET) {
             self.next_token()?; // -> table_name
             update.push_node("table_name", self.parse_table(true)?);
         }
         self.next_token()?; // -> SET
         let mut set = self.construct_node(NodeType::KeywordWithExprs)?;
         self.next_token()?; // SET -> exprs
-        set.push_node_vec("exprs", self.parse_exprs(&vec![], false, true)?);
-        if self.get_token(1)?.is("FROM") {
+        set.push_node_vec("exprs", self.parse_exprs(&[], false, true)?);
+        if self.get_token(1)?.is(Keyword::FROM) {
             self.next_token()?; // exprs -> FROM
             let mut from = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // FROM -> target_name
@@ -2747,7 +3195,7 @@ impl Parser {
             update.push_node("from", from);
         }
         update.push_node("set", set);
-        if self.get_token(1)?.is("WHERE") {
+        if self.get_token(1)?.is(Keyword::WHERE) {
             self.next_token()?; // exprs -> WHERE
             let mut where_ = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // WHERE -> expr
@@ -2757,15 +3205,15 @@ impl Parser {
             );
             update.push_node("where", where_);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             update.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(update)
     }
-    fn parse_merge_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_merge_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut merge = self.construct_node(NodeType::MergeStatement)?;
-        if self.get_token(1)?.is("INTO") {
+        if self.get_token(1)?.is(Keyword::INTO) {
             self.next_token()?; // MERGE -> INTO
             merge.push_node("into", self.construct_node(NodeType::Keyword)?);
         }
@@ -2779,7 +3227,7 @@ impl Parser {
             self.parse_expr(usize::MAX, true, false, false, true)?,
         );
         merge.push_node("using", using);
-        if self.get_token(1)?.is(";") {
+        if self.get_token(1)?.is(Symbol::Semicolon) {
             self.next_token()?; // -> ;
             merge.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
@@ -2792,23 +3240,23 @@ impl Parser {
         );
         merge.push_node("on", on);
         let mut whens = Vec::new();
-        while self.get_token(1)?.is("when") {
+        while self.get_token(1)?.is(Keyword::WHEN) {
             self.next_token()?; // -> WHEN
             let mut when = self.construct_node(NodeType::WhenClause)?;
-            if self.get_token(1)?.is("NOT") {
+            if self.get_token(1)?.is(Keyword::NOT) {
                 self.next_token()?; // WHEN -> NOT
                 when.push_node("not", self.construct_node(NodeType::Keyword)?);
             }
             self.next_token()?; // -> MATCHED
             when.push_node("matched", self.construct_node(NodeType::Keyword)?);
-            if self.get_token(1)?.is("BY") {
+            if self.get_token(1)?.is(Keyword::BY) {
                 self.next_token()?; // -> BY
                 let by = self.construct_node(NodeType::Keyword)?;
                 self.next_token()?; // -> TARGET, SOURCE
                 let target = self.construct_node(NodeType::Keyword)?;
                 when.push_node_vec("by_target_or_source", vec![by, target]);
             }
-            if self.get_token(1)?.is("AND") {
+            if self.get_token(1)?.is(Keyword::AND) {
                 self.next_token()?; // -> AND
                 let mut and = self.construct_node(NodeType::KeywordWithExpr)?;
                 self.next_token()?; // -> expr
@@ -2819,10 +3267,12 @@ impl Parser {
             self.next_token()?; // -> THEN
             let mut then = self.construct_node(NodeType::KeywordWithStatement)?;
             self.next_token()?; // THEN -> stmt
-            let stmt = match self.get_token(0)?.literal.to_uppercase().as_str() {
-                "DELETE" => self.construct_node(NodeType::SingleTokenStatement)?,
-                "UPDATE" => self.parse_update_statement(false)?,
-                "INSERT" => self.parse_insert_statement(false)?,
+            let stmt = match self.get_token(0)?.kind {
+                TokenKind::Keyword(Keyword::DELETE) => {
+                    self.construct_node(NodeType::SingleTokenStatement)?
+                }
+                TokenKind::Keyword(Keyword::UPDATE) => self.parse_update_statement(false)?,
+                TokenKind::Keyword(Keyword::INSERT) => self.parse_insert_statement(false)?,
                 _ => {
                     return Err(BQ2CSTError::from_token(
                         self.get_token(0)?,
@@ -2838,28 +3288,28 @@ impl Parser {
             whens.push(when);
         }
         merge.push_node_vec("whens", whens);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             merge.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(merge)
     }
     // ----- DDL -----
-    fn parse_create_schema_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_create_schema_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut create = self.construct_node(NodeType::CreateSchemaStatement)?;
-        if self.get_token(1)?.is("EXTERNAL") {
+        if self.get_token(1)?.is(Keyword::EXTERNAL) {
             self.next_token()?; // -> EXTERNAL
             create.push_node("external", self.construct_node(NodeType::Keyword)?);
         }
         self.next_token()?; // -> SCHEMA
         create.push_node("what", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             create.push_node_vec("if_not_exists", self.parse_n_keywords(3)?);
         }
         self.next_token()?; // -> ident
         create.push_node("ident", self.parse_identifier()?);
-        if self.get_token(1)?.is("DEFAULT") {
+        if self.get_token(1)?.is(Keyword::DEFAULT) {
             self.next_token()?; // DEFAULT
             let mut default = self.construct_node(NodeType::KeywordSequence)?;
             self.next_token()?; // COLLATE
@@ -2873,23 +3323,23 @@ impl Parser {
             default.push_node("next_keyword", collate);
             create.push_node("default_collate", default);
         }
-        if self.get_token(1)?.is("WITH") && self.get_token(2)?.is("CONNECTION") {
+        if self.get_token(1)?.is(Keyword::WITH) && self.get_token(2)?.is(Keyword::CONNECTION) {
             self.next_token()?; // -> WITH
             create.push_node("with_connection", self.parse_with_connection_clause()?);
         }
-        if self.get_token(1)?.is("OPTIONS") {
+        if self.get_token(1)?.is(Keyword::OPTIONS) {
             self.next_token()?; // OPTIONS
             create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             create.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
         }
         Ok(create)
     }
-    fn parse_create_search_index_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_create_search_index_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut create = self.construct_node(NodeType::CreateIndexStatement)?;
-        if self.get_token(1)?.is("OR") {
+        if self.get_token(1)?.is(Keyword::OR) {
             self.next_token()?; // -> OR
             create.push_node_vec("or_replace", self.parse_n_keywords(2)?);
         }
@@ -2898,7 +3348,7 @@ impl Parser {
         self.next_token()?; // -> INDEX
         what.push_node("next_keyword", self.construct_node(NodeType::Keyword)?);
         create.push_node("what", what);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             create.push_node_vec("if_not_exists", self.parse_n_keywords(3)?);
         }
@@ -2910,7 +3360,7 @@ impl Parser {
         create.push_node("tablename", self.parse_identifier()?);
 
         self.next_token()?; // -> (
-        if self.get_token(1)?.is("ALL") {
+        if self.get_token(1)?.is(Keyword::ALL) {
             let mut group = self.construct_node(NodeType::GroupedExpr)?;
             self.next_token()?; // -> ALL
             let mut all = self.construct_node(NodeType::KeywordSequence)?;
@@ -2923,70 +3373,70 @@ impl Parser {
         } else {
             create.push_node("column_group", self.parse_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is("STORING") {
+        if self.get_token(1)?.is(Keyword::STORING) {
             self.next_token()?; // -> STORING
             create.push_node("storing", self.parse_keyword_with_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is("PARTITION") {
+        if self.get_token(1)?.is(Keyword::PARTITION) {
             self.next_token()?; // -> PARTITION
             create.push_node("partitionby", self.parse_xxxby_exprs()?);
         }
-        if self.get_token(1)?.is("OPTIONS") {
+        if self.get_token(1)?.is(Keyword::OPTIONS) {
             self.next_token()?; // -> OPTIONS
             create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             create.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
         }
         Ok(create)
     }
-    fn parse_create_table_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_create_table_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut create = self.construct_node(NodeType::CreateTableStatement)?;
         let mut external = false;
         let mut snapshot = false;
-        if self.get_token(1)?.is("OR") {
+        if self.get_token(1)?.is(Keyword::OR) {
             self.next_token()?; // -> OR
             create.push_node_vec("or_replace", self.parse_n_keywords(2)?);
         }
         // NOTE actually, TEMP is not allowed in CREATE EXTERNAL TABLE statement
         // but it is allowed here for simplicity
-        if self.get_token(1)?.in_(&vec!["TEMP", "TEMPORARY"]) {
+        if self.get_token(1)?.in_(&[Keyword::TEMP, Keyword::TEMPORARY]) {
             self.next_token()?; // -> TEMP
             create.push_node("temp", self.construct_node(NodeType::Keyword)?);
         }
-        if self.get_token(1)?.is("EXTERNAL") {
+        if self.get_token(1)?.is(Keyword::EXTERNAL) {
             external = true;
             self.next_token()?; // -> EXTERNAL
             create.push_node("external", self.construct_node(NodeType::Keyword)?);
         }
-        if self.get_token(1)?.is("SNAPSHOT") {
+        if self.get_token(1)?.is(Keyword::SNAPSHOT) {
             snapshot = true;
             self.next_token()?; // -> SNAPSHOT
             create.push_node("snapshot", self.construct_node(NodeType::Keyword)?);
         }
         self.next_token()?; // -> TABLE
         create.push_node("what", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             create.push_node_vec("if_not_exists", self.parse_n_keywords(3)?);
         }
         self.next_token()?; // -> ident
         create.push_node("ident", self.parse_identifier()?);
-        if self.get_token(1)?.in_(&vec!["LIKE", "COPY"]) {
+        if self.get_token(1)?.in_(&[Keyword::LIKE, Keyword::COPY]) {
             self.next_token()?; // LIKE | COPY
             create.push_node("like_or_copy", self.construct_node(NodeType::Keyword)?);
             self.next_token()?; // -> ident
             create.push_node("source_table", self.parse_identifier()?);
         }
-        if self.get_token(1)?.is("(") {
+        if self.get_token(1)?.is(Symbol::LParen) {
             self.next_token()?; // -> (
             create.push_node(
                 "column_schema_group",
                 self.parse_grouped_type_declaration_or_constraints(true, false)?,
             );
         }
-        if self.get_token(1)?.is("default") {
+        if self.get_token(1)?.is(Keyword::DEFAULT) {
             self.next_token()?; // DEFAULT
             let mut default = self.construct_node(NodeType::KeywordSequence)?;
             self.next_token()?; // COLLATE
@@ -3002,24 +3452,30 @@ impl Parser {
         }
         // NOTE actually, PARTITION BY has only one expr
         // but for simplicity use parse_xxxby_exprs() here
-        if self.get_token(1)?.is("PARTITION") && !external && !snapshot {
+        if self.get_token(1)?.is(Keyword::PARTITION) && !external && !snapshot {
             self.next_token()?; // -> PARTITION
             create.push_node("partitionby", self.parse_xxxby_exprs()?);
         }
-        if self.get_token(1)?.is("CLUSTER") && !external && !snapshot {
+        if self.get_token(1)?.is(Keyword::CLUSTER) && !external && !snapshot {
             self.next_token()?; // -> CLUSTER
             create.push_node("clusterby", self.parse_xxxby_exprs()?);
         }
-        if self.get_token(1)?.is("WITH") && self.get_token(2)?.is("CONNECTION") && external {
+        if self.get_token(1)?.is(Keyword::WITH)
+            && self.get_token(2)?.is(Keyword::CONNECTION)
+            && external
+        {
             self.next_token()?; // -> WITH
             create.push_node("with_connection", self.parse_with_connection_clause()?);
         }
-        if self.get_token(1)?.is("WITH") && self.get_token(2)?.is("PARTITION") && external {
+        if self.get_token(1)?.is(Keyword::WITH)
+            && self.get_token(2)?.is(Keyword::PARTITION)
+            && external
+        {
             self.next_token()?; // -> WITH
             let mut with = self.construct_node(NodeType::WithPartitionColumnsClause)?;
             self.next_token()?; // -> PARTITION
             with.push_node_vec("partition_columns", self.parse_n_keywords(2)?);
-            if self.get_token(1)?.is("(") {
+            if self.get_token(1)?.is(Symbol::LParen) {
                 self.next_token()?; // -> (
                 with.push_node(
                     "column_schema_group",
@@ -3028,70 +3484,70 @@ impl Parser {
             }
             create.push_node("with_partition_columns", with);
         }
-        if self.get_token(1)?.is("CLONE") {
+        if self.get_token(1)?.is(Keyword::CLONE) {
             self.next_token()?; // -> CLONE
             let mut clone = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // -> identifier
             clone.push_node("expr", self.parse_table(true)?);
             create.push_node("clone", clone);
         }
-        if self.get_token(1)?.is("OPTIONS") {
+        if self.get_token(1)?.is(Keyword::OPTIONS) {
             self.next_token()?; // -> OPTIONS
             create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is("AS") {
+        if self.get_token(1)?.is(Keyword::AS) {
             self.next_token()?; // -> AS
             let mut as_ = self.construct_node(NodeType::KeywordWithStatement)?;
             self.next_token()?; // -> SELECT
             as_.push_node("stmt", self.parse_select_statement(false, true)?);
             create.push_node("as", as_)
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             create.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
         }
         Ok(create)
     }
-    fn parse_create_view_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_create_view_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         // NOTE currently multi token ident is not supported
         let mut create = self.construct_node(NodeType::CreateViewStatement)?;
         let mut materialized = false;
         // NOTE actually, OR REPLACE is not allowed in CREATE MATERIALIZED VIEW statement
         // but it is allowed here for simplicity
-        if self.get_token(1)?.is("OR") {
+        if self.get_token(1)?.is(Keyword::OR) {
             self.next_token()?; // -> OR
             create.push_node_vec("or_replace", self.parse_n_keywords(2)?);
         }
-        if self.get_token(1)?.is("MATERIALIZED") {
+        if self.get_token(1)?.is(Keyword::MATERIALIZED) {
             materialized = true;
             self.next_token()?; // -> MATERIALIZED
             create.push_node("materialized", self.construct_node(NodeType::Keyword)?);
         }
         self.next_token()?; // -> VIEW
         create.push_node("what", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             create.push_node_vec("if_not_exists", self.parse_n_keywords(3)?);
         }
         self.next_token()?; // -> ident
         create.push_node("ident", self.parse_identifier()?);
-        if self.get_token(1)?.is("(") && !materialized {
+        if self.get_token(1)?.is(Symbol::LParen) && !materialized {
             self.next_token()?; // -> (
             let mut column_name_list = self.construct_node(NodeType::GroupedIdentWithOptions)?;
             let mut idents = vec![];
             loop {
                 self.next_token()?; // -> ident
-                if self.get_token(0)?.is(")") {
+                if self.get_token(0)?.is(Symbol::RParen) {
                     column_name_list.push_node("rparen", self.construct_node(NodeType::Symbol)?);
                     break;
                 }
                 let mut ident = self.parse_identifier()?;
                 ident.node_type = NodeType::IdentWithOptions;
-                if self.get_token(1)?.is("OPTIONS") {
+                if self.get_token(1)?.is(Keyword::OPTIONS) {
                     self.next_token()?; // -> OPTIONS
                     ident.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
                 }
-                if self.get_token(1)?.is(",") {
+                if self.get_token(1)?.is(Symbol::Comma) {
                     self.next_token()?; // -> ,
                     ident.push_node("comma", self.construct_node(NodeType::Symbol)?);
                 }
@@ -3100,21 +3556,21 @@ impl Parser {
             column_name_list.push_node_vec("idents", idents);
             create.push_node("column_name_list", column_name_list)
         }
-        if self.get_token(1)?.is("PARTITION") && materialized {
+        if self.get_token(1)?.is(Keyword::PARTITION) && materialized {
             self.next_token()?; // -> PARTITION
             create.push_node("partitionby", self.parse_xxxby_exprs()?);
         }
-        if self.get_token(1)?.is("CLUSTER") && materialized {
+        if self.get_token(1)?.is(Keyword::CLUSTER) && materialized {
             self.next_token()?; // -> CLUSTER
             create.push_node("clusterby", self.parse_xxxby_exprs()?);
         }
-        if self.get_token(1)?.is("OPTIONS") {
+        if self.get_token(1)?.is(Keyword::OPTIONS) {
             self.next_token()?; // -> OPTIONS
             create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is("AS") {
+        if self.get_token(1)?.is(Keyword::AS) {
             self.next_token()?; // -> AS
-            if self.get_token(1)?.is("REPLICA") {
+            if self.get_token(1)?.is(Keyword::REPLICA) {
                 let mut as_ = self.construct_node(NodeType::KeywordSequence)?;
                 self.next_token()?; // -> REPLICA
                 let mut replica = self.construct_node(NodeType::KeywordSequence)?;
@@ -3133,35 +3589,35 @@ impl Parser {
                 create.push_node("as", as_)
             }
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             create.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
         }
         Ok(create)
     }
-    fn parse_create_function_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_create_function_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut node = self.construct_node(NodeType::CreateFunctionStatement)?;
         let mut is_tvf = false;
-        if self.get_token(1)?.literal.to_uppercase() == "OR" {
+        if self.get_token(1)?.is(Keyword::OR) {
             self.next_token()?; // -> OR
             node.push_node_vec("or_replace", self.parse_n_keywords(2)?);
         }
-        if self.get_token(1)?.in_(&vec!["TEMPORARY", "TEMP"]) {
+        if self.get_token(1)?.in_(&[Keyword::TEMPORARY, Keyword::TEMP]) {
             self.next_token()?; // -> TEMP
             node.push_node("temp", self.construct_node(NodeType::Keyword)?);
         }
-        if self.get_token(1)?.is("TABLE") {
+        if self.get_token(1)?.is(Keyword::TABLE) {
             self.next_token()?; // -> TABLE
             node.push_node("table", self.construct_node(NodeType::Keyword)?);
             is_tvf = true;
         }
-        if self.get_token(1)?.is("AGGREGATE") {
+        if self.get_token(1)?.is(Keyword::AGGREGATE) {
             self.next_token()?; // -> AGGREGATE
             node.push_node("aggregate", self.construct_node(NodeType::Keyword)?);
         }
         self.next_token()?; // -> FUNCTION
         node.push_node("what", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.in_(&vec!["IF"]) {
+        if self.get_token(1)?.in_(&[Keyword::IF]) {
             self.next_token()?; // -> IF
             node.push_node_vec("if_not_exists", self.parse_n_keywords(3)?);
         }
@@ -3172,14 +3628,14 @@ impl Parser {
             "group",
             self.parse_grouped_type_declaration_or_constraints(false, true)?,
         );
-        if self.get_token(1)?.is("RETURNS") {
+        if self.get_token(1)?.is(Keyword::RETURNS) {
             self.next_token()?; // -> RETURNS
             let mut returns = self.construct_node(NodeType::KeywordWithType)?;
             self.next_token()?; // -> type
             returns.push_node("type", self.parse_type(false, false)?);
             node.push_node("returns", returns);
         }
-        if self.get_token(1)?.is("REMOTE") {
+        if self.get_token(1)?.is(Keyword::REMOTE) {
             self.next_token()?; // -> REMOTE
             node.push_node("remote", self.construct_node(NodeType::Keyword)?);
 
@@ -3192,11 +3648,11 @@ impl Parser {
             with.push_node("next_keyword", connection);
             node.push_node("connection", with);
 
-            if self.get_token(1)?.is("OPTIONS") {
+            if self.get_token(1)?.is(Keyword::OPTIONS) {
                 self.next_token()?; // -> OPTIONS
                 node.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
             }
-        } else if self.get_token(1)?.is("AS") {
+        } else if self.get_token(1)?.is(Keyword::AS) {
             // sql function definition
             self.next_token()?; // -> AS
             if is_tvf {
@@ -3220,9 +3676,12 @@ impl Parser {
             }
         } else {
             // javascript | python function definition
-            if self.get_token(1)?.in_(&vec!["DETERMINISTIC", "NOT"]) {
+            if self
+                .get_token(1)?
+                .in_(&[Keyword::DETERMINISTIC, Keyword::NOT])
+            {
                 self.next_token()?; // -> DETERMINISTIC | NOT
-                if self.get_token(0)?.is("NOT") {
+                if self.get_token(0)?.is(Keyword::NOT) {
                     node.push_node_vec("determinism", self.parse_n_keywords(2)?);
                 } else {
                     node.push_node_vec("determinism", self.parse_n_keywords(1)?);
@@ -3233,7 +3692,7 @@ impl Parser {
             self.next_token()?; // -> js | python
             language.push_node("expr", self.construct_node(NodeType::Identifier)?);
             node.push_node("language", language);
-            if self.get_token(1)?.is("WITH") {
+            if self.get_token(1)?.is(Keyword::WITH) {
                 self.next_token()?; // -> WITH
                 let mut with = self.construct_node(NodeType::KeywordSequence)?;
                 self.next_token()?; // -> CONNECTION
@@ -3243,7 +3702,7 @@ impl Parser {
                 with.push_node("next_keyword", connection);
                 node.push_node("connection", with);
             }
-            if self.get_token(1)?.is("OPTIONS") {
+            if self.get_token(1)?.is(Keyword::OPTIONS) {
                 self.next_token()?; // -> OPTIONS
                 node.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
             }
@@ -3256,21 +3715,21 @@ impl Parser {
             );
             node.push_node("as", as_);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // ) -> ;
             node.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(node)
     }
-    fn parse_create_procedure_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_create_procedure_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut create = self.construct_node(NodeType::CreateProcedureStatement)?;
-        if self.get_token(1)?.is("OR") {
+        if self.get_token(1)?.is(Keyword::OR) {
             self.next_token()?; // -> OR
             create.push_node_vec("or_replace", self.parse_n_keywords(2)?);
         }
         self.next_token()?; // -> PROCEDURE
         create.push_node("what", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             create.push_node_vec("if_not_exists", self.parse_n_keywords(3)?);
         }
@@ -3281,7 +3740,7 @@ impl Parser {
             "group",
             self.parse_grouped_type_declaration_or_constraints(true, false)?,
         );
-        if self.get_token(1)?.is("EXTERNAL") {
+        if self.get_token(1)?.is(Keyword::EXTERNAL) {
             self.next_token()?; // -> EXTERNAL
             let mut external = self.construct_node(NodeType::KeywordSequence)?;
             self.next_token()?; // -> SECURITY
@@ -3292,25 +3751,25 @@ impl Parser {
             external.push_node("next_keyword", security);
             create.push_node("external", external);
         }
-        if self.get_token(1)?.is("WITH") {
+        if self.get_token(1)?.is(Keyword::WITH) {
             self.next_token()?; // -> WITH
             create.push_node("with_connection", self.parse_with_connection_clause()?);
         }
-        if self.get_token(1)?.is("OPTIONS") {
+        if self.get_token(1)?.is(Keyword::OPTIONS) {
             self.next_token()?; // -> OPTIONS
             create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is("LANGUAGE") {
+        if self.get_token(1)?.is(Keyword::LANGUAGE) {
             self.next_token()?; // -> LANGUAGE
             let mut language = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // -> PYTHON | JAVA | SCALA
             language.push_node("expr", self.construct_node(NodeType::Identifier)?);
             create.push_node("language", language);
         }
-        if self.get_token(1)?.is("BEGIN") {
+        if self.get_token(1)?.is(Keyword::BEGIN) {
             self.next_token()?; // -> BEGIN
             create.push_node("stmt", self.parse_begin_statement(false)?);
-        } else if self.get_token(1)?.is("AS") {
+        } else if self.get_token(1)?.is(Keyword::AS) {
             self.next_token()?; // -> AS
             let mut as_ = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // -> "pyspark code"
@@ -3320,21 +3779,24 @@ impl Parser {
             );
             create.push_node("as", as_);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             create.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(create)
     }
-    fn parse_create_row_access_policy_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_create_row_access_policy_statement(
+        &mut self,
+        semicolon: bool,
+    ) -> BQ2CSTResult<Node<'a>> {
         let mut create = self.construct_node(NodeType::CreateRowAccessPolicyStatement)?;
-        if self.get_token(1)?.is("OR") {
+        if self.get_token(1)?.is(Keyword::OR) {
             self.next_token()?; // -> OR
             create.push_node_vec("or_replace", self.parse_n_keywords(2)?);
         }
         self.next_token()?; // -> ROW
         create.push_node_vec("what", self.parse_n_keywords(3)?);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             create.push_node_vec("if_not_exists", self.parse_n_keywords(3)?);
         }
@@ -3345,7 +3807,7 @@ impl Parser {
         self.next_token()?; // -> tablename
         on.push_node("expr", self.parse_identifier()?);
         create.push_node("on", on);
-        if self.get_token(1)?.is("GRANT") {
+        if self.get_token(1)?.is(Keyword::GRANT) {
             self.next_token()?; // -> GRANT
             create.push_node("grant", self.construct_node(NodeType::Keyword)?);
             self.next_token()?; // -> TO
@@ -3365,31 +3827,31 @@ impl Parser {
         );
         create.push_node("using", using);
 
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             create.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(create)
     }
-    fn parse_create_model_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_create_model_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut create = self.construct_node(NodeType::CreateModelStatement)?;
-        if self.get_token(1)?.is("OR") {
+        if self.get_token(1)?.is(Keyword::OR) {
             self.next_token()?; // -> OR
             create.push_node_vec("or_replace", self.parse_n_keywords(2)?);
         }
         self.next_token()?; // -> MODEL
         create.push_node("what", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             create.push_node_vec("if_not_exists", self.parse_n_keywords(3)?);
         }
         self.next_token()?; // -> ident
         create.push_node("ident", self.parse_identifier()?);
-        if self.get_token(1)?.is("TRANSFORM") {
+        if self.get_token(1)?.is(Keyword::TRANSFORM) {
             self.next_token()?; // -> TRANSFORM
             create.push_node("transform", self.parse_keyword_with_grouped_exprs(true)?);
         }
-        if self.get_token(1)?.is("INPUT") {
+        if self.get_token(1)?.is(Keyword::INPUT) {
             self.next_token()?; // -> INPUT
             let mut input = self.construct_node(NodeType::KeywordWithGroupedXXX)?;
             self.next_token()?; // -> (
@@ -3399,7 +3861,7 @@ impl Parser {
             );
             create.push_node("input", input);
         }
-        if self.get_token(1)?.is("OUTPUT") {
+        if self.get_token(1)?.is(Keyword::OUTPUT) {
             self.next_token()?; // -> OUTPUT
             let mut output = self.construct_node(NodeType::KeywordWithGroupedXXX)?;
             self.next_token()?; // -> (
@@ -3409,7 +3871,7 @@ impl Parser {
             );
             create.push_node("output", output);
         }
-        if self.get_token(1)?.is("REMOTE") {
+        if self.get_token(1)?.is(Keyword::REMOTE) {
             self.next_token()?; // -> REMOTE
             let mut remote = self.construct_node(NodeType::KeywordSequence)?;
             self.next_token()?; // -> WITH
@@ -3417,7 +3879,7 @@ impl Parser {
             self.next_token()?; // -> CONNECTION
             let mut connection = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // -> ident
-            if self.get_token(0)?.is("DEFAULT") {
+            if self.get_token(0)?.is(Keyword::DEFAULT) {
                 connection.push_node("expr", self.construct_node(NodeType::Keyword)?);
             } else {
                 connection.push_node("expr", self.parse_identifier()?);
@@ -3426,13 +3888,13 @@ impl Parser {
             remote.push_node("next_keyword", with);
             create.push_node("remote", remote);
         }
-        if self.get_token(1)?.is("OPTIONS") {
+        if self.get_token(1)?.is(Keyword::OPTIONS) {
             self.next_token()?; // -> OPTIONS
             create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is("AS") {
+        if self.get_token(1)?.is(Keyword::AS) {
             self.next_token()?; // -> AS
-            if self.get_token(2)?.is("training_data") {
+            if self.get_token(2)?.is(Keyword::TRAINING_DATA) {
                 let mut as_ = self.construct_node(NodeType::KeywordWithGroupedXXX)?;
                 self.next_token()?; // -> (
                 let mut group = self.construct_node(NodeType::TrainingDataCustomHolidayClause)?;
@@ -3451,27 +3913,27 @@ impl Parser {
                 create.push_node("query", as_)
             }
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             create.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
         }
         Ok(create)
     }
-    fn parse_alter_schema_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_alter_schema_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut alter = self.construct_node(NodeType::AlterSchemaStatement)?;
         self.next_token()?; // -> SCHEMA
         alter.push_node("what", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             alter.push_node_vec("if_exists", self.parse_n_keywords(2)?);
         }
         self.next_token()?; // -> ident
         alter.push_node("ident", self.parse_identifier()?);
         self.next_token()?; // -> SET | ADD
-        match self.get_token(0)?.literal.to_uppercase().as_str() {
-            "SET" => {
+        match self.get_token(0)?.kind {
+            TokenKind::Keyword(Keyword::SET) => {
                 alter.push_node("set", self.construct_node(NodeType::Keyword)?);
-                if self.get_token(1)?.is("DEFAULT") {
+                if self.get_token(1)?.is(Keyword::DEFAULT) {
                     self.next_token()?; // DEFAULT
                     let mut default = self.construct_node(NodeType::KeywordSequence)?;
                     self.next_token()?; // COLLATE
@@ -3486,7 +3948,7 @@ impl Parser {
                     alter.push_node("default_collate", default);
                 }
             }
-            "ADD" => {
+            TokenKind::Keyword(Keyword::ADD) => {
                 let mut add = self.construct_node(NodeType::KeywordSequence)?;
                 self.next_token()?; // -> REPLICA
                 let mut replica = self.construct_node(NodeType::KeywordWithExpr)?;
@@ -3497,7 +3959,7 @@ impl Parser {
                 add.push_node("next_keyword", replica);
                 alter.push_node("add", add);
             }
-            "DROP" => {
+            TokenKind::Keyword(Keyword::DROP) => {
                 let mut drop = self.construct_node(NodeType::KeywordSequence)?;
                 self.next_token()?; // -> REPLICA
                 let mut replica = self.construct_node(NodeType::KeywordWithExpr)?;
@@ -3518,34 +3980,34 @@ impl Parser {
                 ))
             }
         }
-        if self.get_token(1)?.is("OPTIONS") {
+        if self.get_token(1)?.is(Keyword::OPTIONS) {
             self.next_token()?; // -> OPTIONS
             alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(alter)
     }
-    fn parse_alter_table_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_alter_table_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut alter = self.construct_node(NodeType::AlterTableStatement)?;
         self.next_token()?; // -> TABLE
         alter.push_node("what", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             alter.push_node_vec("if_exists", self.parse_n_keywords(2)?);
         }
         self.next_token()?; // -> ident
         alter.push_node("ident", self.parse_identifier()?);
-        match self.get_token(1)?.literal.to_uppercase().as_str() {
-            "SET" => {
+        match self.get_token(1)?.kind {
+            TokenKind::Keyword(Keyword::SET) => {
                 self.next_token()?; // -> SET
                 alter.push_node("set", self.construct_node(NodeType::Keyword)?);
-                if self.get_token(1)?.is("OPTIONS") {
+                if self.get_token(1)?.is(Keyword::OPTIONS) {
                     self.next_token()?; // -> OPTIONS
                     alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
-                } else if self.get_token(1)?.is("DEFAULT") {
+                } else if self.get_token(1)?.is(Keyword::DEFAULT) {
                     self.next_token()?; // DEFAULT
                     let mut default = self.construct_node(NodeType::KeywordSequence)?;
                     self.next_token()?; // COLLATE
@@ -3560,15 +4022,15 @@ impl Parser {
                     alter.push_node("default_collate", default);
                 }
             }
-            "ADD" => {
-                if self.get_token(2)?.is("COLUMN") {
+            TokenKind::Keyword(Keyword::ADD) => {
+                if self.get_token(2)?.is(Keyword::COLUMN) {
                     let mut add_columns = Vec::new();
-                    while self.get_token(1)?.is("ADD") {
+                    while self.get_token(1)?.is(Keyword::ADD) {
                         self.next_token()?; // -> ADD
                         let mut add_column = self.construct_node(NodeType::AddColumnClause)?;
                         self.next_token()?; // -> COLUMN
                         add_column.push_node("what", self.construct_node(NodeType::Keyword)?);
-                        if self.get_token(1)?.is("IF") {
+                        if self.get_token(1)?.is(Keyword::IF) {
                             self.next_token()?; // -> IF
                             add_column.push_node_vec("if_not_exists", self.parse_n_keywords(3)?);
                         }
@@ -3577,7 +4039,7 @@ impl Parser {
                         self.next_token()?; // -> type
                         ident.push_node("type", self.parse_type(true, false)?);
                         add_column.push_node("type_declaration", ident);
-                        if self.get_token(1)?.is(",") {
+                        if self.get_token(1)?.is(Symbol::Comma) {
                             self.next_token()?; // -> ,
                             add_column.push_node("comma", self.construct_node(NodeType::Symbol)?);
                         }
@@ -3586,13 +4048,13 @@ impl Parser {
                     alter.push_node_vec("add_columns", add_columns);
                 } else {
                     let mut add_constraints = Vec::new();
-                    while self.get_token(1)?.is("ADD") {
+                    while self.get_token(1)?.is(Keyword::ADD) {
                         self.next_token()?; // -> ADD
                         let mut add_constraint =
                             self.construct_node(NodeType::AddConstraintClause)?;
                         self.next_token()?; // -> PRIMARY | CONSTRAINT | REFERENCES
                         let constraint = self.parse_constraint()?;
-                        if self.get_token(1)?.is(",") {
+                        if self.get_token(1)?.is(Symbol::Comma) {
                             self.next_token()?; // -> ,
                             add_constraint
                                 .push_node("comma", self.construct_node(NodeType::Symbol)?);
@@ -3603,8 +4065,8 @@ impl Parser {
                     alter.push_node_vec("add_constraints", add_constraints);
                 }
             }
-            "RENAME" => {
-                if self.get_token(2)?.is("TO") {
+            TokenKind::Keyword(Keyword::RENAME) => {
+                if self.get_token(2)?.is(Keyword::TO) {
                     self.next_token()?; // -> RENAME
                     alter.push_node("rename", self.construct_node(NodeType::Keyword)?);
                     self.next_token()?; // -> TO
@@ -3614,12 +4076,12 @@ impl Parser {
                     alter.push_node("to", to);
                 } else {
                     let mut rename_columns = vec![];
-                    while self.get_token(1)?.is("RENAME") {
+                    while self.get_token(1)?.is(Keyword::RENAME) {
                         self.next_token()?; // -> RENAME
                         let mut rename = self.construct_node(NodeType::RenameColumnClause)?;
                         self.next_token()?; // -> COLUMN
                         rename.push_node("column", self.construct_node(NodeType::Keyword)?);
-                        if self.get_token(1)?.is("IF") {
+                        if self.get_token(1)?.is(Keyword::IF) {
                             self.next_token()?; // -> IF
                             let mut if_ = self.construct_node(NodeType::KeywordSequence)?;
                             self.next_token()?; // -> EXISTS
@@ -3632,7 +4094,7 @@ impl Parser {
                         let mut to = self.construct_node(NodeType::KeywordWithExpr)?;
                         self.next_token()?; // -> ident (new)
                         to.push_node("expr", self.parse_identifier()?);
-                        if self.get_token(1)?.is(",") {
+                        if self.get_token(1)?.is(Symbol::Comma) {
                             self.next_token()?; // -> ;
                             rename.push_node("comma", self.construct_node(NodeType::Symbol)?);
                         }
@@ -3642,32 +4104,32 @@ impl Parser {
                     alter.push_node_vec("rename_columns", rename_columns);
                 }
             }
-            "DROP" => {
+            TokenKind::Keyword(Keyword::DROP) => {
                 let mut drop_columns = Vec::new();
-                while self.get_token(1)?.is("DROP") {
+                while self.get_token(1)?.is(Keyword::DROP) {
                     self.next_token()?; // -> DROP
                     let mut drop_column = self.construct_node(NodeType::AlterTableDropClause)?;
-                    if self.get_token(1)?.is("PRIMARY") {
+                    if self.get_token(1)?.is(Keyword::PRIMARY) {
                         self.next_token()?; // -> PRIMARY
                         let mut pk = self.construct_node(NodeType::KeywordWithExpr)?;
                         self.next_token()?; // -> KEY
                         pk.push_node("expr", self.construct_node(NodeType::Keyword)?);
                         drop_column.push_node("what", pk);
-                        if self.get_token(1)?.is("IF") {
+                        if self.get_token(1)?.is(Keyword::IF) {
                             self.next_token()?; // -> IF
                             drop_column.push_node_vec("if_exists", self.parse_n_keywords(2)?);
                         }
                     } else {
                         self.next_token()?; // -> COLUMN | CONSTRAINT
                         drop_column.push_node("what", self.construct_node(NodeType::Keyword)?);
-                        if self.get_token(1)?.is("IF") {
+                        if self.get_token(1)?.is(Keyword::IF) {
                             self.next_token()?; // -> IF
                             drop_column.push_node_vec("if_exists", self.parse_n_keywords(2)?);
                         }
                         self.next_token()?; // -> ident
                         drop_column.push_node("ident", self.parse_identifier()?);
                     }
-                    if self.get_token(1)?.is(",") {
+                    if self.get_token(1)?.is(Symbol::Comma) {
                         self.next_token()?; // -> ,
                         drop_column.push_node("comma", self.construct_node(NodeType::Symbol)?);
                     }
@@ -3675,7 +4137,7 @@ impl Parser {
                 }
                 alter.push_node_vec("drop_columns", drop_columns);
             }
-            "ALTER" => {
+            TokenKind::Keyword(Keyword::ALTER) => {
                 self.next_token()?; // -> ALTER
                 alter.push_node(
                     "alter_column_stmt",
@@ -3692,30 +4154,30 @@ impl Parser {
                 ))
             }
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(alter)
     }
-    fn parse_alter_column_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_alter_column_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut alter = self.construct_node(NodeType::AlterColumnStatement)?;
         self.next_token()?; // -> COLUMN
         alter.push_node("what", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?;
             alter.push_node_vec("if_exists", self.parse_n_keywords(2)?);
         }
         self.next_token()?; // -> ident
         alter.push_node("ident", self.construct_node(NodeType::Identifier)?);
         self.next_token()?; // -> SET | DROP
-        match self.get_token(0)?.literal.to_uppercase().as_str() {
-            "SET" => {
+        match self.get_token(0)?.kind {
+            TokenKind::Keyword(Keyword::SET) => {
                 alter.push_node("set", self.construct_node(NodeType::Keyword)?);
-                if self.get_token(1)?.is("OPTIONS") {
+                if self.get_token(1)?.is(Keyword::OPTIONS) {
                     self.next_token()?; // -> OPTIONS
                     alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
-                } else if self.get_token(1)?.is("DATA") {
+                } else if self.get_token(1)?.is(Keyword::DATA) {
                     self.next_token()?; // -> DATA
                     alter.push_node_vec("data_type", self.parse_n_keywords(2)?);
                     self.next_token()?; // -> type
@@ -3731,8 +4193,8 @@ impl Parser {
                     alter.push_node("default", default);
                 }
             }
-            "DROP" => {
-                if self.get_token(1)?.is("DEFAULT") {
+            TokenKind::Keyword(Keyword::DROP) => {
+                if self.get_token(1)?.is(Keyword::DEFAULT) {
                     alter.push_node_vec("drop_default", self.parse_n_keywords(2)?);
                 } else {
                     alter.push_node_vec("drop_not_null", self.parse_n_keywords(3)?);
@@ -3748,20 +4210,20 @@ impl Parser {
                 ))
             }
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(alter)
     }
-    fn parse_alter_vector_index_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_alter_vector_index_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut alter = self.construct_node(NodeType::AlterVectorIndexStatement)?;
         self.next_token()?; // -> VECTOR
         let mut vector = self.construct_node(NodeType::KeywordSequence)?;
         self.next_token()?; // -> INDEX
         vector.push_node("next_keyword", self.construct_node(NodeType::Keyword)?);
         alter.push_node("what", vector);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             alter.push_node_vec("if_exists", self.parse_n_keywords(2)?);
         }
@@ -3778,27 +4240,27 @@ impl Parser {
         let operation = self.construct_node(NodeType::Keyword)?;
         alter.push_node("operation", operation);
 
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(alter)
     }
-    fn parse_alter_view_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_alter_view_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut alter = self.construct_node(NodeType::AlterViewStatement)?;
-        if self.get_token(1)?.is("MATERIALIZED") {
+        if self.get_token(1)?.is(Keyword::MATERIALIZED) {
             self.next_token()?; // -> MATERIALIZED
             alter.push_node("materialized", self.construct_node(NodeType::Keyword)?);
         }
         self.next_token()?; // -> VIEW
         alter.push_node("what", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             alter.push_node_vec("if_exists", self.parse_n_keywords(2)?);
         }
         self.next_token()?; // -> ident
         alter.push_node("ident", self.parse_identifier()?);
-        if self.get_token(1)?.is("ALTER") {
+        if self.get_token(1)?.is(Keyword::ALTER) {
             self.next_token()?;
             let alter_column = self.parse_alter_column_statement(false)?;
             alter.push_node("alter_column_stmt", alter_column);
@@ -3808,13 +4270,13 @@ impl Parser {
             self.next_token()?; // -> OPTIONS
             alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(alter)
     }
-    fn parse_alter_organization_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_alter_organization_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut alter = self.construct_node(NodeType::AlterOrganizationStatement)?;
         self.next_token()?; // -> ORGANIZATION
         alter.push_node("what", self.construct_node(NodeType::Keyword)?);
@@ -3822,17 +4284,17 @@ impl Parser {
         alter.push_node("set", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> OPTIONS
         alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(alter)
     }
-    fn parse_alter_project_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_alter_project_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut alter = self.construct_node(NodeType::AlterProjectStatement)?;
         self.next_token()?; // -> PROJECT
         alter.push_node("what", self.construct_node(NodeType::Keyword)?);
-        if !self.get_token(1)?.is("SET") {
+        if !self.get_token(1)?.is(Keyword::SET) {
             self.next_token()?; // -> ident
             alter.push_node("ident", self.parse_identifier()?);
         }
@@ -3840,13 +4302,13 @@ impl Parser {
         alter.push_node("set", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> OPTIONS
         alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(alter)
     }
-    fn parse_alter_bicapacity_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_alter_bicapacity_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut alter = self.construct_node(NodeType::AlterBICapacityStatement)?;
         self.next_token()?; // -> BI_CAPACITY
         alter.push_node("what", self.construct_node(NodeType::Keyword)?);
@@ -3856,13 +4318,13 @@ impl Parser {
         alter.push_node("set", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> OPTIONS
         alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(alter)
     }
-    fn parse_alter_reservation_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_alter_reservation_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut alter = self.construct_node(NodeType::AlterReservationStatement)?;
         self.next_token()?; // -> CAPACITY | RESERVATION
         alter.push_node("what", self.construct_node(NodeType::Keyword)?);
@@ -3872,17 +4334,17 @@ impl Parser {
         alter.push_node("set", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> OPTIONS
         alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(alter)
     }
-    fn parse_alter_model_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_alter_model_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut alter = self.construct_node(NodeType::AlterModelStatement)?;
         self.next_token()?; // -> MODEL
         alter.push_node("what", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             alter.push_node_vec("if_exists", self.parse_n_keywords(2)?);
         }
@@ -3892,25 +4354,28 @@ impl Parser {
         alter.push_node("set", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> OPTIONS
         alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(alter)
     }
-    fn parse_drop_row_access_policy_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_drop_row_access_policy_statement(
+        &mut self,
+        semicolon: bool,
+    ) -> BQ2CSTResult<Node<'a>> {
         let mut drop = self.construct_node(NodeType::DropRowAccessPolicyStatement)?;
         self.next_token()?; // -> ROW | ALL
-        if self.get_token(0)?.is("ROW") {
+        if self.get_token(0)?.is(Keyword::ROW) {
             drop.push_node_vec("what", self.parse_n_keywords(3)?);
         } else {
             drop.push_node_vec("what", self.parse_n_keywords(4)?);
         }
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             drop.push_node_vec("if_exists", self.parse_n_keywords(2)?);
         }
-        if !self.get_token(1)?.is("ON") {
+        if !self.get_token(1)?.is(Keyword::ON) {
             self.next_token()?; // -> ident
             drop.push_node("ident", self.parse_identifier()?);
         }
@@ -3919,26 +4384,27 @@ impl Parser {
         self.next_token()?; // -> tablename
         on.push_node("expr", self.parse_identifier()?);
         drop.push_node("on", on);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             drop.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(drop)
     }
-    fn parse_drop_statement_general(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_drop_statement_general(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut drop = self.construct_node(NodeType::DropStatement)?;
-        if self.get_token(1)?.is("EXTERNAL") {
+        if self.get_token(1)?.is(Keyword::EXTERNAL) {
             self.next_token()?; // -> EXTERNAL
             drop.push_node("external", self.construct_node(NodeType::Keyword)?);
-        } else if self.get_token(1)?.is("MATERIALIZED") {
+        } else if self.get_token(1)?.is(Keyword::MATERIALIZED) {
             self.next_token()?; // -> MATERIALIZED
             drop.push_node("materialized", self.construct_node(NodeType::Keyword)?);
-        } else if self.get_token(1)?.is("TABLE") && self.get_token(2)?.is("FUNCTION") {
+        } else if self.get_token(1)?.is(Keyword::TABLE) && self.get_token(2)?.is(Keyword::FUNCTION)
+        {
             self.next_token()?; // -> TABLE
             drop.push_node("table", self.construct_node(NodeType::Keyword)?)
         }
         self.next_token()?; // -> SCHEMA, TABLE, VIEW, FUNCTION, PROCEDURE, SEARCH
-        if self.get_token(0)?.is("SEARCH") {
+        if self.get_token(0)?.is(Keyword::SEARCH) {
             let mut what = self.construct_node(NodeType::KeywordSequence)?;
             self.next_token()?; // -> INDEX
             what.push_node("next_keyword", self.construct_node(NodeType::Keyword)?);
@@ -3946,53 +4412,56 @@ impl Parser {
         } else {
             drop.push_node("what", self.construct_node(NodeType::Keyword)?);
         }
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             drop.push_node_vec("if_exists", self.parse_n_keywords(2)?);
         }
         self.next_token()?; // -> ident
         drop.push_node("ident", self.parse_identifier()?);
-        if self.get_token(1)?.in_(&vec!["CASCADE", "RESTRICT"]) {
+        if self
+            .get_token(1)?
+            .in_(&[Keyword::CASCADE, Keyword::RESTRICT])
+        {
             self.next_token()?; // -> CASCADE, RESTRICT
             drop.push_node(
                 "cascade_or_restrict",
                 self.construct_node(NodeType::Keyword)?,
             );
         }
-        if self.get_token(1)?.is("on") {
+        if self.get_token(1)?.is(Keyword::ON) {
             self.next_token()?; // -> ON
             let mut on = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // -> tablename
             on.push_node("expr", self.parse_identifier()?);
             drop.push_node("on", on);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             drop.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(drop)
     }
-    fn parse_undrop_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_undrop_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut undrop = self.construct_node(NodeType::UndropStatement)?;
         self.next_token()?; // -> SCHEMA
         undrop.push_node("what", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("IF") {
+        if self.get_token(1)?.is(Keyword::IF) {
             self.next_token()?; // -> IF
             undrop.push_node_vec("if_not_exists", self.parse_n_keywords(3)?);
         }
         self.next_token()?; // -> ident
         undrop.push_node("ident", self.parse_identifier()?);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             undrop.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(undrop)
     }
     // ----- DCL -----
-    fn parse_grant_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_grant_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut grant = self.construct_node(NodeType::GrantStatement)?;
         self.next_token()?; // -> role
-        grant.push_node_vec("roles", self.parse_exprs(&vec![], false, true)?);
+        grant.push_node_vec("roles", self.parse_exprs(&[], false, true)?);
         self.next_token()?; // -> ON
         grant.push_node("on", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> resource_type
@@ -4002,18 +4471,18 @@ impl Parser {
         self.next_token()?; // -> TO
         let mut to = self.construct_node(NodeType::KeywordWithExprs)?;
         self.next_token()?; // -> user
-        to.push_node_vec("exprs", self.parse_exprs(&vec![], false, true)?);
+        to.push_node_vec("exprs", self.parse_exprs(&[], false, true)?);
         grant.push_node("to", to);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // ;
             grant.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
         }
         Ok(grant)
     }
-    fn parse_revoke_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_revoke_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut revoke = self.construct_node(NodeType::RevokeStatement)?;
         self.next_token()?; // -> role
-        revoke.push_node_vec("roles", self.parse_exprs(&vec![], false, true)?);
+        revoke.push_node_vec("roles", self.parse_exprs(&[], false, true)?);
         self.next_token()?; // -> ON
         revoke.push_node("on", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> resource_type
@@ -4023,21 +4492,21 @@ impl Parser {
         self.next_token()?; // -> FROM
         let mut from = self.construct_node(NodeType::KeywordWithExprs)?;
         self.next_token()?; // -> user
-        from.push_node_vec("exprs", self.parse_exprs(&vec![], false, true)?);
+        from.push_node_vec("exprs", self.parse_exprs(&[], false, true)?);
         revoke.push_node("from", from);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // ;
             revoke.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
         }
         Ok(revoke)
     }
-    fn parse_create_reservation_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_create_reservation_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut create = self.construct_node(NodeType::CreateReservationStatement)?;
         self.next_token()?; // -> CAPACITY | RESERVATION | ASSIGNMENT
         create.push_node("what", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> ident
         create.push_node("ident", self.parse_identifier()?);
-        if self.get_token(1)?.is("AS") {
+        if self.get_token(1)?.is(Keyword::AS) {
             // may be deprecated
             self.next_token()?; // -> AS
             create.push_node("as", self.construct_node(NodeType::Keyword)?);
@@ -4048,23 +4517,23 @@ impl Parser {
                 "json_string",
                 self.parse_expr(usize::MAX, false, false, false, true)?,
             );
-        } else if self.get_token(1)?.is("OPTIONS") {
+        } else if self.get_token(1)?.is(Keyword::OPTIONS) {
             self.next_token()?; // -> OPTIONS
             create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // ;
             create.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
         }
         Ok(create)
     }
     // ----- script -----
-    fn parse_declare_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_declare_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut declare = self.construct_node(NodeType::DeclareStatement)?;
         let mut idents = Vec::new();
         loop {
             self.next_token()?; // -> ident
-            if self.get_token(1)?.is(",") {
+            if self.get_token(1)?.is(Symbol::Comma) {
                 let mut ident = self.parse_identifier()?;
                 self.next_token()?; // ident -> comma
                 ident.push_node("comma", self.construct_node(NodeType::Symbol)?);
@@ -4075,11 +4544,11 @@ impl Parser {
             }
         }
         declare.push_node_vec("idents", idents);
-        if !self.get_token(1)?.is("DEFAULT") {
+        if !self.get_token(1)?.is(Keyword::DEFAULT) {
             self.next_token()?; // ident -> variable_type
             declare.push_node("variable_type", self.parse_type(false, false)?);
         }
-        if self.get_token(1)?.is("DEFAULT") {
+        if self.get_token(1)?.is(Keyword::DEFAULT) {
             self.next_token()?; // -> DEFAULT
             let mut default = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // DEFAULT -> expr
@@ -4089,26 +4558,26 @@ impl Parser {
             );
             declare.push_node("default", default);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?;
             declare.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(declare)
     }
-    fn parse_set_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_set_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut set = self.construct_node(NodeType::SetStatement)?;
         self.next_token()?; // set -> expr
         set.push_node(
             "expr",
             self.parse_expr(usize::MAX, false, false, false, true)?,
         );
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?;
             set.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(set)
     }
-    fn parse_execute_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_execute_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut execute = self.construct_node(NodeType::ExecuteStatement)?;
         self.next_token()?; // EXECUTE -> IMMEDIATE
         execute.push_node("immediate", self.construct_node(NodeType::Keyword)?);
@@ -4117,13 +4586,13 @@ impl Parser {
             "sql_expr",
             self.parse_expr(usize::MAX, false, false, false, true)?,
         );
-        if self.get_token(1)?.is("INTO") {
+        if self.get_token(1)?.is(Keyword::INTO) {
             self.next_token()?; // sql_expr -> INTO
             let mut into = self.construct_node(NodeType::KeywordWithExprs)?;
             let mut idents = Vec::new();
             loop {
                 self.next_token()?; // -> ident
-                if self.get_token(1)?.is(",") {
+                if self.get_token(1)?.is(Symbol::Comma) {
                     let mut ident = self.parse_identifier()?;
                     self.next_token()?; // ident -> ,
                     ident.push_node("comma", self.construct_node(NodeType::Symbol)?);
@@ -4136,30 +4605,30 @@ impl Parser {
             into.push_node_vec("exprs", idents);
             execute.push_node("into", into);
         }
-        if self.get_token(1)?.is("USING") {
+        if self.get_token(1)?.is(Keyword::USING) {
             self.next_token()?; // -> using
             let mut using = self.construct_node(NodeType::KeywordWithExprs)?;
             self.next_token()?; // using -> exprs
-            using.push_node_vec("exprs", self.parse_exprs(&vec![], true, true)?);
+            using.push_node_vec("exprs", self.parse_exprs(&[], true, true)?);
             execute.push_node("using", using);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?;
             execute.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(execute)
     }
-    fn parse_begin_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_begin_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut begin = self.construct_node(NodeType::BeginStatement)?;
         let mut stmts = Vec::new();
-        while !self.get_token(1)?.in_(&vec!["END", "EXCEPTION"]) {
+        while !self.get_token(1)?.in_(&[Keyword::END, Keyword::EXCEPTION]) {
             self.next_token()?; // -> stmt
             stmts.push(self.parse_statement(true)?);
         }
-        if 0 < stmts.len() {
+        if !stmts.is_empty() {
             begin.push_node_vec("stmts", stmts);
         }
-        if self.get_token(1)?.is("exception") {
+        if self.get_token(1)?.is(Keyword::EXCEPTION) {
             self.next_token()?; // ; -> EXCEPTION
             let exception = self.construct_node(NodeType::Keyword)?;
             self.next_token()?; // EXCEPTION -> WHEN
@@ -4168,17 +4637,17 @@ impl Parser {
             let error = self.construct_node(NodeType::Keyword)?;
             begin.push_node_vec("exception_when_error", vec![exception, when, error]);
             self.next_token()?; // ERROR -> THEN
-            begin.push_node("then", self.parse_keyword_with_statements(&vec!["END"])?);
+            begin.push_node("then", self.parse_keyword_with_statements(&[Keyword::END])?);
         }
         self.next_token()?; // -> end
         begin.push_node("end", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             begin.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(begin)
     }
-    fn parse_if_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_if_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut if_ = self.construct_node(NodeType::IfStatement)?;
         self.next_token()?; // -> condition
         if_.push_node(
@@ -4189,11 +4658,11 @@ impl Parser {
         self.next_token()?; // -> THEN
         if_.push_node(
             "then",
-            self.parse_keyword_with_statements(&vec!["ELSEIF", "ELSE", "END"])?,
+            self.parse_keyword_with_statements(&[Keyword::ELSEIF, Keyword::ELSE, Keyword::END])?,
         );
 
         let mut elseifs = Vec::new();
-        while self.get_token(1)?.is("ELSEIF") {
+        while self.get_token(1)?.is(Keyword::ELSEIF) {
             self.next_token()?; // -> ELSEIF
             let mut elseif = self.construct_node(NodeType::ElseIfClause)?;
             self.next_token()?; // -> condition
@@ -4204,29 +4673,33 @@ impl Parser {
             self.next_token()?; // -> THEN
             elseif.push_node(
                 "then",
-                self.parse_keyword_with_statements(&vec!["ELSEIF", "ELSE", "END"])?,
+                self.parse_keyword_with_statements(&[
+                    Keyword::ELSEIF,
+                    Keyword::ELSE,
+                    Keyword::END,
+                ])?,
             );
             elseifs.push(elseif);
         }
-        if 0 < elseifs.len() {
+        if !elseifs.is_empty() {
             if_.push_node_vec("elseifs", elseifs);
         }
 
-        if self.get_token(1)?.is("ELSE") {
+        if self.get_token(1)?.is(Keyword::ELSE) {
             self.next_token()?; // -> ELSE
-            if_.push_node("else", self.parse_keyword_with_statements(&vec!["END"])?);
+            if_.push_node("else", self.parse_keyword_with_statements(&[Keyword::END])?);
         }
         self.next_token()?; // -> END
         let end = self.construct_node(NodeType::Keyword)?;
         self.next_token()?; // -> IF
         if_.push_node_vec("end_if", vec![end, self.construct_node(NodeType::Keyword)?]);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             if_.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(if_)
     }
-    fn parse_labeled_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_labeled_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let label = self.construct_node(NodeType::Identifier)?;
         self.next_token()?; // -> :
         let colon = self.construct_node(NodeType::Symbol)?;
@@ -4247,30 +4720,30 @@ impl Parser {
         };
         stmt.push_node("leading_label", label);
         stmt.push_node("colon", colon);
-        if !self.get_token(1)?.is(";") {
+        if !self.get_token(1)?.is(Symbol::Semicolon) {
             self.next_token()?; // -> trailing_label
             stmt.push_node("trailing_label", self.construct_node(NodeType::Identifier)?);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             stmt.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(stmt)
     }
-    fn parse_break_continue_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_break_continue_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut node = self.construct_node(NodeType::BreakContinueStatement)?;
-        if !self.get_token(1)?.is(";") {
+        if !self.get_token(1)?.is(Symbol::Semicolon) {
             self.next_token()?; // -> label
             node.push_node("label", self.construct_node(NodeType::Identifier)?);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             node.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(node)
     }
-    fn parse_loop_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
-        let mut loop_ = self.parse_keyword_with_statements(&vec!["END"])?;
+    fn parse_loop_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
+        let mut loop_ = self.parse_keyword_with_statements(&[Keyword::END])?;
         loop_.node_type = NodeType::LoopStatement;
         self.next_token()?; // -> END
         let end = self.construct_node(NodeType::Keyword)?;
@@ -4279,14 +4752,14 @@ impl Parser {
             "end_loop",
             vec![end, self.construct_node(NodeType::Keyword)?],
         );
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             loop_.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(loop_)
     }
-    fn parse_repeat_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
-        let mut repeat = self.parse_keyword_with_statements(&vec!["UNTIL"])?;
+    fn parse_repeat_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
+        let mut repeat = self.parse_keyword_with_statements(&[Keyword::UNTIL])?;
         repeat.node_type = NodeType::RepeatStatement;
         self.next_token()?; // -> UNTIL
         let mut until = self.construct_node(NodeType::KeywordWithExpr)?;
@@ -4298,13 +4771,13 @@ impl Parser {
         repeat.push_node("until", until);
         self.next_token()?; // -> END
         repeat.push_node_vec("end_repeat", self.parse_n_keywords(2)?);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             repeat.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(repeat)
     }
-    fn parse_while_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_while_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut while_ = self.construct_node(NodeType::WhileStatement)?;
         self.next_token()?; // -> condition
         while_.push_node(
@@ -4312,7 +4785,7 @@ impl Parser {
             self.parse_expr(usize::MAX, false, false, false, true)?,
         );
         self.next_token()?; // -> DO
-        while_.push_node("do", self.parse_keyword_with_statements(&vec!["END"])?);
+        while_.push_node("do", self.parse_keyword_with_statements(&[Keyword::END])?);
         self.next_token()?; // -> END
         let end = self.construct_node(NodeType::Keyword)?;
         self.next_token()?; // -> WHILE
@@ -4320,21 +4793,21 @@ impl Parser {
             "end_while",
             vec![end, self.construct_node(NodeType::Keyword)?],
         );
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             while_.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(while_)
     }
-    fn parse_single_token_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_single_token_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut node = self.construct_node(NodeType::SingleTokenStatement)?;
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             node.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(node)
     }
-    fn parse_for_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_for_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut for_ = self.construct_node(NodeType::ForStatement)?;
         self.next_token()?; // -> ident
         for_.push_node("ident", self.construct_node(NodeType::Identifier)?);
@@ -4344,30 +4817,30 @@ impl Parser {
         in_.push_node("group", self.parse_select_statement(false, true)?);
         for_.push_node("in", in_);
         self.next_token()?; // -> DO
-        for_.push_node("do", self.parse_keyword_with_statements(&vec!["END"])?);
+        for_.push_node("do", self.parse_keyword_with_statements(&[Keyword::END])?);
         self.next_token()?; // -> END
         for_.push_node_vec("end_for", self.parse_n_keywords(2)?);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             for_.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(for_)
     }
-    fn parse_transaction_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_transaction_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut node = self.construct_node(NodeType::TransactionStatement)?;
-        if self.get_token(1)?.is("TRANSACTION") {
+        if self.get_token(1)?.is(Keyword::TRANSACTION) {
             self.next_token()?; // -> TRANSACTION
             node.push_node("transaction", self.construct_node(NodeType::Keyword)?);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             node.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(node)
     }
-    fn parse_raise_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_raise_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut raise = self.construct_node(NodeType::RaiseStatement)?;
-        if self.get_token(1)?.is("using") {
+        if self.get_token(1)?.is(Keyword::USING) {
             self.next_token()?; // -> USING
             let mut using = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // -> MESSAGE
@@ -4377,15 +4850,15 @@ impl Parser {
             );
             raise.push_node("using", using);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             raise.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(raise)
     }
-    fn parse_case_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_case_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut case = self.construct_node(NodeType::CaseStatement)?;
-        if !self.get_token(1)?.is("WHEN") {
+        if !self.get_token(1)?.is(Keyword::WHEN) {
             self.next_token()?; // -> expr
             case.push_node(
                 "expr",
@@ -4393,7 +4866,7 @@ impl Parser {
             );
         }
         let mut arms = Vec::new();
-        while self.get_token(1)?.is("WHEN") {
+        while self.get_token(1)?.is(Keyword::WHEN) {
             self.next_token()?; // -> WHEN
             let mut when = self.construct_node(NodeType::CaseStatementArm)?;
             self.next_token()?; // -> expr
@@ -4404,18 +4877,21 @@ impl Parser {
             self.next_token()?; // -> THEN
             when.push_node("then", self.construct_node(NodeType::Keyword)?);
             let mut stmts = Vec::new();
-            while !self.get_token(1)?.in_(&vec!["WHEN", "ELSE", "END"]) {
+            while !self
+                .get_token(1)?
+                .in_(&[Keyword::WHEN, Keyword::ELSE, Keyword::END])
+            {
                 self.next_token()?; // -> stmt
                 stmts.push(self.parse_statement(true)?);
             }
             when.push_node_vec("stmts", stmts);
             arms.push(when)
         }
-        if self.get_token(1)?.is("ELSE") {
+        if self.get_token(1)?.is(Keyword::ELSE) {
             self.next_token()?; // -> ELSE
             let mut else_ = self.construct_node(NodeType::CaseStatementArm)?;
             let mut stmts = Vec::new();
-            while !self.get_token(1)?.is("END") {
+            while !self.get_token(1)?.is(Keyword::END) {
                 self.next_token()?; // -> stmt
                 stmts.push(self.parse_statement(true)?);
             }
@@ -4425,32 +4901,32 @@ impl Parser {
         case.push_node_vec("arms", arms);
         self.next_token()?; // -> END
         case.push_node_vec("end_case", self.parse_n_keywords(2)?);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             case.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(case)
     }
-    fn parse_call_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_call_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut call = self.construct_node(NodeType::CallStatement)?;
         self.next_token()?; // -> procedure_name
         let procedure = self.parse_expr(usize::MAX, false, false, false, true)?;
         call.push_node("procedure", procedure);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             call.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(call)
     }
     // ----- debug -----
-    fn parse_assert_satement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_assert_satement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut assert = self.construct_node(NodeType::AssertStatement)?;
         self.next_token()?; // -> expr
         assert.push_node(
             "expr",
             self.parse_expr(usize::MAX, false, false, false, true)?,
         );
-        if self.get_token(1)?.is("AS") {
+        if self.get_token(1)?.is(Keyword::AS) {
             self.next_token()?; // -> AS
             assert.push_node("as", self.construct_node(NodeType::Keyword)?);
             self.next_token()?; // -> description
@@ -4459,18 +4935,18 @@ impl Parser {
                 self.parse_expr(usize::MAX, false, false, false, true)?,
             )
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             assert.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(assert)
     }
     // ----- other -----
-    fn parse_export_data_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_export_data_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut export = self.construct_node(NodeType::ExportDataStatement)?;
         self.next_token()?; // -> DATA
         export.push_node("data", self.construct_node(NodeType::Keyword)?);
-        if self.get_token(1)?.is("WITH") {
+        if self.get_token(1)?.is(Keyword::WITH) {
             self.next_token()?; // -> WITH
             export.push_node("with_connection", self.parse_with_connection_clause()?);
         }
@@ -4481,29 +4957,29 @@ impl Parser {
         self.next_token()?; // -> stmt
         as_.push_node("stmt", self.parse_statement(false)?);
         export.push_node("as", as_);
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             export.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(export)
     }
-    fn parse_export_model_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_export_model_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut export = self.construct_node(NodeType::ExportModelStatement)?;
         self.next_token()?; // -> MODEL
         export.push_node("what", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> ident
         export.push_node("ident", self.parse_identifier()?);
-        if self.get_token(1)?.is("OPTIONS") {
+        if self.get_token(1)?.is(Keyword::OPTIONS) {
             self.next_token()?; // -> OPTIONS
             export.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
         }
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             export.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(export)
     }
-    fn parse_load_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+    fn parse_load_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node<'a>> {
         let mut load = self.construct_node(NodeType::LoadStatement)?;
         self.next_token()?; // -> DATA
         load.push_node("data", self.construct_node(NodeType::Keyword)?);
@@ -4511,10 +4987,13 @@ impl Parser {
         load.push_node("into", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> ident
         load.push_node("ident", self.parse_identifier()?);
-        if self.get_token(1)?.in_(&vec!["OVERWRITE", "PARTITIONS"]) {
+        if self
+            .get_token(1)?
+            .in_(&[Keyword::OVERWRITE, Keyword::PARTITIONS])
+        {
             self.next_token()?; // -> OVERWRITE | PARTITIONS
             let mut op = self.construct_node(NodeType::OverwritePartitionsClause)?;
-            if self.get_token(0)?.is("OVERWRITE") {
+            if self.get_token(0)?.is(Keyword::OVERWRITE) {
                 let mut o = op;
                 o.node_type = NodeType::Keyword;
                 self.next_token()?; // -> PARTITIONS
@@ -4531,22 +5010,22 @@ impl Parser {
             );
             load.push_node("overwrite_partitions", op);
         }
-        if self.get_token(1)?.is("(") {
+        if self.get_token(1)?.is(Symbol::LParen) {
             self.next_token()?; // -> (
             load.push_node(
                 "column_group",
                 self.parse_grouped_type_declaration_or_constraints(false, false)?,
             );
         }
-        if self.get_token(1)?.is("PARTITION") {
+        if self.get_token(1)?.is(Keyword::PARTITION) {
             self.next_token()?; // -> PARTITION
             load.push_node("partitionby", self.parse_xxxby_exprs()?);
         }
-        if self.get_token(1)?.is("CLUSTER") {
+        if self.get_token(1)?.is(Keyword::CLUSTER) {
             self.next_token()?; // -> CLUSTER
             load.push_node("clusterby", self.parse_xxxby_exprs()?);
         }
-        if self.get_token(1)?.is("OPTIONS") {
+        if self.get_token(1)?.is(Keyword::OPTIONS) {
             self.next_token()?; // -> OPTIONS
             load.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
         }
@@ -4556,12 +5035,12 @@ impl Parser {
         load.push_node("files", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> from_files
         load.push_node("from_files", self.parse_grouped_exprs(false)?);
-        if self.get_token(1)?.is("WITH") && self.get_token(2)?.is("PARTITION") {
+        if self.get_token(1)?.is(Keyword::WITH) && self.get_token(2)?.is(Keyword::PARTITION) {
             self.next_token()?; // -> WITH
             let mut with = self.construct_node(NodeType::WithPartitionColumnsClause)?;
             self.next_token()?; // -> PARTITION
             with.push_node_vec("partition_columns", self.parse_n_keywords(2)?);
-            if self.get_token(1)?.is("(") {
+            if self.get_token(1)?.is(Symbol::LParen) {
                 self.next_token()?; // -> (
                 with.push_node(
                     "column_schema_group",
@@ -4570,7 +5049,7 @@ impl Parser {
             }
             load.push_node("with_partition_columns", with);
         }
-        if self.get_token(1)?.is("WITH") && self.get_token(2)?.is("CONNECTION") {
+        if self.get_token(1)?.is(Keyword::WITH) && self.get_token(2)?.is(Keyword::CONNECTION) {
             self.next_token()?; // -> WITH
             load.push_node("with", self.construct_node(NodeType::Keyword)?);
             self.next_token()?; // -> CONNECTION
@@ -4579,13 +5058,98 @@ impl Parser {
             load.push_node("connection_name", self.parse_identifier()?);
         }
 
-        if self.get_token(1)?.is(";") && semicolon {
+        if self.get_token(1)?.is(Symbol::Semicolon) && semicolon {
             self.next_token()?; // -> ;
             load.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
         }
         Ok(load)
     }
-    fn parse_standalone_expr(&mut self) -> BQ2CSTResult<Node> {
+    // ----- template -----
+    fn parse_template_block(&mut self) -> BQ2CSTResult<Node<'a>> {
+        let mut block = self.construct_node(NodeType::TemplateBlock)?;
+        self.push_jinja(&mut block)?;
+        let end_idx = self.find_template_end()?;
+        let mut body = Vec::new();
+        while self.get_offset_index(1)? < end_idx {
+            self.next_token()?; // -> fragment
+            body.push(self.parse_fragment(end_idx)?);
+        }
+        block.push_node_vec("body", body);
+        self.next_token()?; // -> {% endmacro %}
+        block.push_node("end", self.construct_node(NodeType::TemplateExprEnd)?);
+        Ok(block)
+    }
+    // the index of `{% endmacro %}` (or `{% endcall %}`) which closes the current tag
+    fn find_template_end(&self) -> BQ2CSTResult<usize> {
+        let name = self.get_token(0)?.template_name().unwrap_or_default();
+        let end_name = format!("end{}", name);
+        let mut depth = 0;
+        for idx in self.position + 1..self.tokens.len() {
+            let token = &self.tokens[idx];
+            match (token.get_template_type(), token.template_name()) {
+                (Some(TemplateType::ExprStart), Some(n)) if n == name => depth += 1,
+                (Some(TemplateType::ExprEnd), Some(n)) if n == end_name => {
+                    if depth == 0 {
+                        return Ok(idx);
+                    }
+                    depth -= 1;
+                }
+                _ => (),
+            }
+        }
+        Err(BQ2CSTError::from_token(
+            self.get_token(0)?,
+            format!("{{% {} %}} is not closed.", end_name),
+        ))
+    }
+    // a partial clause, a statement, an expression or a single token
+    fn parse_fragment(&mut self, end_idx: usize) -> BQ2CSTResult<Node<'a>> {
+        let org = self.save_state();
+        match self.parse_clause_fragment() {
+            Ok(fragment) if self.position < end_idx => return Ok(fragment),
+            _ => self.restore_state(org),
+        }
+        let org = self.save_state();
+        match self.parse_statement(true) {
+            // tokens which cannot start an expression (e.g. `)`) are left as Unknown
+            Ok(stmt) if self.position < end_idx && !has_unknown(&stmt) => return Ok(stmt),
+            _ => self.restore_state(org),
+        }
+        // `(` may be the start of an expression
+        let org = self.save_state();
+        match self.parse_standalone_expr() {
+            Ok(expr) if self.position < end_idx && !has_unknown(&expr) => return Ok(expr),
+            _ => self.restore_state(org),
+        }
+        // the token is kept as is
+        self.construct_node(NodeType::TemplateFragment)
+    }
+    // `WHERE x = 1`, `AND y = 2`, `, z` and so on
+    fn parse_clause_fragment(&mut self) -> BQ2CSTResult<Node<'a>> {
+        let mut fragment = self.construct_node(NodeType::TemplateFragment)?;
+        if !self.get_token(0)?.in_(FRAGMENT_KEYWORDS) && !self.get_token(0)?.is(Symbol::Comma) {
+            return Err(BQ2CSTError::from_token(
+                self.get_token(0)?,
+                "Expected a clause.".to_string(),
+            ));
+        }
+        let mut keywords = Vec::new();
+        while self.get_token(1)?.in_(FRAGMENT_KEYWORDS) {
+            self.next_token()?; // -> keyword
+            keywords.push(self.construct_node(NodeType::Keyword)?);
+        }
+        if !keywords.is_empty() {
+            fragment.push_node_vec("keywords", keywords);
+        }
+        if self.get_template_type(1)?.is_none()
+            || self.get_template_type(1)? == Some(TemplateType::Expr)
+        {
+            self.next_token()?; // -> exprs
+            fragment.push_node_vec("exprs", self.parse_exprs(&[], true, true)?);
+        }
+        Ok(fragment)
+    }
+    fn parse_standalone_expr(&mut self) -> BQ2CSTResult<Node<'a>> {
         let mut standalone_expr = self.construct_node(NodeType::StandAloneExpr)?;
         standalone_expr.children.remove("leading_comments");
         standalone_expr.children.remove("trailing_comments");
@@ -1,5 +1,6 @@
 use super::*;
 use crate::lexer::Lexer;
+use crate::schema;
 use difference::Changeset;
 
 mod tests_core;
@@ -22,6 +23,7 @@ struct SuccessTestCase {
     code: String,
     expected_output: String,
     target_idx: usize,
+    relaxed_macros: bool,
 }
 
 impl SuccessTestCase {
@@ -30,14 +32,20 @@ impl SuccessTestCase {
             code: code.to_string(),
             expected_output: expected_output.to_string(),
             target_idx,
+            relaxed_macros: false,
         }
     }
+    pub fn with_relaxed_macros(mut self) -> Self {
+        self.relaxed_macros = true;
+        self
+    }
 }
 
 impl TestCase for SuccessTestCase {
     fn test(&self) {
-        let l = Lexer::new(self.code.clone());
-        let mut p = Parser::new(l.tokenize_code().expect("Failed to tokenize code."));
+        let l = Lexer::new(&self.code);
+        let mut p = Parser::new(l.tokenize_code().expect("Failed to tokenize code."))
+            .with_relaxed_macros(self.relaxed_macros);
         let stmts = p.parse_code().expect("Failed to parse code.");
         println!(
             "\
@@ -47,9 +55,14 @@ impl TestCase for SuccessTestCase {
 ",
             self.code.trim()
         );
+        for stmt in &stmts {
+            if let Err(e) = schema::validate(stmt) {
+                panic!("The CST does not match the schema.\n{}", e);
+            }
+        }
         let result = stmts[self.target_idx].to_string();
         let changeset = Changeset::new(self.expected_output.as_str(), result.as_str(), "\n");
-        println!("{}\n", changeset.to_string());
+        println!("{}\n", changeset);
         assert_eq!(self.expected_output, result);
     }
 }
@@ -62,7 +75,7 @@ struct ErrorTestCase {
 
 impl ErrorTestCase {
     pub fn new(code: &str, expected_error_line: usize, expected_error_column: usize) -> Self {
-        let l = Lexer::new(code.to_string());
+        let l = Lexer::new(code);
         let mut p = Parser::new(l.tokenize_code().expect("Failed to tokenize code."));
         let error = match p.parse_code() {
             Ok(_) => panic!("Unexpectedly successed to parse code."),
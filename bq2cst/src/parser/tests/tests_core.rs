@@ -1975,3 +1975,45 @@ exprs:
         t.test();
     }
 }
+
+#[test]
+fn test_max_depth() {
+    let parse = |code: &str, max_depth: usize| {
+        let tokens = Lexer::new(code).tokenize_code().unwrap();
+        let res = Parser::new(tokens).with_max_depth(max_depth).parse_code();
+        res.map(|_| ())
+    };
+    // nested parentheses
+    assert!(parse("SELECT ((((1))));", 10).is_ok());
+    let error = parse("SELECT ((((((((((((1))))))))))));", 10).unwrap_err();
+    assert_eq!([error.line, error.column], [1, 16]);
+    // nested types
+    let error = parse("DECLARE x ARRAY<ARRAY<ARRAY<INT64>>>;", 3).unwrap_err();
+    assert_eq!([error.line, error.column], [1, 23]);
+    // each link of a chain is nested in the tree
+    let code = format!("SELECT {};", ["x"; 5].join(" AND "));
+    assert!(parse(&code, 10).is_ok());
+    let error = parse(&format!("SELECT {};", ["x"; 12].join(" AND ")), 10).unwrap_err();
+    assert_eq!([error.line, error.column], [1, 50]);
+    let code = format!("FROM t{};", " |> WHERE TRUE".repeat(5));
+    assert!(parse(&code, 10).is_ok());
+    let code = format!("FROM t{};", " |> WHERE TRUE".repeat(12));
+    assert!(parse(&code, 10).is_err());
+    let code = format!("SELECT 1{};", " UNION ALL SELECT 1".repeat(12));
+    assert!(parse(&code, 10).is_err());
+}
+
+#[test]
+fn test_long_chain() {
+    let chain = |n: usize| format!("SELECT * FROM t WHERE {};", vec!["a = 1"; n].join(" AND "));
+    // too long chains are rejected instead of overflowing the stack
+    let code = chain(50_000);
+    let tokens = Lexer::new(&code).tokenize_code().unwrap();
+    assert!(Parser::new(tokens).parse_code().is_err());
+    // chains within the limit are serialized
+    let code = chain(DEFAULT_MAX_DEPTH / 2);
+    let tokens = Lexer::new(&code).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    let json = serde_json::to_string(&crate::cst::Document::new(&stmts)).unwrap();
+    assert!(json.contains("BinaryOperator"));
+}
@@ -1,6 +1,6 @@
 use super::*;
 #[test]
-fn test_parse_code_core() {
+fn test_parse_code_template() {
     let test_cases: Vec<Box<dyn TestCase>> = vec![
         Box::new(SuccessTestCase::new(
             "\
@@ -14,6 +14,8 @@ exprs:
 - self: {{variable}} (TemplateExpr)
   comma:
     self: , (Symbol)
+  jinja:
+    self: variable (JinjaName)
 - self: {variable} (TemplateExpr)
   comma:
     self: , (Symbol)
@@ -30,6 +32,13 @@ SELECT 1
 self: None (StandAloneExpr)
 expr:
   self: {{ config() }} (TemplateExpr)
+  jinja:
+    self: ( (JinjaCall)
+    args: []
+    func:
+      self: config (JinjaName)
+    rparen:
+      self: ) (Symbol)
   leading_comments:
   - self: -- leading comment (Comment)
   trailing_comments:
@@ -49,6 +58,13 @@ SELECT 1;
 self: None (StandAloneExpr)
 expr:
   self: {{ config() }} (TemplateExpr)
+  jinja:
+    self: ( (JinjaCall)
+    args: []
+    func:
+      self: config (JinjaName)
+    rparen:
+      self: ) (Symbol)
 ",
             2,
         )),
@@ -86,6 +102,22 @@ exprs:
   - self: {{ i }} (TemplateExpr)
     comma:
       self: , (Symbol)
+    jinja:
+      self: i (JinjaName)
+  jinja:
+    self: for (JinjaStatement)
+    exprs:
+    - self: in (JinjaOperator)
+      left:
+        self: i (JinjaName)
+      right:
+        self: ( (JinjaCall)
+        args:
+        - self: 10 (JinjaLiteral)
+        func:
+          self: range (JinjaName)
+        rparen:
+          self: ) (Symbol)
 ",
             0,
         )),
@@ -108,6 +140,22 @@ exprs:
   - self: {{ i }} (TemplateExpr)
     comma:
       self: , (Symbol)
+    jinja:
+      self: i (JinjaName)
+  jinja:
+    self: for (JinjaStatement)
+    exprs:
+    - self: in (JinjaOperator)
+      left:
+        self: i (JinjaName)
+      right:
+        self: ( (JinjaCall)
+        args:
+        - self: 10 (JinjaLiteral)
+        func:
+          self: range (JinjaName)
+        rparen:
+          self: ) (Symbol)
 - self: 100 (NumericLiteral)
   comma:
     self: , (Symbol)
@@ -138,6 +186,10 @@ exprs:
     self: {% endif %} (TemplateExprEnd)
   exprs:
   - self: foo (Identifier)
+  jinja:
+    self: if (JinjaStatement)
+    exprs:
+    - self: true (JinjaName)
 - self: 100 (NumericLiteral)
   comma:
     self: , (Symbol)
@@ -156,6 +208,10 @@ exprs:
   end:
     self: {% endblock %} (TemplateExprEnd)
   exprs: []
+  jinja:
+    self: block (JinjaStatement)
+    exprs:
+    - self: exprs (JinjaName)
 ",
             0,
         )),
@@ -187,8 +243,40 @@ exprs:
         self: , (Symbol)
       left:
         self: {{ i }} (TemplateExpr)
+        jinja:
+          self: i (JinjaName)
       right:
         self: {{ j }} (TemplateExpr)
+        jinja:
+          self: j (JinjaName)
+    jinja:
+      self: for (JinjaStatement)
+      exprs:
+      - self: in (JinjaOperator)
+        left:
+          self: j (JinjaName)
+        right:
+          self: ( (JinjaCall)
+          args:
+          - self: 10 (JinjaLiteral)
+          func:
+            self: range (JinjaName)
+          rparen:
+            self: ) (Symbol)
+  jinja:
+    self: for (JinjaStatement)
+    exprs:
+    - self: in (JinjaOperator)
+      left:
+        self: i (JinjaName)
+      right:
+        self: ( (JinjaCall)
+        args:
+        - self: 10 (JinjaLiteral)
+        func:
+          self: range (JinjaName)
+        rparen:
+          self: ) (Symbol)
 - self: 100 (NumericLiteral)
   comma:
     self: , (Symbol)
@@ -229,11 +317,101 @@ where:
         self: {% endif %} (TemplateExprEnd)
       exprs:
       - self: foo (Identifier)
+      jinja:
+        self: if (JinjaStatement)
+        exprs:
+        - self: true (JinjaName)
     right:
       self: baz (Identifier)
 ",
             0,
         )),
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT {% raw %}{{ x }}, y{% endraw %}
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: {% raw %}{{ x }}, y{% endraw %} (TemplateExpr)
+",
+            0,
+        )),
+        Box::new(
+            SuccessTestCase::new(
+                "\
+{% macro filter_recent(days) %}
+WHERE dt >= {{ days }}
+  AND deleted
+{% endmacro %}
+",
+                "\
+self: {% macro filter_recent(days) %} (TemplateBlock)
+body:
+- self: WHERE (TemplateFragment)
+  exprs:
+  - self: AND (BinaryOperator)
+    left:
+      self: >= (BinaryOperator)
+      left:
+        self: dt (Identifier)
+      right:
+        self: {{ days }} (TemplateExpr)
+        jinja:
+          self: days (JinjaName)
+    right:
+      self: deleted (Identifier)
+end:
+  self: {% endmacro %} (TemplateExprEnd)
+jinja:
+  self: macro (JinjaStatement)
+  exprs:
+  - self: ( (JinjaCall)
+    args:
+    - self: days (JinjaName)
+    func:
+      self: filter_recent (JinjaName)
+    rparen:
+      self: ) (Symbol)
+",
+                0,
+            )
+            .with_relaxed_macros(),
+        ),
+        Box::new(
+            SuccessTestCase::new(
+                "\
+{% call statement('x') %}
+SELECT 1
+) -- broken
+{% endcall %}
+",
+                "\
+self: {% call statement('x') %} (TemplateBlock)
+body:
+- self: SELECT (SelectStatement)
+  exprs:
+  - self: 1 (NumericLiteral)
+- self: ) (TemplateFragment)
+  trailing_comments:
+  - self: -- broken (Comment)
+end:
+  self: {% endcall %} (TemplateExprEnd)
+jinja:
+  self: call (JinjaStatement)
+  exprs:
+  - self: ( (JinjaCall)
+    args:
+    - self: 'x' (JinjaLiteral)
+    func:
+      self: statement (JinjaName)
+    rparen:
+      self: ) (Symbol)
+",
+                0,
+            )
+            .with_relaxed_macros(),
+        ),
     ];
     for t in test_cases {
         t.test();
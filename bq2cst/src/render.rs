@@ -0,0 +1,272 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node};
+use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::token::{TemplateType, Token};
+use std::collections::HashMap;
+
+// NOTE
+// Templates are rendered roughly (only the first branch of `{% if %}` is kept and so on).
+// The purpose is to parse templated SQL, not to run it.
+
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    rendered: usize, // byte offset in the rendered code
+    original: usize, // byte offset in the original code
+    len: usize,
+    copied: bool, // false means the segment is generated from a template
+}
+
+#[derive(Debug, Clone)]
+enum Frame {
+    Keep,
+    Drop, // contents are not rendered
+}
+
+pub struct Rendered<'a> {
+    original: &'a str,
+    pub code: String,
+    segments: Vec<Segment>,
+    original_line_starts: Vec<usize>,
+    rendered_line_starts: Vec<usize>,
+}
+
+fn line_starts(code: &str) -> Vec<usize> {
+    let mut res = vec![0];
+    res.extend(code.match_indices('\n').map(|(i, _)| i + 1));
+    res
+}
+
+// `{{ x }}` -> `x`
+fn inner_text(tag: &str) -> &str {
+    let inner = &tag[2..tag.len() - 2];
+    inner
+        .trim_start_matches(['-', '+'])
+        .trim_end_matches(['-', '+'])
+        .trim()
+}
+
+fn tag_name(tag: &str) -> &str {
+    inner_text(tag)
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+}
+
+// returns the length of `{{ ... }}`, `{% ... %}` or `{# ... #}`
+fn read_tag(code: &str, start: usize) -> BQ2CSTResult<usize> {
+    let closing = match &code[start + 1..start + 2] {
+        "{" => "}}",
+        "%" => "%}",
+        _ => "#}",
+    };
+    let mut chars = code[start + 2..].char_indices();
+    let mut quote: Option<char> = None;
+    while let Some((i, ch)) = chars.next() {
+        match (quote, ch) {
+            (Some(_), '\\') => {
+                chars.next(); // escaped character
+            }
+            (Some(q), _) if ch == q => quote = None,
+            (Some(_), _) => (),
+            (None, '\'' | '"') if closing != "#}" => quote = Some(ch),
+            (None, _) if code[start + 2 + i..].starts_with(closing) => return Ok(i + 4),
+            _ => (),
+        }
+    }
+    Err(error_at(code, start, "Template is not closed."))
+}
+
+// returns the start of `{% endraw %}`
+fn find_endraw(code: &str, from: usize) -> BQ2CSTResult<Option<usize>> {
+    let mut position_ = from;
+    while let Some(i) = code[position_..].find("{%") {
+        let start = position_ + i;
+        let len = read_tag(code, start)?;
+        if tag_name(&code[start..start + len]) == "endraw" {
+            return Ok(Some(start));
+        }
+        position_ = start + len;
+    }
+    Ok(None)
+}
+
+fn error_at(code: &str, offset: usize, message: &str) -> BQ2CSTError {
+    let (line, column) = position(code, &line_starts(code), offset);
+    BQ2CSTError::new(line, column, message.to_string())
+}
+
+// byte offset -> (line, column)
+fn position(code: &str, line_starts: &[usize], offset: usize) -> (usize, usize) {
+    let line = line_starts.partition_point(|s| *s <= offset);
+    let line_start = line_starts[line - 1];
+    let column = code[line_start..offset].chars().count() + 1;
+    (line, column)
+}
+
+// (line, column) -> byte offset
+fn offset(code: &str, line_starts: &[usize], line: usize, column: usize) -> Option<usize> {
+    let line_start = *line_starts.get(line.checked_sub(1)?)?;
+    let rest = &code[line_start..];
+    match rest.char_indices().nth(column.checked_sub(1)?) {
+        Some((i, _)) => Some(line_start + i),
+        None => Some(code.len()),
+    }
+}
+
+impl<'a> Rendered<'a> {
+    // `substitute` receives each `{{ ... }}` and returns its rendered value.
+    // `None` means a dummy identifier is used instead.
+    #[allow(clippy::collapsible_match)]
+    pub fn new(
+        code: &'a str,
+        mut substitute: impl FnMut(&str) -> Option<String>,
+    ) -> BQ2CSTResult<Rendered<'a>> {
+        let mut rendered = String::new();
+        let mut segments = Vec::new();
+        let mut frames: Vec<Frame> = Vec::new();
+        let mut dummy_count = 0;
+        let mut position_ = 0;
+        let mut copy_from = 0;
+        macro_rules! push {
+            ($original:expr, $text:expr, $copied:expr) => {{
+                let text: &str = $text;
+                if !text.is_empty() {
+                    segments.push(Segment {
+                        rendered: rendered.len(),
+                        original: $original,
+                        len: text.len(),
+                        copied: $copied,
+                    });
+                    rendered.push_str(text);
+                }
+            }};
+        }
+        while let Some(i) = code[position_..].find('{') {
+            let start = position_ + i;
+            if !matches!(code.get(start + 1..start + 2), Some("{" | "%" | "#")) {
+                position_ = start + 1;
+                continue;
+            }
+            let dropping = frames.iter().any(|f| matches!(f, Frame::Drop));
+            if !dropping {
+                push!(copy_from, &code[copy_from..start], true);
+            }
+            let len = read_tag(code, start)?;
+            let tag = &code[start..start + len];
+            let mut end = start + len;
+            let name = tag_name(tag);
+            match Token::new(0, 0, tag).get_template_type() {
+                Some(TemplateType::Expr) if tag.starts_with("{{") => {
+                    if !dropping {
+                        let value = match substitute(inner_text(tag)) {
+                            Some(value) => value,
+                            None => {
+                                dummy_count += 1;
+                                format!("__tpl_{}__", dummy_count)
+                            }
+                        };
+                        push!(start, &value, false);
+                    }
+                }
+                // the body is rendered as is
+                Some(TemplateType::Expr) if name == "raw" => {
+                    let body_end = match find_endraw(code, end)? {
+                        Some(body_end) => body_end,
+                        None => return Err(error_at(code, start, "{% endraw %} is missing.")),
+                    };
+                    if !dropping {
+                        push!(end, &code[end..body_end], true);
+                    }
+                    end = body_end + read_tag(code, body_end)?;
+                }
+                Some(TemplateType::ExprStart) => match name {
+                    "macro" | "call" | "set" => frames.push(Frame::Drop),
+                    _ => frames.push(Frame::Keep),
+                },
+                // only the first branch is rendered
+                Some(TemplateType::ExprContinue) => {
+                    if let Some(last) = frames.last_mut() {
+                        *last = Frame::Drop;
+                    }
+                }
+                Some(TemplateType::ExprEnd) => {
+                    frames.pop();
+                }
+                _ => (), // comments and so on
+            }
+            position_ = end;
+            copy_from = end;
+        }
+        push!(copy_from, &code[copy_from..], true);
+        Ok(Rendered {
+            original: code,
+            original_line_starts: line_starts(code),
+            rendered_line_starts: line_starts(&rendered),
+            code: rendered,
+            segments,
+        })
+    }
+    pub fn with_substitutions(
+        code: &'a str,
+        substitutions: &HashMap<String, String>,
+    ) -> BQ2CSTResult<Rendered<'a>> {
+        Rendered::new(code, |inner| substitutions.get(inner).cloned())
+    }
+    // maps a position in the rendered code to the original code.
+    // generated text is mapped to the start of its template.
+    pub fn original_position(&self, line: usize, column: usize) -> (usize, usize) {
+        let rendered_offset = match offset(&self.code, &self.rendered_line_starts, line, column) {
+            Some(o) => o,
+            None => return (line, column),
+        };
+        let idx = self
+            .segments
+            .partition_point(|s| s.rendered <= rendered_offset);
+        let original_offset = match idx.checked_sub(1).map(|i| self.segments[i]) {
+            Some(s) if s.copied => s.original + (rendered_offset - s.rendered).min(s.len),
+            Some(s) => s.original,
+            None => 0,
+        };
+        position(self.original, &self.original_line_starts, original_offset)
+    }
+    fn map_token(&self, token: &mut Token) {
+        if token.line == usize::MAX {
+            return; // EOF
+        }
+        (token.line, token.column) = self.original_position(token.line, token.column);
+    }
+    fn map_node(&self, node: &mut Node) {
+        if let Some(token) = &mut node.token {
+            self.map_token(token);
+        }
+        for content in node.children.values_mut() {
+            match content {
+                ContentType::Node(n) => self.map_node(n),
+                ContentType::NodeVec(ns) => ns.iter_mut().for_each(|n| self.map_node(n)),
+            }
+        }
+    }
+    pub fn map_error(&self, mut error: BQ2CSTError) -> BQ2CSTError {
+        if error.line != usize::MAX {
+            (error.line, error.column) = self.original_position(error.line, error.column);
+        }
+        error
+    }
+    // parse the rendered code. positions of tokens and errors point to the original code.
+    pub fn parse(&self) -> BQ2CSTResult<Vec<Node<'_>>> {
+        let tokens = Lexer::new(&self.code)
+            .tokenize_code()
+            .map_err(|e| self.map_error(e))?;
+        let mut stmts = Parser::new(tokens)
+            .parse_code()
+            .map_err(|e| self.map_error(e))?;
+        for stmt in &mut stmts {
+            self.map_node(stmt);
+        }
+        Ok(stmts)
+    }
+}
@@ -0,0 +1,109 @@
+use super::*;
+
+#[test]
+fn test_render() {
+    let code = "\
+SELECT {{ col }}, 'x' AS s
+FROM my_{{ env }}_table {# comment #}
+{% if target.name == 'dev' %}
+WHERE dt = CURRENT_DATE()
+{% elif x %}
+WHERE dt = '{{ var(\"dt\") }}'
+{% endif %}
+{% raw %}{{ not rendered }}{% endraw %}
+";
+    let mut substitutions = HashMap::new();
+    substitutions.insert("col".to_string(), "amount".to_string());
+    let rendered = Rendered::with_substitutions(code, &substitutions).unwrap();
+    assert_eq!(
+        "\
+SELECT amount, 'x' AS s
+FROM my___tpl_1___table 
+
+WHERE dt = CURRENT_DATE()
+
+{{ not rendered }}
+",
+        rendered.code
+    );
+    // `amount`
+    assert_eq!((1, 8), rendered.original_position(1, 8));
+    assert_eq!((1, 8), rendered.original_position(1, 12));
+    // `'x'`
+    assert_eq!((1, 19), rendered.original_position(1, 16));
+    // `_table`
+    assert_eq!((2, 18), rendered.original_position(2, 18));
+    // `WHERE`
+    assert_eq!((4, 1), rendered.original_position(4, 1));
+    // `{{ not rendered }}`
+    assert_eq!((8, 10), rendered.original_position(6, 1));
+}
+
+#[test]
+fn test_render_dropped_blocks() {
+    let code = "\
+{% macro m(x) %}{{ x }} +{% endmacro %}
+{% for i in range(3) %}SELECT {{ i }}{% else %}SELECT 0{% endfor %};
+";
+    let rendered = Rendered::new(code, |_| None).unwrap();
+    assert_eq!("\nSELECT __tpl_1__;\n", rendered.code);
+}
+
+#[test]
+fn test_parse() {
+    let code = "\
+SELECT {{ dbt_utils.star(ref('a')) }}
+FROM {{ ref('a') }}
+WHERE x = {{ var('x') }};
+";
+    let rendered = Rendered::new(code, |_| None).unwrap();
+    let stmts = rendered.parse().unwrap();
+    assert_eq!(
+        "\
+self: SELECT (SelectStatement)
+exprs:
+- self: __tpl_1__ (Identifier)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: __tpl_2__ (Identifier)
+semicolon:
+  self: ; (Symbol)
+where:
+  self: WHERE (KeywordWithExpr)
+  expr:
+    self: = (BinaryOperator)
+    left:
+      self: x (Identifier)
+    right:
+      self: __tpl_3__ (Identifier)
+",
+        stmts[0].to_string()
+    );
+    let from = match &stmts[0].children["from"] {
+        ContentType::Node(n) => n,
+        _ => unreachable!(),
+    };
+    let table = match &from.children["expr"] {
+        ContentType::Node(n) => n.token.unwrap(),
+        _ => unreachable!(),
+    };
+    assert_eq!((2, 6), (table.line, table.column));
+    let semicolon = match &stmts[0].children["semicolon"] {
+        ContentType::Node(n) => n.token.unwrap(),
+        _ => unreachable!(),
+    };
+    assert_eq!((3, 25), (semicolon.line, semicolon.column));
+
+    // errors point to the original code
+    let code = "\
+SELECT {{ a }},
+FROM {{ b }} WHERE {{ c }} NOT;
+";
+    let rendered = Rendered::new(code, |_| None).unwrap();
+    let error = rendered.parse().err().unwrap();
+    assert_eq!((2, 31), (error.line, error.column));
+
+    let error = Rendered::new("SELECT {{ a", |_| None).err().unwrap();
+    assert_eq!((1, 8), (error.line, error.column));
+}
@@ -0,0 +1,415 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{escape_dot, ContentType, Node, NodeType};
+use crate::diagnostic::{Diagnostic, Severity, Span};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use serde::Serialize;
+
+// namespaces of built-in functions and system procedures (e.g. `NET.HOST()`, `BQ.JOBS_CANCEL()`)
+const BUILTIN_NAMESPACES: &[&str] = &[
+    "AEAD",
+    "AI",
+    "BQ",
+    "DETERMINISTIC_DECRYPT_BYTES",
+    "HLL_COUNT",
+    "KEYS",
+    "KLL_QUANTILES",
+    "ML",
+    "NET",
+    "OBJ",
+    "SAFE",
+    "VECTOR_INDEX",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RoutineKind {
+    Function,
+    AggregateFunction,
+    TableFunction,
+    Procedure,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ParameterMode {
+    In,
+    Out,
+    InOut,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Parameter {
+    pub name: String,
+    pub mode: ParameterMode,
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Routine {
+    pub file: String,
+    pub name: Vec<String>,
+    pub kind: RoutineKind,
+    pub temp: bool,
+    pub parameters: Vec<Parameter>,
+    pub returns: Option<String>,
+    pub language: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Call {
+    pub file: String,
+    // `None` means the top level of the file
+    pub caller: Option<usize>,
+    pub name: Vec<String>,
+    pub kind: RoutineKind,
+    pub args: usize,
+    pub routine: Option<usize>,
+    pub span: Span,
+    // whether each argument is a variable, which OUT and INOUT parameters require
+    #[serde(skip)]
+    variables: Vec<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileDiagnostic {
+    pub file: String,
+    #[serde(flatten)]
+    pub diagnostic: Diagnostic,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Project {
+    pub routines: Vec<Routine>,
+    pub calls: Vec<Call>,
+    pub diagnostics: Vec<FileDiagnostic>,
+}
+
+fn is_builtin(name: &[String]) -> bool {
+    name.len() == 2
+        && BUILTIN_NAMESPACES
+            .iter()
+            .any(|ns| name[0].eq_ignore_ascii_case(ns))
+}
+
+// unresolved calls of unqualified functions (e.g. `COUNT(*)`) are assumed to be built-in
+fn is_builtin_call(call: &Call) -> bool {
+    (call.kind != RoutineKind::Procedure && call.name.len() == 1) || is_builtin(&call.name)
+}
+
+// `ds.f` matches `proj.ds.f` (the default project) and vice versa
+fn matches(call: &[String], definition: &[String]) -> bool {
+    call.iter()
+        .rev()
+        .zip(definition.iter().rev())
+        .all(|(a, b)| a.eq_ignore_ascii_case(b))
+}
+
+fn parse_error(file: &str, line: usize, column: usize, message: &str) -> FileDiagnostic {
+    FileDiagnostic {
+        file: file.to_string(),
+        diagnostic: Diagnostic {
+            severity: Severity::Error,
+            code: "parse-error",
+            message: message.to_string(),
+            span: Span {
+                line,
+                column,
+                end_line: line,
+                end_column: column,
+            },
+        },
+    }
+}
+
+struct Indexer<'p> {
+    project: &'p mut Project,
+    file: String,
+    caller: Option<usize>,
+}
+
+impl Indexer<'_> {
+    fn node(&mut self, node: &Node) {
+        match node.node_type {
+            NodeType::CreateFunctionStatement | NodeType::CreateProcedureStatement => {
+                let caller = self.caller;
+                self.caller = self.definition(node);
+                self.children(node);
+                self.caller = caller;
+                return;
+            }
+            NodeType::CallStatement => {
+                if let Some(procedure) = node.get_node("procedure") {
+                    self.call(procedure, RoutineKind::Procedure);
+                    for arg in procedure.get_node_vec("args") {
+                        self.node(arg);
+                    }
+                }
+                return;
+            }
+            NodeType::CallingFunction => self.call(node, RoutineKind::Function),
+            NodeType::CallingTableFunction => self.call(node, RoutineKind::TableFunction),
+            _ => (),
+        }
+        self.children(node);
+    }
+    fn children(&mut self, node: &Node) {
+        for content in node.children.values() {
+            match content {
+                ContentType::Node(n) => self.node(n),
+                ContentType::NodeVec(ns) => {
+                    for n in ns {
+                        self.node(n);
+                    }
+                }
+            }
+        }
+    }
+    fn definition(&mut self, node: &Node) -> Option<usize> {
+        let name = node.get_node("ident")?.identifier_path()?;
+        let kind = match node.node_type {
+            NodeType::CreateProcedureStatement => RoutineKind::Procedure,
+            _ if node.get_node("table").is_some() => RoutineKind::TableFunction,
+            _ if node.get_node("aggregate").is_some() => RoutineKind::AggregateFunction,
+            _ => RoutineKind::Function,
+        };
+        let mut parameters = Vec::new();
+        if let Some(group) = node.get_node("group") {
+            for declaration in group.get_node_vec("declarations") {
+                let mode = match declaration.get_node("in_out").and_then(|n| n.token) {
+                    Some(t) if t.literal.eq_ignore_ascii_case("OUT") => ParameterMode::Out,
+                    Some(t) if t.literal.eq_ignore_ascii_case("INOUT") => ParameterMode::InOut,
+                    _ => ParameterMode::In,
+                };
+                parameters.push(Parameter {
+                    name: declaration
+                        .token
+                        .map(|t| t.literal.to_string())
+                        .unwrap_or_default(),
+                    mode,
+                    type_: declaration
+                        .get_node("type")
+                        .map(Node::type_text)
+                        .unwrap_or_default(),
+                });
+            }
+        }
+        let language = node
+            .get_node("language")
+            .and_then(|n| n.get_node("expr"))
+            .and_then(|expr| expr.token)
+            .map_or("SQL".to_string(), |t| t.literal.to_uppercase());
+        self.project.routines.push(Routine {
+            file: self.file.clone(),
+            name,
+            kind,
+            temp: node.get_node("temp").is_some(),
+            parameters,
+            returns: node
+                .get_node("returns")
+                .and_then(|n| n.get_node("type"))
+                .map(Node::type_text),
+            language,
+            span: Span::from_token(&node.token?),
+        });
+        Some(self.project.routines.len() - 1)
+    }
+    fn call(&mut self, node: &Node, kind: RoutineKind) {
+        let name = match node.get_node("func").and_then(|n| n.identifier_path()) {
+            Some(name) => name,
+            None => return,
+        };
+        // the first part of the name
+        let mut func = match node.get_node("func") {
+            Some(func) => func,
+            None => return,
+        };
+        while let Some(left) = func.get_node("left") {
+            func = left;
+        }
+        let span = match func.token {
+            Some(token) => Span::from_token(&token),
+            None => return,
+        };
+        let args = node.get_node_vec("args");
+        self.project.calls.push(Call {
+            file: self.file.clone(),
+            caller: self.caller,
+            name,
+            kind,
+            args: args.len(),
+            routine: None,
+            span,
+            variables: args
+                .iter()
+                .map(|a| a.node_type == NodeType::Identifier)
+                .collect(),
+        });
+    }
+}
+
+impl Project {
+    // `files` are pairs of a path and its content
+    pub fn new(files: &[(&str, &str)]) -> Project {
+        let mut project = Project {
+            routines: Vec::new(),
+            calls: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+        for (file, code) in files {
+            let tokens = match Lexer::new(code).tokenize_code() {
+                Ok(tokens) => tokens,
+                Err(e) => {
+                    let diagnostic = parse_error(file, e.line, e.column, e.message());
+                    project.diagnostics.push(diagnostic);
+                    continue;
+                }
+            };
+            let stmts = match Parser::new(tokens).parse_code() {
+                Ok(stmts) => stmts,
+                Err(e) => {
+                    let diagnostic = parse_error(file, e.line, e.column, e.message());
+                    project.diagnostics.push(diagnostic);
+                    continue;
+                }
+            };
+            let mut indexer = Indexer {
+                project: &mut project,
+                file: file.to_string(),
+                caller: None,
+            };
+            for stmt in &stmts {
+                indexer.node(stmt);
+            }
+        }
+        project.resolve();
+        project
+    }
+    fn lookup(&self, call: &Call) -> Option<usize> {
+        let candidates = self.routines.iter().enumerate().filter(|(_, r)| {
+            let kind = match r.kind {
+                RoutineKind::AggregateFunction => RoutineKind::Function,
+                kind => kind,
+            };
+            kind == call.kind
+                && (!r.temp || r.file == call.file)
+                && (r.name.len() == 1) == (call.name.len() == 1)
+                && matches(&call.name, &r.name)
+        });
+        // prefer routines in the same file
+        let mut first = None;
+        for (i, r) in candidates {
+            if r.file == call.file {
+                return Some(i);
+            }
+            first = first.or(Some(i));
+        }
+        first
+    }
+    fn resolve(&mut self) {
+        let mut diagnostics = Vec::new();
+        for i in 0..self.calls.len() {
+            let routine = self.lookup(&self.calls[i]);
+            let call = &mut self.calls[i];
+            call.routine = routine;
+            let error = |code, message| FileDiagnostic {
+                file: call.file.clone(),
+                diagnostic: Diagnostic {
+                    severity: Severity::Error,
+                    code,
+                    message,
+                    span: call.span,
+                },
+            };
+            match routine {
+                Some(r) => {
+                    let parameters = &self.routines[r].parameters;
+                    let expected = parameters.len();
+                    for (parameter, variable) in parameters.iter().zip(&call.variables) {
+                        if parameter.mode != ParameterMode::In && !variable {
+                            diagnostics.push(error(
+                                "invalid-out-argument",
+                                format!(
+                                    "The argument for {:?} parameter `{}` must be a variable.",
+                                    parameter.mode, parameter.name
+                                ),
+                            ));
+                        }
+                    }
+                    if call.args != expected {
+                        diagnostics.push(error(
+                            "arity-mismatch",
+                            format!(
+                                "{} expects {} arguments but {} were given.",
+                                call.name.join("."),
+                                expected,
+                                call.args
+                            ),
+                        ));
+                    }
+                }
+                None if is_builtin_call(call) => (),
+                None => {
+                    let mut diagnostic = error(
+                        "unknown-routine",
+                        format!("The routine is not defined: {}", call.name.join(".")),
+                    );
+                    diagnostic.diagnostic.severity = Severity::Warning;
+                    diagnostics.push(diagnostic);
+                }
+            }
+        }
+        // only calls of user-defined routines are kept
+        self.calls
+            .retain(|call| call.routine.is_some() || !is_builtin_call(call));
+        self.diagnostics.extend(diagnostics);
+        self.diagnostics.sort_by(|a, b| {
+            (&a.file, a.diagnostic.span.line, a.diagnostic.span.column).cmp(&(
+                &b.file,
+                b.diagnostic.span.line,
+                b.diagnostic.span.column,
+            ))
+        });
+    }
+    // caller -> callee, files stand for their top level statements
+    pub fn to_dot(&self) -> String {
+        let mut res = vec!["digraph {".to_string(), "  node [shape=box];".to_string()];
+        let mut files: Vec<&str> = self.calls.iter().map(|c| c.file.as_str()).collect();
+        files.sort();
+        files.dedup();
+        for (i, file) in files.iter().enumerate() {
+            res.push(format!(
+                "  f{} [label=\"{}\", shape=note];",
+                i,
+                escape_dot(file)
+            ));
+        }
+        for (i, routine) in self.routines.iter().enumerate() {
+            res.push(format!(
+                "  r{} [label=\"{}\"];",
+                i,
+                escape_dot(&routine.name.join("."))
+            ));
+        }
+        let mut edges = Vec::new();
+        for call in &self.calls {
+            let to = match call.routine {
+                Some(r) => format!("r{}", r),
+                None => continue,
+            };
+            let from = match call.caller {
+                Some(r) => format!("r{}", r),
+                None => format!("f{}", files.iter().position(|f| *f == call.file).unwrap()),
+            };
+            let edge = format!("  {} -> {};", from, to);
+            if !edges.contains(&edge) {
+                edges.push(edge);
+            }
+        }
+        res.extend(edges);
+        res.push("}".to_string());
+        res.join("\n") + "\n"
+    }
+}
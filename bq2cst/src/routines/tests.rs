@@ -0,0 +1,170 @@
+use super::*;
+
+fn diagnostics(project: &Project) -> Vec<(&str, &'static str, usize, usize)> {
+    project
+        .diagnostics
+        .iter()
+        .map(|d| {
+            let span = d.diagnostic.span;
+            (d.file.as_str(), d.diagnostic.code, span.line, span.column)
+        })
+        .collect()
+}
+
+#[test]
+fn test_routines() {
+    let project = Project::new(&[
+        (
+            "a.sql",
+            "\
+CREATE TEMP FUNCTION f(x INT64, y ARRAY<STRUCT<a NUMERIC(10, 2), b ANY TYPE>>)
+RETURNS INT64 LANGUAGE js AS '1';
+CREATE OR REPLACE TABLE FUNCTION ds.tf(a INT64) RETURNS TABLE<x INT64> AS SELECT 1;
+CREATE AGGREGATE FUNCTION `proj.ds.agg`(x FLOAT64) AS (SUM(x));
+",
+        ),
+        (
+            "b.sql",
+            "\
+CREATE PROCEDURE ds.p(IN a INT64, OUT b STRING, INOUT c INT64)
+BEGIN
+  SELECT * FROM proj.ds.tf(a);
+END;
+",
+        ),
+    ]);
+    let summary = project
+        .routines
+        .iter()
+        .map(|r| {
+            (
+                r.name.join("."),
+                r.kind,
+                r.temp,
+                r.returns.clone(),
+                r.language.as_str(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "f".to_string(),
+                RoutineKind::Function,
+                true,
+                Some("INT64".to_string()),
+                "JS"
+            ),
+            (
+                "ds.tf".to_string(),
+                RoutineKind::TableFunction,
+                false,
+                Some("TABLE<x INT64>".to_string()),
+                "SQL"
+            ),
+            (
+                "proj.ds.agg".to_string(),
+                RoutineKind::AggregateFunction,
+                false,
+                None,
+                "SQL"
+            ),
+            (
+                "ds.p".to_string(),
+                RoutineKind::Procedure,
+                false,
+                None,
+                "SQL"
+            ),
+        ]
+    );
+    assert_eq!(
+        project.routines[0].parameters[1],
+        Parameter {
+            name: "y".to_string(),
+            mode: ParameterMode::In,
+            type_: "ARRAY<STRUCT<a NUMERIC(10, 2), b ANY TYPE>>".to_string(),
+        }
+    );
+    let modes = project.routines[3]
+        .parameters
+        .iter()
+        .map(|p| p.mode)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        modes,
+        vec![ParameterMode::In, ParameterMode::Out, ParameterMode::InOut]
+    );
+    // the table function is called from the procedure, `SUM(x)` is not recorded
+    assert_eq!(project.calls.len(), 1);
+    assert_eq!(project.calls[0].caller, Some(3));
+    assert_eq!(project.calls[0].routine, Some(1));
+}
+
+#[test]
+fn test_calls() {
+    let project = Project::new(&[
+        (
+            "lib.sql",
+            "\
+CREATE FUNCTION ds.f(x INT64) AS (x);
+CREATE PROCEDURE ds.p(x INT64, OUT y INT64) BEGIN SELECT ds.f(x); END;
+CREATE TEMP FUNCTION g() AS (1);
+",
+        ),
+        (
+            "main.sql",
+            "\
+DECLARE y INT64;
+CALL proj.ds.p(1, y);
+CALL ds.p(1, 2);
+CALL ds.unknown();
+CALL BQ.REFRESH_MATERIALIZED_VIEW('ds.mv');
+SELECT ds.f(1, 2), g(), NET.HOST('x'), SAFE.ds.f(1);
+",
+        ),
+        ("broken.sql", "SELECT 'abc\n"),
+    ]);
+    assert_eq!(
+        diagnostics(&project),
+        vec![
+            ("broken.sql", "parse-error", 2, 1),
+            ("main.sql", "invalid-out-argument", 3, 6),
+            ("main.sql", "unknown-routine", 4, 6),
+            ("main.sql", "arity-mismatch", 6, 8),
+        ]
+    );
+    let expected = "\
+digraph {
+  node [shape=box];
+  f0 [label=\"lib.sql\", shape=note];
+  f1 [label=\"main.sql\", shape=note];
+  r0 [label=\"ds.f\"];
+  r1 [label=\"ds.p\"];
+  r2 [label=\"g\"];
+  r1 -> r0;
+  f1 -> r1;
+  f1 -> r0;
+}
+";
+    assert_eq!(project.to_dot(), expected);
+    // built-in functions and procedures are not recorded (`g` is temporary in lib.sql)
+    let mut calls = project
+        .calls
+        .iter()
+        .map(|c| (c.span.line, c.span.column, c.name.join(".")))
+        .collect::<Vec<_>>();
+    calls.sort();
+    assert_eq!(
+        calls,
+        vec![
+            (2, 6, "proj.ds.p".to_string()),
+            (2, 58, "ds.f".to_string()),
+            (3, 6, "ds.p".to_string()),
+            (4, 6, "ds.unknown".to_string()),
+            (6, 8, "ds.f".to_string()),
+            (6, 40, "SAFE.ds.f".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,2106 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node, NodeType};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+// NOTE
+// This is the single source of truth about which children each node has.
+// `src/bq2cst.d.ts` is generated from it (`make dts`)
+// and every node the parser produces is checked against it in tests.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenSchema {
+    Inherit,
+    Nullable, // Token | null
+    NonNull,  // Token
+    Null,     // null
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChildKind {
+    Node,
+    NodeVec,
+    Undefined, // the key inherited from the parent type never appears
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ChildrenSchema {
+    None,
+    Own(&'static [ChildSchema]),
+    SameAs(&'static str), // X["children"]
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ChildSchema {
+    pub key: &'static str,
+    pub kind: ChildKind,
+    pub required: bool,
+    // name of node types (concrete or abstract), empty means any node
+    pub allowed: &'static [&'static str],
+    pub note: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NodeSchema {
+    name: Option<&'static str>,
+    pub node_type: Option<NodeType>, // `None` means abstract type
+    pub extends: &'static [&'static str],
+    pub token: TokenSchema,
+    pub children: ChildrenSchema,
+    pub note: Option<&'static str>,
+}
+
+// ----- builders -----
+const fn abstract_node(name: &'static str) -> NodeSchema {
+    NodeSchema {
+        name: Some(name),
+        node_type: None,
+        extends: &[],
+        token: TokenSchema::Inherit,
+        children: ChildrenSchema::None,
+        note: None,
+    }
+}
+
+const fn node(node_type: NodeType) -> NodeSchema {
+    NodeSchema {
+        name: None,
+        node_type: Some(node_type),
+        extends: &[],
+        token: TokenSchema::Inherit,
+        children: ChildrenSchema::None,
+        note: None,
+    }
+}
+
+// in the case that the name conflicts with TypeScript built-ins
+const fn named_node(node_type: NodeType, name: &'static str) -> NodeSchema {
+    let mut res = node(node_type);
+    res.name = Some(name);
+    res
+}
+
+const fn child(key: &'static str, kind: ChildKind) -> ChildSchema {
+    ChildSchema {
+        key,
+        kind,
+        required: true,
+        allowed: &[],
+        note: None,
+    }
+}
+
+const fn node_child(key: &'static str) -> ChildSchema {
+    child(key, ChildKind::Node)
+}
+
+const fn node_vec_child(key: &'static str) -> ChildSchema {
+    child(key, ChildKind::NodeVec)
+}
+
+const fn undefined_child(key: &'static str) -> ChildSchema {
+    child(key, ChildKind::Undefined)
+}
+
+impl NodeSchema {
+    const fn extends(mut self, parents: &'static [&'static str]) -> NodeSchema {
+        self.extends = parents;
+        self
+    }
+    const fn token(mut self) -> NodeSchema {
+        self.token = TokenSchema::NonNull;
+        self
+    }
+    const fn null_token(mut self) -> NodeSchema {
+        self.token = TokenSchema::Null;
+        self
+    }
+    const fn nullable_token(mut self) -> NodeSchema {
+        self.token = TokenSchema::Nullable;
+        self
+    }
+    const fn children(mut self, children: &'static [ChildSchema]) -> NodeSchema {
+        self.children = ChildrenSchema::Own(children);
+        self
+    }
+    const fn children_of(mut self, name: &'static str) -> NodeSchema {
+        self.children = ChildrenSchema::SameAs(name);
+        self
+    }
+    const fn note(mut self, note: &'static str) -> NodeSchema {
+        self.note = Some(note);
+        self
+    }
+    pub fn name(&self) -> String {
+        match (self.name, &self.node_type) {
+            (Some(name), _) => name.to_string(),
+            (None, Some(node_type)) => format!("{:?}", node_type),
+            (None, None) => unreachable!("abstract types always have their name"),
+        }
+    }
+    pub fn is_abstract(&self) -> bool {
+        self.node_type.is_none()
+    }
+}
+
+impl ChildSchema {
+    const fn optional(mut self) -> ChildSchema {
+        self.required = false;
+        self
+    }
+    const fn of(mut self, allowed: &'static [&'static str]) -> ChildSchema {
+        self.allowed = allowed;
+        self
+    }
+    const fn note(mut self, note: &'static str) -> ChildSchema {
+        self.note = Some(note);
+        self
+    }
+}
+
+// ----- schema -----
+pub static SCHEMA: &[NodeSchema] = &[
+    abstract_node("BaseNode").nullable_token().children(&[
+        node_vec_child("leading_comments")
+            .of(&["Comment"])
+            .optional(),
+        node_vec_child("trailing_comments")
+            .of(&["Comment"])
+            .optional(),
+    ]),
+    // ----- sub types of BaseNode (abstract) -----
+    abstract_node("CallingFunctionGeneral")
+        .extends(&["Expr"])
+        .children(&[
+            node_child("func").of(&["IdentifierGeneral", "GroupedExpr", "FunctionChain"]),
+            node_child("distinct").optional(),
+            node_vec_child("args")
+                .of(&[
+                    "Expr",
+                    "SelectStatement",
+                    "SetOperator",
+                    "CastArgument",
+                    "ExtractArgument",
+                ])
+                .optional(),
+            node_vec_child("ignore_nulls").optional(),
+            node_child("orderby").optional(),
+            node_child("limit").optional(),
+            node_child("having").optional(),
+            node_child("rparen"),
+            node_child("over").optional(),
+        ]),
+    abstract_node("Expr")
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("as").of(&["Keyword"]).optional(),
+            node_child("alias").of(&["Expr"]).optional(),
+            node_child("row_value_alias")
+                .optional()
+                .note("only in UNPIVOT operator"),
+            node_child("comma").optional(),
+            node_child("order").optional(),
+            node_vec_child("null_order").optional(),
+        ]),
+    abstract_node("FromItemExpr").extends(&["Expr"]).children(&[
+        node_child("with_offset").optional(),
+        node_child("pivot").of(&["PivotOperator"]).optional(),
+        node_child("unpivot").of(&["UnpivotOperator"]).optional(),
+        node_child("match_recognize").optional(),
+    ]),
+    abstract_node("LabelableStatement")
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("leading_label").optional(),
+            node_child("colon").optional(),
+            node_child("trailing_label").optional(),
+        ]),
+    abstract_node("IdentifierGeneral")
+        .extends(&["FromItemExpr"])
+        .children(&[
+            node_child("tablesample")
+                .optional()
+                .note("TABLESAMPLE SYSTEM can only be applied directly to base tables"),
+            node_child("for_system_time_as_of").optional(),
+        ]),
+    abstract_node("XXXStatement")
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_child("semicolon").of(&["Symbol_"]).optional()]),
+    abstract_node("PipeOperator")
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("keywords").optional(),
+            node_vec_child("exprs").optional(),
+        ]),
+    // the contents of Jinja templates
+    abstract_node("JinjaExpr").extends(&["BaseNode"]).token(),
+    // ----- sub types of BaseNode (concrete) -----
+    node(NodeType::AddColumnClause)
+        .extends(&["BaseNode"])
+        .children(&[
+            node_child("what"),
+            node_vec_child("if_not_exists").optional(),
+            node_child("type_declaration"),
+            node_child("comma").optional(),
+        ]),
+    node(NodeType::AddConstraintClause)
+        .extends(&["BaseNode"])
+        .children(&[
+            node_child("what").optional(),
+            node_child("comma").optional(),
+        ]),
+    node(NodeType::AggregatePipeOperator)
+        .extends(&["PipeOperator"])
+        .children(&[node_child("group_and_order_by").optional()]),
+    node(NodeType::AlterBICapacityStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("what"),
+            node_child("ident"),
+            node_child("set"),
+            node_child("options"),
+        ]),
+    node(NodeType::AlterColumnStatement)
+        .extends(&["BaseNode"])
+        .note("NOTE this is not XXXStatement!")
+        .children(&[
+            node_child("what"),
+            node_vec_child("if_exists").optional(),
+            node_child("ident"),
+            node_child("set").optional().note("SET"),
+            node_child("options").optional(),
+            node_vec_child("data_type").optional(),
+            node_child("type").optional(),
+            node_child("default").optional(),
+            node_vec_child("drop_not_null").optional().note("DROP"),
+            node_vec_child("drop_default").optional(),
+        ]),
+    node(NodeType::AlterModelStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("what"),
+            node_vec_child("if_exists").optional(),
+            node_child("ident"),
+            node_child("set"),
+            node_child("options"),
+        ]),
+    node(NodeType::AlterOrganizationStatement)
+        .extends(&["XXXStatement"])
+        .children(&[node_child("what"), node_child("set"), node_child("options")]),
+    node(NodeType::AlterProjectStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("what"),
+            node_child("ident").optional(),
+            node_child("set"),
+            node_child("options"),
+        ]),
+    node(NodeType::AlterReservationStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("what"),
+            node_child("ident"),
+            node_child("set"),
+            node_child("options"),
+        ]),
+    node(NodeType::AlterSchemaStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("what"),
+            node_vec_child("if_exists").optional(),
+            node_child("ident"),
+            node_child("set").optional(),
+            node_child("add").optional(),
+            node_child("drop").optional(),
+            node_child("default_collate").optional(),
+            node_child("options").optional(),
+        ]),
+    node(NodeType::AlterTableDropClause)
+        .extends(&["BaseNode"])
+        .children(&[
+            node_child("what"),
+            node_vec_child("if_exists").optional(),
+            node_child("ident").optional(),
+            node_child("comma").optional(),
+        ]),
+    node(NodeType::AlterTableStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("what"),
+            node_vec_child("if_exists").optional(),
+            node_child("ident"),
+            node_child("set").optional().note("SET"),
+            node_child("options").optional(),
+            node_child("default_collate").optional(),
+            node_vec_child("add_columns").optional().note("ADD COLUMN"),
+            node_vec_child("add_constraints")
+                .optional()
+                .note("ADD CONSTRAINT"),
+            node_child("rename").optional().note("RENAME TO"),
+            node_child("to").optional(),
+            node_vec_child("rename_columns")
+                .optional()
+                .note("RENAME COLUMN"),
+            node_vec_child("drop_columns")
+                .optional()
+                .note("DROP COLUMN"),
+            node_child("alter_column_stmt")
+                .optional()
+                .note("ALTER COLUMN statement"),
+        ]),
+    node(NodeType::AlterVectorIndexStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("what"),
+            node_vec_child("if_exists").optional(),
+            node_child("ident"),
+            node_child("on"),
+            node_child("operation"),
+        ]),
+    node(NodeType::AlterViewStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("materialized").optional(),
+            node_child("what"),
+            node_vec_child("if_exists").optional(),
+            node_child("ident"),
+            node_child("set").optional().note("SET"),
+            node_child("options").optional(),
+            node_child("alter_column_stmt")
+                .optional()
+                .note("ALTER COLUMN statement"),
+        ]),
+    node(NodeType::AccessOperator)
+        .extends(&["Expr"])
+        .children(&[
+            undefined_child("not"),
+            node_child("left"),
+            node_child("right"),
+            node_child("rparen"),
+        ]),
+    node(NodeType::ArrayLiteral).extends(&["Expr"]).children(&[
+        node_child("type").optional(),
+        node_vec_child("exprs"),
+        node_child("rparen"),
+    ]),
+    node(NodeType::AssertStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("expr"),
+            node_child("as").optional(),
+            node_child("description").optional(),
+        ]),
+    node(NodeType::Asterisk).extends(&["Expr"]).children(&[
+        node_child("except")
+            .of(&["KeywordWithGroupedXXX"])
+            .optional(),
+        node_child("replace")
+            .of(&["KeywordWithGroupedXXX"])
+            .optional(),
+        undefined_child("order"),
+        undefined_child("null_order"),
+    ]),
+    node(NodeType::BasePipeOperator).extends(&["PipeOperator"]),
+    node(NodeType::BinaryOperator)
+        .extends(&["Expr"])
+        .children(&[
+            node_child("not").optional(),
+            node_child("left").of(&["Expr"]),
+            node_child("quantifier").optional(),
+            node_child("right").of(&["Expr"]),
+        ]),
+    node(NodeType::BeginStatement)
+        .extends(&["LabelableStatement"])
+        .children(&[
+            node_vec_child("stmts").optional(),
+            node_vec_child("exception_when_error").optional(),
+            node_child("then").optional(),
+            node_child("end"),
+        ]),
+    node(NodeType::BetweenOperator)
+        .extends(&["Expr"])
+        .children(&[
+            node_child("left"),
+            node_child("not").optional(),
+            node_child("right_min"),
+            node_child("right_max"),
+            node_child("and"),
+        ]),
+    node(NodeType::BooleanLiteral).extends(&["Expr"]),
+    node(NodeType::BreakContinueStatement)
+        .extends(&["XXXStatement"])
+        .children(&[node_child("label").optional()]),
+    node(NodeType::CallingFunction).extends(&["CallingFunctionGeneral"]),
+    node(NodeType::CallingTableFunction)
+        .extends(&["FromItemExpr", "CallingFunctionGeneral"])
+        .children(&[
+            undefined_child("distinct"),
+            undefined_child("ignore_nulls"),
+            undefined_child("orderby"),
+            undefined_child("limit"),
+            undefined_child("having"),
+            undefined_child("over"),
+            undefined_child("comma"),
+            undefined_child("order"),
+            undefined_child("null_order"),
+        ]),
+    node(NodeType::CallingUnnest)
+        .extends(&["FromItemExpr", "CallingFunctionGeneral"])
+        .children(&[
+            undefined_child("distinct"),
+            undefined_child("ignore_nulls"),
+            undefined_child("orderby"),
+            undefined_child("limit"),
+            undefined_child("having"),
+            undefined_child("over"),
+            undefined_child("order"),
+            undefined_child("null_order"),
+            undefined_child("comma"),
+        ]),
+    node(NodeType::CallStatement)
+        .extends(&["XXXStatement"])
+        .children(&[node_child("procedure")]),
+    node(NodeType::CaseExpr).extends(&["Expr"]).children(&[
+        node_child("expr").optional(),
+        node_vec_child("arms"),
+        node_child("end"),
+    ]),
+    node(NodeType::CaseExprArm)
+        .extends(&["BaseNode"])
+        .children(&[
+            node_child("expr").optional(),
+            node_child("then").optional(),
+            node_child("result"),
+        ]),
+    node(NodeType::CaseStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("expr").optional(),
+            node_vec_child("arms"),
+            node_vec_child("end_case"),
+        ]),
+    node(NodeType::CaseStatementArm)
+        .extends(&["BaseNode"])
+        .children(&[
+            node_child("expr").optional(),
+            node_child("then").optional(),
+            node_vec_child("stmts"),
+        ]),
+    node(NodeType::CastArgument)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("cast_from"),
+            node_child("cast_to"),
+            node_child("format").optional(),
+        ]),
+    node(NodeType::Comment)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            undefined_child("leading_comments"),
+            undefined_child("trailing_comments"),
+        ]),
+    node(NodeType::Constraint)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("constraint").optional(),
+            node_child("ident").optional(),
+            node_vec_child("if_not_exists").optional(),
+            node_child("key"),
+            node_child("columns").optional(),
+            node_child("references").optional(),
+            node_child("enforced").optional(),
+            node_child("comma").optional(),
+        ]),
+    node(NodeType::CreateFunctionStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_vec_child("or_replace").optional(),
+            node_child("temp").optional(),
+            node_child("table").optional(),
+            node_child("aggregate").optional(),
+            node_child("what"),
+            node_vec_child("if_not_exists").optional(),
+            node_child("ident"),
+            node_child("group"),
+            node_child("returns").optional(),
+            node_child("remote").optional(),
+            node_child("connection").optional(),
+            node_vec_child("determinism").optional(),
+            node_child("language").optional(),
+            node_child("options").optional(),
+            node_child("as").optional(),
+        ]),
+    node(NodeType::CreateModelStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_vec_child("or_replace").optional(),
+            node_child("what"),
+            node_vec_child("if_not_exists").optional(),
+            node_child("ident"),
+            node_child("transform").optional(),
+            node_child("output").optional(),
+            node_child("input").optional(),
+            node_child("remote").optional(),
+            node_child("options").optional(),
+            node_child("query").optional(),
+            node_child("training_data_custom_holiday").optional(),
+        ]),
+    node(NodeType::CreateProcedureStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_vec_child("or_replace").optional(),
+            node_child("what"),
+            node_vec_child("if_not_exists").optional(),
+            node_child("ident"),
+            node_child("group"),
+            node_child("external").optional(),
+            node_child("with_connection").optional(),
+            node_child("options").optional(),
+            node_child("language").optional(),
+            node_child("stmt").optional(),
+            node_child("as").optional(),
+        ]),
+    node(NodeType::CreateReservationStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("what"),
+            node_child("ident"),
+            node_child("as").optional(),
+            node_child("json").optional(),
+            node_child("json_string").optional(),
+            node_child("options").optional(),
+        ]),
+    node(NodeType::CreateRowAccessPolicyStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_vec_child("or_replace").optional(),
+            node_vec_child("what"),
+            node_vec_child("if_not_exists").optional(),
+            node_child("ident"),
+            node_child("on"),
+            node_child("grant").optional(),
+            node_child("to").optional(),
+            node_child("filter"),
+            node_child("using"),
+        ]),
+    node(NodeType::CreateSchemaStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("external").optional(),
+            node_child("what"),
+            node_vec_child("if_not_exists").optional(),
+            node_child("ident"),
+            node_child("default_collate").optional(),
+            node_child("with_connection").optional(),
+            node_child("options").optional(),
+        ]),
+    node(NodeType::CreateIndexStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_vec_child("or_replace").optional(),
+            node_child("what"),
+            node_vec_child("if_not_exists").optional(),
+            node_child("ident"),
+            node_child("on"),
+            node_child("tablename"),
+            node_child("column_group"),
+            node_child("storing").optional(),
+            node_child("partitionby").optional(),
+            node_child("options").optional(),
+        ]),
+    node(NodeType::CreateTableStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_vec_child("or_replace").optional(),
+            node_child("temp").optional(),
+            node_child("external").optional(),
+            node_child("snapshot").optional(),
+            node_child("what"),
+            node_vec_child("if_not_exists").optional(),
+            node_child("ident"),
+            node_child("like_or_copy").optional(),
+            node_child("source_table").optional(),
+            node_child("column_schema_group").optional(),
+            node_child("default_collate").optional(),
+            node_child("clone").optional(),
+            node_child("partitionby").optional(),
+            node_child("clusterby").optional(),
+            node_child("with_connection").optional(),
+            node_child("with_partition_columns").optional(),
+            node_child("options").optional(),
+            node_child("as").optional(),
+        ]),
+    node(NodeType::CreateViewStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_vec_child("or_replace").optional(),
+            node_child("materialized").optional(),
+            node_child("what"),
+            node_vec_child("if_not_exists").optional(),
+            node_child("ident"),
+            node_child("column_name_list").optional(),
+            node_child("partitionby").optional(),
+            node_child("clusterby").optional(),
+            node_child("options").optional(),
+            node_child("as"),
+        ]),
+    node(NodeType::DeclareStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_vec_child("idents"),
+            node_child("variable_type").optional(),
+            node_child("default").optional(),
+        ]),
+    node(NodeType::DeleteStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("from").optional(),
+            node_child("table_name"),
+            node_child("where"),
+        ]),
+    node(NodeType::DifferentialPrivacyClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("differential_privacy"),
+            node_child("options").optional(),
+        ]),
+    node(NodeType::DotOperator)
+        .extends(&["IdentifierGeneral"])
+        .children(&[
+            node_child("left").of(&["IdentifierGeneral", "FunctionChain"]),
+            node_child("right").of(&["IdentifierGeneral", "Asterisk"]),
+        ]),
+    node(NodeType::DropRowAccessPolicyStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_vec_child("what"),
+            node_vec_child("if_exists").optional(),
+            node_child("ident").optional(),
+            node_child("on"),
+        ]),
+    node(NodeType::DropStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("external").optional(),
+            node_child("materialized").optional(),
+            node_child("table").optional(),
+            node_child("what"),
+            node_vec_child("if_exists").optional(),
+            node_child("ident"),
+            node_child("on").optional(),
+            node_child("cascade_or_restrict").optional(),
+        ]),
+    node(NodeType::ElseIfClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_child("condition"), node_child("then")]),
+    node(NodeType::EmptyStruct)
+        .extends(&["Expr"])
+        .children(&[node_child("rparen")]),
+    node(NodeType::EOF)
+        .extends(&["BaseNode"])
+        .null_token()
+        .children(&[undefined_child("trailing_comments")]),
+    node(NodeType::ExecuteStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("immediate"),
+            node_child("sql_expr"),
+            node_child("into").optional(),
+            node_child("using").optional(),
+        ]),
+    node(NodeType::ExportDataStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("data"),
+            node_child("with_connection").optional(),
+            node_child("options"),
+            node_child("as"),
+        ]),
+    node(NodeType::ExportModelStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("what"),
+            node_child("ident"),
+            node_child("options").optional(),
+        ]),
+    node(NodeType::ExtractArgument)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("extract_datepart"),
+            node_child("extract_from"),
+            node_vec_child("at_time_zone").optional(),
+            node_child("time_zone").optional(),
+        ]),
+    node(NodeType::ExtendPipeOperator)
+        .extends(&["PipeOperator"])
+        .children_of("SelectPipeOperator"),
+    node(NodeType::ForStatement)
+        .extends(&["LabelableStatement"])
+        .children(&[
+            node_child("ident"),
+            node_child("in"),
+            node_child("do"),
+            node_vec_child("end_for"),
+        ]),
+    node(NodeType::ForSystemTimeAsOfClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_vec_child("system_time_as_of"), node_child("expr")]),
+    node(NodeType::FromStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("with").of(&["WithClause"]).optional(),
+            node_child("expr"),
+        ]),
+    node(NodeType::FunctionChain)
+        .extends(&["FromItemExpr"])
+        .children(&[node_child("left"), node_child("right")]),
+    node(NodeType::GrantStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_vec_child("roles"),
+            node_child("on"),
+            node_child("resource_type"),
+            node_child("ident"),
+            node_child("to"),
+        ]),
+    node(NodeType::GroupByExprs)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("by"),
+            node_vec_child("how").optional(),
+            node_vec_child("exprs").of(&["Expr"]).optional(),
+        ]),
+    node(NodeType::GroupedExpr)
+        .extends(&["FromItemExpr"])
+        .children(&[
+            node_child("expr").of(&["Expr", "KeywordSequence"]),
+            node_child("rparen"),
+        ]),
+    node(NodeType::GroupedExprs)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_vec_child("exprs")
+                .of(&["Expr", "GroupedExprs"])
+                .optional()
+                .note(
+                    "NOTE: contains GroupedExprs in UnpivotConfig. it's a little inconsistent...",
+                ),
+            node_child("rparen"),
+            node_child("as").optional().note("only in UNPIVOT operator"),
+            node_child("row_value_alias").optional(),
+            node_child("comma")
+                .optional()
+                .note("only in INSERT statement"),
+        ]),
+    node(NodeType::GroupedIdentWithOptions)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_vec_child("idents"), node_child("rparen")]),
+    node(NodeType::GroupedPattern)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_vec_child("patterns"),
+            node_child("rparen"),
+            node_vec_child("suffixes"),
+        ]),
+    node(NodeType::GroupedStatement)
+        .extends(&["FromItemExpr", "XXXStatement"])
+        .children(&[
+            node_child("with").of(&["WithClause"]).optional(),
+            node_child("stmt"),
+            node_child("rparen"),
+            node_child("orderby").optional(),
+            node_child("limit").optional(),
+        ]),
+    node(NodeType::GroupedTypeDeclarationOrConstraints)
+        .extends(&["BaseNode"])
+        .children(&[
+            node_vec_child("declarations").optional(),
+            node_child("rparen"),
+        ]),
+    node(NodeType::GroupedType)
+        .extends(&["BaseNode"])
+        .children(&[node_child("type"), node_child("rparen")]),
+    node(NodeType::Identifier).extends(&["IdentifierGeneral"]),
+    node(NodeType::IdentWithOptions)
+        .extends(&["Expr"])
+        .children(&[
+            undefined_child("as"),
+            undefined_child("alias"),
+            undefined_child("order"),
+            undefined_child("null_order"),
+            node_child("options").optional(),
+        ]),
+    node(NodeType::IfStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("condition"),
+            node_child("then"),
+            node_vec_child("elseifs").optional(),
+            node_child("else").optional(),
+            node_vec_child("end_if"),
+        ]),
+    node(NodeType::InOperator).extends(&["Expr"]).children(&[
+        node_child("not").optional(),
+        node_child("left"),
+        node_child("right"),
+    ]),
+    node(NodeType::InsertStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("into").optional(),
+            node_child("target_name").optional(),
+            node_child("columns").optional(),
+            node_child("input"),
+        ]),
+    node(NodeType::IntervalLiteral)
+        .extends(&["Expr"])
+        .children(&[
+            node_child("expr"),
+            node_child("date_part"),
+            node_child("to").optional(),
+            node_child("to_date_part").optional(),
+            undefined_child("order"),
+            undefined_child("null_order"),
+        ]),
+    node(NodeType::IsDistinctFromOperator)
+        .extends(&["Expr"])
+        .children(&[
+            node_child("not").optional(),
+            node_child("distinct"),
+            node_child("from"),
+            node_child("left"),
+            node_child("right"),
+        ]),
+    node(NodeType::JinjaAttribute)
+        .extends(&["JinjaExpr"])
+        .children(&[
+            node_child("left").of(&["JinjaExpr"]),
+            node_child("right").of(&["JinjaName"]),
+        ]),
+    node(NodeType::JinjaCall)
+        .extends(&["JinjaExpr"])
+        .children(&[
+            node_child("func").of(&["JinjaExpr"]),
+            node_vec_child("args").of(&["JinjaExpr"]),
+            node_child("rparen").of(&["Symbol_"]),
+        ]),
+    node(NodeType::JinjaFilter)
+        .extends(&["JinjaExpr"])
+        .children(&[
+            node_child("left").of(&["JinjaExpr"]),
+            node_child("filter").of(&["JinjaName", "JinjaAttribute", "JinjaCall"]),
+        ]),
+    node(NodeType::JinjaGroup)
+        .extends(&["JinjaExpr"])
+        .children(&[
+            node_vec_child("exprs").of(&["JinjaExpr"]),
+            node_child("end").of(&["Symbol_"]),
+        ]),
+    node(NodeType::JinjaKeywordArg)
+        .extends(&["JinjaExpr"])
+        .children(&[
+            node_child("left").of(&["JinjaExpr"]),
+            node_child("right").of(&["JinjaExpr"]),
+        ]),
+    node(NodeType::JinjaLiteral).extends(&["JinjaExpr"]),
+    node(NodeType::JinjaName).extends(&["JinjaExpr"]),
+    node(NodeType::JinjaOperator)
+        .extends(&["JinjaExpr"])
+        .children(&[
+            node_child("left").of(&["JinjaExpr"]).optional(),
+            node_child("not").of(&["Keyword"]).optional().note("is not"),
+            node_child("in").of(&["Keyword"]).optional().note("not in"),
+            node_child("right").of(&["JinjaExpr"]),
+        ]),
+    node(NodeType::JinjaPair)
+        .extends(&["JinjaExpr"])
+        .children(&[
+            node_child("left").of(&["JinjaExpr"]),
+            node_child("right").of(&["JinjaExpr"]),
+        ]),
+    node(NodeType::JinjaStatement)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_vec_child("exprs").of(&["JinjaExpr"])]),
+    node(NodeType::JinjaSubscript)
+        .extends(&["JinjaExpr"])
+        .children(&[
+            node_child("left").of(&["JinjaExpr"]),
+            node_child("right").of(&["JinjaExpr"]),
+            node_child("rbrack").of(&["Symbol_"]),
+        ]),
+    node(NodeType::JoinOperator)
+        .extends(&["FromItemExpr"])
+        .children(&[
+            node_child("join_type").optional(),
+            node_child("outer").optional(),
+            node_child("left"),
+            node_child("right"),
+            node_child("on").optional(),
+            node_child("using").optional(),
+            undefined_child("order"),
+            undefined_child("null_order"),
+            undefined_child("comma"),
+        ]),
+    node(NodeType::JoinPipeOperator)
+        .extends(&["PipeOperator"])
+        .children(&[
+            node_child("method").optional(),
+            node_child("on").optional(),
+            node_child("using").optional(),
+        ]),
+    node(NodeType::Keyword).extends(&["BaseNode"]).token(),
+    node(NodeType::KeywordSequence)
+        .extends(&["BaseNode"])
+        .children(&[node_child("next_keyword")
+            .of(&[
+                "Keyword",
+                "KeywordSequence",
+                "KeywordWithExpr",
+                "KeywordWithExprs",
+                "KeywordWithGroupedXXX",
+            ])
+            .optional()]),
+    node(NodeType::KeywordWithExpr)
+        .extends(&["BaseNode"])
+        .children(&[node_child("expr").of(&["Expr", "Keyword", "KeywordSequence"])]),
+    node(NodeType::KeywordWithExprs)
+        .extends(&["BaseNode"])
+        .children(&[node_vec_child("exprs")]),
+    node(NodeType::KeywordWithGroupedXXX)
+        .extends(&["BaseNode"])
+        .children(&[node_child("group")]),
+    node(NodeType::KeywordWithStatement)
+        .extends(&["BaseNode"])
+        .children(&[node_child("stmt")]),
+    node(NodeType::KeywordWithStatements)
+        .extends(&["BaseNode"])
+        .children(&[node_vec_child("stmts")]),
+    node(NodeType::KeywordWithType)
+        .extends(&["BaseNode"])
+        .children(&[node_child("type")]),
+    node(NodeType::LimitClause)
+        .extends(&["BaseNode"])
+        .children(&[node_child("expr"), node_child("offset").optional()]),
+    node(NodeType::LimitPipeOperator)
+        .extends(&["PipeOperator"])
+        .children(&[node_child("offset").optional()]),
+    node(NodeType::MatchRecognizeClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("as").optional(),
+            node_child("alias").optional(),
+            node_child("config"),
+        ]),
+    node(NodeType::MatchRecognizeConfig)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("partitionby").optional(),
+            node_child("orderby").optional(),
+            node_child("measures").optional(),
+            node_child("skip_rule").optional(),
+            node_child("pattern").optional(),
+            node_child("define").optional(),
+            node_child("options").optional(),
+            node_child("rparen"),
+        ]),
+    node(NodeType::MatchRecognizePipeOperator)
+        .extends(&["BaseNode"])
+        .token()
+        .children_of("MatchRecognizeClause"),
+    node(NodeType::LoopStatement)
+        .extends(&["LabelableStatement"])
+        .children(&[
+            node_vec_child("stmts").optional(),
+            node_vec_child("end_loop"),
+        ]),
+    node(NodeType::LoadStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("data"),
+            node_child("into"),
+            node_child("ident"),
+            node_child("overwrite_partitions").optional(),
+            node_child("column_group").optional(),
+            node_child("partitionby").optional(),
+            node_child("clusterby").optional(),
+            node_child("options").optional(),
+            node_child("from"),
+            node_child("files"),
+            node_child("from_files"),
+            node_child("with_partition_columns").optional(),
+            node_child("with").optional(),
+            node_child("connection").optional(),
+            node_child("connection_name").optional(),
+        ]),
+    node(NodeType::MergeStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("into").optional(),
+            node_child("table_name"),
+            node_child("using"),
+            node_child("on"),
+            node_vec_child("whens"),
+        ]),
+    node(NodeType::MultiTokenIdentifier)
+        .extends(&["IdentifierGeneral"])
+        .children(&[node_vec_child("trailing_idents").of(&["IdentifierGeneral"])]),
+    node(NodeType::NullLiteral).extends(&["Expr"]),
+    node(NodeType::NumericLiteral).extends(&["Expr"]),
+    node(NodeType::OrPattern)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_vec_child("left"), node_vec_child("right")]),
+    node(NodeType::OverClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_child("window")]),
+    node(NodeType::OverwritePartitionsClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("overwrite").optional(),
+            node_child("grouped_expr"),
+        ]),
+    node(NodeType::Parameter).extends(&["IdentifierGeneral"]),
+    node(NodeType::Pattern)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_vec_child("suffixes")]),
+    node(NodeType::PatternClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_child("pattern")]),
+    node(NodeType::PatternQuantifier)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("min").optional(),
+            node_child("comma").optional(),
+            node_child("max").optional(),
+            node_child("rbrace"),
+        ]),
+    node(NodeType::PipeStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("with").of(&["WithClause"]).optional(),
+            node_child("left"),
+            node_child("right"),
+        ]),
+    node(NodeType::PivotPipeOperator)
+        .extends(&["PipeOperator"])
+        .children(&[
+            undefined_child("exprs"),
+            node_child("config").of(&["PivotConfig"]),
+            node_child("as").optional(),
+            node_child("alias").optional(),
+        ]),
+    node(NodeType::PivotOperator)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("config").of(&["PivotConfig"]),
+            node_child("as").optional(),
+            node_child("alias").optional(),
+        ]),
+    node(NodeType::PivotConfig)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_vec_child("exprs").of(&["Expr"]),
+            node_child("for").of(&["KeywordWithExpr"]),
+            node_child("in").of(&["KeywordWithGroupedXXX"]),
+            node_child("rparen"),
+        ]),
+    node(NodeType::RaiseStatement)
+        .extends(&["XXXStatement"])
+        .children(&[node_child("using").optional()]),
+    node(NodeType::RangeLiteral)
+        .extends(&["Expr"])
+        .children(&[node_child("type")]),
+    node(NodeType::RenameColumnClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("column"),
+            node_child("if_exists").optional(),
+            node_child("ident"),
+            node_child("to"),
+            node_child("comma").optional(),
+        ]),
+    node(NodeType::RepeatStatement)
+        .extends(&["LabelableStatement"])
+        .children(&[
+            node_vec_child("stmts"),
+            node_child("until"),
+            node_vec_child("end_repeat"),
+        ]),
+    node(NodeType::RevokeStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_vec_child("roles"),
+            node_child("on"),
+            node_child("resource_type"),
+            node_child("ident"),
+            node_child("from"),
+        ]),
+    node(NodeType::SelectStatement)
+        .extends(&["XXXStatement"])
+        .token()
+        .children(&[
+            node_child("with").of(&["WithClause"]).optional(),
+            node_child("differential_privacy").optional(),
+            node_vec_child("as_struct_or_value").optional(),
+            node_child("distinct_or_all").optional(),
+            node_vec_child("exprs"),
+            node_child("from").optional(),
+            node_child("where").optional(),
+            node_child("groupby").optional(),
+            node_child("having").optional(),
+            node_child("qualify").optional(),
+            node_child("window").optional(),
+            node_child("orderby").optional(),
+            node_child("limit").optional(),
+        ]),
+    node(NodeType::SelectPipeOperator)
+        .extends(&["PipeOperator"])
+        .children(&[node_child("window").optional()]),
+    node(NodeType::SetOperator)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("with").of(&["WithClause"]).optional(),
+            node_child("method").optional(),
+            node_child("by").optional(),
+            node_child("corresponding").optional(),
+            node_child("distinct_or_all"),
+            node_child("left").of(&["SetOperator", "SelectStatement", "GroupedStatement"]),
+            node_child("right").of(&["SetOperator", "SelectStatement", "GroupedStatement"]),
+        ]),
+    node(NodeType::SetStatement)
+        .extends(&["XXXStatement"])
+        .children(&[node_child("expr")]),
+    node(NodeType::SingleTokenStatement).extends(&["XXXStatement"]),
+    node(NodeType::SqlxBlock)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_vec_child("stmts")
+            .of(&["XXXStatement"])
+            .optional()
+            .note("pre_operations and post_operations")]),
+    node(NodeType::SqlxInterpolation)
+        .extends(&["IdentifierGeneral"])
+        .children(&[
+            node_child("expr")
+                .of(&["JinjaExpr"])
+                .optional()
+                .note("simple JavaScript expressions are parsed as Jinja"),
+            node_vec_child("exprs")
+                .of(&["JinjaExpr"])
+                .optional()
+                .note("interpolations glued to other characters (e.g. `${self()}_tmp`)"),
+        ]),
+    node(NodeType::StandAloneExpr)
+        .extends(&["BaseNode"])
+        .null_token()
+        .children(&[
+            undefined_child("leading_comments"),
+            undefined_child("trailing_comments"),
+            node_child("expr"),
+        ]),
+    node(NodeType::StringLiteral).extends(&["Expr"]),
+    node(NodeType::StructLiteral).extends(&["Expr"]).children(&[
+        node_child("type").optional(),
+        node_vec_child("exprs"),
+        node_child("rparen"),
+    ]),
+    named_node(NodeType::Symbol, "Symbol_")
+        .extends(&["BaseNode"])
+        .token(),
+    node(NodeType::TableSampleClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_child("system"), node_child("group")]),
+    node(NodeType::TableSamplePipeOperator)
+        .extends(&["PipeOperator"])
+        .children(&[undefined_child("exprs"), node_child("group").optional()]),
+    node(NodeType::TableSampleRatio)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("expr"),
+            node_child("percent"),
+            node_child("rparen"),
+        ]),
+    node(NodeType::TemplateBlock)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("jinja").of(&["JinjaStatement"]).optional(),
+            node_vec_child("body"),
+            node_child("end"),
+        ]),
+    node(NodeType::TemplateExpr)
+        .extends(&["IdentifierGeneral"])
+        .children(&[node_child("jinja")
+            .of(&["JinjaExpr", "JinjaStatement"])
+            .optional()]),
+    node(NodeType::TemplateExprEnd)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[]),
+    node(NodeType::TemplateExprContinue)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_vec_child("exprs")]),
+    node(NodeType::TemplateExprStart)
+        .extends(&["FromItemExpr"])
+        .token()
+        .children(&[
+            node_child("jinja").of(&["JinjaStatement"]).optional(),
+            node_vec_child("exprs"),
+            node_vec_child("continues"),
+            node_child("end"),
+        ]),
+    node(NodeType::TemplateFragment)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_vec_child("keywords").optional(),
+            node_vec_child("exprs").optional(),
+        ]),
+    node(NodeType::TransactionStatement)
+        .extends(&["XXXStatement"])
+        .children(&[node_child("transaction").optional()]),
+    node(NodeType::TrainingDataCustomHolidayClause)
+        .extends(&["BaseNode"])
+        .children(&[
+            node_child("training_data"),
+            node_child("custom_holiday"),
+            node_child("rparen"),
+        ]),
+    node(NodeType::TruncateStatement)
+        .extends(&["XXXStatement"])
+        .children(&[node_child("table"), node_child("table_name")]),
+    node(NodeType::Type)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("type").optional().note("ANY TYPE"),
+            node_child("type_declaration").optional(),
+            node_child("parameter").optional(),
+            node_vec_child("not_null").optional(),
+            node_child("constraint").optional(),
+            node_child("primarykey").optional(),
+            node_child("references").optional(),
+            node_child("enforced").optional(),
+            node_child("default").optional(),
+            node_child("generated_as").optional(),
+            node_child("stored_options").optional(),
+            node_child("options").optional(),
+            node_child("collate").optional(),
+            node_child("aggregate").optional(),
+        ]),
+    node(NodeType::TypeDeclaration)
+        .extends(&["BaseNode"])
+        .nullable_token()
+        .children(&[
+            node_child("in_out").optional(),
+            node_child("type"),
+            node_child("comma").optional(),
+        ]),
+    node(NodeType::UnaryOperator)
+        .extends(&["Expr"])
+        .token()
+        .children(&[node_child("right")]),
+    node(NodeType::UndropStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("what"),
+            node_vec_child("if_not_exists").optional(),
+            node_child("ident"),
+        ]),
+    node(NodeType::UnionPipeOperator)
+        .extends(&["PipeOperator"])
+        .children(&[
+            node_child("method").optional(),
+            node_child("by").optional(),
+            node_child("corresponding").optional(),
+        ]),
+    node(NodeType::UnpivotConfig)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("expr"),
+            node_child("for").of(&["KeywordWithExpr"]),
+            node_child("in").of(&["KeywordWithGroupedXXX"]),
+            node_child("rparen"),
+        ]),
+    node(NodeType::UnpivotPipeOperator)
+        .extends(&["PipeOperator"])
+        .children(&[
+            undefined_child("exprs"),
+            node_child("config").of(&["UnpivotConfig"]),
+            node_child("as").optional(),
+            node_child("alias").optional(),
+        ]),
+    node(NodeType::UnpivotOperator)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_vec_child("include_or_exclude_nulls").optional(),
+            node_child("config").of(&["UnpivotConfig"]),
+            node_child("as").optional(),
+            node_child("alias").optional(),
+        ]),
+    node(NodeType::UpdateStatement)
+        .extends(&["XXXStatement"])
+        .children(&[
+            node_child("table_name").optional(),
+            node_child("set"),
+            node_child("from").optional(),
+            node_child("where").optional(),
+        ]),
+    node(NodeType::WhenClause)
+        .extends(&["BaseNode"])
+        .children(&[
+            node_child("not").optional(),
+            node_child("matched"),
+            node_vec_child("by_target_or_source").optional(),
+            node_child("and").optional(),
+            node_child("then"),
+        ]),
+    node(NodeType::WhileStatement)
+        .extends(&["LabelableStatement"])
+        .children(&[
+            node_child("condition"),
+            node_child("do"),
+            node_vec_child("end_while"),
+        ]),
+    node(NodeType::WindowClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_vec_child("window_exprs")]),
+    node(NodeType::WindowExpr)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("as"),
+            node_child("window"),
+            node_child("comma").optional(),
+        ]),
+    node(NodeType::WindowFrameClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("between").optional(),
+            node_vec_child("start"),
+            node_child("and").optional(),
+            node_vec_child("end").optional(),
+        ]),
+    node(NodeType::WindowSpecification)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("name").optional(),
+            node_child("partitionby").optional(),
+            node_child("orderby").optional(),
+            node_child("frame").optional(),
+            node_child("rparen"),
+        ]),
+    node(NodeType::WithClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_vec_child("queries").of(&["WithQuery"]),
+            node_child("recursive").optional(),
+        ]),
+    node(NodeType::WithOffsetClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("offset").of(&["Keyword"]),
+            node_child("as").of(&["Keyword"]).optional(),
+            node_child("alias").optional(),
+        ]),
+    node(NodeType::WithPipeOperator)
+        .extends(&["BaseNode"])
+        .token()
+        .children_of("WithClause"),
+    node(NodeType::WithPartitionColumnsClause)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_vec_child("partition_columns"),
+            node_child("column_schema_group").optional(),
+        ]),
+    node(NodeType::WithQuery)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[
+            node_child("as").of(&["Keyword"]),
+            node_child("stmt").of(&["GroupedStatement"]),
+            node_child("comma").optional(),
+        ]),
+    node(NodeType::XXXByExprs)
+        .extends(&["BaseNode"])
+        .token()
+        .children(&[node_child("by"), node_vec_child("exprs").of(&["Expr"])]),
+];
+
+// ----- lookup -----
+static BY_NAME: LazyLock<HashMap<String, &'static NodeSchema>> =
+    LazyLock::new(|| SCHEMA.iter().map(|s| (s.name(), s)).collect());
+
+static BY_NODE_TYPE: LazyLock<HashMap<NodeType, &'static NodeSchema>> = LazyLock::new(|| {
+    SCHEMA
+        .iter()
+        .filter_map(|s| s.node_type.map(|t| (t, s)))
+        .collect()
+});
+
+pub fn get(name: &str) -> Option<&'static NodeSchema> {
+    BY_NAME.get(name).copied()
+}
+
+pub fn get_by_node_type(node_type: &NodeType) -> Option<&'static NodeSchema> {
+    BY_NODE_TYPE.get(node_type).copied()
+}
+
+impl NodeSchema {
+    // the type itself first, then its ancestors (depth first)
+    fn lineage(&'static self) -> Vec<&'static NodeSchema> {
+        let mut res = vec![self];
+        for parent in self.extends {
+            if let Some(p) = get(parent) {
+                res.extend(p.lineage());
+            }
+        }
+        res
+    }
+    pub fn is_subtype_of(&'static self, name: &str) -> bool {
+        self.lineage().iter().any(|s| s.name() == name)
+    }
+    pub fn effective_token(&'static self) -> TokenSchema {
+        self.lineage()
+            .iter()
+            .map(|s| s.token)
+            .find(|t| *t != TokenSchema::Inherit)
+            .unwrap_or(TokenSchema::Nullable)
+    }
+    // the nearest definition of each key wins
+    pub fn effective_children(&'static self) -> Vec<ChildSchema> {
+        let mut res: Vec<ChildSchema> = Vec::new();
+        for s in self.lineage() {
+            let own: Vec<ChildSchema> = match s.children {
+                ChildrenSchema::None => Vec::new(),
+                ChildrenSchema::Own(children) => children.to_vec(),
+                ChildrenSchema::SameAs(name) => match get(name) {
+                    Some(other) => other.effective_children(),
+                    None => Vec::new(),
+                },
+            };
+            for c in own {
+                if !res.iter().any(|r| r.key == c.key) {
+                    res.push(c);
+                }
+            }
+        }
+        res
+    }
+}
+
+// ----- validation -----
+pub fn validate(node: &Node) -> Result<(), String> {
+    let mut errors = Vec::new();
+    validate_node(node, "$", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+fn validate_node(node: &Node, path: &str, errors: &mut Vec<String>) {
+    let schema = match get_by_node_type(&node.node_type) {
+        Some(s) => s,
+        None => {
+            errors.push(format!("{}: {:?} is not defined", path, node.node_type));
+            return;
+        }
+    };
+    let name = schema.name();
+    match (schema.effective_token(), node.token) {
+        (TokenSchema::NonNull, None) => {
+            errors.push(format!("{}: token of {} should not be null", path, name))
+        }
+        (TokenSchema::Null, Some(_)) => {
+            errors.push(format!("{}: token of {} should be null", path, name))
+        }
+        _ => (),
+    }
+    let children = schema.effective_children();
+    for c in &children {
+        if c.required && c.kind != ChildKind::Undefined && !node.children.contains_key(c.key) {
+            errors.push(format!("{}: {} requires `{}`", path, name, c.key));
+        }
+    }
+    let mut keys: Vec<&String> = node.children.keys().collect();
+    keys.sort();
+    for key in keys {
+        let child_path = format!("{}.{}", path, key);
+        let c = match children.iter().find(|c| c.key == key) {
+            Some(c) => c,
+            None => {
+                errors.push(format!("{}: {} does not have `{}`", path, name, key));
+                continue;
+            }
+        };
+        let nodes: Vec<&Node> = match (&node.children[key], c.kind) {
+            (ContentType::Node(n), ChildKind::Node) => vec![n],
+            (ContentType::NodeVec(ns), ChildKind::NodeVec) => ns.iter().collect(),
+            _ => {
+                errors.push(format!("{}: expected {:?}", child_path, c.kind));
+                continue;
+            }
+        };
+        for (i, n) in nodes.iter().enumerate() {
+            let n_path = match c.kind {
+                ChildKind::NodeVec => format!("{}[{}]", child_path, i),
+                _ => child_path.clone(),
+            };
+            if !c.allowed.is_empty() {
+                let ok = match get_by_node_type(&n.node_type) {
+                    Some(s) => c.allowed.iter().any(|a| s.is_subtype_of(a)),
+                    None => false,
+                };
+                if !ok {
+                    errors.push(format!(
+                        "{}: {:?} is not allowed (expected {})",
+                        n_path,
+                        n.node_type,
+                        c.allowed.join(" | ")
+                    ));
+                }
+            }
+            validate_node(n, &n_path, errors);
+        }
+    }
+}
+
+// ----- TypeScript -----
+const HEADER: &str = "\
+// NOTE:
+// This file is generated from src/schema.rs.
+// Do not edit it directly, run `make dts` instead.
+
+export function parse(code: string, strict?: boolean): UnknownNode[];
+export function tokenize(code: string, strict?: boolean): Token[];
+export function parseDocument(code: string, compact?: boolean): Document;
+export function nodeTypes(): string[];
+export function exportTree(
+  code: string,
+  format: \"dot\" | \"mermaid\" | \"sexp\",
+): string[];
+export function dbtCalls(code: string): DbtCall[];
+export function parseRelaxed(code: string): UnknownNode[];
+export function parseSqlx(code: string): UnknownNode[];
+export function sqlxTables(code: string): SqlxTable[];
+export function resolveVariables(code: string): Resolution;
+export function controlFlowGraph(code: string): string;
+export function checkControlFlow(code: string): Diagnostic[];
+export function validateLabels(code: string): Diagnostic[];
+export function analyzeRoutines(files: Record<string, string>): Project;
+export function schemaCatalog(code: string): Catalog;
+export function diffTables(before: string, after: string): Migration;
+export function tableSchema(code: string): TableFieldSchema[];
+export function columnDefinitions(fields: TableFieldSchema[]): string;
+export function extractOptions(code: string): OptionsClause[];
+export function decodeLiterals(code: string): DecodedLiteral[];
+export function renderTemplate(
+  code: string,
+  substitutions?: Record<string, string>,
+): string;
+export function parseTemplate(
+  code: string,
+  substitutions?: Record<string, string>,
+): UnknownNode[];
+";
+
+const TOKEN: &str = "\
+export type Token = {
+  line: number;
+  column: number;
+  literal: string;
+};
+";
+
+const DOCUMENT: &str = "\
+export type Document =
+  | { version: number; compact: false; stmts: UnknownNode[] }
+  | { version: number; compact: true; stmts: CompactNode[] };
+
+export type CompactNode = {
+  token: Token | null;
+  // index of nodeTypes()
+  tag: number;
+  children?: { [key: string]: CompactNode | CompactNode[] };
+};
+";
+
+const DBT_CALL: &str = "\
+export type DbtCall = {
+  kind: \"Ref\" | \"Source\" | \"Var\" | \"Config\";
+  token: Token;
+  // null means the argument is not a string literal
+  args: (string | null)[];
+  kwargs: [string, string | null][];
+};
+";
+
+const SQLX_TABLE: &str = "\
+export type SqlxTable = {
+  // ref | resolve
+  token: Token;
+  // null means it is not given as a string literal
+  database: string | null;
+  schema: string | null;
+  name: string | null;
+};
+";
+
+const DIAGNOSTIC: &str = "\
+export type Span = {
+  line: number;
+  column: number;
+  // the position just after the last character
+  end_line: number;
+  end_column: number;
+};
+
+export type Diagnostic = {
+  severity: \"Error\" | \"Warning\";
+  code: string;
+  message: string;
+  span: Span;
+};
+";
+
+const RESOLUTION: &str = "\
+export type Variable = {
+  token: Token;
+  kind: \"Declare\" | \"For\" | \"Parameter\" | \"OutParameter\";
+};
+
+export type Reference = {
+  token: Token;
+  // index of variables
+  variable: number;
+  write: boolean;
+};
+
+export type Resolution = {
+  variables: Variable[];
+  references: Reference[];
+  diagnostics: Diagnostic[];
+};
+";
+
+const PROJECT: &str = "\
+export type RoutineKind =
+  | \"Function\"
+  | \"AggregateFunction\"
+  | \"TableFunction\"
+  | \"Procedure\";
+
+export type Parameter = {
+  name: string;
+  mode: \"In\" | \"Out\" | \"InOut\";
+  type: string;
+};
+
+export type Routine = {
+  file: string;
+  name: string[];
+  kind: RoutineKind;
+  temp: boolean;
+  parameters: Parameter[];
+  returns: string | null;
+  language: string;
+  span: Span;
+};
+
+export type Call = {
+  file: string;
+  // index of routines, null means the top level of the file
+  caller: number | null;
+  name: string[];
+  kind: RoutineKind;
+  args: number;
+  // index of routines, null means it is not defined in the project
+  routine: number | null;
+  span: Span;
+};
+
+export type FileDiagnostic = Diagnostic & { file: string };
+
+export type Project = {
+  routines: Routine[];
+  // calls of built-in functions (e.g. `COUNT(*)`) are not included
+  calls: Call[];
+  diagnostics: FileDiagnostic[];
+};
+";
+
+const CATALOG: &str = "\
+// `value` is the original code of the expression
+export type OptionEntry = {
+  name: string;
+  value: string;
+};
+
+export type Column = {
+  name: string;
+  // null means it is not declared
+  type: string | null;
+  not_null: boolean;
+  default: string | null;
+  options: OptionEntry[];
+};
+
+export type Constraint = {
+  name: string | null;
+  kind: \"PrimaryKey\" | \"ForeignKey\";
+  columns: string[];
+  referenced_table: string[] | null;
+  referenced_columns: string[];
+  enforced: boolean;
+};
+
+export type Table = {
+  name: string;
+  kind:
+    | \"Table\"
+    | \"ExternalTable\"
+    | \"Snapshot\"
+    | \"View\"
+    | \"MaterializedView\";
+  columns: Column[];
+  constraints: Constraint[];
+  partition_by: string | null;
+  cluster_by: string[];
+  options: OptionEntry[];
+};
+
+export type Dataset = {
+  // null means the default project
+  project: string | null;
+  name: string;
+  options: OptionEntry[];
+  tables: Table[];
+};
+
+export type Catalog = {
+  datasets: Dataset[];
+  diagnostics: Diagnostic[];
+};
+";
+
+const MIGRATION: &str = "\
+export type Migration = {
+  statements: string[];
+  // changes which ALTER TABLE cannot apply, the table has to be recreated
+  rebuild_reasons: string[];
+  // RENAME COLUMN which may be used instead of DROP COLUMN and ADD COLUMN (keeping the data)
+  possible_renames: string[];
+  // changes which cannot be migrated (e.g. the type of a column is unknown)
+  warnings: string[];
+};
+";
+
+// https://cloud.google.com/bigquery/docs/reference/rest/v2/tables#TableFieldSchema
+const TABLE_FIELD_SCHEMA: &str = "\
+export type TableFieldSchema = {
+  name: string;
+  type: string;
+  mode?: \"NULLABLE\" | \"REQUIRED\" | \"REPEATED\";
+  fields?: TableFieldSchema[];
+  description?: string;
+  maxLength?: string;
+  precision?: string;
+  scale?: string;
+  collation?: string;
+  defaultValueExpression?: string;
+  rangeElementType?: { type: string };
+};
+";
+
+const OPTIONS_CLAUSE: &str = "\
+export type OptionValue =
+  | { type: \"String\"; value: string }
+  | { type: \"Int\"; value: number }
+  | { type: \"Float\"; value: number }
+  | { type: \"Bool\"; value: boolean }
+  | { type: \"Null\" }
+  | { type: \"Array\"; value: OptionValue[] }
+  | { type: \"Struct\"; value: { name: string | null; value: OptionValue }[] }
+  | { type: \"Literal\"; value: Literal }
+  | { type: \"Expression\"; value: string };
+
+export type OptionsClause = {
+  target: string[];
+  column: string[];
+  span: Span;
+  options: Record<string, OptionValue>;
+};
+";
+
+const LITERAL: &str = "\
+export type Literal =
+  | { type: \"String\"; value: string }
+  | { type: \"Bytes\"; value: number[] }
+  | { type: \"Int\"; value: number }
+  | { type: \"Float\"; value: number }
+  | { type: \"Bool\"; value: boolean }
+  | { type: \"Null\" }
+  | {
+      type:
+        | \"Date\"
+        | \"Datetime\"
+        | \"Time\"
+        | \"Timestamp\"
+        | \"Json\"
+        | \"Numeric\"
+        | \"BigNumeric\";
+      value: string;
+    }
+  | {
+      type: \"Range\";
+      value: { element_type: string; start: string | null; end: string | null };
+    }
+  | {
+      type: \"Interval\";
+      value: { months: number; days: number; nanos: number };
+    };
+
+export type DecodedLiteral = {
+  span: Span;
+  // null if the literal is invalid (e.g. `NUMERIC 'abc'`), see `error`
+  literal: Literal | null;
+  error: string | null;
+};
+";
+
+const CHILD_ALIASES: &str = "\
+export type NodeChild = { Node: UnknownNode };
+export type NodeVecChild = { NodeVec: UnknownNode[] };
+";
+
+// max line width of prettier
+const PRINT_WIDTH: usize = 80;
+
+fn ts_type_name(name: &str) -> String {
+    match get(name) {
+        Some(s) if s.is_abstract() => format!("{} & UnknownNode", name),
+        _ => name.to_string(),
+    }
+}
+
+fn ts_child(c: &ChildSchema, indent: &str) -> Vec<String> {
+    let mut res = Vec::new();
+    if let Some(note) = c.note {
+        res.push(format!("{}// {}", indent, note));
+    }
+    let key = if c.required || c.kind == ChildKind::Undefined {
+        c.key.to_string()
+    } else {
+        format!("{}?", c.key)
+    };
+    let types: Vec<String> = c.allowed.iter().map(|a| ts_type_name(a)).collect();
+    let parenthesize = |t: &String| {
+        if t.contains(' ') {
+            format!("({})", t)
+        } else {
+            t.clone()
+        }
+    };
+    let type_ = match c.kind {
+        ChildKind::Undefined => "undefined".to_string(),
+        ChildKind::Node if types.is_empty() => "NodeChild".to_string(),
+        ChildKind::NodeVec if types.is_empty() => "NodeVecChild".to_string(),
+        ChildKind::Node => {
+            let union = if types.len() == 1 {
+                types[0].clone()
+            } else {
+                types
+                    .iter()
+                    .map(parenthesize)
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            };
+            let line = format!("{}{}: {{ Node: {} }};", indent, key, union);
+            if PRINT_WIDTH < line.len() && 1 < types.len() {
+                res.push(format!("{}{}: {{", indent, key));
+                res.push(format!("{}  Node:", indent));
+                for (i, t) in types.iter().enumerate() {
+                    let end = if i == types.len() - 1 { ";" } else { "" };
+                    res.push(format!("{}    | {}{}", indent, parenthesize(t), end));
+                }
+                res.push(format!("{}}};", indent));
+                return res;
+            }
+            format!("{{ Node: {} }}", union)
+        }
+        ChildKind::NodeVec => {
+            let element = if types.len() == 1 {
+                parenthesize(&types[0])
+            } else {
+                let union = types.iter().map(parenthesize).collect::<Vec<_>>();
+                format!("({})", union.join(" | "))
+            };
+            let line = format!("{}{}: {{ NodeVec: {}[] }};", indent, key, element);
+            if PRINT_WIDTH < line.len() && 1 < types.len() {
+                res.push(format!("{}{}: {{", indent, key));
+                res.push(format!("{}  NodeVec: (", indent));
+                for t in &types {
+                    res.push(format!("{}    | {}", indent, parenthesize(t)));
+                }
+                res.push(format!("{}  )[];", indent));
+                res.push(format!("{}}};", indent));
+                return res;
+            }
+            format!("{{ NodeVec: {}[] }}", element)
+        }
+    };
+    res.push(format!("{}{}: {};", indent, key, type_));
+    res
+}
+
+fn ts_node(s: &NodeSchema) -> String {
+    let name = s.name();
+    let mut lines = Vec::new();
+    // prettier breaks intersections of multiple types
+    let indent = if s.extends.len() < 2 {
+        lines.push(format!(
+            "export type {} = {} & {{",
+            name,
+            s.extends.join(" & ")
+        ));
+        "  "
+    } else {
+        lines.push(format!("export type {} = {} &", name, s.extends[0]));
+        for (i, parent) in s.extends[1..].iter().enumerate() {
+            let end = if i == s.extends.len() - 2 {
+                " & {"
+            } else {
+                " &"
+            };
+            lines.push(format!("  {}{}", parent, end));
+        }
+        "    "
+    };
+    match s.token {
+        TokenSchema::NonNull => lines.push(format!("{}token: Token;", indent)),
+        TokenSchema::Null => lines.push(format!("{}token: null;", indent)),
+        TokenSchema::Nullable => lines.push(format!("{}token: Token | null;", indent)),
+        TokenSchema::Inherit => (),
+    }
+    if let Some(note) = s.note {
+        lines.push(format!("{}// {}", indent, note));
+    }
+    if let Some(node_type) = &s.node_type {
+        lines.push(format!("{}node_type: \"{:?}\";", indent, node_type));
+    }
+    match s.children {
+        ChildrenSchema::None => (),
+        ChildrenSchema::SameAs(other) => {
+            lines.push(format!("{}children: {}[\"children\"];", indent, other))
+        }
+        ChildrenSchema::Own([]) => lines.push(format!("{}children: {{}};", indent)),
+        ChildrenSchema::Own(children) => {
+            lines.push(format!("{}children: {{", indent));
+            let child_indent = format!("{}  ", indent);
+            for c in children {
+                lines.extend(ts_child(c, &child_indent));
+            }
+            lines.push(format!("{}}};", indent));
+        }
+    }
+    lines.push(format!("{}}};", &indent[2..]));
+    lines.join("\n") + "\n"
+}
+
+fn ts_base_node(s: &NodeSchema) -> String {
+    let mut lines = vec![
+        format!("interface {} {{", s.name()),
+        "  token: Token | null;".to_string(),
+        "  node_type: string;".to_string(),
+        "  children: {".to_string(),
+    ];
+    if let ChildrenSchema::Own(children) = s.children {
+        for c in children {
+            lines.extend(ts_child(c, "    "));
+        }
+    }
+    lines.push("  };".to_string());
+    lines.push("}".to_string());
+    lines.join("\n") + "\n"
+}
+
+pub fn typescript_declarations() -> String {
+    let concretes: Vec<&NodeSchema> = SCHEMA.iter().filter(|s| !s.is_abstract()).collect();
+    let abstracts: Vec<&NodeSchema> = SCHEMA
+        .iter()
+        .filter(|s| s.is_abstract() && !s.extends.is_empty())
+        .collect();
+    let mut sections = vec![HEADER.to_string()];
+    let mut union: Vec<String> = concretes
+        .iter()
+        .map(|s| format!("  | {}", s.name()))
+        .collect();
+    union.sort();
+    sections.push(format!(
+        "export type UnknownNode =\n{};\n",
+        union.join("\n")
+    ));
+    sections.push(TOKEN.to_string());
+    sections.push(DOCUMENT.to_string());
+    sections.push(DBT_CALL.to_string());
+    sections.push(SQLX_TABLE.to_string());
+    sections.push(DIAGNOSTIC.to_string());
+    sections.push(RESOLUTION.to_string());
+    sections.push(PROJECT.to_string());
+    sections.push(CATALOG.to_string());
+    sections.push(MIGRATION.to_string());
+    sections.push(TABLE_FIELD_SCHEMA.to_string());
+    sections.push(OPTIONS_CLAUSE.to_string());
+    sections.push(LITERAL.to_string());
+    for s in SCHEMA.iter().filter(|s| s.extends.is_empty()) {
+        sections.push(ts_base_node(s));
+    }
+    sections.push(CHILD_ALIASES.to_string());
+    let mut abstract_section = vec!["// ----- sub types of BaseNode (abstract) -----".to_string()];
+    abstract_section.extend(abstracts.iter().map(|s| ts_node(s)));
+    sections.push(abstract_section.join("\n"));
+    let mut concrete_section = vec!["// ----- sub types of BaseNode (concrete) -----".to_string()];
+    concrete_section.extend(concretes.iter().map(|s| ts_node(s)));
+    sections.push(concrete_section.join("\n"));
+    sections.join("\n")
+}
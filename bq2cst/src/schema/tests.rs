@@ -0,0 +1,31 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+#[test]
+fn test_typescript_declarations() {
+    let expected = typescript_declarations();
+    let actual =
+        std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/src/bq2cst.d.ts")).unwrap();
+    assert_eq!(expected, actual, "src/bq2cst.d.ts is stale, run `make dts`");
+}
+
+#[test]
+fn test_validate_fixtures() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../prettier-plugin-bq/input");
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "sql"))
+        .collect();
+    paths.sort();
+    for path in paths {
+        let code = std::fs::read_to_string(&path).unwrap();
+        let tokens = Lexer::new(&code).tokenize_code().unwrap();
+        for stmt in Parser::new(tokens).parse_code().unwrap() {
+            if let Err(e) = validate(&stmt) {
+                panic!("{}\n{}", path.display(), e);
+            }
+        }
+    }
+}
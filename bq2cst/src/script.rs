@@ -0,0 +1,394 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node, NodeType};
+use crate::diagnostic::Diagnostic;
+use crate::token::Token;
+use serde::Serialize;
+
+// NOTE
+// Identifiers in queries may be columns, so they are resolved only when a variable is found.
+// Identifiers in scripting expressions (e.g. `SET x = y`) must be variables.
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum VariableKind {
+    Declare,      // DECLARE x INT64
+    For,          // FOR x IN (SELECT 1)
+    Parameter,    // CREATE PROCEDURE p(x INT64)
+    OutParameter, // CREATE PROCEDURE p(OUT x INT64)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Variable<'a> {
+    pub token: Token<'a>,
+    pub kind: VariableKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Reference<'a> {
+    pub token: Token<'a>,
+    pub variable: usize, // index of `Resolution.variables`
+    pub write: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Resolution<'a> {
+    pub variables: Vec<Variable<'a>>,
+    pub references: Vec<Reference<'a>>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Context {
+    Script,
+    Query,
+}
+
+// `CURRENT_DATE` and so on can be called without parentheses
+const NILADIC_FUNCTIONS: [&str; 4] = [
+    "current_date",
+    "current_datetime",
+    "current_time",
+    "current_timestamp",
+];
+
+// these children are never variables
+const SKIPPED_KEYS: [&str; 11] = [
+    "alias",
+    "as",
+    "cast_to",
+    "date_part",
+    "extract_datepart",
+    "func",
+    "label",
+    "leading_label",
+    "to_date_part",
+    "trailing_label",
+    "type",
+];
+
+// the position of the date part argument (e.g. `MONTH` of `DATE_TRUNC(d, MONTH)`)
+const DATE_PART_ARGS: [(&str, usize); 9] = [
+    ("date_diff", 2),
+    ("datetime_diff", 2),
+    ("time_diff", 2),
+    ("timestamp_diff", 2),
+    ("date_trunc", 1),
+    ("datetime_trunc", 1),
+    ("time_trunc", 1),
+    ("timestamp_trunc", 1),
+    ("last_day", 1),
+];
+
+fn date_part_arg(node: &Node) -> Option<usize> {
+    if node.node_type != NodeType::CallingFunction {
+        return None;
+    }
+    let name = node.get_node("func")?.token?.literal.to_lowercase();
+    DATE_PART_ARGS
+        .iter()
+        .find(|(f, _)| *f == name)
+        .map(|(_, i)| *i)
+}
+
+// variables are case insensitive
+fn variable_name(token: &Token) -> String {
+    token.literal.trim_matches('`').to_lowercase()
+}
+
+fn is_query(node: &Node) -> bool {
+    matches!(
+        node.node_type,
+        NodeType::SelectStatement
+            | NodeType::GroupedStatement
+            | NodeType::SetOperator
+            | NodeType::FromStatement
+            | NodeType::PipeStatement
+    )
+}
+
+struct Resolver<'a> {
+    scopes: Vec<Vec<usize>>,
+    res: Resolution<'a>,
+}
+
+impl<'a> Resolver<'a> {
+    fn lookup(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .copied()
+            .find(|i| variable_name(&self.res.variables[*i].token) == name)
+    }
+    fn declare(&mut self, token: Token<'a>, kind: VariableKind) {
+        if let Some(i) = self.lookup(&variable_name(&token)) {
+            let first = self.res.variables[i].token;
+            self.res.diagnostics.push(Diagnostic::error(
+                "duplicate-variable",
+                &token,
+                format!(
+                    "Variable `{}` is already declared at {}:{}.",
+                    token.literal, first.line, first.column
+                ),
+            ));
+        }
+        self.res.variables.push(Variable { token, kind });
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(self.res.variables.len() - 1);
+        }
+    }
+    fn reference(&mut self, token: Token<'a>, context: Context, write: bool) {
+        let name = variable_name(&token);
+        match self.lookup(&name) {
+            Some(variable) => self.res.references.push(Reference {
+                token,
+                variable,
+                write,
+            }),
+            None if context == Context::Script && !NILADIC_FUNCTIONS.contains(&name.as_str()) => {
+                self.res.diagnostics.push(Diagnostic::error(
+                    "undeclared-variable",
+                    &token,
+                    format!("Variable `{}` is not declared.", token.literal),
+                ))
+            }
+            None => (),
+        }
+    }
+    fn push_scope(&mut self) {
+        self.scopes.push(Vec::new());
+    }
+    fn pop_scope(&mut self) {
+        for i in self.scopes.pop().unwrap_or_default() {
+            let variable = &self.res.variables[i];
+            // values of OUT parameters are read by the caller
+            let used = self.res.references.iter().any(|r| {
+                r.variable == i && (!r.write || variable.kind == VariableKind::OutParameter)
+            });
+            if !used {
+                self.res.diagnostics.push(Diagnostic::warning(
+                    "unused-variable",
+                    &variable.token,
+                    format!("Variable `{}` is never used.", variable.token.literal),
+                ));
+            }
+        }
+    }
+    // DECLARE is allowed only at the start of a block
+    fn stmts(&mut self, stmts: &[Node<'a>], block: bool) {
+        let mut leading = block;
+        for stmt in stmts {
+            if stmt.node_type != NodeType::DeclareStatement {
+                leading = false;
+            } else if let (false, Some(token)) = (leading, &stmt.token) {
+                self.res.diagnostics.push(Diagnostic::error(
+                    "misplaced-declare",
+                    token,
+                    "DECLARE is only allowed at the start of a block.".to_string(),
+                ));
+            }
+            self.stmt(stmt);
+        }
+    }
+    // THEN ... | DO ... | ELSE ...
+    fn keyword_stmts(&mut self, node: Option<&Node<'a>>) {
+        if let Some(node) = node {
+            self.stmts(node.get_node_vec("stmts"), false);
+        }
+    }
+    fn stmt(&mut self, node: &Node<'a>) {
+        match node.node_type {
+            NodeType::DeclareStatement => {
+                // the default value is evaluated before the declaration
+                if let Some(default) = node.get_node("default") {
+                    self.expr(default.get_node("expr"), Context::Script);
+                }
+                for ident in node.get_node_vec("idents") {
+                    if let Some(token) = ident.token {
+                        self.declare(token, VariableKind::Declare);
+                    }
+                }
+            }
+            NodeType::SetStatement => match node.get_node("expr") {
+                Some(assign) if assign.node_type == NodeType::BinaryOperator => {
+                    if let Some(left) = assign.get_node("left") {
+                        if left.node_type == NodeType::StructLiteral {
+                            for expr in left.get_node_vec("exprs") {
+                                self.target(expr);
+                            }
+                        } else {
+                            self.target(left);
+                        }
+                    }
+                    self.expr(assign.get_node("right"), Context::Script);
+                }
+                expr => self.expr(expr, Context::Script),
+            },
+            NodeType::ForStatement => {
+                self.expr(node.get_node("in"), Context::Query);
+                self.push_scope();
+                if let Some(token) = node.get_node("ident").and_then(|n| n.token) {
+                    self.declare(token, VariableKind::For);
+                }
+                self.keyword_stmts(node.get_node("do"));
+                self.pop_scope();
+            }
+            NodeType::BeginStatement => {
+                self.push_scope();
+                self.stmts(node.get_node_vec("stmts"), true);
+                self.keyword_stmts(node.get_node("then")); // EXCEPTION WHEN ERROR THEN
+                self.pop_scope();
+            }
+            NodeType::CreateProcedureStatement => {
+                // variables of the script are not visible from the procedure
+                let outer = std::mem::take(&mut self.scopes);
+                self.push_scope();
+                if let Some(group) = node.get_node("group") {
+                    for declaration in group.get_node_vec("declarations") {
+                        let kind = match declaration.get_node("in_out").and_then(|n| n.token) {
+                            Some(t) if !t.literal.eq_ignore_ascii_case("IN") => {
+                                VariableKind::OutParameter
+                            }
+                            _ => VariableKind::Parameter,
+                        };
+                        if let Some(token) = declaration.token {
+                            self.declare(token, kind);
+                        }
+                    }
+                }
+                if let Some(body) = node.get_node("stmt") {
+                    self.stmt(body);
+                }
+                self.pop_scope();
+                self.scopes = outer;
+            }
+            NodeType::IfStatement => {
+                self.expr(node.get_node("condition"), Context::Script);
+                self.keyword_stmts(node.get_node("then"));
+                for elseif in node.get_node_vec("elseifs") {
+                    self.expr(elseif.get_node("condition"), Context::Script);
+                    self.keyword_stmts(elseif.get_node("then"));
+                }
+                self.keyword_stmts(node.get_node("else"));
+            }
+            NodeType::WhileStatement => {
+                self.expr(node.get_node("condition"), Context::Script);
+                self.keyword_stmts(node.get_node("do"));
+            }
+            NodeType::LoopStatement => self.stmts(node.get_node_vec("stmts"), false),
+            NodeType::RepeatStatement => {
+                self.stmts(node.get_node_vec("stmts"), false);
+                if let Some(until) = node.get_node("until") {
+                    self.expr(until.get_node("expr"), Context::Script);
+                }
+            }
+            NodeType::CaseStatement => {
+                self.expr(node.get_node("expr"), Context::Script);
+                for arm in node.get_node_vec("arms") {
+                    self.expr(arm.get_node("expr"), Context::Script);
+                    self.stmts(arm.get_node_vec("stmts"), false);
+                }
+            }
+            NodeType::ExecuteStatement => {
+                self.expr(node.get_node("sql_expr"), Context::Script);
+                if let Some(into) = node.get_node("into") {
+                    for expr in into.get_node_vec("exprs") {
+                        self.target(expr);
+                    }
+                }
+                if let Some(using) = node.get_node("using") {
+                    for expr in using.get_node_vec("exprs") {
+                        self.expr(Some(expr), Context::Script);
+                    }
+                }
+            }
+            NodeType::CallStatement => {
+                if let Some(procedure) = node.get_node("procedure") {
+                    for arg in procedure.get_node_vec("args") {
+                        self.expr(Some(arg), Context::Script);
+                    }
+                }
+            }
+            // RAISE USING MESSAGE = x
+            NodeType::RaiseStatement => {
+                if let Some(using) = node.get_node("using").and_then(|n| n.get_node("expr")) {
+                    self.expr(using.get_node("right"), Context::Script);
+                }
+            }
+            // labels and parameters of functions are not variables
+            NodeType::BreakContinueStatement | NodeType::CreateFunctionStatement => (),
+            _ => self.expr(Some(node), Context::Query),
+        }
+    }
+    // SET x = ... | EXECUTE IMMEDIATE ... INTO x
+    fn target(&mut self, node: &Node<'a>) {
+        match (node.node_type, node.token) {
+            (NodeType::Identifier, Some(token)) => self.reference(token, Context::Script, true),
+            _ => self.expr(Some(node), Context::Script),
+        }
+    }
+    fn expr(&mut self, node: Option<&Node<'a>>, context: Context) {
+        let Some(node) = node else {
+            return;
+        };
+        let context = if is_query(node) {
+            Context::Query
+        } else {
+            context
+        };
+        match (node.node_type, node.token) {
+            (NodeType::Identifier, Some(token)) => self.reference(token, context, false),
+            // the right side is a field
+            (NodeType::DotOperator, _) => {
+                self.expr(node.get_node("left"), context);
+                return;
+            }
+            // f(x => 1)
+            (NodeType::BinaryOperator, Some(token)) if token.literal == "=>" => {
+                self.expr(node.get_node("right"), context);
+                return;
+            }
+            (NodeType::MultiTokenIdentifier | NodeType::Type, _) => return,
+            _ => (),
+        }
+        let date_part = date_part_arg(node);
+        for (key, content) in &node.children {
+            if SKIPPED_KEYS.contains(&key.as_str()) {
+                continue;
+            }
+            match content {
+                ContentType::Node(n) => self.expr(Some(n), context),
+                ContentType::NodeVec(ns) => {
+                    for (i, n) in ns.iter().enumerate() {
+                        if key == "args" && date_part == Some(i) {
+                            continue;
+                        }
+                        self.expr(Some(n), context);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// variables declared by DECLARE, FOR and procedure parameters
+pub fn resolve_variables<'a>(stmts: &[Node<'a>]) -> Resolution<'a> {
+    let mut resolver = Resolver {
+        scopes: Vec::new(),
+        res: Resolution {
+            variables: Vec::new(),
+            references: Vec::new(),
+            diagnostics: Vec::new(),
+        },
+    };
+    resolver.push_scope();
+    resolver.stmts(stmts, true);
+    resolver.pop_scope();
+    let mut res = resolver.res;
+    res.references
+        .sort_by_key(|r| (r.token.line, r.token.column));
+    res.diagnostics
+        .sort_by_key(|d| (d.span.line, d.span.column));
+    res
+}
@@ -0,0 +1,133 @@
+use super::*;
+use crate::diagnostic::Span;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn resolve(code: &str) -> Resolution<'_> {
+    let tokens = Lexer::new(code).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    resolve_variables(&stmts)
+}
+
+fn diagnostics(res: &Resolution) -> Vec<(&'static str, usize, usize)> {
+    res.diagnostics
+        .iter()
+        .map(|d| (d.code, d.span.line, d.span.column))
+        .collect()
+}
+
+#[test]
+fn test_resolve_variables() {
+    let code = "\
+DECLARE x, y INT64 DEFAULT 1;
+DECLARE z STRING;
+SET x = y + 1;
+SET (x, z) = (1, 'a');
+BEGIN
+  DECLARE w INT64;
+  SELECT w, t.col FROM t WHERE t.x = X;
+EXCEPTION WHEN ERROR THEN
+  SELECT @@error.message;
+END;
+FOR r IN (SELECT 1 AS a) DO
+  SELECT r.a;
+END FOR;
+EXECUTE IMMEDIATE 'SELECT ?' INTO x USING y AS p;
+CREATE PROCEDURE p(IN a INT64, OUT b STRING)
+BEGIN
+  SET b = CAST(a AS STRING);
+END;
+IF x > 1 THEN SELECT 1; ELSEIF y THEN SELECT 2; END IF;
+";
+    let res = resolve(code);
+    assert_eq!(
+        vec!["x", "y", "z", "w", "r", "a", "b"],
+        res.variables
+            .iter()
+            .map(|v| v.token.literal)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(VariableKind::OutParameter, res.variables[6].kind);
+    // (line, column, variable, write)
+    assert_eq!(
+        vec![
+            (3, 5, 0, true),
+            (3, 9, 1, false),
+            (4, 6, 0, true),
+            (4, 9, 2, true),
+            (7, 10, 3, false),
+            (7, 38, 0, false),
+            (12, 10, 4, false),
+            (14, 35, 0, true),
+            (14, 43, 1, false),
+            (17, 7, 6, true),
+            (17, 16, 5, false),
+            (19, 4, 0, false),
+            (19, 32, 1, false),
+        ],
+        res.references
+            .iter()
+            .map(|r| (r.token.line, r.token.column, r.variable, r.write))
+            .collect::<Vec<_>>()
+    );
+    // `z` is only written
+    assert_eq!(vec![("unused-variable", 2, 9)], diagnostics(&res));
+}
+
+#[test]
+fn test_diagnostics() {
+    let code = "\
+SET a = 1;
+DECLARE b INT64;
+BEGIN
+  DECLARE b STRING;
+  SELECT b, c;
+END;
+IF d THEN SELECT CURRENT_DATE; END IF;
+SET b = CURRENT_DATE;
+WHILE TRUE DO
+  DECLARE e INT64;
+END WHILE;
+";
+    let res = resolve(code);
+    assert_eq!(
+        vec![
+            ("undeclared-variable", 1, 5),
+            ("misplaced-declare", 2, 1),
+            ("unused-variable", 2, 9),
+            ("duplicate-variable", 4, 11),
+            ("undeclared-variable", 7, 4),
+            ("misplaced-declare", 10, 3),
+            ("unused-variable", 10, 11),
+        ],
+        diagnostics(&res)
+    );
+    assert_eq!(
+        Span {
+            line: 4,
+            column: 11,
+            end_line: 4,
+            end_column: 12
+        },
+        res.diagnostics[3].span
+    );
+}
+
+#[test]
+fn test_date_parts() {
+    let code = "\
+DECLARE d DATE;
+DECLARE n INT64;
+SET n = EXTRACT(YEAR FROM d);
+SET d = DATE_TRUNC(d, MONTH);
+SET d = LAST_DAY(d, WEEK);
+SET n = DATE_DIFF(d, d, WEEK(MONDAY));
+SET n = TIMESTAMP_DIFF(CURRENT_TIMESTAMP(), CURRENT_TIMESTAMP(), HOUR);
+SET d = d + INTERVAL 1 MONTH;
+SET d = DATE_TRUNC(d, QUARTER) + INTERVAL '1-2' YEAR TO MONTH;
+SET n = DATE_DIFF(d, e, DAY);
+SELECT n;
+";
+    let res = resolve(code);
+    assert_eq!(diagnostics(&res), vec![("undeclared-variable", 10, 22)]);
+}
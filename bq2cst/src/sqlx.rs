@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node, NodeType};
+use crate::error::BQ2CSTResult;
+use crate::lexer::{sqlx_interpolations, Lexer};
+use crate::parser::Parser;
+use crate::template::{parse_interpolation, string_value};
+use crate::token::{Token, TokenKind};
+use serde::Serialize;
+
+// NOTE
+// Dataform SQLX files consist of blocks (`config { ... }` and so on) and GoogleSQL.
+// The contents of `config` and `js` blocks are JavaScript and they are kept as is.
+// The contents of `pre_operations` and `post_operations` blocks are SQL.
+
+// blocks whose contents are SQL
+const SQL_BLOCKS: [&str; 2] = ["pre_operations", "post_operations"];
+
+pub fn parse(code: &str) -> BQ2CSTResult<Vec<Node<'_>>> {
+    let tokens = Lexer::new(code).with_sqlx(true).tokenize_code()?;
+    Parser::new(tokens).parse_code()
+}
+
+// tokens of the contents, `None` if the block is not a SQL block
+pub(crate) fn sql_block_tokens<'a>(token: &Token<'a>) -> BQ2CSTResult<Option<Vec<Token<'a>>>> {
+    let literal = token.literal;
+    if !SQL_BLOCKS.iter().any(|name| literal.starts_with(name)) {
+        return Ok(None);
+    }
+    let (head, body) = match literal.find('{') {
+        Some(i) => (&literal[..=i], &literal[i + 1..literal.len() - 1]),
+        None => return Ok(None),
+    };
+    // the position just after `{`
+    let line = token.line + head.matches('\n').count();
+    let column = match head.rfind('\n') {
+        Some(i) => head[i + 1..].chars().count() + 1,
+        None => token.column + head.chars().count(),
+    };
+    let tokens = Lexer::new(body)
+        .with_sqlx(true)
+        .with_position(line, column)
+        .tokenize_code()?;
+    Ok(Some(tokens))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SqlxTable<'a> {
+    pub token: Token<'a>, // `ref` or `resolve`
+    // `None` means it is not given as a string literal
+    pub database: Option<String>,
+    pub schema: Option<String>,
+    pub name: Option<String>,
+}
+
+fn collect_tables<'a>(node: &Node<'a>, res: &mut Vec<SqlxTable<'a>>) {
+    if node.node_type == NodeType::JinjaCall {
+        if let (Some(ContentType::Node(func)), Some(ContentType::NodeVec(args))) =
+            (node.children.get("func"), node.children.get("args"))
+        {
+            match func.token {
+                Some(token)
+                    if func.node_type == NodeType::JinjaName
+                        && matches!(token.literal, "ref" | "resolve") =>
+                {
+                    res.push(table(token, args));
+                }
+                _ => (),
+            }
+        }
+    }
+    let mut children: Vec<(&String, &ContentType)> = node.children.iter().collect();
+    children.sort_by_key(|(k, _)| *k);
+    for (_, content) in children {
+        match content {
+            ContentType::Node(n) => collect_tables(n, res),
+            ContentType::NodeVec(ns) => ns.iter().for_each(|n| collect_tables(n, res)),
+        }
+    }
+}
+
+// ref("name") | ref("schema", "name") | ref({schema: "schema", name: "name"})
+fn table<'a>(token: Token<'a>, args: &[Node<'a>]) -> SqlxTable<'a> {
+    let mut res = SqlxTable {
+        token,
+        database: None,
+        schema: None,
+        name: None,
+    };
+    match args {
+        [object] if object.token.is_some_and(|t| t.literal == "{") => {
+            if let Some(ContentType::NodeVec(pairs)) = object.children.get("exprs") {
+                for pair in pairs {
+                    let (Some(ContentType::Node(key)), Some(ContentType::Node(value))) =
+                        (pair.children.get("left"), pair.children.get("right"))
+                    else {
+                        continue;
+                    };
+                    let key = match key.token.map(|t| t.literal.trim_matches(['\'', '"'])) {
+                        Some(key) => key,
+                        None => continue,
+                    };
+                    match key {
+                        "database" => res.database = string_value(value),
+                        "schema" => res.schema = string_value(value),
+                        "name" => res.name = string_value(value),
+                        _ => (),
+                    }
+                }
+            }
+        }
+        [name] => res.name = string_value(name),
+        [schema, name] => {
+            res.schema = string_value(schema);
+            res.name = string_value(name);
+        }
+        _ => (),
+    }
+    res
+}
+
+// tables referenced by `${ref(...)}` and `${resolve(...)}` in the order of appearance.
+// interpolations which cannot be parsed are ignored.
+pub fn referenced_tables(code: &str) -> BQ2CSTResult<Vec<SqlxTable<'_>>> {
+    let tokens = Lexer::new(code).with_sqlx(true).tokenize_code()?;
+    let mut res = Vec::new();
+    tables_of_tokens(&tokens, &mut res)?;
+    Ok(res)
+}
+
+fn tables_of_tokens<'a>(tokens: &[Token<'a>], res: &mut Vec<SqlxTable<'a>>) -> BQ2CSTResult<()> {
+    for token in tokens {
+        match token.kind {
+            // `${self()}_tmp`, `'${ref("a")}'`
+            TokenKind::SqlxInterpolation | TokenKind::StringLiteral => {
+                for part in sqlx_interpolations(token) {
+                    if let Ok(node) = parse_interpolation(&part) {
+                        let mut tables = Vec::new();
+                        collect_tables(&node, &mut tables);
+                        tables.sort_by_key(|t| (t.token.line, t.token.column));
+                        res.extend(tables);
+                    }
+                }
+            }
+            TokenKind::SqlxBlock => {
+                if let Some(tokens) = sql_block_tokens(token)? {
+                    tables_of_tokens(&tokens, res)?;
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use super::*;
+
+#[test]
+fn test_parse() {
+    let code = "\
+config {
+  type: \"table\",
+  tags: [\"daily\"], // }
+  description: `{${\"x\"}}`,
+}
+
+js {
+  const x = '}';
+}
+
+SELECT *
+FROM ${ref(\"a\")} AS a
+WHERE dt = ${when(incremental(), \"CURRENT_DATE()\", \"dt\")};
+
+post_operations {
+  DROP TABLE IF EXISTS ${self()}_tmp
+}
+";
+    let stmts = parse(code).unwrap();
+    assert_eq!(
+        vec![
+            NodeType::SqlxBlock,
+            NodeType::SqlxBlock,
+            NodeType::SelectStatement,
+            NodeType::SqlxBlock,
+            NodeType::EOF,
+        ],
+        stmts.iter().map(|s| s.node_type).collect::<Vec<_>>()
+    );
+    assert_eq!(Some(1), stmts[0].token.map(|t| t.line));
+    assert!(stmts[0]
+        .token
+        .is_some_and(|t| t.literal.ends_with("`{${\"x\"}}`,\n}")));
+    assert_eq!(
+        Some("js {\n  const x = '}';\n}"),
+        stmts[1].token.map(|t| t.literal)
+    );
+    for stmt in &stmts {
+        crate::schema::validate(stmt).unwrap();
+    }
+    let dumped = stmts[2].to_string();
+    assert!(dumped.contains("SqlxInterpolation"));
+    assert!(dumped.contains("JinjaCall"));
+    // the contents of post_operations are SQL
+    let operations = stmts[3].get_node_vec("stmts");
+    assert_eq!(
+        vec![NodeType::DropStatement],
+        operations.iter().map(|s| s.node_type).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some((16, 3)),
+        operations[0].token.map(|t| (t.line, t.column))
+    );
+    assert!(stmts[0].get_node_vec("stmts").is_empty());
+    let ident = operations[0].get_node("ident").unwrap();
+    assert_eq!(NodeType::SqlxInterpolation, ident.node_type);
+    assert_eq!(Some("${self()}_tmp"), ident.token.map(|t| t.literal));
+    assert_eq!(1, ident.get_node_vec("exprs").len());
+}
+
+#[test]
+fn test_parse_without_sqlx() {
+    // pattern quantifiers are not SQLX blocks
+    let code = "\
+SELECT * FROM t MATCH_RECOGNIZE (
+  ORDER BY x
+  PATTERN (a
+  {1,2})
+  DEFINE a AS true
+)";
+    assert!(parse(code).is_ok());
+    assert!(parse("config {").is_err());
+}
+
+#[test]
+fn test_referenced_tables() {
+    let code = "\
+config { type: \"view\" }
+SELECT * FROM ${ref(\"a\")}
+JOIN ${ref(\"s\", \"b\")} USING (id)
+JOIN ${resolve({database: \"d\", schema: \"s\", name: \"c\"})} USING (id)
+JOIN ${ref(name)} USING (id)
+JOIN ${self()} USING (id)
+JOIN ${ref(\"c\")}_${ref(\"d\")} USING (id)
+WHERE x = '${ref(\"e\")}'
+";
+    let tables = referenced_tables(code).unwrap();
+    assert_eq!(
+        vec![
+            (None, None, Some("a")),
+            (None, Some("s"), Some("b")),
+            (Some("d"), Some("s"), Some("c")),
+            (None, None, None),
+            (None, None, Some("c")),
+            (None, None, Some("d")),
+            (None, None, Some("e")),
+        ],
+        tables
+            .iter()
+            .map(|t| (
+                t.database.as_deref(),
+                t.schema.as_deref(),
+                t.name.as_deref()
+            ))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!((2, 17), (tables[0].token.line, tables[0].token.column));
+    assert_eq!("resolve", tables[2].token.literal);
+}
+
+#[test]
+fn test_referenced_tables_in_operations() {
+    let code = "\
+pre_operations {
+  DECLARE x DEFAULT (SELECT MAX(dt) FROM ${ref(\"a\")});
+}
+SELECT * FROM ${ref(\"b\")}
+";
+    let tables = referenced_tables(code).unwrap();
+    assert_eq!(
+        vec![(Some("a"), 2, 44), (Some("b"), 4, 17)],
+        tables
+            .iter()
+            .map(|t| (t.name.as_deref(), t.token.line, t.token.column))
+            .collect::<Vec<_>>()
+    );
+    let stmts = parse(code).unwrap();
+    assert_eq!(
+        NodeType::DeclareStatement,
+        stmts[0].get_node_vec("stmts")[0].node_type
+    );
+}
@@ -0,0 +1,501 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node, NodeType};
+use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::lexer::Lexer;
+use crate::parser::DEFAULT_MAX_DEPTH;
+use crate::token::Token;
+use serde::Serialize;
+
+// NOTE
+// This is a small subset of Jinja expressions.
+// It is enough to find calls like `ref('model')` and it is not intended to render templates.
+
+// ----- lexer -----
+struct JinjaLexer<'a> {
+    input: &'a str,
+    position: usize, // byte offset in `input`
+    line: usize,
+    column: usize,
+    tokens: Vec<Token<'a>>,
+}
+
+impl<'a> JinjaLexer<'a> {
+    fn new(input: &'a str, line: usize, column: usize) -> JinjaLexer<'a> {
+        JinjaLexer {
+            input,
+            position: 0,
+            line,
+            column,
+            tokens: Vec::new(),
+        }
+    }
+    fn get_char(&self, offset: usize) -> Option<char> {
+        self.input[self.position..].chars().nth(offset)
+    }
+    fn next_char(&mut self) {
+        if let Some(ch) = self.get_char(0) {
+            if ch == '\n' {
+                self.column = 1;
+                self.line += 1;
+            } else {
+                self.column += 1;
+            }
+            self.position += ch.len_utf8();
+        }
+    }
+    // `{{`, `{%-` and so on
+    fn skip(&mut self, length: usize) {
+        for _ in 0..length {
+            self.next_char();
+        }
+    }
+    fn tokenize(mut self, end: usize) -> BQ2CSTResult<Vec<Token<'a>>> {
+        loop {
+            while self.get_char(0).is_some_and(|ch| ch.is_whitespace()) {
+                self.next_char();
+            }
+            if end <= self.position {
+                break;
+            }
+            let (line, column, first_position) = (self.line, self.column, self.position);
+            let ch = self.get_char(0).unwrap_or_default();
+            if ch.is_ascii_alphabetic() || ch == '_' {
+                while self
+                    .get_char(0)
+                    .is_some_and(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+                {
+                    self.next_char();
+                }
+            } else if ch.is_ascii_digit() {
+                while self
+                    .get_char(0)
+                    .is_some_and(|ch| ch.is_ascii_digit() || ch == '.' || ch == '_')
+                {
+                    self.next_char();
+                }
+            } else if ch == '\'' || ch == '"' {
+                self.next_char(); // -> contents
+                loop {
+                    match self.get_char(0) {
+                        Some('\\') => self.skip(2),
+                        Some(c) if c == ch => break,
+                        Some(_) => self.next_char(),
+                        None => {
+                            return Err(BQ2CSTError::new(
+                                line,
+                                column,
+                                "String literal is not closed.".to_string(),
+                            ))
+                        }
+                    }
+                }
+                self.next_char(); // -> quote
+            } else {
+                let two: String = [self.get_char(0), self.get_char(1)]
+                    .iter()
+                    .flatten()
+                    .collect();
+                if ["==", "!=", "<=", ">=", "//", "**"].contains(&two.as_str()) {
+                    self.skip(2);
+                } else if "()[]{},.:|=<>+-*/%~".contains(ch) {
+                    self.next_char();
+                } else {
+                    return Err(BQ2CSTError::new(
+                        line,
+                        column,
+                        format!("Unexpected character in template: {}", ch),
+                    ));
+                }
+            }
+            let literal = &self.input[first_position..self.position];
+            self.tokens.push(Token::new(line, column, literal));
+        }
+        Ok(self.tokens)
+    }
+}
+
+// ----- parser -----
+struct JinjaParser<'a> {
+    tokens: Vec<Token<'a>>,
+    position: usize,
+    depth: usize,
+}
+
+impl<'a> JinjaParser<'a> {
+    fn get_token(&self, offset: usize) -> Option<&Token<'a>> {
+        self.tokens.get(self.position + offset)
+    }
+    fn peek_literal(&self) -> Option<&'a str> {
+        self.get_token(0).map(|t| t.literal)
+    }
+    fn next_token(&mut self) -> BQ2CSTResult<Token<'a>> {
+        match self.tokens.get(self.position) {
+            Some(token) => {
+                self.position += 1;
+                Ok(*token)
+            }
+            None => Err(self.error("Unexpected end of template.")),
+        }
+    }
+    fn error(&self, message: &str) -> BQ2CSTError {
+        match self.tokens.get(self.position).or(self.tokens.last()) {
+            Some(token) => BQ2CSTError::from_token(token, message.to_string()),
+            None => BQ2CSTError::new(0, 0, message.to_string()),
+        }
+    }
+    fn expect(&mut self, literal: &str) -> BQ2CSTResult<Token<'a>> {
+        if self.peek_literal() != Some(literal) {
+            return Err(self.error(&format!("Expected `{}`.", literal)));
+        }
+        self.next_token()
+    }
+    // lower value means lower precedence
+    fn binary_precedence(&self) -> Option<usize> {
+        let precedence = match self.peek_literal()? {
+            "if" | "else" => 1,
+            "or" => 2,
+            "and" => 3,
+            "==" | "!=" | "<" | ">" | "<=" | ">=" | "in" | "is" => 5,
+            "not" if self.get_token(1).map(|t| t.literal) == Some("in") => 5,
+            "~" => 6,
+            "+" | "-" => 7,
+            "*" | "/" | "//" | "%" => 8,
+            "**" => 10,
+            _ => return None,
+        };
+        Some(precedence)
+    }
+    // nested expressions are parsed recursively
+    fn parse_expr(&mut self, precedence: usize) -> BQ2CSTResult<Node<'a>> {
+        if DEFAULT_MAX_DEPTH <= self.depth {
+            return Err(self.error(&format!(
+                "Nesting is too deep (max depth: {})",
+                DEFAULT_MAX_DEPTH
+            )));
+        }
+        self.depth += 1;
+        let res = self.parse_expr_inner(precedence);
+        self.depth -= 1;
+        res
+    }
+    fn parse_expr_inner(&mut self, precedence: usize) -> BQ2CSTResult<Node<'a>> {
+        let mut left = match self.peek_literal() {
+            Some("not") => {
+                let mut node = Node::new(self.next_token()?, NodeType::JinjaOperator);
+                node.push_node("right", self.parse_expr(4)?);
+                node
+            }
+            Some("-" | "+") => {
+                let mut node = Node::new(self.next_token()?, NodeType::JinjaOperator);
+                node.push_node("right", self.parse_expr(9)?);
+                node
+            }
+            _ => self.parse_postfix_expr()?,
+        };
+        while let Some(p) = self.binary_precedence() {
+            if p <= precedence {
+                break;
+            }
+            let mut node = Node::new(self.next_token()?, NodeType::JinjaOperator);
+            if node.token.is_some_and(|t| t.literal == "not") {
+                node.push_node("in", Node::new(self.next_token()?, NodeType::Keyword));
+            } else if node.token.is_some_and(|t| t.literal == "is")
+                && self.peek_literal() == Some("not")
+            {
+                node.push_node("not", Node::new(self.next_token()?, NodeType::Keyword));
+            }
+            node.push_node("left", left);
+            node.push_node("right", self.parse_expr(p)?);
+            left = node;
+        }
+        Ok(left)
+    }
+    fn parse_postfix_expr(&mut self) -> BQ2CSTResult<Node<'a>> {
+        let mut left = self.parse_primary_expr()?;
+        loop {
+            match self.peek_literal() {
+                Some(".") => {
+                    let mut node = Node::new(self.next_token()?, NodeType::JinjaAttribute);
+                    node.push_node("left", left);
+                    node.push_node("right", Node::new(self.next_token()?, NodeType::JinjaName));
+                    left = node;
+                }
+                Some("[") => {
+                    let mut node = Node::new(self.next_token()?, NodeType::JinjaSubscript);
+                    node.push_node("left", left);
+                    node.push_node("right", self.parse_expr(0)?);
+                    node.push_node("rbrack", Node::new(self.expect("]")?, NodeType::Symbol));
+                    left = node;
+                }
+                Some("(") => {
+                    left = self.parse_call(left)?;
+                }
+                Some("|") => {
+                    let mut node = Node::new(self.next_token()?, NodeType::JinjaFilter);
+                    node.push_node("left", left);
+                    let mut filter = Node::new(self.next_token()?, NodeType::JinjaName);
+                    while self.peek_literal() == Some(".") {
+                        let mut attr = Node::new(self.next_token()?, NodeType::JinjaAttribute);
+                        attr.push_node("left", filter);
+                        attr.push_node("right", Node::new(self.next_token()?, NodeType::JinjaName));
+                        filter = attr;
+                    }
+                    if self.peek_literal() == Some("(") {
+                        filter = self.parse_call(filter)?;
+                    }
+                    node.push_node("filter", filter);
+                    left = node;
+                }
+                _ => break,
+            }
+        }
+        Ok(left)
+    }
+    fn parse_call(&mut self, func: Node<'a>) -> BQ2CSTResult<Node<'a>> {
+        let mut node = Node::new(self.next_token()?, NodeType::JinjaCall);
+        node.push_node("func", func);
+        let mut args = Vec::new();
+        while self.peek_literal() != Some(")") {
+            let mut arg = self.parse_expr(0)?;
+            if self.peek_literal() == Some("=") {
+                let mut kwarg = Node::new(self.next_token()?, NodeType::JinjaKeywordArg);
+                kwarg.push_node("left", arg);
+                kwarg.push_node("right", self.parse_expr(0)?);
+                arg = kwarg;
+            }
+            args.push(arg);
+            if self.peek_literal() == Some(",") {
+                self.next_token()?;
+            } else {
+                break;
+            }
+        }
+        node.push_node_vec("args", args);
+        node.push_node("rparen", Node::new(self.expect(")")?, NodeType::Symbol));
+        Ok(node)
+    }
+    fn parse_primary_expr(&mut self) -> BQ2CSTResult<Node<'a>> {
+        let token = self.next_token()?;
+        let first = token.literal.chars().next().unwrap_or_default();
+        let node = match token.literal {
+            "(" | "[" | "{" => {
+                let closing = match token.literal {
+                    "(" => ")",
+                    "[" => "]",
+                    _ => "}",
+                };
+                let mut node = Node::new(token, NodeType::JinjaGroup);
+                let mut exprs = Vec::new();
+                while self.peek_literal() != Some(closing) {
+                    let mut expr = self.parse_expr(0)?;
+                    if self.peek_literal() == Some(":") {
+                        let mut pair = Node::new(self.next_token()?, NodeType::JinjaPair);
+                        pair.push_node("left", expr);
+                        pair.push_node("right", self.parse_expr(0)?);
+                        expr = pair;
+                    }
+                    exprs.push(expr);
+                    if self.peek_literal() == Some(",") {
+                        self.next_token()?;
+                    } else {
+                        break;
+                    }
+                }
+                node.push_node_vec("exprs", exprs);
+                node.push_node("end", Node::new(self.expect(closing)?, NodeType::Symbol));
+                node
+            }
+            _ if first == '\'' || first == '"' || first.is_ascii_digit() => {
+                Node::new(token, NodeType::JinjaLiteral)
+            }
+            _ if first.is_ascii_alphabetic() || first == '_' => {
+                Node::new(token, NodeType::JinjaName)
+            }
+            _ => {
+                self.position -= 1;
+                return Err(self.error("Unexpected token in template."));
+            }
+        };
+        Ok(node)
+    }
+    // `{% tag expr, expr = expr %}`
+    fn parse_statement(&mut self) -> BQ2CSTResult<Node<'a>> {
+        let mut node = Node::new(self.next_token()?, NodeType::JinjaStatement);
+        let mut exprs = Vec::new();
+        while self.get_token(0).is_some() {
+            if matches!(self.peek_literal(), Some("," | "=")) {
+                self.next_token()?;
+                continue;
+            }
+            exprs.push(self.parse_expr(0)?);
+        }
+        node.push_node_vec("exprs", exprs);
+        Ok(node)
+    }
+}
+
+// parse the inside of `{{ ... }}` or `{% ... %}`
+pub fn parse_tag<'a>(token: &Token<'a>) -> BQ2CSTResult<Node<'a>> {
+    let literal = token.literal;
+    let is_statement = literal.starts_with("{%");
+    if !(literal.starts_with("{{") || is_statement) {
+        return Err(BQ2CSTError::from_token(
+            token,
+            "Only Jinja expressions and statements are supported.".to_string(),
+        ));
+    }
+    let mut start = 2;
+    if literal[2..].starts_with(['-', '+']) {
+        start = 3;
+    }
+    let mut end = literal.len().saturating_sub(2);
+    if start < end && literal[..end].ends_with(['-', '+']) {
+        end -= 1;
+    }
+    parse_inner(token, start, end, is_statement)
+}
+
+// parse the inside of `${ ... }` of Dataform SQLX.
+// simple JavaScript expressions such as `ref("table")` are also valid Jinja expressions.
+pub fn parse_interpolation<'a>(token: &Token<'a>) -> BQ2CSTResult<Node<'a>> {
+    let literal = token.literal;
+    if !(literal.starts_with("${") && literal.ends_with('}')) {
+        return Err(BQ2CSTError::from_token(
+            token,
+            "Only SQLX interpolations are supported.".to_string(),
+        ));
+    }
+    parse_inner(token, 2, literal.len() - 1, false)
+}
+
+fn parse_inner<'a>(
+    token: &Token<'a>,
+    start: usize,
+    end: usize,
+    is_statement: bool,
+) -> BQ2CSTResult<Node<'a>> {
+    let mut lexer = JinjaLexer::new(token.literal, token.line, token.column);
+    lexer.skip(start);
+    let tokens = lexer.tokenize(end)?;
+    let mut parser = JinjaParser {
+        tokens,
+        position: 0,
+        depth: 0,
+    };
+    let node = if is_statement {
+        parser.parse_statement()?
+    } else {
+        parser.parse_expr(0)?
+    };
+    if parser.get_token(0).is_some() {
+        return Err(parser.error("Unexpected token in template."));
+    }
+    Ok(node)
+}
+
+// ----- dbt -----
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum DbtCallKind {
+    Ref,
+    Source,
+    Var,
+    Config,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DbtCall<'a> {
+    pub kind: DbtCallKind,
+    pub token: Token<'a>, // name of the function
+    // `None` means the argument is not a string literal
+    pub args: Vec<Option<String>>,
+    pub kwargs: Vec<(String, Option<String>)>,
+}
+
+pub(crate) fn string_value(node: &Node) -> Option<String> {
+    let literal = node.token?.literal;
+    if node.node_type != NodeType::JinjaLiteral || !literal.starts_with(['\'', '"']) {
+        return None;
+    }
+    let mut res = String::new();
+    let mut chars = literal[1..literal.len() - 1].chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => res.extend(chars.next()),
+            _ => res.push(ch),
+        }
+    }
+    Some(res)
+}
+
+fn collect_dbt_calls<'a>(node: &Node<'a>, res: &mut Vec<DbtCall<'a>>) {
+    if node.node_type == NodeType::JinjaCall {
+        if let Some(ContentType::Node(func)) = node.children.get("func") {
+            let kind = match func.token.map(|t| t.literal) {
+                _ if func.node_type != NodeType::JinjaName => None,
+                Some("ref") => Some(DbtCallKind::Ref),
+                Some("source") => Some(DbtCallKind::Source),
+                Some("var") => Some(DbtCallKind::Var),
+                Some("config") => Some(DbtCallKind::Config),
+                _ => None,
+            };
+            if let (Some(kind), Some(token)) = (kind, func.token) {
+                let mut call = DbtCall {
+                    kind,
+                    token,
+                    args: Vec::new(),
+                    kwargs: Vec::new(),
+                };
+                if let Some(ContentType::NodeVec(args)) = node.children.get("args") {
+                    for arg in args {
+                        match (
+                            arg.node_type,
+                            arg.children.get("left"),
+                            arg.children.get("right"),
+                        ) {
+                            (
+                                NodeType::JinjaKeywordArg,
+                                Some(ContentType::Node(key)),
+                                Some(ContentType::Node(value)),
+                            ) => {
+                                let key = key.token.map(|t| t.literal.to_string());
+                                call.kwargs
+                                    .push((key.unwrap_or_default(), string_value(value)));
+                            }
+                            _ => call.args.push(string_value(arg)),
+                        }
+                    }
+                }
+                res.push(call);
+            }
+        }
+    }
+    let mut children: Vec<(&String, &ContentType)> = node.children.iter().collect();
+    children.sort_by_key(|(k, _)| *k);
+    for (_, content) in children {
+        match content {
+            ContentType::Node(n) => collect_dbt_calls(n, res),
+            ContentType::NodeVec(ns) => ns.iter().for_each(|n| collect_dbt_calls(n, res)),
+        }
+    }
+}
+
+// `ref()`, `source()`, `var()` and `config()` in the order of appearance.
+// tags which cannot be parsed are ignored.
+pub fn dbt_calls(code: &str) -> BQ2CSTResult<Vec<DbtCall<'_>>> {
+    let tokens = Lexer::new(code).tokenize_code()?;
+    let mut res = Vec::new();
+    for token in &tokens {
+        if token.get_template_type().is_none() {
+            continue;
+        }
+        if let Ok(node) = parse_tag(token) {
+            let mut calls = Vec::new();
+            collect_dbt_calls(&node, &mut calls);
+            calls.sort_by_key(|c| (c.token.line, c.token.column));
+            res.extend(calls);
+        }
+    }
+    Ok(res)
+}
@@ -0,0 +1,150 @@
+use super::*;
+
+fn parse(literal: &str) -> String {
+    let token = Token::new(1, 1, literal);
+    parse_tag(&token)
+        .expect("Failed to parse template.")
+        .to_string()
+}
+
+#[test]
+fn test_parse_tag() {
+    assert_eq!(
+        "\
+self: | (JinjaFilter)
+filter:
+  self: ( (JinjaCall)
+  args:
+  - self: = (JinjaKeywordArg)
+    left:
+      self: sep (JinjaName)
+    right:
+      self: ',' (JinjaLiteral)
+  func:
+    self: join (JinjaName)
+  rparen:
+    self: ) (Symbol)
+left:
+  self: . (JinjaAttribute)
+  left:
+    self: model (JinjaName)
+  right:
+    self: columns (JinjaName)
+",
+        parse("{{- model.columns | join(sep=',') -}}")
+    );
+    assert_eq!(
+        "\
+self: set (JinjaStatement)
+exprs:
+- self: x (JinjaName)
+- self: or (JinjaOperator)
+  left:
+    self: not (JinjaOperator)
+    in:
+      self: in (Keyword)
+    left:
+      self: a (JinjaName)
+    right:
+      self: [ (JinjaGroup)
+      end:
+        self: ] (Symbol)
+      exprs:
+      - self: 1 (JinjaLiteral)
+      - self: 2 (JinjaLiteral)
+  right:
+    self: [ (JinjaSubscript)
+    left:
+      self: b (JinjaName)
+    rbrack:
+      self: ] (Symbol)
+    right:
+      self: 'k' (JinjaLiteral)
+",
+        parse("{% set x = a not in [1, 2] or b['k'] %}")
+    );
+    assert_eq!(
+        "\
+self: + (JinjaOperator)
+left:
+  self: 1 (JinjaLiteral)
+right:
+  self: * (JinjaOperator)
+  left:
+    self: 2 (JinjaLiteral)
+  right:
+    self: 3 (JinjaLiteral)
+",
+        parse("{{ 1 + 2 * 3 }}")
+    );
+    // not a Jinja expression
+    assert!(parse_tag(&Token::new(1, 1, "{variable}")).is_err());
+    assert!(parse_tag(&Token::new(1, 1, "{{ f( }}")).is_err());
+    assert!(parse_tag(&Token::new(1, 1, "{{ a b }}")).is_err());
+}
+
+#[test]
+fn test_dbt_calls() {
+    let code = "\
+{{ config(materialized='table', tags=var('tags')) }}
+SELECT *
+FROM {{ ref('orders') }} AS o
+JOIN {{ source(\"raw\", 'customers') }} AS c
+  ON o.customer_id = c.id
+{% set d = ref('my_package', model_name) %}
+";
+    let calls = dbt_calls(code).expect("Failed to tokenize code.");
+    let summary: Vec<(DbtCallKind, usize, usize, Vec<Option<String>>)> = calls
+        .iter()
+        .map(|c| (c.kind, c.token.line, c.token.column, c.args.clone()))
+        .collect();
+    assert_eq!(
+        vec![
+            (DbtCallKind::Config, 1, 4, vec![]),
+            (DbtCallKind::Var, 1, 38, vec![Some("tags".to_string())]),
+            (DbtCallKind::Ref, 3, 9, vec![Some("orders".to_string())]),
+            (
+                DbtCallKind::Source,
+                4,
+                9,
+                vec![Some("raw".to_string()), Some("customers".to_string())]
+            ),
+            (
+                DbtCallKind::Ref,
+                6,
+                12,
+                vec![Some("my_package".to_string()), None]
+            ),
+        ],
+        summary
+    );
+    assert_eq!(
+        vec![
+            ("materialized".to_string(), Some("table".to_string())),
+            ("tags".to_string(), None),
+        ],
+        calls[0].kwargs
+    );
+}
+
+#[test]
+fn test_parse_tag_multibyte() {
+    assert!(parse_tag(&Token::new(1, 1, "{{ 日本 }}")).is_err());
+    assert_eq!("self: '日本' (JinjaLiteral)\n", parse("{{ '日本' }}"));
+}
+
+#[test]
+fn test_parse_tag_deeply_nested() {
+    let nested = format!("{{{{ {}1{} }}}}", "(".repeat(5000), ")".repeat(5000));
+    assert!(parse_tag(&Token::new(1, 1, &nested)).is_err());
+    let nested = format!("{{{{ {}x }}}}", "not ".repeat(5000));
+    assert!(parse_tag(&Token::new(1, 1, &nested)).is_err());
+    // the template is kept as an opaque token
+    let code = format!("SELECT {}", nested);
+    let tokens = Lexer::new(&code).tokenize_code().unwrap();
+    let stmts = crate::parser::Parser::new(tokens).parse_code().unwrap();
+    let expr = &stmts[0].get_node_vec("exprs")[0];
+    assert!(expr.get_node("jinja").is_none());
+    let shallow = format!("{{{{ {}1{} }}}}", "(".repeat(10), ")".repeat(10));
+    assert!(parse_tag(&Token::new(1, 1, &shallow)).is_ok());
+}
@@ -1,19 +1,76 @@
 #[cfg(test)]
 mod tests;
 
-use crate::constants;
+pub use crate::constants::Keyword;
 use regex::Regex;
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use std::sync::LazyLock;
 
-#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
-pub struct Token {
+// `literal` borrows from the source code, so tokens are cheap to copy
+#[derive(PartialEq, Debug, Clone, Copy, Serialize)]
+pub struct Token<'a> {
     pub line: usize,
     pub column: usize,
-    pub literal: String,
+    pub literal: &'a str,
+    // derived from `literal`, so it is not exposed to JavaScript
+    #[serde(skip)]
+    pub kind: TokenKind,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum TokenKind {
+    Keyword(Keyword),
+    Identifier,       // xxx
+    QuotedIdentifier, // `xxx`
+    StringLiteral,    // 'xxx', "xxx", '''xxx''', """xxx"""
+    NumericLiteral,   // 1, 1.1, .1e10
+    Parameter,        // @xxx, @@xxx
+    Symbol(Symbol),
+    Comment, // -- xxx, # xxx, /* xxx */
+    Template(TemplateType),
+    SqlxBlock,         // config { ... }
+    SqlxInterpolation, // ${ ... }
+    EOF,
+    Other,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Symbol {
+    LParen,       // (
+    RParen,       // )
+    LBracket,     // [
+    RBracket,     // ]
+    LBrace,       // {
+    RBrace,       // }
+    Comma,        // ,
+    Dot,          // .
+    Semicolon,    // ;
+    Colon,        // :
+    Asterisk,     // *
+    Slash,        // /
+    Concat,       // ||
+    Plus,         // +
+    Minus,        // -
+    Tilde,        // ~
+    ShiftLeft,    // <<
+    ShiftRight,   // >>
+    Ampersand,    // &
+    Caret,        // ^
+    Pipe,         // |
+    PipeOperator, // |>
+    Eq,           // =
+    NotEq,        // !=
+    LtGt,         // <>
+    Lt,           // <
+    Gt,           // >
+    LtEq,         // <=
+    GtEq,         // >=
+    Arrow,        // =>
+    Question,     // ?
+    Dollar,       // $
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum TemplateType {
     Expr,
     ExprStart,
@@ -22,47 +79,142 @@ pub enum TemplateType {
     Comment,
 }
 
-static NUMBER_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^([0-9]+|([0-9]*\.[0-9]+))([eE][\+\-]?[0-9]+)?$").unwrap());
+static NUMBER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(0[xX][0-9a-fA-F]+|([0-9]+|([0-9]*\.[0-9]+))([eE][\+\-]?[0-9]+)?)$").unwrap()
+});
 static TEMPLATE_JINJA_STATEMENT: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^\{%-?\s*(\w+).*$").unwrap());
+    LazyLock::new(|| Regex::new(r"(?s)^\{%-?\s*(\w+).*$").unwrap());
 static TEMPLATE_JINJA_ONELINE_SET: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^\{%-?\s*set\s*\w+\s*=.*$").unwrap());
+    LazyLock::new(|| Regex::new(r"(?s)^\{%-?\s*set\s*\w+\s*=.*$").unwrap());
 static TEMPLATE_OTHER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\{.+$").unwrap());
 
-impl Token {
-    pub fn new(line: usize, column: usize, literal: String) -> Token {
-        Token {
-            line,
-            column,
-            literal,
-        }
+impl Symbol {
+    pub fn from_literal(literal: &str) -> Option<Symbol> {
+        let symbol = match literal {
+            "(" => Symbol::LParen,
+            ")" => Symbol::RParen,
+            "[" => Symbol::LBracket,
+            "]" => Symbol::RBracket,
+            "{" => Symbol::LBrace,
+            "}" => Symbol::RBrace,
+            "," => Symbol::Comma,
+            "." => Symbol::Dot,
+            ";" => Symbol::Semicolon,
+            ":" => Symbol::Colon,
+            "*" => Symbol::Asterisk,
+            "/" => Symbol::Slash,
+            "||" => Symbol::Concat,
+            "+" => Symbol::Plus,
+            "-" => Symbol::Minus,
+            "~" => Symbol::Tilde,
+            "<<" => Symbol::ShiftLeft,
+            ">>" => Symbol::ShiftRight,
+            "&" => Symbol::Ampersand,
+            "^" => Symbol::Caret,
+            "|" => Symbol::Pipe,
+            "|>" => Symbol::PipeOperator,
+            "=" => Symbol::Eq,
+            "!=" => Symbol::NotEq,
+            "<>" => Symbol::LtGt,
+            "<" => Symbol::Lt,
+            ">" => Symbol::Gt,
+            "<=" => Symbol::LtEq,
+            ">=" => Symbol::GtEq,
+            "=>" => Symbol::Arrow,
+            "?" => Symbol::Question,
+            "$" => Symbol::Dollar,
+            _ => return None,
+        };
+        Some(symbol)
     }
-    pub fn eof() -> Token {
-        // `const EOF` is not allowed
-        // because of `"".to_string()`
-        Token {
-            line: usize::MAX,
-            column: usize::MAX,
-            literal: "".to_string(),
+}
+
+impl From<Keyword> for TokenKind {
+    fn from(keyword: Keyword) -> TokenKind {
+        TokenKind::Keyword(keyword)
+    }
+}
+
+impl From<Symbol> for TokenKind {
+    fn from(symbol: Symbol) -> TokenKind {
+        TokenKind::Symbol(symbol)
+    }
+}
+
+impl TokenKind {
+    pub fn classify(literal: &str) -> TokenKind {
+        let mut iter = literal.chars();
+        let first_char = match iter.next() {
+            Some(c) => c,
+            None => return TokenKind::EOF,
+        };
+        let second_char = iter.next();
+        match (first_char, second_char) {
+            ('#', _) | ('-', Some('-')) | ('/', Some('*')) => return TokenKind::Comment,
+            ('{', Some(_)) => {
+                if let Some(template_type) = TokenKind::template_type(literal) {
+                    return TokenKind::Template(template_type);
+                }
+            }
+            ('@', _) => return TokenKind::Parameter,
+            ('$', Some('{')) => return TokenKind::SqlxInterpolation,
+            // `tmp_${x}` (the name of a block is followed by `{` without `$`)
+            (c, _)
+                if (c.is_alphabetic() || c == '_')
+                    && literal
+                        .find('{')
+                        .is_some_and(|i| literal[..i].ends_with('$')) =>
+            {
+                return TokenKind::SqlxInterpolation
+            }
+            ('a'..='z', _) if literal.ends_with('}') => return TokenKind::SqlxBlock,
+            _ => (),
+        }
+        if let Some(symbol) = Symbol::from_literal(literal) {
+            return TokenKind::Symbol(symbol);
+        }
+        if quoted_by(literal, '`') {
+            return TokenKind::QuotedIdentifier;
+        }
+        if quoted_by(literal, '"') || quoted_by(literal, '\'') {
+            return TokenKind::StringLiteral;
+        }
+        if NUMBER_RE.is_match(literal) {
+            return TokenKind::NumericLiteral;
+        }
+        if let Some(keyword) = Keyword::from_literal(literal) {
+            return TokenKind::Keyword(keyword);
+        }
+        match first_char {
+            'a'..='z' | 'A'..='Z' | '_' => (),
+            _ => return TokenKind::Other,
+        }
+        if literal
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '_'))
+        {
+            TokenKind::Identifier
+        } else {
+            TokenKind::Other
         }
     }
-    pub fn get_template_type(&self) -> Option<TemplateType> {
-        if self.literal.starts_with("{{") {
+    fn template_type(literal: &str) -> Option<TemplateType> {
+        if literal.starts_with("{{") {
             return Some(TemplateType::Expr);
-        } else if self.literal.starts_with("{#") {
+        } else if literal.starts_with("{#") {
             return Some(TemplateType::Comment);
-        } else if self.literal.starts_with("{%") {
-            if let Some(caps) = TEMPLATE_JINJA_STATEMENT.captures(self.literal.as_str()) {
+        } else if literal.starts_with("{%") {
+            if let Some(caps) = TEMPLATE_JINJA_STATEMENT.captures(literal) {
                 match &caps[1] {
-                    "for" | "if" | "raw" | "block" | "macro" | "call" | "filter" => {
+                    "for" | "if" | "block" | "macro" | "call" | "filter" => {
                         return Some(TemplateType::ExprStart)
                     }
-                    "include" => return Some(TemplateType::Expr),
+                    // `{% raw %}...{% endraw %}` is a single token
+                    "include" | "raw" => return Some(TemplateType::Expr),
                     "elif" | "else" => return Some(TemplateType::ExprContinue),
                     "extends" | "import" | "from" => return Some(TemplateType::Comment),
                     "set" => {
-                        if TEMPLATE_JINJA_ONELINE_SET.is_match(self.literal.as_str()) {
+                        if TEMPLATE_JINJA_ONELINE_SET.is_match(literal) {
                             return Some(TemplateType::Comment);
                         } else {
                             return Some(TemplateType::ExprStart);
@@ -78,127 +230,110 @@ impl Token {
                     }
                 };
             }
-        } else if TEMPLATE_OTHER.is_match(self.literal.as_str()) {
+        } else if TEMPLATE_OTHER.is_match(literal) {
             // python f-string or something (actually, out of scope)
             return Some(TemplateType::Expr);
         };
 
-        return None;
+        None
     }
-    pub fn is_string(&self) -> bool {
-        if self.quoted_by('"') {
-            true
-        } else if self.quoted_by('\'') {
-            true
-        } else {
-            false
+}
+
+// `name` as an identifier, e.g. `a` -> `a`, `select` -> `\`select\``
+pub fn quote_identifier(name: &str) -> String {
+    match TokenKind::classify(name) {
+        TokenKind::Identifier => name.to_string(),
+        TokenKind::Keyword(keyword) if !keyword.is_reserved() => name.to_string(),
+        _ => format!("`{}`", name.replace('\\', "\\\\").replace('`', "\\`")),
+    }
+}
+
+fn quoted_by(literal: &str, ch: char) -> bool {
+    if literal.len() < 2 {
+        return false;
+    }
+    literal.starts_with(ch) && literal.ends_with(ch)
+}
+
+impl<'a> Token<'a> {
+    pub fn new(line: usize, column: usize, literal: &'a str) -> Token<'a> {
+        Token {
+            line,
+            column,
+            literal,
+            kind: TokenKind::classify(literal),
         }
     }
-    pub fn in_(&self, vec: &Vec<&str>) -> bool {
-        for v in vec {
-            if self.literal.to_uppercase() == v.to_uppercase() {
-                return true;
-            };
+    pub fn eof() -> Token<'a> {
+        Token {
+            line: usize::MAX,
+            column: usize::MAX,
+            literal: "",
+            kind: TokenKind::EOF,
         }
-        false
     }
-    pub fn is(&self, literal: &str) -> bool {
-        self.literal.to_uppercase() == literal.to_uppercase()
+    pub fn get_template_type(&self) -> Option<TemplateType> {
+        match self.kind {
+            TokenKind::Template(template_type) => Some(template_type),
+            _ => None,
+        }
+    }
+    // `{% for x in xs %}` -> `for`
+    pub fn template_name(&self) -> Option<&'a str> {
+        let caps = TEMPLATE_JINJA_STATEMENT.captures(self.literal)?;
+        caps.get(1).map(|m| m.as_str())
+    }
+    pub fn is_string(&self) -> bool {
+        self.kind == TokenKind::StringLiteral
+    }
+    pub fn in_<T: Into<TokenKind> + Copy>(&self, kinds: &[T]) -> bool {
+        kinds.iter().any(|k| self.kind == (*k).into())
+    }
+    pub fn is(&self, kind: impl Into<TokenKind>) -> bool {
+        self.kind == kind.into()
     }
     pub fn is_identifier(&self) -> bool {
-        if self.quoted_by('`') {
-            return true;
-        }
-        if self.is_reserved_keyword() {
-            return false;
-        }
-        for kw in constants::KEYWORDS.iter() {
-            // `PartialEq<&str>` is implemented for String
-            if self.literal.to_uppercase() == *kw {
-                return false;
-            }
+        match self.kind {
+            TokenKind::Identifier | TokenKind::QuotedIdentifier => true,
+            TokenKind::Keyword(keyword) => !keyword.is_reserved(),
+            _ => false,
         }
-        let mut iterator = self.literal.chars();
-        match iterator.next() {
-            Some('a'..='z') | Some('A'..='Z') | Some('_') => (),
-            _ => return false, // "", ";", ...
-        }
-        for i in iterator {
-            match i {
-                'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => (),
-                _ => return false,
-            }
-        }
-        true
     }
     pub fn is_parameter(&self) -> bool {
-        let mut iterator = self.literal.chars();
-        match iterator.next() {
-            Some('?') | Some('@') => return true,
-            _ => return false,
-        }
+        matches!(
+            self.kind,
+            TokenKind::Parameter | TokenKind::Symbol(Symbol::Question)
+        )
     }
     pub fn is_numeric(&self) -> bool {
-        NUMBER_RE.is_match(self.literal.as_str())
+        self.kind == TokenKind::NumericLiteral
     }
     pub fn is_boolean(&self) -> bool {
-        self.literal.to_uppercase() == "TRUE" || self.literal.to_uppercase() == "FALSE"
+        matches!(
+            self.kind,
+            TokenKind::Keyword(Keyword::TRUE) | TokenKind::Keyword(Keyword::FALSE)
+        )
     }
     pub fn is_reserved_keyword(&self) -> bool {
-        for kw in constants::KEYWORDS.iter() {
-            // `PartialEq<&str>` is implemented for String
-            if self.literal.to_uppercase() == *kw {
-                return true;
-            }
+        match self.kind {
+            TokenKind::Keyword(keyword) => keyword.is_reserved(),
+            _ => false,
         }
-        false
     }
     pub fn is_comment(&self) -> bool {
-        let mut iter = self.literal.chars();
-        if self.get_template_type() == Some(TemplateType::Comment) {
-            return true;
-        };
-        let first_char = match iter.next() {
-            Some(c) => match c {
-                '-' | '/' => c,
-                '#' => return true,
-                _ => return false,
-            },
-            None => return false,
-        };
-        let second_char = match iter.next() {
-            Some(c) => c,
-            None => return false,
-        };
-        if first_char == '-' && second_char == '-' || first_char == '/' && second_char == '*' {
-            true
-        } else {
-            false
-        }
-    }
-    fn quoted_by(&self, ch: char) -> bool {
-        if self.literal.len() < 2 {
-            return false;
-        }
-        // unwrap is safe because the length is longer then 2
-        self.literal.chars().next().unwrap() == ch && self.literal.chars().last().unwrap() == ch
+        matches!(
+            self.kind,
+            TokenKind::Comment | TokenKind::Template(TemplateType::Comment)
+        )
     }
 }
 
 #[cfg(test)]
-impl Token {
-    pub fn from_str(line: usize, column: usize, literal: &str) -> Token {
-        Token {
-            line,
-            column,
-            literal: literal.to_string(),
-        }
+impl<'a> Token<'a> {
+    pub fn from_str(line: usize, column: usize, literal: &'a str) -> Token<'a> {
+        Token::new(line, column, literal)
     }
-    pub fn from_str0(literal: &str) -> Token {
-        Token {
-            line: 0,
-            column: 0,
-            literal: literal.to_string(),
-        }
+    pub fn from_str0(literal: &'a str) -> Token<'a> {
+        Token::new(0, 0, literal)
     }
 }
@@ -39,11 +39,15 @@ fn test_is_numeric() {
     assert!(Token::from_str0("10.11E10").is_numeric());
     assert!(Token::from_str0("10.11E+10").is_numeric());
     assert!(Token::from_str0("10.11E-10").is_numeric());
+    assert!(Token::from_str0("0x1F").is_numeric());
+    assert!(Token::from_str0("0Xab").is_numeric());
 
     // invalid
     assert!(!Token::from_str0("e10").is_numeric());
     assert!(!Token::from_str0("xxx").is_numeric());
     assert!(!Token::from_str0("x01").is_numeric());
+    assert!(!Token::from_str0("0x").is_numeric());
+    assert!(!Token::from_str0("0x1G").is_numeric());
 }
 
 #[test]
@@ -85,8 +89,60 @@ fn test_get_template_type() {
         Token::from_str0("{% endfor %}").get_template_type(),
         Some(TemplateType::ExprEnd)
     );
+    assert_eq!(
+        Token::from_str0("{% raw %}\n{{ x }}\n{% endraw %}").get_template_type(),
+        Some(TemplateType::Expr)
+    );
     assert_eq!(
         Token::from_str0("{% materialization %}").get_template_type(),
         Some(TemplateType::ExprStart)
     );
 }
+
+#[test]
+fn test_kind() {
+    assert_eq!(
+        Token::from_str0("select").kind,
+        TokenKind::Keyword(Keyword::SELECT)
+    );
+    assert_eq!(
+        Token::from_str0("Options").kind,
+        TokenKind::Keyword(Keyword::OPTIONS)
+    );
+    assert_eq!(Token::from_str0("xxx").kind, TokenKind::Identifier);
+    assert_eq!(Token::from_str0("`xxx`").kind, TokenKind::QuotedIdentifier);
+    assert_eq!(Token::from_str0("'xxx'").kind, TokenKind::StringLiteral);
+    assert_eq!(Token::from_str0("1.1").kind, TokenKind::NumericLiteral);
+    assert_eq!(Token::from_str0("@@xxx").kind, TokenKind::Parameter);
+    assert_eq!(
+        Token::from_str0("|>").kind,
+        TokenKind::Symbol(Symbol::PipeOperator)
+    );
+    assert_eq!(Token::from_str0("-- xxx").kind, TokenKind::Comment);
+    assert_eq!(
+        Token::from_str0("{{ xxx }}").kind,
+        TokenKind::Template(TemplateType::Expr)
+    );
+    assert_eq!(
+        Token::from_str0("${ref(\"x\")}").kind,
+        TokenKind::SqlxInterpolation
+    );
+    assert_eq!(Token::from_str0("config {}").kind, TokenKind::SqlxBlock);
+    assert_eq!(Token::from_str0("").kind, TokenKind::EOF);
+    assert_eq!(Token::from_str0("列").kind, TokenKind::Other);
+
+    assert!(Token::from_str0("select").is(Keyword::SELECT));
+    assert!(Token::from_str0("(").in_(&[Symbol::LParen, Symbol::Dot]));
+    assert!(Token::from_str0("TRUE").is_boolean());
+    assert!(Token::from_str0("?").is_parameter());
+}
+
+#[test]
+fn test_quote_identifier() {
+    assert_eq!(quote_identifier("a_1"), "a_1");
+    assert_eq!(quote_identifier("date"), "date");
+    assert_eq!(quote_identifier("select"), "`select`");
+    assert_eq!(quote_identifier("a-b"), "`a-b`");
+    assert_eq!(quote_identifier("1a"), "`1a`");
+    assert_eq!(quote_identifier("a`b"), "`a\\`b`");
+}
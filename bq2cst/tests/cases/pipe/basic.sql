@@ -0,0 +1,3 @@
+FROM t
+|> WHERE x > 0
+|> AGGREGATE COUNT(*) AS n GROUP BY y;
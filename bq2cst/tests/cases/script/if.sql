@@ -0,0 +1,8 @@
+DECLARE x INT64 DEFAULT 1;
+IF x = 1 THEN
+  SELECT 'one';
+ELSEIF x = 2 THEN
+  SELECT 'two';
+ELSE
+  SELECT 'many';
+END IF;
@@ -0,0 +1,2 @@
+SELECT 1;
+SELECT DISTINCT a, b AS c FROM t WHERE a > 1 ORDER BY b DESC LIMIT 10;
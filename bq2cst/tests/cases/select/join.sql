@@ -0,0 +1,4 @@
+-- comments are attached to nodes
+SELECT *
+FROM t1 LEFT OUTER JOIN t2 USING (id)
+INNER JOIN t3 ON t2.x = t3.x;
@@ -0,0 +1,84 @@
+// SQL files in tests/cases/ are parsed and compared with their sibling .cst files.
+// run `BQ2CST_UPDATE=1 cargo test --test golden` to rewrite the .cst files.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use bq2cst::lexer::Lexer;
+use bq2cst::parser::Parser;
+use bq2cst::schema;
+use difference::Changeset;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn collect_sql_files(dir: &Path, res: &mut Vec<PathBuf>) {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .expect("Failed to read directory.")
+        .map(|e| e.expect("Failed to read directory entry.").path())
+        .collect();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_sql_files(&path, res);
+        } else if path.extension().is_some_and(|ext| ext == "sql") {
+            res.push(path);
+        }
+    }
+}
+
+fn render(code: &str) -> Result<String, String> {
+    let tokens = Lexer::new(code)
+        .tokenize_code()
+        .map_err(|e| format!("{:?}", e))?;
+    let stmts = Parser::new(tokens)
+        .parse_code()
+        .map_err(|e| format!("{:?}", e))?;
+    for stmt in &stmts {
+        schema::validate(stmt)?;
+    }
+    let dumps: Vec<String> = stmts.iter().map(|stmt| stmt.to_string()).collect();
+    Ok(dumps.join("\n"))
+}
+
+#[test]
+fn test_golden() {
+    let update = std::env::var("BQ2CST_UPDATE").is_ok_and(|v| v == "1");
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/cases");
+    let mut paths = Vec::new();
+    collect_sql_files(&root, &mut paths);
+    assert!(!paths.is_empty(), "No test case was found.");
+
+    let mut failures = Vec::new();
+    for path in paths {
+        let name = path.strip_prefix(&root).unwrap_or(&path).display();
+        let code = fs::read_to_string(&path).expect("Failed to read SQL.");
+        let actual = match render(&code) {
+            Ok(actual) => actual,
+            Err(e) => {
+                failures.push(format!("{}: {}", name, e));
+                continue;
+            }
+        };
+        let cst_path = path.with_extension("cst");
+        if update {
+            fs::write(&cst_path, &actual).expect("Failed to write .cst file.");
+            continue;
+        }
+        let expected = match fs::read_to_string(&cst_path) {
+            Ok(expected) => expected,
+            Err(_) => {
+                failures.push(format!("{}: .cst file is missing", name));
+                continue;
+            }
+        };
+        if expected != actual {
+            let changeset = Changeset::new(&expected, &actual, "\n");
+            failures.push(format!("{}:\n{}", name, changeset));
+        }
+    }
+    if !failures.is_empty() {
+        panic!(
+            "{}\n\nrun `BQ2CST_UPDATE=1 cargo test --test golden` if the changes are expected.",
+            failures.join("\n\n")
+        );
+    }
+}
@@ -1,4 +1,4 @@
-import { parse } from "bq2cst";
+import { parse, parseSqlx } from "bq2cst";
 import { printSQL } from "./printer";
 
 const languages = [
@@ -7,6 +7,11 @@ const languages = [
     name: "sql",
     parsers: ["sql-parse"],
   },
+  {
+    extensions: [".sqlx"],
+    name: "sqlx",
+    parsers: ["sqlx-parse"],
+  },
 ];
 
 const parsers = {
@@ -14,6 +19,10 @@ const parsers = {
     parse: (text: string) => parse(text),
     astFormat: "sql-ast",
   },
+  "sqlx-parse": {
+    parse: (text: string) => parseSqlx(text),
+    astFormat: "sql-ast",
+  },
 };
 
 const printers = {
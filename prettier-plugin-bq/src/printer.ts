@@ -816,6 +816,10 @@ export const printSQL = (
       return printSetStatement(path, options, print, node);
     case "SingleTokenStatement":
       return printSingleTokenStatement(path, options, print, node);
+    case "SqlxBlock":
+      return printSqlxBlock(path, options, print, node);
+    case "SqlxInterpolation":
+      return printIdentifier(path, options, print, node);
     case "StandAloneExpr":
       return printStandAloneExpr(path, options, print, node);
     case "StringLiteral":
@@ -3894,7 +3898,10 @@ const printGroupedTypeDeclarations: PrintFunc<
 };
 
 const printIdentifier: PrintFunc<
-  bq2cst.Identifier | bq2cst.Parameter | bq2cst.TemplateExpr
+  | bq2cst.Identifier
+  | bq2cst.Parameter
+  | bq2cst.SqlxInterpolation
+  | bq2cst.TemplateExpr
 > = (path, options, print, node) => {
   const p = new Printer(path, options, print, node);
   const docs: { [Key in Docs<bq2cst.Identifier>]: Doc } = {
@@ -5468,6 +5475,28 @@ const printSingleTokenStatement: PrintFunc<bq2cst.SingleTokenStatement> = (
   ];
 };
 
+// the contents are printed as is (even if they are SQL)
+const printSqlxBlock: PrintFunc<bq2cst.SqlxBlock> = (
+  path,
+  options,
+  print,
+  node,
+) => {
+  const p = new Printer(path, options, print, node);
+  const docs: { [Key in Docs<bq2cst.SqlxBlock>]: Doc } = {
+    leading_comments: printLeadingComments(path, options, print, node),
+    self: p.self("asItIs"),
+    stmts: "", // eslint-disable-line unicorn/no-unused-properties
+    trailing_comments: printTrailingComments(path, options, print, node),
+  };
+  return [
+    docs.leading_comments,
+    docs.self,
+    docs.trailing_comments,
+    p.newLine(),
+  ];
+};
+
 const printStandAloneExpr: PrintFunc<bq2cst.StandAloneExpr> = (
   path,
   options,
@@ -5686,6 +5715,7 @@ const printTemplateExprStart: PrintFunc<bq2cst.TemplateExprStart> = (
     leading_comments: printLeadingComments(path, options, print, node),
     self: p.self("asItIs"),
     trailing_comments: printTrailingComments(path, options, print, node),
+    jinja: "", // eslint-disable-line unicorn/no-unused-properties
     exprs: p.child("exprs", (x) => [line, x]),
     continues: p.child("continues", (x) => [line, x]),
     end: p.child("end"),
@@ -8,6 +8,9 @@ import { useState } from "react";
 import * as prettier from "prettier/standalone";
 import * as prettierPluginBq from "./prettier-plugin-bq";
 import type { Plugin } from "prettier";
+import { exportTree } from "bq2cst";
+
+type TreeFormat = "sexp" | "dot" | "mermaid";
 
 const SAMPLE_SQL = `\
 -- BigQuery Scripting is supported
@@ -40,9 +43,19 @@ async function prettify(sql: string) {
   return res;
 }
 
+function tree(sql: string, format: TreeFormat) {
+  try {
+    return exportTree(sql, format).join("\n");
+  } catch {
+    return "Failed to parse SQL.";
+  }
+}
+
 function App() {
   const [sql, setSql] = useState(SAMPLE_SQL);
   const [showErrorMessage, setShowErrorMessage] = useState(false);
+  const [showTree, setShowTree] = useState(false);
+  const [treeFormat, setTreeFormat] = useState<TreeFormat>("sexp");
   return (
     <>
       <header className={clsx("p-4", "font-extrabold text-5xl")}>
@@ -54,16 +67,44 @@ function App() {
           "flex flex-col items-center justify-center", // flex
         )}
       >
-        <textarea
-          id="sql"
+        <div
           className={clsx(
-            "shadow bg-white", // appearance
-            "max-w-2xl p-4 flex-grow rounded-md mx-4 w-full", // shape
-            "outline-none outline-4 outline-dark focus:outline-solid", // outline
+            "flex-grow w-full gap-x-4", // shape
+            "flex justify-center", // flex
           )}
-          value={sql}
-          onChange={(e) => setSql(e.target.value)}
-        />
+        >
+          <textarea
+            id="sql"
+            className={clsx(
+              "shadow bg-white", // appearance
+              "max-w-2xl p-4 flex-grow rounded-md w-full", // shape
+              "outline-none outline-4 outline-dark focus:outline-solid", // outline
+            )}
+            value={sql}
+            onChange={(e) => setSql(e.target.value)}
+          />
+          {showTree && (
+            <div className="max-w-2xl w-full flex flex-col gap-y-2">
+              <select
+                className="self-end bg-white rounded-md px-2 py-1 shadow"
+                value={treeFormat}
+                onChange={(e) => setTreeFormat(e.target.value as TreeFormat)}
+              >
+                <option value="sexp">S-expression</option>
+                <option value="dot">Graphviz DOT</option>
+                <option value="mermaid">Mermaid</option>
+              </select>
+              <pre
+                className={clsx(
+                  "shadow bg-white", // appearance
+                  "p-4 flex-grow rounded-md overflow-auto text-sm", // shape
+                )}
+              >
+                {tree(sql, treeFormat)}
+              </pre>
+            </div>
+          )}
+        </div>
         {/* TODO: enable to configure options */}
         <button
           className={clsx(
@@ -92,6 +133,14 @@ function App() {
           FORMAT
           <SparklesIcon className="size-4 inline" />
         </button>
+        <label className="flex items-center gap-x-1 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={showTree}
+            onChange={(e) => setShowTree(e.target.checked)}
+          />
+          show tree
+        </label>
       </main>
       <footer
         className={clsx(